@@ -1,16 +1,39 @@
+use clap::Parser;
 use processor::IndexerGrpcProcessorConfig;
+use processor_config::IndexerGrpcProcessorConfigBuilder;
 use server_framework::RunnableConfig;
-use std::io::Write;
+use service::ProcessorsHealth;
+use std::sync::atomic::Ordering;
+use supervisor::spawn_with_restart;
 use tokio::task::JoinSet;
 use tokio::time::Duration;
 
+mod migrations;
+mod processor_config;
 mod service;
+mod supervisor;
 
-const RUNTIME_WORKER_MULTIPLIER: usize = 2;
+/// How many times a failed processor is restarted before the indexer exits.
+const PROCESSOR_MAX_RESTARTS: u32 = 5;
+/// The delay before the first restart of a failed processor, doubled for each next restart.
+const PROCESSOR_RESTART_BASE_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Parser)]
+#[clap(about = "Runs the Movement indexer processors")]
+struct Args {
+	/// The first version to index, overrides INDEXER_STARTING_VERSION.
+	#[clap(long)]
+	start_version: Option<u64>,
+	/// The last version to index, the indexer exits once every processor has indexed it.
+	#[clap(long)]
+	end_version: Option<u64>,
+}
 
 fn main() -> Result<(), anyhow::Error> {
 	use tracing_subscriber::EnvFilter;
 
+	let args = Args::parse();
+
 	tracing_subscriber::fmt()
 		.with_env_filter(
 			EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
@@ -27,31 +50,45 @@ fn main() -> Result<(), anyhow::Error> {
 		maptos_config.indexer.maptos_indexer_grpc_healthcheck_port
 	);
 
-	let default_indexer_config = build_processor_conf("default_processor", &maptos_config)?;
-	let usertx_indexer_config = build_processor_conf("user_transaction_processor", &maptos_config)?;
-	let accounttx_indexer_config =
-		build_processor_conf("account_transactions_processor", &maptos_config)?;
-	let coin_indexer_config = build_processor_conf("coin_processor", &maptos_config)?;
-	let event_indexer_config = build_processor_conf("events_processor", &maptos_config)?;
-	let fungible_indexer_config = build_processor_conf("fungible_asset_processor", &maptos_config)?;
-	let txmeta_indexer_config =
-		build_processor_conf("transaction_metadata_processor", &maptos_config)?;
+	let build_processor_conf = |processor_name: &str| {
+		build_processor_conf_with_versions(
+			processor_name,
+			&maptos_config,
+			args.start_version,
+			args.end_version,
+		)
+	};
+	let default_indexer_config = build_processor_conf("default_processor")?;
+	let usertx_indexer_config = build_processor_conf("user_transaction_processor")?;
+	let accounttx_indexer_config = build_processor_conf("account_transactions_processor")?;
+	let coin_indexer_config = build_processor_conf("coin_processor")?;
+	let event_indexer_config = build_processor_conf("events_processor")?;
+	let fungible_indexer_config = build_processor_conf("fungible_asset_processor")?;
+	let txmeta_indexer_config = build_processor_conf("transaction_metadata_processor")?;
 
 	// Token processor
 	let activate_tokes: bool = std::env::var("ACTIVATE_TOKEN_INDEXING")
 		.map(|t| t.parse().unwrap_or(true))
 		.unwrap_or(true);
 	let token_configs = if activate_tokes {
-		let token_indexer_config = build_processor_conf(
-			"token_processor
-  nft_points_contract: null",
-			&maptos_config,
+		let token_indexer_config = load_processor_conf(
+			processor_conf_builder(
+				"token_processor",
+				&maptos_config,
+				args.start_version,
+				args.end_version,
+			)
+			.processor_option("nft_points_contract", serde_yaml::Value::Null),
 		)?;
 
-		let tokenv2_indexer_config = build_processor_conf(
-			"token_v2_processor
-  query_retries: 5",
-			&maptos_config,
+		let tokenv2_indexer_config = load_processor_conf(
+			processor_conf_builder(
+				"token_v2_processor",
+				&maptos_config,
+				args.start_version,
+				args.end_version,
+			)
+			.processor_option("query_retries", 5),
 		)?;
 		Some((token_indexer_config, tokenv2_indexer_config))
 	} else {
@@ -59,7 +96,11 @@ fn main() -> Result<(), anyhow::Error> {
 	};
 
 	let num_cpus = num_cpus::get();
-	let worker_threads = (num_cpus * RUNTIME_WORKER_MULTIPLIER).max(16);
+	let worker_threads = compute_worker_threads(
+		num_cpus,
+		maptos_config.indexer.worker_multiplier,
+		maptos_config.indexer.min_worker_threads,
+	);
 	println!(
 		"[Processor] Starting processor tokio runtime: num_cpus={}, worker_threads={}",
 		num_cpus, worker_threads
@@ -80,42 +121,105 @@ fn main() -> Result<(), anyhow::Error> {
 				test_grpc_connection(&maptos_config).await?;
 
 				let mut set = JoinSet::new();
-				set.spawn(async move { crate::service::run_service(health_check_url).await });
-				set.spawn(async move { default_indexer_config.run().await });
-				//wait all the migration is done.
-				tokio::time::sleep(Duration::from_secs(12)).await;
-				set.spawn(async move { usertx_indexer_config.run().await });
-				set.spawn(async move { accounttx_indexer_config.run().await });
-				set.spawn(async move { coin_indexer_config.run().await });
-				set.spawn(async move { event_indexer_config.run().await });
-				set.spawn(async move { fungible_indexer_config.run().await });
-				set.spawn(async move { txmeta_indexer_config.run().await });
+				let health = ProcessorsHealth::new();
+				let mut restarts = Vec::new();
+				let mut spawn_processor =
+					|set: &mut JoinSet<_>, config: IndexerGrpcProcessorConfig| {
+						let name = config.get_server_name();
+						let count = spawn_with_restart(
+							set,
+							config,
+							PROCESSOR_MAX_RESTARTS,
+							PROCESSOR_RESTART_BASE_DELAY,
+							health.register(name.clone()),
+						);
+						restarts.push((name, count));
+					};
+				set.spawn({
+					let health = health.clone();
+					async move { crate::service::run_service(health_check_url, health).await }
+				});
+				spawn_processor(&mut set, default_indexer_config);
+				// the other processors start once the default one has migrated the database
+				migrations::wait_for_migrations(
+					&maptos_config.indexer_processor.postgres_connection_string,
+					Duration::from_secs(maptos_config.indexer.migration_timeout_sec),
+				)
+				.await?;
+				spawn_processor(&mut set, usertx_indexer_config);
+				spawn_processor(&mut set, accounttx_indexer_config);
+				spawn_processor(&mut set, coin_indexer_config);
+				spawn_processor(&mut set, event_indexer_config);
+				spawn_processor(&mut set, fungible_indexer_config);
+				spawn_processor(&mut set, txmeta_indexer_config);
 				if let Some((token_indexer_config, tokenv2_indexer_config)) = token_configs {
-					set.spawn(async move { token_indexer_config.run().await });
-					set.spawn(async move { tokenv2_indexer_config.run().await });
+					spawn_processor(&mut set, token_indexer_config);
+					spawn_processor(&mut set, tokenv2_indexer_config);
 				}
 
+				// With an end version the processors end once they have indexed it,
+				// otherwise a processor only ends here once its restarts are exhausted.
+				let mut result =
+					Err(anyhow::anyhow!("At least One indexer processor failed. Exit"));
+				let mut finished = 0;
 				while let Some(res) = set.join_next().await {
+					if args.end_version.is_some() && matches!(res, Ok(Ok(()))) {
+						finished += 1;
+						if finished == restarts.len() {
+							tracing::info!("Indexed up to version {:?}", args.end_version);
+							result = Ok(());
+							break;
+						}
+						continue;
+					}
 					tracing::error!("An Error occurs during indexer execution: {res:?}");
 					// If a processor break to avoid data inconsistency between processor
 					break;
 				}
 				set.shutdown().await;
-				Err(anyhow::anyhow!("At least One indexer processor failed. Exit"))
+				for (name, count) in &restarts {
+					tracing::info!(
+						"Processor {} restarted {} times",
+						name,
+						count.load(Ordering::SeqCst)
+					);
+				}
+				result
 			}
 		});
 	if let Err(err) = ret {
 		tracing::error!("Indexer execution failed: {err}");
 		std::process::exit(1);
 	} else {
-		std::process::exit(1);
+		std::process::exit(0);
 	}
 }
 
-fn build_processor_conf(
+/// Number of worker threads of the indexer runtime, never less than `min` nor than one thread.
+fn compute_worker_threads(num_cpus: usize, multiplier: usize, min: usize) -> usize {
+	num_cpus.saturating_mul(multiplier).max(min).max(1)
+}
+
+fn build_processor_conf_with_versions(
 	processor_name: &str,
 	maptos_config: &maptos_execution_util::config::Config,
+	start_version: Option<u64>,
+	end_version: Option<u64>,
 ) -> Result<IndexerGrpcProcessorConfig, anyhow::Error> {
+	load_processor_conf(processor_conf_builder(
+		processor_name,
+		maptos_config,
+		start_version,
+		end_version,
+	))
+}
+
+fn processor_conf_builder(
+	processor_name: &str,
+	maptos_config: &maptos_execution_util::config::Config,
+	start_version: Option<u64>,
+	end_version: Option<u64>,
+) -> IndexerGrpcProcessorConfigBuilder {
 	let indexer_grpc_data_service_address = build_grpc_url(maptos_config);
 
 	let default_sleep_time_between_request: u64 = std::env::var("SLEEP_TIME_BETWEEN_REQUEST_MS")
@@ -123,50 +227,45 @@ fn build_processor_conf(
 		.unwrap_or(10);
 
 	// If the starting version is not defined, don't put a default value in the conf.
-	let starting_version_entry = std::env::var("INDEXER_STARTING_VERSION")
-		.map(|t| t.parse().unwrap_or(0))
-		.map(|t| format!("starting_version: {}", t))
-		.unwrap_or(String::new());
-
-	//create config file
-	let indexer_config_content = format!(
-		"processor_config:
-  type: {}
-postgres_connection_string: {}/postgres
-indexer_grpc_data_service_address: {}
-indexer_grpc_http2_ping_interval_in_secs: {}
-indexer_grpc_http2_ping_timeout_in_secs: {}
-auth_token: \"{}\"
-default_sleep_time_between_request: {}
-{}",
-		processor_name,
-		maptos_config.indexer_processor.postgres_connection_string,
-		indexer_grpc_data_service_address,
-		maptos_config.indexer.maptos_indexer_grpc_inactivity_timeout,
-		maptos_config.indexer.maptos_indexer_grpc_inactivity_ping_interval,
-		maptos_config.indexer_processor.indexer_processor_auth_token,
-		default_sleep_time_between_request,
-		starting_version_entry,
-	);
+	let starting_version = start_version
+		.or_else(|| std::env::var("INDEXER_STARTING_VERSION").ok().map(|t| t.parse().unwrap_or(0)));
 
-	//let indexer_config_path = dot_movement.get_path().join("indexer_config.yaml");
-	let mut output_file = tempfile::NamedTempFile::new()?;
-	write!(output_file, "{}", indexer_config_content)?;
+	IndexerGrpcProcessorConfigBuilder::new(processor_name)
+		.postgres_connection_string(
+			maptos_config.indexer_processor.postgres_connection_string.clone(),
+		)
+		.indexer_grpc_data_service_address(indexer_grpc_data_service_address)
+		.indexer_grpc_http2_ping_interval_in_secs(
+			maptos_config.indexer.maptos_indexer_grpc_inactivity_timeout,
+		)
+		.indexer_grpc_http2_ping_timeout_in_secs(
+			maptos_config.indexer.maptos_indexer_grpc_inactivity_ping_interval,
+		)
+		.auth_token(maptos_config.indexer_processor.indexer_processor_auth_token.clone())
+		.default_sleep_time_between_request(default_sleep_time_between_request)
+		.starting_version(starting_version)
+		.ending_version(end_version)
+}
 
-	let mut indexer_config =
-		server_framework::load::<IndexerGrpcProcessorConfig>(&output_file.path().to_path_buf())?;
+#[cfg(not(feature = "yaml-config"))]
+fn load_processor_conf(
+	builder: IndexerGrpcProcessorConfigBuilder,
+) -> Result<IndexerGrpcProcessorConfig, anyhow::Error> {
+	builder.build()
+}
 
-	// Leave here for debug purpose. Will be removed later.
-	// Use to print the generated config, to have an example when activating a new processor.
-	// indexer_config.processor_config = ProcessorConfig::TokenV2Processor(TokenV2ProcessorConfig {
-	// 	query_retries: 5,
-	// 	query_retry_delay_ms: 100,
-	// });
+/// Deprecated: writes the config to a temporary YAML file and loads it with `server_framework`.
+/// Only kept as a fallback until the in memory builder has been used in all environments.
+#[cfg(feature = "yaml-config")]
+fn load_processor_conf(
+	builder: IndexerGrpcProcessorConfigBuilder,
+) -> Result<IndexerGrpcProcessorConfig, anyhow::Error> {
+	use std::io::Write;
 
-	// let yaml = serde_yaml::to_string(&indexer_config)?;
-	// println!("{yaml}",);
+	let mut output_file = tempfile::NamedTempFile::new()?;
+	write!(output_file, "{}", builder.to_yaml()?)?;
 
-	Ok(indexer_config)
+	Ok(server_framework::load::<IndexerGrpcProcessorConfig>(&output_file.path().to_path_buf())?)
 }
 
 use reqwest::Client as HttpClient;
@@ -230,3 +329,20 @@ fn build_grpc_url(maptos_config: &maptos_execution_util::config::Config) -> Stri
 	);
 	indexer_grpc_data_service_address
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_compute_worker_threads() {
+		// Default config: 2 threads per CPU, at least 16.
+		assert_eq!(compute_worker_threads(1, 2, 16), 16);
+		assert_eq!(compute_worker_threads(8, 2, 16), 16);
+		assert_eq!(compute_worker_threads(32, 2, 16), 64);
+		// A runtime always needs one worker.
+		assert_eq!(compute_worker_threads(1, 0, 0), 1);
+		assert_eq!(compute_worker_threads(0, 2, 0), 1);
+		assert_eq!(compute_worker_threads(usize::MAX, 2, 16), usize::MAX);
+	}
+}
@@ -0,0 +1,179 @@
+use processor::IndexerGrpcProcessorConfig;
+use serde_yaml::{Mapping, Value};
+
+/// Builds an `IndexerGrpcProcessorConfig` in memory, without writing a YAML file to disk.
+///
+/// The config is assembled as a YAML value with the same fields as the config file
+/// and deserialized directly, so the processor crate stays the source of truth for defaults.
+pub struct IndexerGrpcProcessorConfigBuilder {
+	processor_name: String,
+	processor_options: Mapping,
+	postgres_connection_string: String,
+	indexer_grpc_data_service_address: String,
+	indexer_grpc_http2_ping_interval_in_secs: u64,
+	indexer_grpc_http2_ping_timeout_in_secs: u64,
+	auth_token: String,
+	default_sleep_time_between_request: u64,
+	starting_version: Option<u64>,
+	ending_version: Option<u64>,
+}
+
+impl IndexerGrpcProcessorConfigBuilder {
+	pub fn new(processor_name: &str) -> Self {
+		IndexerGrpcProcessorConfigBuilder {
+			processor_name: processor_name.to_string(),
+			processor_options: Mapping::new(),
+			postgres_connection_string: String::new(),
+			indexer_grpc_data_service_address: String::new(),
+			indexer_grpc_http2_ping_interval_in_secs: 0,
+			indexer_grpc_http2_ping_timeout_in_secs: 0,
+			auth_token: String::new(),
+			default_sleep_time_between_request: 10,
+			starting_version: None,
+			ending_version: None,
+		}
+	}
+
+	/// Adds a processor specific field, e.g. `query_retries` for the token v2 processor.
+	pub fn processor_option(mut self, name: &str, value: impl Into<Value>) -> Self {
+		self.processor_options.insert(Value::from(name), value.into());
+		self
+	}
+
+	pub fn postgres_connection_string(mut self, postgres_connection_string: String) -> Self {
+		self.postgres_connection_string = postgres_connection_string;
+		self
+	}
+
+	pub fn indexer_grpc_data_service_address(mut self, address: String) -> Self {
+		self.indexer_grpc_data_service_address = address;
+		self
+	}
+
+	pub fn indexer_grpc_http2_ping_interval_in_secs(mut self, secs: u64) -> Self {
+		self.indexer_grpc_http2_ping_interval_in_secs = secs;
+		self
+	}
+
+	pub fn indexer_grpc_http2_ping_timeout_in_secs(mut self, secs: u64) -> Self {
+		self.indexer_grpc_http2_ping_timeout_in_secs = secs;
+		self
+	}
+
+	pub fn auth_token(mut self, auth_token: String) -> Self {
+		self.auth_token = auth_token;
+		self
+	}
+
+	pub fn default_sleep_time_between_request(mut self, millis: u64) -> Self {
+		self.default_sleep_time_between_request = millis;
+		self
+	}
+
+	pub fn starting_version(mut self, starting_version: Option<u64>) -> Self {
+		self.starting_version = starting_version;
+		self
+	}
+
+	/// The processor stops once it has indexed `ending_version`.
+	pub fn ending_version(mut self, ending_version: Option<u64>) -> Self {
+		self.ending_version = ending_version;
+		self
+	}
+
+	fn to_value(&self) -> Value {
+		let mut processor_config = Mapping::new();
+		processor_config.insert("type".into(), self.processor_name.clone().into());
+		processor_config.extend(self.processor_options.clone());
+
+		let mut config = Mapping::new();
+		config.insert("processor_config".into(), processor_config.into());
+		config.insert(
+			"postgres_connection_string".into(),
+			format!("{}/postgres", self.postgres_connection_string).into(),
+		);
+		config.insert(
+			"indexer_grpc_data_service_address".into(),
+			self.indexer_grpc_data_service_address.clone().into(),
+		);
+		config.insert(
+			"indexer_grpc_http2_ping_interval_in_secs".into(),
+			self.indexer_grpc_http2_ping_interval_in_secs.into(),
+		);
+		config.insert(
+			"indexer_grpc_http2_ping_timeout_in_secs".into(),
+			self.indexer_grpc_http2_ping_timeout_in_secs.into(),
+		);
+		config.insert("auth_token".into(), self.auth_token.clone().into());
+		config.insert(
+			"default_sleep_time_between_request".into(),
+			self.default_sleep_time_between_request.into(),
+		);
+		if let Some(starting_version) = self.starting_version {
+			config.insert("starting_version".into(), starting_version.into());
+		}
+		if let Some(ending_version) = self.ending_version {
+			config.insert("ending_version".into(), ending_version.into());
+		}
+		config.into()
+	}
+
+	/// Content of the equivalent YAML config file.
+	#[cfg(feature = "yaml-config")]
+	pub fn to_yaml(&self) -> Result<String, anyhow::Error> {
+		Ok(serde_yaml::to_string(&self.to_value())?)
+	}
+
+	#[cfg_attr(feature = "yaml-config", allow(dead_code))]
+	pub fn build(self) -> Result<IndexerGrpcProcessorConfig, anyhow::Error> {
+		Ok(serde_yaml::from_value(self.to_value())?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_builder_value() {
+		let value = IndexerGrpcProcessorConfigBuilder::new("token_v2_processor")
+			.processor_option("query_retries", 5)
+			.postgres_connection_string("postgres://localhost:5432".to_string())
+			.indexer_grpc_data_service_address("http://0.0.0.0:30734".to_string())
+			.auth_token("auth_token".to_string())
+			.starting_version(Some(12))
+			.to_value();
+
+		assert_eq!(value["processor_config"]["type"], Value::from("token_v2_processor"));
+		assert_eq!(value["processor_config"]["query_retries"], Value::from(5));
+		assert_eq!(
+			value["postgres_connection_string"],
+			Value::from("postgres://localhost:5432/postgres")
+		);
+		assert_eq!(value["default_sleep_time_between_request"], Value::from(10));
+		assert_eq!(value["starting_version"], Value::from(12));
+	}
+
+	#[test]
+	fn test_builder_without_starting_version() {
+		let value = IndexerGrpcProcessorConfigBuilder::new("default_processor").to_value();
+		assert!(value.get("starting_version").is_none());
+		assert!(value.get("ending_version").is_none());
+	}
+
+	#[test]
+	fn test_builder_with_versions() -> Result<(), anyhow::Error> {
+		let builder = IndexerGrpcProcessorConfigBuilder::new("default_processor")
+			.indexer_grpc_data_service_address("http://0.0.0.0:30734".to_string())
+			.starting_version(Some(100))
+			.ending_version(Some(200));
+		let yaml = serde_yaml::to_string(&builder.to_value())?;
+		assert!(yaml.contains("starting_version: 100"));
+		assert!(yaml.contains("ending_version: 200"));
+
+		let config = builder.build()?;
+		assert_eq!(config.starting_version, Some(100));
+		assert_eq!(config.ending_version, Some(200));
+		Ok(())
+	}
+}
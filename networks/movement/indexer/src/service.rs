@@ -1,16 +1,104 @@
 use anyhow::Error;
 use futures::prelude::*;
 use poem::listener::TcpListener;
-use poem::{get, handler, IntoResponse, Response, Route, Server};
+use poem::{
+	get, handler,
+	http::StatusCode,
+	web::{Data, Json},
+	Endpoint, EndpointExt, IntoResponse, Response, Route, Server,
+};
+use std::collections::BTreeMap;
 use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
 
-pub fn run_service(url: String) -> impl Future<Output = Result<(), Error>> + Send {
-	let route = Route::new().at("/health", get(health));
+/// Whether each processor is still running, by processor name.
+#[derive(Clone, Default)]
+pub struct ProcessorsHealth {
+	processors: Arc<RwLock<BTreeMap<String, Arc<AtomicBool>>>>,
+}
+
+impl ProcessorsHealth {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Registers the processor `name` as running and returns its running flag.
+	pub fn register(&self, name: String) -> Arc<AtomicBool> {
+		let running = Arc::new(AtomicBool::new(true));
+		self.processors
+			.write()
+			.expect("processors health lock poisoned")
+			.insert(name, running.clone());
+		running
+	}
+
+	/// Returns whether each processor is running.
+	pub fn statuses(&self) -> BTreeMap<String, bool> {
+		let processors = self.processors.read().expect("processors health lock poisoned");
+		processors
+			.iter()
+			.map(|(name, running)| (name.clone(), running.load(Ordering::SeqCst)))
+			.collect()
+	}
+}
+
+pub fn run_service(
+	url: String,
+	health: ProcessorsHealth,
+) -> impl Future<Output = Result<(), Error>> + Send {
 	tracing::info!("Start health check access on :{url} .");
-	Server::new(TcpListener::bind(url)).run(route).map_err(Into::into)
+	Server::new(TcpListener::bind(url)).run(routes(health)).map_err(Into::into)
+}
+
+fn routes(health: ProcessorsHealth) -> impl Endpoint {
+	Route::new()
+		.at("/health", get(health_check))
+		.at("/healthz", get(processors_health))
+		.data(health)
 }
 
 #[handler]
-async fn health() -> Response {
+async fn health_check() -> Response {
 	"{\"OK\": \"healthy\"}".into_response()
 }
+
+/// Reports whether each processor is running, answering with 503 once one has exited.
+#[handler]
+async fn processors_health(health: Data<&ProcessorsHealth>) -> Response {
+	let processors = health.statuses();
+	let healthy = processors.values().all(|running| *running);
+	let (status, code) =
+		if healthy { ("ok", StatusCode::OK) } else { ("failed", StatusCode::SERVICE_UNAVAILABLE) };
+	Json(serde_json::json!({ "status": status, "processors": processors }))
+		.with_status(code)
+		.into_response()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use poem::test::TestClient;
+
+	#[tokio::test]
+	async fn test_healthz_reports_the_processors() {
+		let health = ProcessorsHealth::new();
+		health.register("default_processor".to_string());
+		let coin = health.register("coin_processor".to_string());
+		let client = TestClient::new(routes(health));
+
+		let response = client.get("/healthz").send().await;
+		response.assert_status_is_ok();
+		response
+			.assert_json(serde_json::json!({
+				"status": "ok",
+				"processors": { "coin_processor": true, "default_processor": true },
+			}))
+			.await;
+
+		// the coin processor exited
+		coin.store(false, Ordering::SeqCst);
+		let response = client.get("/healthz").send().await;
+		response.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+	}
+}
@@ -0,0 +1,110 @@
+use anyhow::Context;
+use server_framework::RunnableConfig;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+use tokio::task::JoinSet;
+use tokio::time::Duration;
+
+/// Spawns the processor of `config` on `set`, restarting it when it fails.
+/// The processor is restarted up to `max_attempts` times, waiting `base_delay` before
+/// the first restart and doubling the delay before each of the next ones.
+/// Once the restarts are exhausted, the task returns the last error to `set`.
+/// `running` is cleared when the task ends.
+/// Returns the counter of the restarts of the processor.
+pub fn spawn_with_restart<C: RunnableConfig>(
+	set: &mut JoinSet<Result<(), anyhow::Error>>,
+	config: C,
+	max_attempts: u32,
+	base_delay: Duration,
+	running: Arc<AtomicBool>,
+) -> Arc<AtomicU32> {
+	let restarts = Arc::new(AtomicU32::new(0));
+	set.spawn({
+		let restarts = restarts.clone();
+		async move {
+			let result = supervise(config, max_attempts, base_delay, &restarts).await;
+			running.store(false, Ordering::SeqCst);
+			result
+		}
+	});
+	restarts
+}
+
+async fn supervise<C: RunnableConfig>(
+	config: C,
+	max_attempts: u32,
+	base_delay: Duration,
+	restarts: &AtomicU32,
+) -> Result<(), anyhow::Error> {
+	let name = config.get_server_name();
+	let mut delay = base_delay;
+	loop {
+		// run on its own task, so that a panic is handled as a failure
+		let result = {
+			let config = config.clone();
+			tokio::spawn(async move { config.run().await }).await
+		};
+		let err = match result {
+			Ok(Ok(())) => return Ok(()),
+			Ok(Err(err)) => err,
+			Err(join_err) => anyhow::Error::from(join_err),
+		};
+
+		let attempt = restarts.load(Ordering::SeqCst);
+		if attempt >= max_attempts {
+			return Err(err)
+				.with_context(|| format!("Processor {} failed after {} restarts", name, attempt));
+		}
+		tracing::warn!(
+			"Processor {} failed, restarting in {:?} (restart {} of {}): {:#}",
+			name,
+			delay,
+			attempt + 1,
+			max_attempts,
+			err
+		);
+		tokio::time::sleep(delay).await;
+		restarts.fetch_add(1, Ordering::SeqCst);
+		delay = delay.saturating_mul(2);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde::Deserialize;
+
+	/// A processor that always fails.
+	#[derive(Clone, Deserialize)]
+	struct FailingConfig;
+
+	#[async_trait::async_trait]
+	impl RunnableConfig for FailingConfig {
+		async fn run(&self) -> anyhow::Result<()> {
+			anyhow::bail!("connection refused")
+		}
+
+		fn get_server_name(&self) -> String {
+			"failing_processor".to_string()
+		}
+	}
+
+	#[tokio::test]
+	async fn test_failing_processor_is_restarted() -> Result<(), anyhow::Error> {
+		let mut set = JoinSet::new();
+		let running = Arc::new(AtomicBool::new(true));
+		let restarts = spawn_with_restart(
+			&mut set,
+			FailingConfig,
+			3,
+			Duration::from_millis(1),
+			running.clone(),
+		);
+
+		let result = set.join_next().await.context("no task in the set")??;
+		assert!(result.is_err());
+		assert_eq!(restarts.load(Ordering::SeqCst), 3);
+		assert!(!running.load(Ordering::SeqCst));
+		Ok(())
+	}
+}
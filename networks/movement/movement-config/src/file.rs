@@ -0,0 +1,155 @@
+use crate::Config;
+use anyhow::Context;
+use serde_json::Value;
+use std::path::Path;
+
+/// The version of the config file schema written by [`Config::write_to_file`].
+pub const CONFIG_SCHEMA_VERSION: u32 = 2;
+
+/// The key of the schema version in the config file.
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// Upgrades the JSON of a config file by one schema version, in place.
+pub type Migration = fn(&mut Value) -> Result<(), anyhow::Error>;
+
+/// The migrations of the config file schema: `MIGRATIONS[i]` upgrades version `i + 1`
+/// to version `i + 2`. A migration is appended here with each bump of [`CONFIG_SCHEMA_VERSION`].
+const MIGRATIONS: &[Migration] = &[stringify_min_validator_stake];
+
+/// Version 2 holds `mcr.settle.min_validator_stake` as a decimal string instead of a number,
+/// as the stake of an 18 decimals token doesn't fit in a `u64`.
+fn stringify_min_validator_stake(json: &mut Value) -> Result<(), anyhow::Error> {
+	let Some(stake) = json.pointer_mut("/mcr/settle/min_validator_stake") else {
+		return Ok(());
+	};
+	if let Value::Number(number) = stake {
+		*stake = Value::String(number.to_string());
+	}
+	Ok(())
+}
+
+impl Config {
+	/// Writes the config as JSON to `path`, tagged with the current schema version.
+	pub fn write_to_file(&self, path: &Path) -> Result<(), anyhow::Error> {
+		let mut json = serde_json::to_value(self)?;
+		json.as_object_mut()
+			.context("The config is not serialized as a JSON object")?
+			.insert(SCHEMA_VERSION_KEY.to_string(), CONFIG_SCHEMA_VERSION.into());
+		std::fs::write(path, serde_json::to_string_pretty(&json)?)
+			.with_context(|| format!("Failed to write the config to {}", path.display()))?;
+		Ok(())
+	}
+
+	/// Reads the config from the JSON file at `path`, migrating it from an older schema version.
+	/// A file without a schema version, as written before versioning, is read as version 1.
+	pub fn read_from_file(path: &Path) -> Result<Self, anyhow::Error> {
+		let contents = std::fs::read_to_string(path)
+			.with_context(|| format!("Failed to read the config from {}", path.display()))?;
+		let json = serde_json::from_str(&contents)
+			.with_context(|| format!("Failed to parse the config {}", path.display()))?;
+		let json = migrate(json, CONFIG_SCHEMA_VERSION, MIGRATIONS)?;
+		Ok(serde_json::from_value(json)?)
+	}
+}
+
+/// Migrates the JSON of a config file to `target_version` and strips its schema version.
+fn migrate(
+	mut json: Value,
+	target_version: u32,
+	migrations: &[Migration],
+) -> Result<Value, anyhow::Error> {
+	let object = json.as_object_mut().context("The config file is not a JSON object")?;
+	let version = match object.remove(SCHEMA_VERSION_KEY) {
+		None => 1,
+		Some(version) => version
+			.as_u64()
+			.and_then(|version| u32::try_from(version).ok())
+			.context("The config schema version is not a u32")?,
+	};
+	if version == 0 || version > target_version {
+		anyhow::bail!(
+			"Unsupported config schema version {}, the latest is {}",
+			version,
+			target_version
+		);
+	}
+
+	for from in version..target_version {
+		let migration = migrations
+			.get(from as usize - 1)
+			.with_context(|| format!("No migration from config schema version {}", from))?;
+		migration(&mut json)
+			.with_context(|| format!("Failed to migrate the config from version {}", from))?;
+	}
+	Ok(json)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn test_round_trip() -> Result<(), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+		let path = dir.path().join("config.json");
+		let mut config = Config::default();
+		config.execution_config.maptos_config.load_shedding.max_transactions_in_flight = Some(42);
+
+		config.write_to_file(&path)?;
+		let written: Value = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+		assert_eq!(written[SCHEMA_VERSION_KEY], json!(CONFIG_SCHEMA_VERSION));
+
+		let read = Config::read_from_file(&path)?;
+		assert_eq!(serde_json::to_value(&read)?, serde_json::to_value(&config)?);
+		Ok(())
+	}
+
+	#[test]
+	fn test_unversioned_file_is_read() -> Result<(), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+		let path = dir.path().join("config.json");
+		std::fs::write(&path, serde_json::to_string(&Config::default())?)?;
+
+		Config::read_from_file(&path)?;
+		Ok(())
+	}
+
+	#[test]
+	fn test_migration_renames_a_field() -> Result<(), anyhow::Error> {
+		fn rename_port(json: &mut Value) -> Result<(), anyhow::Error> {
+			let object = json.as_object_mut().context("not an object")?;
+			let port = object.remove("port").context("missing port")?;
+			object.insert("listen_port".to_string(), port);
+			Ok(())
+		}
+
+		let v1 = json!({ "schema_version": 1, "port": 30731 });
+		let v2 = migrate(v1, 2, &[rename_port])?;
+		assert_eq!(v2, json!({ "listen_port": 30731 }));
+
+		// an up to date file is left as is
+		let v2 = migrate(json!({ "schema_version": 2, "listen_port": 30731 }), 2, &[rename_port])?;
+		assert_eq!(v2, json!({ "listen_port": 30731 }));
+		Ok(())
+	}
+
+	#[test]
+	fn test_min_validator_stake_is_migrated() -> Result<(), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+		let path = dir.path().join("config.json");
+		let mut v1 = serde_json::to_value(Config::default())?;
+		v1["mcr"]["settle"]["min_validator_stake"] = json!(1000);
+		std::fs::write(&path, serde_json::to_string(&v1)?)?;
+
+		let config = Config::read_from_file(&path)?;
+		assert_eq!(config.mcr.settle.min_validator_stake, "1000");
+		Ok(())
+	}
+
+	#[test]
+	fn test_newer_version_is_rejected() {
+		let json = json!({ "schema_version": CONFIG_SCHEMA_VERSION + 1 });
+		assert!(migrate(json, CONFIG_SCHEMA_VERSION, MIGRATIONS).is_err());
+	}
+}
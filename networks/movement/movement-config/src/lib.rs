@@ -1,12 +1,21 @@
 pub mod da_db;
 pub mod execution_extension;
+pub mod file;
+pub mod partial;
 pub mod syncing;
+pub mod watcher;
+
+pub use partial::PartialConfig;
+pub use watcher::ConfigWatcher;
 
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 use maptos_execution_util::config::MaptosConfig;
 use mcr_settlement_config::Config as McrConfig;
-use movement_celestia_da_util::config::CelestiaDaLightNodeConfig;
+use movement_celestia_da_util::config::{
+	CelestiaDaLightNodeConfig, ConfigError as DaLightNodeConfigError,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -43,3 +52,177 @@ impl Default for Config {
 		}
 	}
 }
+
+/// Misconfiguration detected by `Config::validate`.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ConfigValidationError {
+	#[error("maptos_rest_listen_port must be greater than 1024, got {0}")]
+	PrivilegedRestPort(u16),
+	#[error("maptos_faucet_rest_listen_hostname must not be empty")]
+	EmptyFaucetHostname,
+	#[error("Invalid indexer gRPC address {address}: {reason}")]
+	InvalidIndexerGrpcAddress { address: String, reason: String },
+	#[error("Invalid Postgres connection string: {0}")]
+	InvalidPostgresConnectionString(String),
+	#[error("Invalid MCR contract address {address}: {reason}")]
+	InvalidMcrContractAddress { address: String, reason: String },
+	#[error("Invalid DA light node config: {0}")]
+	DaLightNode(#[from] DaLightNodeConfigError),
+}
+
+impl Config {
+	/// Checks the config before the node is built, so that a misconfiguration is reported
+	/// at startup instead of when a connection fails. Returns all the errors found.
+	pub fn validate(&self) -> Result<(), Vec<ConfigValidationError>> {
+		let maptos_config = &self.execution_config.maptos_config;
+		let mut errors = Vec::new();
+
+		let rest_port = maptos_config.chain.maptos_rest_listen_port;
+		if rest_port <= 1024 {
+			errors.push(ConfigValidationError::PrivilegedRestPort(rest_port));
+		}
+
+		if maptos_config.faucet.maptos_faucet_rest_listen_hostname.trim().is_empty() {
+			errors.push(ConfigValidationError::EmptyFaucetHostname);
+		}
+
+		let indexer_address = format!(
+			"http://{}:{}",
+			maptos_config.indexer.maptos_indexer_grpc_listen_hostname,
+			maptos_config.indexer.maptos_indexer_grpc_listen_port
+		);
+		if let Err(err) = url::Url::parse(&indexer_address) {
+			errors.push(ConfigValidationError::InvalidIndexerGrpcAddress {
+				address: indexer_address,
+				reason: err.to_string(),
+			});
+		}
+
+		// the connection string is not included in the error, it holds the password
+		match url::Url::parse(&maptos_config.indexer_processor.postgres_connection_string) {
+			Ok(url) if url.scheme() == "postgres" || url.scheme() == "postgresql" => {}
+			Ok(url) => errors.push(ConfigValidationError::InvalidPostgresConnectionString(
+				format!("unsupported scheme {}", url.scheme()),
+			)),
+			Err(err) => {
+				errors.push(ConfigValidationError::InvalidPostgresConnectionString(err.to_string()))
+			}
+		}
+
+		let mcr_address = &self.mcr.settle.mcr_contract_address;
+		if let Err(reason) = validate_eth_address(mcr_address) {
+			errors.push(ConfigValidationError::InvalidMcrContractAddress {
+				address: mcr_address.clone(),
+				reason,
+			});
+		}
+
+		// the transactions are batched for the DA with the light node config
+		if let Err(da_light_node_errors) = self.celestia_da_light_node.validate() {
+			errors.extend(da_light_node_errors.into_iter().map(ConfigValidationError::from));
+		}
+
+		if errors.is_empty() {
+			Ok(())
+		} else {
+			Err(errors)
+		}
+	}
+}
+
+/// Checks that `address` is a 0x prefixed, 20 bytes hex string.
+fn validate_eth_address(address: &str) -> Result<(), String> {
+	let hex = address.strip_prefix("0x").ok_or_else(|| "missing 0x prefix".to_string())?;
+	if hex.len() != 40 {
+		return Err(format!("expected 40 hex digits, got {}", hex.len()));
+	}
+	if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+		return Err("not a hex string".to_string());
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_validate_default_config() {
+		assert_eq!(Config::default().validate(), Ok(()));
+	}
+
+	#[test]
+	fn test_validate_rest_port() {
+		let mut config = Config::default();
+		config.execution_config.maptos_config.chain.maptos_rest_listen_port = 80;
+		assert_eq!(config.validate(), Err(vec![ConfigValidationError::PrivilegedRestPort(80)]));
+	}
+
+	#[test]
+	fn test_validate_faucet_hostname() {
+		let mut config = Config::default();
+		config.execution_config.maptos_config.faucet.maptos_faucet_rest_listen_hostname =
+			" ".to_string();
+		assert_eq!(config.validate(), Err(vec![ConfigValidationError::EmptyFaucetHostname]));
+	}
+
+	#[test]
+	fn test_validate_indexer_grpc_address() {
+		let mut config = Config::default();
+		let indexer = &mut config.execution_config.maptos_config.indexer;
+		indexer.maptos_indexer_grpc_listen_hostname = "bad host".to_string();
+		let errors = config.validate().unwrap_err();
+		assert_eq!(errors.len(), 1);
+		assert!(matches!(errors[0], ConfigValidationError::InvalidIndexerGrpcAddress { .. }));
+	}
+
+	#[test]
+	fn test_validate_postgres_connection_string() {
+		for connection_string in ["localhost:5432", "mysql://root@localhost:3306"] {
+			let mut config = Config::default();
+			let indexer_processor = &mut config.execution_config.maptos_config.indexer_processor;
+			indexer_processor.postgres_connection_string = connection_string.to_string();
+			let errors = config.validate().unwrap_err();
+			assert_eq!(errors.len(), 1, "{}", connection_string);
+			assert!(matches!(errors[0], ConfigValidationError::InvalidPostgresConnectionString(_)));
+		}
+	}
+
+	#[test]
+	fn test_validate_mcr_contract_address() {
+		for address in [
+			"5fc8d32690cc91d4c39d9d3abcbd16989f875707",
+			"0x5fc8d32690cc91d4c39d9d3abcbd16989f8757",
+			"0x5fc8d32690cc91d4c39d9d3abcbd16989f87570g",
+		] {
+			let mut config = Config::default();
+			config.mcr.settle.mcr_contract_address = address.to_string();
+			let errors = config.validate().unwrap_err();
+			assert_eq!(errors.len(), 1, "{}", address);
+			assert!(matches!(errors[0], ConfigValidationError::InvalidMcrContractAddress { .. }));
+		}
+	}
+
+	#[test]
+	fn test_validate_da_light_node_max_batch_count() {
+		let mut config = Config::default();
+		let da_light_node = &mut config.celestia_da_light_node.celestia_da_light_node_config;
+		da_light_node.local_mut().da_light_node.movement_da_light_node_max_batch_count = 0;
+		assert_eq!(
+			config.validate(),
+			Err(vec![ConfigValidationError::DaLightNode(
+				DaLightNodeConfigError::ZeroMaxBatchCount
+			)])
+		);
+	}
+
+	#[test]
+	fn test_validate_reports_all_errors() {
+		let mut config = Config::default();
+		config.execution_config.maptos_config.chain.maptos_rest_listen_port = 443;
+		config.execution_config.maptos_config.faucet.maptos_faucet_rest_listen_hostname =
+			String::new();
+		config.mcr.settle.mcr_contract_address = "0x0".to_string();
+		assert_eq!(config.validate().unwrap_err().len(), 3);
+	}
+}
@@ -0,0 +1,203 @@
+use crate::Config;
+use aptos_types::chain_id::ChainId;
+use std::str::FromStr;
+
+/// The node specific fields of a [`Config`], to override a base config baked into an image.
+/// Each field is named after the field it overrides and read from the same environment
+/// variable as its default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PartialConfig {
+	/// `MAPTOS_CHAIN_ID`
+	pub maptos_chain_id: Option<ChainId>,
+	/// `MAPTOS_API_LISTEN_HOSTNAME`
+	pub maptos_rest_listen_hostname: Option<String>,
+	/// `MAPTOS_API_LISTEN_PORT`
+	pub maptos_rest_listen_port: Option<u16>,
+	/// `MAPTOS_READ_ONLY`
+	pub maptos_read_only: Option<bool>,
+	/// `FAUCET_API_LISTEN_HOSTNAME`
+	pub maptos_faucet_rest_listen_hostname: Option<String>,
+	/// `FAUCET_API_LISTEN_PORT`
+	pub maptos_faucet_rest_listen_port: Option<u16>,
+	/// `INDEXER_PROCESSOR_POSTGRES_CONNECTION_STRING`
+	pub postgres_connection_string: Option<String>,
+	/// `MOVEMENT_DA_LIGHT_NODE_CONNECTION_HOSTNAME`
+	pub movement_da_light_node_connection_hostname: Option<String>,
+	/// `MOVEMENT_DA_LIGHT_NODE_CONNECTION_PORT`
+	pub movement_da_light_node_connection_port: Option<u16>,
+	/// `ETH_RPC_CONNECTION_HOSTNAME`
+	pub eth_rpc_connection_hostname: Option<String>,
+	/// `ETH_RPC_CONNECTION_PORT`
+	pub eth_rpc_connection_port: Option<u16>,
+	/// `ETH_WS_CONNECTION_HOSTNAME`
+	pub eth_ws_connection_hostname: Option<String>,
+	/// `ETH_WS_CONNECTION_PORT`
+	pub eth_ws_connection_port: Option<u16>,
+	/// `MCR_CONTRACT_ADDRESS`
+	pub mcr_contract_address: Option<String>,
+	/// `ETH_SIGNER_PRIVATE_KEY`
+	pub signer_private_key: Option<String>,
+}
+
+impl PartialConfig {
+	/// Reads the environment variables that are set, the others are left to the base config.
+	pub fn from_env() -> Self {
+		Self::from_vars(|name| std::env::var(name).ok())
+	}
+
+	fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+		Self {
+			maptos_chain_id: parse(&var, "MAPTOS_CHAIN_ID"),
+			maptos_rest_listen_hostname: parse(&var, "MAPTOS_API_LISTEN_HOSTNAME"),
+			maptos_rest_listen_port: parse(&var, "MAPTOS_API_LISTEN_PORT"),
+			maptos_read_only: parse(&var, "MAPTOS_READ_ONLY"),
+			maptos_faucet_rest_listen_hostname: parse(&var, "FAUCET_API_LISTEN_HOSTNAME"),
+			maptos_faucet_rest_listen_port: parse(&var, "FAUCET_API_LISTEN_PORT"),
+			postgres_connection_string: parse(&var, "INDEXER_PROCESSOR_POSTGRES_CONNECTION_STRING"),
+			movement_da_light_node_connection_hostname: parse(
+				&var,
+				"MOVEMENT_DA_LIGHT_NODE_CONNECTION_HOSTNAME",
+			),
+			movement_da_light_node_connection_port: parse(
+				&var,
+				"MOVEMENT_DA_LIGHT_NODE_CONNECTION_PORT",
+			),
+			eth_rpc_connection_hostname: parse(&var, "ETH_RPC_CONNECTION_HOSTNAME"),
+			eth_rpc_connection_port: parse(&var, "ETH_RPC_CONNECTION_PORT"),
+			eth_ws_connection_hostname: parse(&var, "ETH_WS_CONNECTION_HOSTNAME"),
+			eth_ws_connection_port: parse(&var, "ETH_WS_CONNECTION_PORT"),
+			mcr_contract_address: parse(&var, "MCR_CONTRACT_ADDRESS"),
+			signer_private_key: parse(&var, "ETH_SIGNER_PRIVATE_KEY"),
+		}
+	}
+}
+
+/// Reads and parses the variable `name`, a value that can't be parsed is ignored.
+fn parse<T: FromStr>(var: &impl Fn(&str) -> Option<String>, name: &str) -> Option<T> {
+	let value = var(name)?;
+	let parsed = value.parse().ok();
+	if parsed.is_none() {
+		tracing::warn!("Ignoring the invalid value of {}", name);
+	}
+	parsed
+}
+
+/// Replaces `field` with the override if there is one.
+fn set<T>(field: &mut T, value: Option<T>) {
+	if let Some(value) = value {
+		*field = value;
+	}
+}
+
+impl Config {
+	/// Overrides the fields of `base` that are set in `overrides`.
+	pub fn merge(base: Config, overrides: PartialConfig) -> Config {
+		let mut config = base;
+
+		let maptos_config = &mut config.execution_config.maptos_config;
+		set(&mut maptos_config.chain.maptos_chain_id, overrides.maptos_chain_id);
+		set(
+			&mut maptos_config.chain.maptos_rest_listen_hostname,
+			overrides.maptos_rest_listen_hostname,
+		);
+		set(&mut maptos_config.chain.maptos_rest_listen_port, overrides.maptos_rest_listen_port);
+		set(&mut maptos_config.chain.maptos_read_only, overrides.maptos_read_only);
+		set(
+			&mut maptos_config.faucet.maptos_faucet_rest_listen_hostname,
+			overrides.maptos_faucet_rest_listen_hostname,
+		);
+		set(
+			&mut maptos_config.faucet.maptos_faucet_rest_listen_port,
+			overrides.maptos_faucet_rest_listen_port,
+		);
+		set(
+			&mut maptos_config.indexer_processor.postgres_connection_string,
+			overrides.postgres_connection_string,
+		);
+
+		let da_light_node = &mut config
+			.celestia_da_light_node
+			.celestia_da_light_node_config
+			.local_mut()
+			.da_light_node;
+		set(
+			&mut da_light_node.movement_da_light_node_connection_hostname,
+			overrides.movement_da_light_node_connection_hostname,
+		);
+		set(
+			&mut da_light_node.movement_da_light_node_connection_port,
+			overrides.movement_da_light_node_connection_port,
+		);
+
+		let eth_connection = &mut config.mcr.eth_connection;
+		set(&mut eth_connection.eth_rpc_connection_hostname, overrides.eth_rpc_connection_hostname);
+		set(&mut eth_connection.eth_rpc_connection_port, overrides.eth_rpc_connection_port);
+		set(&mut eth_connection.eth_ws_connection_hostname, overrides.eth_ws_connection_hostname);
+		set(&mut eth_connection.eth_ws_connection_port, overrides.eth_ws_connection_port);
+		set(&mut config.mcr.settle.mcr_contract_address, overrides.mcr_contract_address);
+		set(&mut config.mcr.settle.signer_private_key, overrides.signer_private_key);
+
+		config
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::collections::HashMap;
+
+	#[test]
+	fn test_merge_overrides_only_the_set_fields() {
+		let mut base = Config::default();
+		base.execution_config.maptos_config.chain.maptos_rest_listen_port = 30731;
+		base.execution_config.maptos_config.faucet.maptos_faucet_rest_listen_port = 30732;
+		base.mcr.settle.mcr_contract_address = "0x5fc8d32690cc91d4c39d9d3abcbd16989f875707".into();
+
+		let overrides = PartialConfig {
+			maptos_rest_listen_port: Some(40731),
+			mcr_contract_address: Some("0x0000000000000000000000000000000000000001".into()),
+			..Default::default()
+		};
+		let merged = Config::merge(base.clone(), overrides);
+
+		let maptos_config = &merged.execution_config.maptos_config;
+		assert_eq!(maptos_config.chain.maptos_rest_listen_port, 40731);
+		assert_eq!(
+			merged.mcr.settle.mcr_contract_address,
+			"0x0000000000000000000000000000000000000001"
+		);
+		// the fields without override are kept
+		assert_eq!(maptos_config.faucet.maptos_faucet_rest_listen_port, 30732);
+		assert_eq!(
+			maptos_config.chain.maptos_chain_id,
+			base.execution_config.maptos_config.chain.maptos_chain_id
+		);
+		assert_eq!(merged.mcr.settle.signer_private_key, base.mcr.settle.signer_private_key);
+	}
+
+	#[test]
+	fn test_merge_without_overrides_keeps_the_base() {
+		let base = Config::default();
+		let merged = Config::merge(base.clone(), PartialConfig::default());
+		assert_eq!(serde_json::to_value(&merged).unwrap(), serde_json::to_value(&base).unwrap());
+	}
+
+	#[test]
+	fn test_from_vars_reads_only_the_set_variables() {
+		let vars: HashMap<_, _> = [
+			("MOVEMENT_DA_LIGHT_NODE_CONNECTION_HOSTNAME", "light-node"),
+			("ETH_WS_CONNECTION_PORT", "not a port"),
+		]
+		.into_iter()
+		.collect();
+
+		let overrides =
+			PartialConfig::from_vars(|name| vars.get(name).map(|value| value.to_string()));
+		assert_eq!(
+			overrides.movement_da_light_node_connection_hostname,
+			Some("light-node".to_string())
+		);
+		assert_eq!(overrides.eth_ws_connection_port, None);
+		assert_eq!(overrides.eth_rpc_connection_hostname, None);
+	}
+}
@@ -0,0 +1,135 @@
+use crate::Config;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// Watches the config file and broadcasts the config each time the file changes.
+/// A config that fails to parse or to validate is not broadcast, the previous one stays current.
+/// The file stops being watched when the `ConfigWatcher` is dropped.
+pub struct ConfigWatcher {
+	sender: Arc<watch::Sender<Config>>,
+	// kept alive to keep receiving the file events
+	_watcher: RecommendedWatcher,
+}
+
+impl ConfigWatcher {
+	/// Starts watching the config file at `path`, with `initial` as the current config.
+	pub fn new(
+		path: impl Into<PathBuf>,
+		initial: Config,
+	) -> Result<(Self, watch::Receiver<Config>), anyhow::Error> {
+		let path = path.into();
+		let (sender, receiver) = watch::channel(initial);
+		let sender = Arc::new(sender);
+
+		let mut watcher = notify::recommended_watcher({
+			let sender = sender.clone();
+			let path = path.clone();
+			move |event: notify::Result<Event>| match event {
+				Ok(event) if is_update_of(&event, &path) => reload(&path, &sender),
+				Ok(_) => {}
+				Err(err) => tracing::warn!("Error watching the config file: {}", err),
+			}
+		})?;
+		// The parent directory is watched, as editors replace the file rather than write to it.
+		let directory = match path.parent() {
+			Some(parent) if !parent.as_os_str().is_empty() => parent,
+			_ => Path::new("."),
+		};
+		watcher.watch(directory, RecursiveMode::NonRecursive)?;
+
+		Ok((Self { sender, _watcher: watcher }, receiver))
+	}
+
+	/// Returns a new receiver of the config updates.
+	pub fn subscribe(&self) -> watch::Receiver<Config> {
+		self.sender.subscribe()
+	}
+}
+
+/// Whether `event` created or modified the file at `path`.
+fn is_update_of(event: &Event, path: &Path) -> bool {
+	matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+		&& event.paths.iter().any(|changed| changed.file_name() == path.file_name())
+}
+
+/// Reads the config file and broadcasts it if it is valid and differs from the current config.
+fn reload(path: &Path, sender: &watch::Sender<Config>) {
+	let config = match Config::read_from_file(path) {
+		Ok(config) => config,
+		Err(err) => {
+			// a write may be observed half done, the next event carries the complete file
+			tracing::warn!("Failed to reload the config file {}: {:#}", path.display(), err);
+			return;
+		}
+	};
+	if let Err(errors) = config.validate() {
+		let errors: Vec<_> = errors.iter().map(|err| err.to_string()).collect();
+		tracing::warn!(
+			"Ignoring the invalid config file {}: {}",
+			path.display(),
+			errors.join(", ")
+		);
+		return;
+	}
+
+	// a single write emits several events, only broadcast actual changes
+	let updated = serde_json::to_value(&config).ok();
+	sender.send_if_modified(|current| {
+		if serde_json::to_value(&*current).ok() == updated {
+			return false;
+		}
+		tracing::info!("Reloaded the config file {}", path.display());
+		*current = config;
+		true
+	});
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::time::Duration;
+
+	#[tokio::test]
+	async fn test_file_write_updates_the_receiver() -> Result<(), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+		let path = dir.path().join("config.json");
+		let mut config = Config::default();
+		config.write_to_file(&path)?;
+		let (_watcher, mut receiver) = ConfigWatcher::new(&path, config.clone())?;
+
+		config.execution_config.maptos_config.load_shedding.max_transactions_in_flight = Some(42);
+		config.write_to_file(&path)?;
+
+		tokio::time::timeout(Duration::from_secs(10), receiver.changed()).await??;
+		let updated = receiver.borrow();
+		let load_shedding = &updated.execution_config.maptos_config.load_shedding;
+		assert_eq!(load_shedding.max_transactions_in_flight, Some(42));
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_invalid_config_is_not_broadcast() -> Result<(), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+		let path = dir.path().join("config.json");
+		let mut config = Config::default();
+		config.write_to_file(&path)?;
+		let (_watcher, mut receiver) = ConfigWatcher::new(&path, config.clone())?;
+
+		let mut invalid = config.clone();
+		invalid.execution_config.maptos_config.chain.maptos_rest_listen_port = 80;
+		invalid.write_to_file(&path)?;
+		std::fs::write(dir.path().join("other.json"), "not a config")?;
+		config.execution_config.maptos_config.load_shedding.max_transactions_in_flight = Some(42);
+		config.write_to_file(&path)?;
+
+		// only the valid config is broadcast
+		tokio::time::timeout(Duration::from_secs(10), receiver.changed()).await??;
+		let updated = receiver.borrow();
+		let maptos_config = &updated.execution_config.maptos_config;
+		assert_ne!(maptos_config.chain.maptos_rest_listen_port, 80);
+		assert_eq!(maptos_config.load_shedding.max_transactions_in_flight, Some(42));
+		Ok(())
+	}
+}
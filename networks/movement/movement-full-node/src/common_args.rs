@@ -1,6 +1,5 @@
 use clap::Parser;
 use dot_movement::DotMovement;
-use godfig::{backend::config_file::ConfigFile, Godfig};
 use movement_config::Config;
 
 /// A struct containing common arguments for the Suzuka network.
@@ -30,9 +29,6 @@ impl MovementArgs {
 	/// Get the config
 	pub async fn config(&self) -> Result<Config, anyhow::Error> {
 		let dot_movement = self.dot_movement()?;
-		let config_file = dot_movement.try_get_or_create_config_file().await?;
-		let godfig: Godfig<Config, ConfigFile> = Godfig::new(ConfigFile::new(config_file), vec![]);
-
-		godfig.try_wait_for_ready().await.map_err(|e| e.into())
+		Config::read_from_file(&dot_movement.get_config_json_path())
 	}
 }
@@ -1,20 +1,20 @@
 use super::partial::MovementPartialNode;
 use anyhow::Context;
-use godfig::{backend::config_file::ConfigFile, Godfig};
-use movement_config::Config;
+use movement_config::{Config, ConfigWatcher};
+use std::path::PathBuf;
 use tokio::signal::unix::signal;
 use tokio::signal::unix::SignalKind;
+use tokio_util::sync::CancellationToken;
 
 #[derive(Clone)]
 pub struct Manager {
-	godfig: Godfig<Config, ConfigFile>,
+	config_path: PathBuf,
 }
 
 // Implements a very simple manager using a marker strategy pattern.
 impl Manager {
-	pub async fn new(file: tokio::fs::File) -> Result<Self, anyhow::Error> {
-		let godfig = Godfig::new(ConfigFile::new(file), vec![]);
-		Ok(Self { godfig })
+	pub async fn new(config_path: PathBuf) -> Result<Self, anyhow::Error> {
+		Ok(Self { config_path })
 	}
 
 	pub async fn try_run(&self) -> Result<(), anyhow::Error> {
@@ -41,23 +41,49 @@ impl Manager {
 			}
 		});
 
-		let config = self.godfig.try_wait_for_ready().await?;
+		// migrated from the schema version it was written with
+		let config = Config::read_from_file(&self.config_path)?;
+		// Fail on a misconfiguration before any client is built.
+		config.validate().map_err(|errors| {
+			let errors: Vec<_> = errors.iter().map(|err| format!("  - {}", err)).collect();
+			anyhow::anyhow!("Invalid node config:\n{}", errors.join("\n"))
+		})?;
 
-		let node = MovementPartialNode::try_from_config(config)
+		// The watcher is kept until the node stops, a node without it just does not reload.
+		let config_watcher = match ConfigWatcher::new(&self.config_path, config.clone()) {
+			Ok(watcher) => Some(watcher),
+			Err(err) => {
+				tracing::warn!("Config hot reload is disabled, failed to watch the config: {err}");
+				None
+			}
+		};
+
+		let mut node = MovementPartialNode::try_from_config(config)
 			.await
 			.context("Failed to create the executor")?;
+		if let Some((_, config_updates)) = &config_watcher {
+			node = node.with_config_updates(config_updates.clone());
+		}
 
-		let join_handle = tokio::spawn(node.run());
+		let shutdown = CancellationToken::new();
+		let mut join_handle = tokio::spawn(node.run(shutdown.clone()));
 
 		// Use tokio::select! to wait for either the handle or a cancellation signal
-		tokio::select! {
-			_ = stop_rx.changed() =>(),
+		let stopped = tokio::select! {
+			_ = stop_rx.changed() => true,
 			// manage Movement node execution return.
-			res = join_handle => {
+			res = &mut join_handle => {
 				res??;
+				false
 			},
 		};
 
+		if stopped {
+			// let the node drain its transaction pipe before exiting
+			shutdown.cancel();
+			join_handle.await??;
+		}
+
 		Ok(())
 	}
 }
@@ -5,13 +5,16 @@ use mcr_settlement_client::McrSettlementClient;
 use mcr_settlement_manager::CommitmentEventStream;
 use mcr_settlement_manager::McrSettlementManager;
 use movement_config::Config;
+use movement_da_light_node_client::retry::{RetryConfig, RetryableClient};
 use movement_da_light_node_client::MovementDaLightNodeClient;
 use movement_rest::MovementRest;
 
 use anyhow::Context;
-use tokio::sync::mpsc;
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
 use tokio::try_join;
-use tracing::debug;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
 
 pub struct MovementPartialNode<T> {
 	executor: T,
@@ -20,6 +23,8 @@ pub struct MovementPartialNode<T> {
 	commitment_events: Option<CommitmentEventStream>,
 	movement_rest: MovementRest,
 	config: Config,
+	config_updates: Option<watch::Receiver<Config>>,
+	stake_monitor: Option<tasks::stake_monitor::Task>,
 	da_db: DaDB,
 }
 
@@ -35,13 +40,51 @@ where
 		&self.executor
 	}
 
+	/// Applies the reloadable settings of the configs received on `config_updates`
+	/// to the running node.
+	pub fn with_config_updates(mut self, config_updates: watch::Receiver<Config>) -> Self {
+		self.config_updates = Some(config_updates);
+		self
+	}
+
 	// ! Currently this only implements opt.
 	/// Runs the executor until crash or shutdown.
-	pub async fn run(self) -> Result<(), anyhow::Error> {
+	/// Once `shutdown` is cancelled, returns when the transaction pipe has drained
+	/// and the transactions it passed on are written to the DA, or the drain timeout elapsed.
+	pub async fn run(self, shutdown: CancellationToken) -> Result<(), anyhow::Error> {
 		let (transaction_sender, transaction_receiver) = mpsc::channel(16);
-		let (context, exec_background) = self
-			.executor
-			.background(transaction_sender, &self.config.execution_config.maptos_config)?;
+		let (context, exec_background) = self.executor.background(
+			transaction_sender,
+			&self.config.execution_config.maptos_config,
+			shutdown.clone(),
+		)?;
+		if let Some(mut config_updates) = self.config_updates {
+			let in_flight_limit = self.executor.transactions_in_flight_limit();
+			// stops when the config stops being watched
+			tokio::spawn(async move {
+				while config_updates.changed().await.is_ok() {
+					let limit = config_updates
+						.borrow_and_update()
+						.execution_config
+						.maptos_config
+						.load_shedding
+						.max_transactions_in_flight;
+					if in_flight_limit.send_replace(limit) != limit {
+						info!("Updated the limit on transactions in flight to {:?}", limit);
+					}
+				}
+			});
+		}
+		if let Some(stake_monitor) = self.stake_monitor {
+			// the monitoring does not affect the node
+			tokio::spawn(async move {
+				if let Err(e) = stake_monitor.run().await {
+					warn!("Failed to monitor the validator stake: {e:#}");
+				}
+			});
+		}
+		let mempool_config = &self.config.execution_config.maptos_config.mempool;
+		let drain_timeout = Duration::from_millis(mempool_config.drain_timeout_ms);
 		let services = context.services();
 		let mut movement_rest = self.movement_rest;
 		movement_rest.set_context(services.opt_api_context());
@@ -54,29 +97,61 @@ where
 			self.config.execution_extension.clone(),
 			self.config.mcr.clone(),
 		);
+		// The writes failing while the light node is unreachable are retried before failing.
 		let transaction_ingress_task = tasks::transaction_ingress::Task::new(
 			transaction_receiver,
-			self.light_node_client,
+			RetryableClient::new(self.light_node_client, RetryConfig::default()),
 			// FIXME: why are the struct member names so tautological?
 			self.config.celestia_da_light_node.celestia_da_light_node_config,
-		);
-
-		let (
-			execution_and_settlement_result,
-			transaction_ingress_result,
-			background_task_result,
-			services_result,
-		) = try_join!(
-			tokio::spawn(async move { exec_settle_task.run().await }),
-			tokio::spawn(async move { transaction_ingress_task.run().await }),
-			tokio::spawn(exec_background),
-			tokio::spawn(services.run()),
-			// tokio::spawn(async move { movement_rest.run_service().await }),
+			context.metrics_registry(),
 		)?;
-		execution_and_settlement_result
-			.and(transaction_ingress_result)
-			.and(background_task_result)
-			.and(services_result)
+
+		let mut background_handle = tokio::spawn(exec_background);
+		let mut transaction_ingress_handle =
+			tokio::spawn(async move { transaction_ingress_task.run().await });
+		let tasks = async {
+			let (
+				execution_and_settlement_result,
+				transaction_ingress_result,
+				background_task_result,
+				services_result,
+			) = try_join!(
+				tokio::spawn(async move { exec_settle_task.run().await }),
+				&mut transaction_ingress_handle,
+				&mut background_handle,
+				tokio::spawn(services.run()),
+				// tokio::spawn(async move { movement_rest.run_service().await }),
+			)?;
+			execution_and_settlement_result
+				.and(transaction_ingress_result)
+				.and(background_task_result)
+				.and(services_result)
+		};
+
+		let result = tokio::select! {
+			result = tasks => Some(result),
+			_ = shutdown.cancelled() => None,
+		};
+		match result {
+			Some(result) => result,
+			None => {
+				// the background task stops on its own once it has drained the transaction pipe,
+				// which closes the transaction stream of the ingress task
+				let background_result = background_handle.await?;
+				// the ingress task then writes the batches left to the DA
+				match tokio::time::timeout(drain_timeout, &mut transaction_ingress_handle).await {
+					Ok(transaction_ingress_result) => transaction_ingress_result??,
+					Err(_) => {
+						warn!(
+							"Transactions not written to the DA within {:?} of the shutdown",
+							drain_timeout
+						);
+						transaction_ingress_handle.abort();
+					}
+				}
+				background_result
+			}
+		}
 	}
 }
 
@@ -109,33 +184,33 @@ impl MovementPartialNode<Executor> {
 			"Connecting to light node at {}:{}",
 			light_node_connection_hostname, light_node_connection_port
 		);
-		let light_node_client = if config
-			.celestia_da_light_node
-			.celestia_da_light_node_config
-			.movement_da_light_node_http1()
-		{
-			MovementDaLightNodeClient::try_http1(
-				format!(
-					"{}://{}:{}",
-					light_node_connection_protocol,
-					light_node_connection_hostname,
-					light_node_connection_port
-				)
-				.as_str(),
-			)
-			.context("Failed to connect to light node")?
-		} else {
-			MovementDaLightNodeClient::try_http2(
-				format!(
-					"{}://{}:{}",
-					light_node_connection_protocol,
-					light_node_connection_hostname,
-					light_node_connection_port
-				)
-				.as_str(),
+		let light_node_connection_string = format!(
+			"{}://{}:{}",
+			light_node_connection_protocol,
+			light_node_connection_hostname,
+			light_node_connection_port
+		);
+		let da_light_node_config = &config.celestia_da_light_node.celestia_da_light_node_config;
+		let light_node_client = match (
+			da_light_node_config.movement_da_light_node_tls_config(),
+			da_light_node_config.movement_da_light_node_http1(),
+		) {
+			(Some(_), true) => {
+				anyhow::bail!("TLS connections to the light node are only supported over HTTP/2")
+			}
+			(Some(tls_config), false) => MovementDaLightNodeClient::try_http2_with_tls(
+				&light_node_connection_string,
+				&tls_config.ca_cert,
+				&tls_config.client_cert,
+				&tls_config.client_key,
 			)
 			.await
-			.context("Failed to connect to light node")?
+			.context("Failed to connect to light node over TLS")?,
+			(None, true) => MovementDaLightNodeClient::try_http1(&light_node_connection_string)
+				.context("Failed to connect to light node")?,
+			(None, false) => MovementDaLightNodeClient::try_http2(&light_node_connection_string)
+				.await
+				.context("Failed to connect to light node")?,
 		};
 
 		debug!("Creating the executor");
@@ -154,6 +229,14 @@ impl MovementPartialNode<Executor> {
 			(None, None)
 		};
 
+		let min_validator_stake = config
+			.mcr
+			.settle
+			.parse_min_validator_stake()
+			.context("Failed to parse the minimum validator stake")?;
+		let stake_monitor = (config.mcr.should_settle() && !min_validator_stake.is_zero())
+			.then(|| tasks::stake_monitor::Task::new(config.mcr.clone(), min_validator_stake));
+
 		debug!("Creating the movement rest service");
 		let movement_rest =
 			MovementRest::try_from_env().context("Failed to create MovementRest")?;
@@ -169,6 +252,8 @@ impl MovementPartialNode<Executor> {
 			commitment_events,
 			movement_rest,
 			config,
+			config_updates: None,
+			stake_monitor,
 			da_db,
 		})
 	}
@@ -11,7 +11,7 @@ use movement_da_light_node_client::MovementDaLightNodeClient;
 use movement_da_light_node_proto::{
 	blob_response, StreamReadFromHeightRequest, StreamReadFromHeightResponse,
 };
-use movement_types::block::{Block, BlockCommitment, BlockCommitmentEvent};
+use movement_types::block::{self, Block, BlockCommitment, BlockCommitmentEvent};
 
 use anyhow::Context;
 use futures::{future::Either, stream};
@@ -130,14 +130,20 @@ where
 
 		let block: Block = bcs::from_bytes(&block_bytes[..])?;
 
-		// get the transactions
-		let transactions_count = block.transactions().len();
+		// decode the transactions once, for the execution and the senders in flight
+		let transactions = block
+			.transactions()
+			.map(|transaction| bcs::from_bytes::<SignedTransaction>(transaction.data()))
+			.collect::<Result<Vec<_>, _>>()?;
+		let senders: Vec<_> = transactions.iter().map(SignedTransaction::sender).collect();
 		let span = info_span!(target: "movement_timing", "execute_block", id = ?block_id);
-		let commitment =
-			self.execute_block_with_retries(block, block_timestamp).instrument(span).await?;
+		let commitment = self
+			.execute_block_with_retries(block.id(), transactions, block_timestamp)
+			.instrument(span)
+			.await?;
 
 		// decrement the number of transactions in flight on the executor
-		self.executor.decrement_transactions_in_flight(transactions_count as u64);
+		self.executor.decrement_transactions_in_flight(&senders);
 
 		// mark the da_height - 1 as synced
 		// we can't mark this height as synced because we must allow for the possibility of multiple blocks at the same height according to the m1 da specifications (which currently is built on celestia which itself allows more than one block at the same height)
@@ -182,12 +188,13 @@ where
 	/// However, this has to be deterministic, otherwise nodes will not be able to agree on the block commitment.
 	async fn execute_block_with_retries(
 		&mut self,
-		block: Block,
+		block_id: block::Id,
+		transactions: Vec<SignedTransaction>,
 		mut block_timestamp: u64,
 	) -> anyhow::Result<BlockCommitment> {
 		for _ in 0..self.execution_extension.block_retry_count {
-			// we have to clone here because the block is supposed to be consumed by the executor
-			match self.execute_block(block.clone(), block_timestamp).await {
+			// we have to clone here because the transactions are consumed by the executor
+			match self.execute_block(block_id, transactions.clone(), block_timestamp).await {
 				Ok(commitment) => return Ok(commitment),
 				Err(e) => {
 					info!("Failed to execute block: {:?}. Retrying", e);
@@ -201,11 +208,11 @@ where
 
 	async fn execute_block(
 		&mut self,
-		block: Block,
+		block_id: block::Id,
+		transactions: Vec<SignedTransaction>,
 		block_timestamp: u64,
 	) -> anyhow::Result<BlockCommitment> {
-		let block_id = block.id();
-		let block_hash = HashValue::from_slice(block.id())?;
+		let block_hash = HashValue::from_slice(block_id)?;
 
 		// get the transactions
 		let mut block_transactions = Vec::new();
@@ -217,9 +224,7 @@ where
 			SignatureVerifiedTransaction::Valid(Transaction::BlockMetadata(block_metadata));
 		block_transactions.push(block_metadata_transaction);
 
-		for transaction in block.transactions() {
-			let signed_transaction: SignedTransaction = bcs::from_bytes(transaction.data())?;
-
+		for signed_transaction in transactions {
 			// check if the transaction has already been executed to prevent replays
 			if self
 				.executor
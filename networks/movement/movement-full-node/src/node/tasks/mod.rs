@@ -1,4 +1,5 @@
 //! Modules to separate full node processing into actor-like tasks.
 
 pub mod execute_settle;
+pub mod stake_monitor;
 pub mod transaction_ingress;
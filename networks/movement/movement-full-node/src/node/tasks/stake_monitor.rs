@@ -0,0 +1,111 @@
+//! Task to warn when the stake of the validator nears the minimum stake.
+
+use alloy_primitives::U256;
+use mcr_settlement_client::{McrSettlementClient, McrStakeOperations};
+use mcr_settlement_config::Config as McrConfig;
+
+use tracing::{info, warn};
+
+use std::time::Duration;
+
+/// The stake is reported once it falls below this percentage of the minimum stake.
+const STAKE_WARNING_PERCENT: u64 = 110;
+
+pub struct Task {
+	mcr_config: McrConfig,
+	min_stake: U256,
+}
+
+impl Task {
+	pub(crate) fn new(mcr_config: McrConfig, min_stake: U256) -> Self {
+		Task { mcr_config, min_stake }
+	}
+
+	/// Checks the stake of the validator once per epoch.
+	/// Only returns if the settlement client cannot be built.
+	pub async fn run(self) -> anyhow::Result<()> {
+		let settlement_client = McrSettlementClient::build_with_config(&self.mcr_config).await?;
+		info!("Monitoring the stake of validator {}", settlement_client.signer_address);
+		monitor_stake(&settlement_client, self.min_stake).await
+	}
+}
+
+/// Checks the stake with `client` once per epoch, never returns.
+async fn monitor_stake<C: McrStakeOperations>(client: &C, min_stake: U256) -> anyhow::Result<()> {
+	let epoch_duration = match client.get_epoch_duration().await {
+		Ok(duration) => Duration::from_secs(duration.max(1)),
+		Err(e) => {
+			warn!("failed to get the epoch duration, checking the stake every minute: {e:#}");
+			Duration::from_secs(60)
+		}
+	};
+	info!("Checking the validator stake every {:?}", epoch_duration);
+
+	let mut interval = tokio::time::interval(epoch_duration);
+	loop {
+		interval.tick().await;
+		check_stake(client, min_stake).await;
+	}
+}
+
+/// Warns if the stake is below the warning threshold of `min_stake`.
+/// Returns whether the stake was reported.
+async fn check_stake<C: McrStakeOperations>(client: &C, min_stake: U256) -> bool {
+	match client.is_underfunded(warning_threshold(min_stake)).await {
+		Ok(underfunded) => {
+			if underfunded {
+				warn!(
+					"validator stake is below {STAKE_WARNING_PERCENT}% of the minimum stake {min_stake}"
+				);
+			}
+			underfunded
+		}
+		Err(e) => {
+			warn!("failed to get the validator stake: {e:#}");
+			false
+		}
+	}
+}
+
+/// The stake below which the stake is reported as close to `min_stake`.
+fn warning_threshold(min_stake: U256) -> U256 {
+	min_stake.saturating_mul(U256::from(STAKE_WARNING_PERCENT)) / U256::from(100)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use mcr_settlement_client::mock::McrSettlementClient as MockMcrSettlementClient;
+
+	#[tokio::test]
+	async fn test_low_stake_is_reported() {
+		let client = MockMcrSettlementClient::new();
+		// 1000 tokens with 18 decimals, more than a u64 holds
+		let min_stake: U256 = "1000000000000000000000".parse().unwrap();
+
+		// above the minimum, but within the margin
+		client.set_stake(min_stake + min_stake / U256::from(20)).await;
+		assert!(check_stake(&client, min_stake).await);
+
+		client.set_stake(min_stake * U256::from(2)).await;
+		assert!(!check_stake(&client, min_stake).await);
+	}
+
+	#[tokio::test]
+	async fn test_stake_is_checked_every_epoch() {
+		let mut client = MockMcrSettlementClient::new();
+		client.epoch_duration = 1;
+		let stake_reads = client.stake_reads.clone();
+		let monitor = tokio::spawn(async move { monitor_stake(&client, U256::from(1000)).await });
+
+		// the first check is immediate, the second one an epoch later
+		tokio::time::timeout(Duration::from_secs(5), async {
+			while *stake_reads.read().await < 2 {
+				tokio::time::sleep(Duration::from_millis(50)).await;
+			}
+		})
+		.await
+		.expect("the stake was not checked again after an epoch");
+		monitor.abort();
+	}
+}
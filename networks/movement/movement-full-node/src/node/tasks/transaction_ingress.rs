@@ -1,54 +1,337 @@
 //! Task to process incoming transactions and write to DA
 
+use anyhow::Context;
+use lru::LruCache;
 use maptos_dof_execution::SignedTransaction;
 use movement_celestia_da_util::config::Config as LightNodeConfig;
-use movement_da_light_node_client::MovementDaLightNodeClient;
-use movement_da_light_node_proto::{BatchWriteRequest, BlobWrite};
+use movement_da_light_node_client::retry::RetryableClient;
+use movement_da_light_node_proto::{BatchWriteRequest, BatchWriteResponse, BlobWrite};
 
+use prometheus::{Histogram, HistogramOpts, Registry};
+use sha2::{Digest, Sha256};
 use tokio::sync::mpsc;
-use tracing::{info, warn};
+use tokio::task::JoinSet;
+use tracing::{debug, error, info, warn};
 
 use prost::Message;
+use std::collections::{HashSet, VecDeque};
+use std::future::Future;
+use std::num::NonZeroUsize;
 use std::ops::ControlFlow;
 use std::sync::atomic::AtomicU64;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 const LOGGING_UID: AtomicU64 = AtomicU64::new(0);
 
-pub struct Task {
+/// The number of times a failed batch write to the DA is retried before the batch is discarded.
+const MAX_BATCH_WRITE_RETRIES: u32 = 3;
+
+/// Prometheus metrics of the transaction ingress [`Task`].
+pub struct TransactionIngressMetrics {
+	/// The time taken by the batch writes to the DA, in milliseconds.
+	pub da_write_duration_ms: Histogram,
+}
+
+impl TransactionIngressMetrics {
+	/// Creates the metrics and registers them with `registry`.
+	pub fn try_new(registry: &Registry) -> Result<Self, prometheus::Error> {
+		let da_write_duration_ms = Histogram::with_opts(
+			HistogramOpts::new(
+				"da_write_duration_ms",
+				"Time taken by a batch write to the DA, in milliseconds",
+			)
+			.buckets(vec![
+				5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0, 10000.0,
+				30000.0,
+			]),
+		)?;
+
+		registry.register(Box::new(da_write_duration_ms.clone()))?;
+
+		Ok(Self { da_write_duration_ms })
+	}
+}
+
+/// Writes the batches of transactions to the DA.
+pub trait DaBatchWriter: Clone + Send + 'static {
+	fn batch_write(
+		&mut self,
+		request: BatchWriteRequest,
+	) -> impl Future<Output = Result<BatchWriteResponse, tonic::Status>> + Send;
+}
+
+impl DaBatchWriter for RetryableClient {
+	fn batch_write(
+		&mut self,
+		request: BatchWriteRequest,
+	) -> impl Future<Output = Result<BatchWriteResponse, tonic::Status>> + Send {
+		RetryableClient::batch_write(self, request)
+	}
+}
+
+/// The state the [`Task`] shares with its background batch writes.
+struct BatchWrites {
+	/// The batches whose write to the DA failed, with the number of times each was retried.
+	retry_queue: Mutex<VecDeque<(BatchWriteRequest, u32)>>,
+	/// The SHA-256 hashes of the recently written transactions, to skip their resubmissions.
+	recently_written: Mutex<LruCache<[u8; 32], ()>>,
+	/// The SHA-256 hashes of the transactions being written or queued for retry,
+	/// their resubmissions are skipped too.
+	pending: Mutex<HashSet<[u8; 32]>>,
+	max_retries: u32,
+	metrics: TransactionIngressMetrics,
+}
+
+impl BatchWrites {
+	fn new(
+		dedup_capacity: NonZeroUsize,
+		max_retries: u32,
+		metrics: TransactionIngressMetrics,
+	) -> Self {
+		Self {
+			retry_queue: Mutex::new(VecDeque::new()),
+			recently_written: Mutex::new(LruCache::new(dedup_capacity)),
+			pending: Mutex::new(HashSet::new()),
+			max_retries,
+			metrics,
+		}
+	}
+
+	/// Takes the oldest batch whose write failed, with the number of times it was retried.
+	fn pop_retry(&self) -> Option<(BatchWriteRequest, u32)> {
+		self.retry_queue.lock().expect("retry queue lock poisoned").pop_front()
+	}
+
+	/// Whether the transaction with this hash was recently written to the DA,
+	/// or is being written.
+	fn written_or_pending(&self, hash: &[u8; 32]) -> bool {
+		self.pending.lock().expect("pending lock poisoned").contains(hash)
+			|| self
+				.recently_written
+				.lock()
+				.expect("recently written lock poisoned")
+				.contains(hash)
+	}
+
+	/// Marks the transactions of a batch as being written, until [`Self::write`] completes.
+	fn track_pending(&self, batch_write: &BatchWriteRequest) {
+		let mut pending = self.pending.lock().expect("pending lock poisoned");
+		pending.extend(batch_write.blobs.iter().map(blob_hash));
+	}
+
+	/// Forgets that the transactions of a batch are being written.
+	fn untrack_pending(&self, batch_write: &BatchWriteRequest) {
+		let mut pending = self.pending.lock().expect("pending lock poisoned");
+		for blob in &batch_write.blobs {
+			pending.remove(&blob_hash(blob));
+		}
+	}
+
+	/// Writes a batch to the DA with `write`, the batch having been retried `retries` times.
+	/// On success, the hashes of its transactions are remembered. On failure, the batch is
+	/// queued for retry, or discarded once it has been retried `max_retries` times.
+	/// The transactions stay pending until the batch is written or discarded.
+	async fn write<W, F, T, E>(
+		&self,
+		write: W,
+		batch_id: u64,
+		batch_write: BatchWriteRequest,
+		retries: u32,
+	) where
+		W: FnOnce(BatchWriteRequest) -> F,
+		F: Future<Output = Result<T, E>>,
+		E: std::fmt::Debug,
+	{
+		let batch_size = batch_write.blobs.len();
+		match record_da_write(&self.metrics, batch_id, batch_size, write(batch_write.clone())).await
+		{
+			Ok(_) => {
+				info!(
+					target: "movement_timing",
+					batch_id = %batch_id,
+					"batch_write_success"
+				);
+				{
+					let mut recently_written =
+						self.recently_written.lock().expect("recently written lock poisoned");
+					for blob in &batch_write.blobs {
+						recently_written.put(blob_hash(blob), ());
+					}
+				}
+				self.untrack_pending(&batch_write);
+			}
+			Err(e) if retries < self.max_retries => {
+				warn!("failed to write batch to DA, queued for retry: {:?} {:?}", e, batch_id);
+				self.retry_queue
+					.lock()
+					.expect("retry queue lock poisoned")
+					.push_back((batch_write, retries + 1));
+			}
+			Err(e) => {
+				error!(
+					"failed to write batch to DA after {} retries, discarding {} transactions: {:?} {:?}",
+					retries, batch_size, e, batch_id
+				);
+				self.untrack_pending(&batch_write);
+			}
+		}
+	}
+}
+
+/// The SHA-256 hash identifying a transaction of a batch.
+fn blob_hash(blob: &BlobWrite) -> [u8; 32] {
+	Sha256::digest(&blob.data).into()
+}
+
+pub struct Task<C = RetryableClient> {
 	transaction_receiver: mpsc::Receiver<(u64, SignedTransaction)>,
-	da_light_node_client: MovementDaLightNodeClient,
+	da_light_node_client: C,
 	da_light_node_config: LightNodeConfig,
+	// The maximum size of the serialized transactions of a batch
+	max_batch_bytes: usize,
+	// The maximum number of transactions of a batch
+	max_batch_count: usize,
+	// A transaction that did not fit in the previous batch, it opens the next one
+	overflow: Option<BlobWrite>,
+	// Filled by the background writes, the failed batches are flushed one per built batch
+	writes: Arc<BatchWrites>,
+	// The background writes, awaited when the transaction stream closes
+	in_flight: JoinSet<()>,
 }
 
-impl Task {
+impl<C: DaBatchWriter> Task<C> {
 	pub(crate) fn new(
 		transaction_receiver: mpsc::Receiver<(u64, SignedTransaction)>,
-		da_light_node_client: MovementDaLightNodeClient,
+		da_light_node_client: C,
 		da_light_node_config: LightNodeConfig,
-	) -> Self {
-		Task { transaction_receiver, da_light_node_client, da_light_node_config }
+		metrics_registry: &Registry,
+	) -> Result<Self, anyhow::Error> {
+		let dedup_capacity =
+			NonZeroUsize::new(da_light_node_config.movement_da_light_node_dedup_capacity())
+				.context("the DA light node dedup capacity must not be zero")?;
+		Ok(Task {
+			transaction_receiver,
+			da_light_node_client,
+			max_batch_bytes: da_light_node_config.movement_da_light_node_max_batch_bytes(),
+			max_batch_count: da_light_node_config.movement_da_light_node_max_batch_count(),
+			da_light_node_config,
+			overflow: None,
+			writes: Arc::new(BatchWrites::new(
+				dedup_capacity,
+				MAX_BATCH_WRITE_RETRIES,
+				TransactionIngressMetrics::try_new(metrics_registry)?,
+			)),
+			in_flight: JoinSet::new(),
+		})
 	}
 
+	/// Writes the received transactions to the DA until the transaction stream is closed,
+	/// then returns once the batches in flight and their retries are written or discarded.
 	pub async fn run(mut self) -> anyhow::Result<()> {
 		while let ControlFlow::Continue(()) = self.spawn_write_next_transaction_batch().await? {}
+		self.flush().await;
 		Ok(())
 	}
 
+	/// Awaits the background writes, retrying the failed batches until they are discarded.
+	async fn flush(&mut self) {
+		loop {
+			while let Some((batch_write, retries)) = self.writes.pop_retry() {
+				let batch_id = LOGGING_UID.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+				info!(batch_id = %batch_id, "retrying batch write to DA, retry {}", retries);
+				self.spawn_batch_write(batch_id, batch_write, retries);
+			}
+			if self.in_flight.join_next().await.is_none() {
+				break;
+			}
+		}
+	}
+
 	/// Constructs a batch of transactions then spawns the write request to the DA in the background.
+	/// A batch whose write previously failed is retried first.
 	async fn spawn_write_next_transaction_batch(
 		&mut self,
 	) -> Result<ControlFlow<(), ()>, anyhow::Error> {
 		use ControlFlow::{Break, Continue};
 
+		if let Some((batch_write, retries)) = self.writes.pop_retry() {
+			let batch_id = LOGGING_UID.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+			info!(batch_id = %batch_id, "retrying batch write to DA, retry {}", retries);
+			self.spawn_batch_write(batch_id, batch_write, retries);
+		}
+
+		let batch_id = LOGGING_UID.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+		let transactions = match self.build_next_transaction_batch(batch_id).await? {
+			Continue(transactions) => transactions,
+			Break(()) => return Ok(Break(())),
+		};
+
+		if transactions.len() > 0 {
+			info!(
+				target: "movement_timing",
+				batch_id = %batch_id,
+				transaction_count = transactions.len(),
+				"built_batch_write"
+			);
+			let batch_write = BatchWriteRequest { blobs: transactions };
+			let mut buf = Vec::new();
+			batch_write.encode_raw(&mut buf);
+			info!("batch_write size: {}", buf.len());
+			self.spawn_batch_write(batch_id, batch_write, 0);
+		}
+
+		Ok(Continue(()))
+	}
+
+	/// Spawns the write of a batch to the DA in the background,
+	/// the batch having been retried `retries` times.
+	fn spawn_batch_write(&mut self, batch_id: u64, batch_write: BatchWriteRequest, retries: u32) {
+		// forget the writes that have completed
+		while self.in_flight.try_join_next().is_some() {}
+		// before the write is spawned, so that the next batch already skips the resubmissions
+		self.writes.track_pending(&batch_write);
+		let mut da_light_node_client = self.da_light_node_client.clone();
+		let writes = self.writes.clone();
+		self.in_flight.spawn(async move {
+			writes
+				.write(
+					|request| async move { da_light_node_client.batch_write(request).await },
+					batch_id,
+					batch_write,
+					retries,
+				)
+				.await
+		});
+	}
+
+	/// Accumulates transactions until the half block building time has elapsed
+	/// or the batch has reached its size or count limit.
+	/// Returns `Break` once the transaction stream is closed and no transaction is left to batch.
+	async fn build_next_transaction_batch(
+		&mut self,
+		batch_id: u64,
+	) -> Result<ControlFlow<(), Vec<BlobWrite>>, anyhow::Error> {
+		use ControlFlow::{Break, Continue};
+
 		// limit the total time batching transactions
 		let start = Instant::now();
 		let (_, half_building_time) = self.da_light_node_config.try_block_building_parameters()?;
 
 		let mut transactions = Vec::new();
+		// the hashes of the batched transactions, to skip their resubmissions within the batch
+		let mut batched = HashSet::new();
+		let mut batch_bytes = 0;
+		if let Some(blob) = self.overflow.take() {
+			batch_bytes += blob.data.len();
+			batched.insert(blob_hash(&blob));
+			transactions.push(blob);
+		}
 
-		let batch_id = LOGGING_UID.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
 		loop {
+			if transactions.len() >= self.max_batch_count {
+				break;
+			}
 			let remaining = match half_building_time.checked_sub(start.elapsed().as_millis() as u64)
 			{
 				Some(remaining) => remaining,
@@ -81,11 +364,35 @@ impl Task {
 							transaction.sequence_number(),
 						);
 						let serialized_transaction = serde_json::to_vec(&movement_transaction)?;
-						transactions.push(BlobWrite { data: serialized_transaction });
+
+						// the first transaction of a batch is always taken, so that
+						// a transaction larger than the limit is not held back forever
+						let size = serialized_transaction.len();
+						let blob = BlobWrite { data: serialized_transaction };
+						let hash = blob_hash(&blob);
+						if batched.contains(&hash) || self.writes.written_or_pending(&hash) {
+							debug!(
+								batch_id = %batch_id,
+								tx_hash = %transaction.committed_hash(),
+								"skipping a transaction already batched",
+							);
+							continue;
+						}
+						if !transactions.is_empty() && batch_bytes + size > self.max_batch_bytes {
+							self.overflow = Some(blob);
+							break;
+						}
+						batch_bytes += size;
+						batched.insert(hash);
+						transactions.push(blob);
 					}
 					None => {
-						// The transaction stream is closed, terminate the task.
-						return Ok(Break(()));
+						// The transaction stream is closed, terminate the task
+						// once the transactions already batched are written.
+						if transactions.is_empty() {
+							return Ok(Break(()));
+						}
+						break;
 					}
 				},
 				Err(_) => {
@@ -94,36 +401,378 @@ impl Task {
 			}
 		}
 
-		if transactions.len() > 0 {
-			info!(
-				target: "movement_timing",
-				batch_id = %batch_id,
-				transaction_count = transactions.len(),
-				"built_batch_write"
-			);
-			let batch_write = BatchWriteRequest { blobs: transactions };
-			let mut buf = Vec::new();
-			batch_write.encode_raw(&mut buf);
-			info!("batch_write size: {}", buf.len());
-			// spawn the actual batch write request in the background
-			let mut da_light_node_client = self.da_light_node_client.clone();
-			tokio::spawn(async move {
-				match da_light_node_client.batch_write(batch_write.clone()).await {
-					Ok(_) => {
-						info!(
-							target: "movement_timing",
-							batch_id = %batch_id,
-							"batch_write_success"
-						);
-						return;
-					}
-					Err(e) => {
-						warn!("failed to write batch to DA: {:?} {:?}", e, batch_id);
-					}
+		Ok(Continue(transactions))
+	}
+}
+
+/// Awaits the DA write of a batch of `batch_size` transactions, recording its duration.
+async fn record_da_write<T, E>(
+	metrics: &TransactionIngressMetrics,
+	batch_id: u64,
+	batch_size: usize,
+	write: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+	let start = tokio::time::Instant::now();
+	let result = write.await;
+	let write_duration_ms = start.elapsed().as_millis() as u64;
+	metrics.da_write_duration_ms.observe(write_duration_ms as f64);
+	info!(
+		target: "movement_telemetry",
+		batch_id = %batch_id,
+		batch_size,
+		write_duration_ms,
+		success = result.is_ok(),
+		"da_batch_write"
+	);
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use aptos_crypto::ed25519::{Ed25519PrivateKey, Ed25519Signature};
+	use aptos_crypto::{PrivateKey, Uniform};
+	use aptos_types::account_address::AccountAddress;
+	use aptos_types::chain_id::ChainId;
+	use aptos_types::transaction::{RawTransaction, Script, TransactionPayload};
+	use std::sync::atomic::{AtomicU32, Ordering};
+
+	fn create_signed_transaction(sequence_number: u64) -> SignedTransaction {
+		let private_key = Ed25519PrivateKey::generate_for_testing();
+		let payload = TransactionPayload::Script(Script::new(vec![0], vec![], vec![]));
+		let raw_transaction = RawTransaction::new(
+			AccountAddress::random(),
+			sequence_number,
+			payload,
+			0,
+			0,
+			0,
+			ChainId::test(),
+		);
+		SignedTransaction::new(
+			raw_transaction,
+			private_key.public_key(),
+			Ed25519Signature::dummy_signature(),
+		)
+	}
+
+	/// A DA client failing its first `failures` batch writes.
+	/// The writes complete only while its gate is not write locked.
+	#[derive(Clone)]
+	struct MockDaClient {
+		failures: u32,
+		calls: Arc<AtomicU32>,
+		attempted: Arc<Mutex<Vec<BatchWriteRequest>>>,
+		delivered: Arc<Mutex<Vec<BatchWriteRequest>>>,
+		gate: Arc<tokio::sync::RwLock<()>>,
+	}
+
+	impl MockDaClient {
+		fn new(failures: u32) -> Self {
+			Self {
+				failures,
+				calls: Arc::default(),
+				attempted: Arc::default(),
+				delivered: Arc::default(),
+				gate: Arc::default(),
+			}
+		}
+
+		async fn write(&self, request: BatchWriteRequest) -> Result<(), tonic::Status> {
+			self.attempted.lock().unwrap().push(request.clone());
+			let calls = self.calls.fetch_add(1, Ordering::SeqCst);
+			let _gate = self.gate.read().await;
+			if calls < self.failures {
+				return Err(tonic::Status::unavailable("light node unavailable"));
+			}
+			self.delivered.lock().unwrap().push(request);
+			Ok(())
+		}
+
+		/// Waits until the client has been called `calls` times.
+		async fn wait_for_calls(&self, calls: u32) -> Result<(), anyhow::Error> {
+			tokio::time::timeout(Duration::from_secs(10), async {
+				while self.calls.load(Ordering::SeqCst) < calls {
+					tokio::time::sleep(Duration::from_millis(10)).await;
 				}
-			});
+			})
+			.await?;
+			Ok(())
 		}
+	}
 
-		Ok(Continue(()))
+	impl DaBatchWriter for MockDaClient {
+		async fn batch_write(
+			&mut self,
+			request: BatchWriteRequest,
+		) -> Result<BatchWriteResponse, tonic::Status> {
+			self.write(request).await?;
+			Ok(BatchWriteResponse { blobs: vec![] })
+		}
+	}
+
+	type TestTask = Task<MockDaClient>;
+
+	fn setup(
+		max_batch_bytes: usize,
+		max_batch_count: usize,
+	) -> Result<(TestTask, mpsc::Sender<(u64, SignedTransaction)>), anyhow::Error> {
+		setup_with_dedup_capacity(max_batch_bytes, max_batch_count, 16)
+	}
+
+	fn setup_with_dedup_capacity(
+		max_batch_bytes: usize,
+		max_batch_count: usize,
+		dedup_capacity: usize,
+	) -> Result<(TestTask, mpsc::Sender<(u64, SignedTransaction)>), anyhow::Error> {
+		setup_with_client(max_batch_bytes, max_batch_count, dedup_capacity, MockDaClient::new(0))
+	}
+
+	fn setup_with_client(
+		max_batch_bytes: usize,
+		max_batch_count: usize,
+		dedup_capacity: usize,
+		client: MockDaClient,
+	) -> Result<(TestTask, mpsc::Sender<(u64, SignedTransaction)>), anyhow::Error> {
+		let mut config = LightNodeConfig::default();
+		let local = config.local_mut();
+		local.memseq.memseq_build_time = 100;
+		local.da_light_node.movement_da_light_node_max_batch_bytes = max_batch_bytes;
+		local.da_light_node.movement_da_light_node_max_batch_count = max_batch_count;
+		local.da_light_node.movement_da_light_node_dedup_capacity = dedup_capacity;
+		let (sender, receiver) = mpsc::channel(16);
+		Ok((Task::new(receiver, client, config, &Registry::new())?, sender))
+	}
+
+	async fn next_batch(task: &mut TestTask) -> Result<Vec<BlobWrite>, anyhow::Error> {
+		match task.build_next_transaction_batch(0).await? {
+			ControlFlow::Continue(transactions) => Ok(transactions),
+			ControlFlow::Break(()) => anyhow::bail!("the transaction stream is closed"),
+		}
+	}
+
+	async fn next_batch_len(task: &mut TestTask) -> Result<usize, anyhow::Error> {
+		Ok(next_batch(task).await?.len())
+	}
+
+	#[tokio::test]
+	async fn test_batch_breaks_at_count_limit() -> Result<(), anyhow::Error> {
+		let (mut task, sender) = setup(usize::MAX, 2)?;
+		for sequence_number in 0..3 {
+			sender.send((0, create_signed_transaction(sequence_number))).await?;
+		}
+
+		assert_eq!(next_batch_len(&mut task).await?, 2);
+		assert_eq!(next_batch_len(&mut task).await?, 1);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_batch_breaks_at_byte_limit() -> Result<(), anyhow::Error> {
+		// room for a single transaction per batch
+		let (mut task, sender) = setup(1, usize::MAX)?;
+		for sequence_number in 0..3 {
+			sender.send((0, create_signed_transaction(sequence_number))).await?;
+		}
+
+		// the transaction that did not fit opens the next batch
+		assert_eq!(next_batch_len(&mut task).await?, 1);
+		assert_eq!(next_batch_len(&mut task).await?, 1);
+		assert_eq!(next_batch_len(&mut task).await?, 1);
+		assert_eq!(next_batch_len(&mut task).await?, 0);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_resubmitted_transaction_is_batched_once() -> Result<(), anyhow::Error> {
+		let (mut task, sender) = setup(usize::MAX, usize::MAX)?;
+		let transaction = create_signed_transaction(0);
+		sender.send((0, transaction.clone())).await?;
+		sender.send((0, transaction.clone())).await?;
+		let transactions = next_batch(&mut task).await?;
+		assert_eq!(transactions.len(), 1);
+
+		// nor in a later batch once written
+		let client = MockDaClient::new(0);
+		let write = |request| client.write(request);
+		task.writes.write(write, 0, BatchWriteRequest { blobs: transactions }, 0).await;
+		sender.send((0, transaction)).await?;
+		sender.send((0, create_signed_transaction(1))).await?;
+		assert_eq!(next_batch_len(&mut task).await?, 1);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_queued_transaction_is_skipped_until_discarded() -> Result<(), anyhow::Error> {
+		let (mut task, sender) = setup(usize::MAX, usize::MAX)?;
+		let transaction = create_signed_transaction(0);
+		sender.send((0, transaction.clone())).await?;
+		let batch_write = BatchWriteRequest { blobs: next_batch(&mut task).await? };
+
+		// the failed batch is queued for retry, the resubmission is skipped
+		let client = MockDaClient::new(u32::MAX);
+		task.writes.track_pending(&batch_write);
+		task.writes.write(|request| client.write(request), 0, batch_write, 0).await;
+		sender.send((0, transaction.clone())).await?;
+		assert_eq!(next_batch_len(&mut task).await?, 0);
+
+		// once the batch is discarded, the transaction is batched again
+		let (batch_write, _) = task.writes.pop_retry().expect("the failed batch is queued");
+		let write = |request| client.write(request);
+		task.writes.write(write, 0, batch_write, MAX_BATCH_WRITE_RETRIES).await;
+		sender.send((0, transaction)).await?;
+		assert_eq!(next_batch_len(&mut task).await?, 1);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_resubmission_during_write_is_delivered_once() -> Result<(), anyhow::Error> {
+		let client = MockDaClient::new(0);
+		let (task, sender) = setup_with_client(usize::MAX, usize::MAX, 16, client.clone())?;
+		// the writes to the DA don't complete until the gate is released
+		let gate = client.gate.clone().write_owned().await;
+		let task = tokio::spawn(task.run());
+		let transaction = create_signed_transaction(0);
+		sender.send((0, transaction.clone())).await?;
+		client.wait_for_calls(1).await?;
+
+		// the transaction is resubmitted while its batch is in flight
+		sender.send((0, transaction)).await?;
+		sender.send((0, create_signed_transaction(1))).await?;
+		client.wait_for_calls(2).await?;
+		drop(gate);
+		drop(sender);
+		task.await??;
+
+		// the writes complete in the background
+		tokio::time::timeout(Duration::from_secs(10), async {
+			while client.delivered.lock().unwrap().len() < 2 {
+				tokio::time::sleep(Duration::from_millis(10)).await;
+			}
+		})
+		.await?;
+		let delivered = client.delivered.lock().unwrap();
+		let blobs: Vec<_> = delivered.iter().flat_map(|batch| &batch.blobs).collect();
+		assert_eq!(blobs.len(), 2);
+		assert_ne!(blob_hash(blobs[0]), blob_hash(blobs[1]));
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_overflowing_transaction_is_batched_once() -> Result<(), anyhow::Error> {
+		// room for a single transaction per batch
+		let (mut task, sender) = setup(1, usize::MAX)?;
+		let transaction = create_signed_transaction(1);
+		sender.send((0, create_signed_transaction(0))).await?;
+		sender.send((0, transaction.clone())).await?;
+		assert_eq!(next_batch_len(&mut task).await?, 1);
+
+		// the transaction that overflowed opens the next batch, its resubmission is skipped
+		sender.send((0, transaction)).await?;
+		assert_eq!(next_batch_len(&mut task).await?, 1);
+		assert_eq!(next_batch_len(&mut task).await?, 0);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_evicted_transaction_is_batched_again() -> Result<(), anyhow::Error> {
+		let (mut task, sender) = setup_with_dedup_capacity(usize::MAX, usize::MAX, 1)?;
+		let client = MockDaClient::new(0);
+		let transaction = create_signed_transaction(0);
+		for sent in [transaction.clone(), create_signed_transaction(1)] {
+			sender.send((0, sent)).await?;
+			let transactions = next_batch(&mut task).await?;
+			let write = |request| client.write(request);
+			task.writes.write(write, 0, BatchWriteRequest { blobs: transactions }, 0).await;
+		}
+
+		sender.send((0, transaction)).await?;
+		assert_eq!(next_batch_len(&mut task).await?, 1);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_zero_dedup_capacity_is_rejected() {
+		assert!(setup_with_dedup_capacity(usize::MAX, usize::MAX, 0).is_err());
+	}
+
+	#[tokio::test]
+	async fn test_failed_batch_write_is_retried() -> Result<(), anyhow::Error> {
+		let client = MockDaClient::new(2);
+		let (task, sender) = setup_with_client(usize::MAX, usize::MAX, 16, client.clone())?;
+		let task = tokio::spawn(task.run());
+		sender.send((0, create_signed_transaction(0))).await?;
+
+		// a failed batch is retried when the next batch is built
+		client.wait_for_calls(3).await?;
+		drop(sender);
+		task.await??;
+		let delivered = client.delivered.lock().unwrap();
+		assert_eq!(delivered.len(), 1);
+		assert_eq!(delivered[0].blobs.len(), 1);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_batch_is_discarded_after_max_retries() -> Result<(), anyhow::Error> {
+		let client = MockDaClient::new(u32::MAX);
+		let (task, sender) = setup_with_client(usize::MAX, usize::MAX, 16, client.clone())?;
+		let task = tokio::spawn(task.run());
+		sender.send((0, create_signed_transaction(0))).await?;
+
+		// the first attempt and the retries
+		client.wait_for_calls(MAX_BATCH_WRITE_RETRIES + 1).await?;
+		// the batches built next, of another transaction until it is discarded too,
+		// don't write the discarded batch again
+		sender.send((0, create_signed_transaction(1))).await?;
+		client.wait_for_calls(2 * (MAX_BATCH_WRITE_RETRIES + 1)).await?;
+		drop(sender);
+		task.await??;
+
+		let attempted = client.attempted.lock().unwrap();
+		let discarded = blob_hash(&attempted[0].blobs[0]);
+		let attempts =
+			attempted.iter().filter(|batch| blob_hash(&batch.blobs[0]) == discarded).count();
+		assert_eq!(attempts, MAX_BATCH_WRITE_RETRIES as usize + 1);
+		assert_eq!(attempted.len(), 2 * (MAX_BATCH_WRITE_RETRIES as usize + 1));
+		assert!(client.delivered.lock().unwrap().is_empty());
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_batches_are_flushed_when_the_stream_closes() -> Result<(), anyhow::Error> {
+		// the first write fails, so the final flush retries it
+		let client = MockDaClient::new(1);
+		let (task, sender) = setup_with_client(usize::MAX, usize::MAX, 16, client.clone())?;
+		for sequence_number in 0..2 {
+			sender.send((0, create_signed_transaction(sequence_number))).await?;
+		}
+		drop(sender);
+
+		// the task returns once the batched transactions are delivered
+		task.run().await?;
+		let delivered = client.delivered.lock().unwrap();
+		assert_eq!(delivered.iter().map(|batch| batch.blobs.len()).sum::<usize>(), 2);
+		Ok(())
+	}
+
+	#[tracing_test::traced_test]
+	#[tokio::test]
+	async fn test_da_write_duration_is_recorded() -> Result<(), anyhow::Error> {
+		let registry = Registry::new();
+		let metrics = TransactionIngressMetrics::try_new(&registry)?;
+		// stands in for a write to the DA
+		let write = async {
+			tokio::time::sleep(Duration::from_millis(20)).await;
+			Err::<(), _>("unavailable")
+		};
+
+		assert!(record_da_write(&metrics, 7, 3, write).await.is_err());
+		assert!(logs_contain("write_duration_ms="));
+		assert!(logs_contain("batch_size=3"));
+		assert!(logs_contain("success=false"));
+		assert_eq!(metrics.da_write_duration_ms.get_sample_count(), 1);
+		assert!(metrics.da_write_duration_ms.get_sample_sum() >= 20.0);
+		Ok(())
 	}
 }
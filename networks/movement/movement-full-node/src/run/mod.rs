@@ -11,11 +11,8 @@ pub struct Run {
 
 impl Run {
 	pub async fn execute(&self) -> Result<(), anyhow::Error> {
-		// get the config file
 		let dot_movement = self.movement_args.dot_movement()?;
-		let config_file = dot_movement.try_get_or_create_config_file().await?;
-
-		let manager = Manager::new(config_file).await?;
+		let manager = Manager::new(dot_movement.get_config_json_path()).await?;
 		manager.try_run().await?;
 
 		Ok(())
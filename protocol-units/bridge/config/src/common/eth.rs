@@ -13,7 +13,11 @@ const DEFAULT_ETH_INITIATOR_CONTRACT: &str = "Oxeee";
 const DEFAULT_ETH_COUNTERPARTY_CONTRACT: &str = "0xccc";
 const DEFAULT_ETH_WETH_CONTRACT: &str = "0xe3e3";
 const DEFAULT_ETH_MOVETOKEN_CONTRACT: &str = "0xe3e2";
+const DEFAULT_ETH_RELAYER_FEE_CONTRACT: &str = "0x0000000000000000000000000000000000000000";
 const DEFAULT_ASSET: &str = "MOVE";
+const DEFAULT_ETH_CONFIRMATIONS_REQUIRED: u64 = 12;
+const DEFAULT_ETH_GAS_ESTIMATE_MULTIPLIER: f64 = 1.2;
+const DEFAULT_ETH_MAX_PRIORITY_FEE: u64 = 1_000_000_000; // 1 gwei
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EthConfig {
@@ -42,6 +46,12 @@ pub struct EthConfig {
 	pub eth_weth_contract: String,
 	#[serde(default = "default_eth_move_token_contract")]
 	pub eth_move_token_contract: String,
+	/// The contract paying the relayer fees. The zero address disables the fee claims.
+	#[serde(default = "default_eth_relayer_fee_contract")]
+	pub eth_relayer_fee_contract: String,
+	/// The fee claimed by the relayer for each transfer it relays, 0 to claim none.
+	#[serde(default = "default_eth_relayer_fee")]
+	pub eth_relayer_fee: u64,
 
 	#[serde(default = "default_signer_private_key")]
 	pub signer_private_key: String,
@@ -49,16 +59,32 @@ pub struct EthConfig {
 	#[serde(default = "default_time_lock_secs")]
 	pub time_lock_secs: u64,
 
+	/// The maximum fee of a transaction in wei.
+	/// 0 estimates the gas of each transaction instead, without capping the fee.
 	#[serde(default = "default_gas_limit")]
 	pub gas_limit: u64,
+	/// The factor applied to the node's gas estimates, as they are often too low.
+	#[serde(default = "default_eth_gas_estimate_multiplier")]
+	pub eth_gas_estimate_multiplier: f64,
 	#[serde(default = "default_transaction_send_retries")]
 	pub transaction_send_retries: u32,
+	/// The maximum fee per gas of the EIP-1559 transactions, in wei.
+	/// 0 sends legacy transactions instead.
+	#[serde(default = "default_eth_max_fee_per_gas")]
+	pub eth_max_fee_per_gas: u64,
+	/// The priority fee per gas tipped on top of the base fee by the EIP-1559 transactions, in wei.
+	#[serde(default = "default_eth_max_priority_fee")]
+	pub eth_max_priority_fee: u64,
 
 	#[serde(default = "default_asset")]
 	pub asset: String,
 
 	#[serde(default = "rest_connection_timeout_secs")]
 	pub rest_connection_timeout_secs: u64,
+
+	/// The number of blocks an event's block must be buried under before the event is relayed.
+	#[serde(default = "default_eth_confirmations_required")]
+	pub eth_confirmations_required: u64,
 }
 
 env_default!(
@@ -94,6 +120,13 @@ env_default!(
 	String,
 	DEFAULT_ETH_MOVETOKEN_CONTRACT.to_string()
 );
+env_default!(
+	default_eth_relayer_fee_contract,
+	"ETH_RELAYER_FEE_CONTRACT",
+	String,
+	DEFAULT_ETH_RELAYER_FEE_CONTRACT.to_string()
+);
+env_default!(default_eth_relayer_fee, "ETH_RELAYER_FEE", u64, 0);
 
 env_default!(default_asset, "ASSET", String, DEFAULT_ASSET.to_string());
 
@@ -103,6 +136,22 @@ env_short_default!(default_gas_limit, u64, 10_000_000_000_000_000 as u64);
 
 env_short_default!(default_transaction_send_retries, u32, 10 as u32);
 
+env_default!(
+	default_eth_gas_estimate_multiplier,
+	"ETH_GAS_ESTIMATE_MULTIPLIER",
+	f64,
+	DEFAULT_ETH_GAS_ESTIMATE_MULTIPLIER
+);
+
+env_default!(default_eth_max_fee_per_gas, "ETH_MAX_FEE_PER_GAS", u64, 0);
+
+env_default!(
+	default_eth_max_priority_fee,
+	"ETH_MAX_PRIORITY_FEE",
+	u64,
+	DEFAULT_ETH_MAX_PRIORITY_FEE
+);
+
 env_default!(
 	default_eth_rpc_connection_protocol,
 	"ETH_RPC_CONNECTION_PROTOCOL",
@@ -147,6 +196,13 @@ env_default!(
 
 env_default!(default_eth_chain_id, "ETH_CHAIN_ID", u64, 0);
 
+env_default!(
+	default_eth_confirmations_required,
+	"ETH_CONFIRMATIONS_REQUIRED",
+	u64,
+	DEFAULT_ETH_CONFIRMATIONS_REQUIRED
+);
+
 pub fn default_signer_private_key() -> String {
 	let random_wallet = PrivateKeySigner::random();
 	let random_wallet_string = random_wallet.to_bytes().to_string();
@@ -189,16 +245,23 @@ impl Default for EthConfig {
 			eth_counterparty_contract: default_eth_counterparty_contract(),
 			eth_weth_contract: default_eth_weth_contract(),
 			eth_move_token_contract: default_eth_move_token_contract(),
+			eth_relayer_fee_contract: default_eth_relayer_fee_contract(),
+			eth_relayer_fee: default_eth_relayer_fee(),
 
 			time_lock_secs: default_time_lock_secs(),
 
 			signer_private_key: default_signer_private_key(),
 			gas_limit: default_gas_limit(),
+			eth_gas_estimate_multiplier: default_eth_gas_estimate_multiplier(),
 			transaction_send_retries: default_transaction_send_retries(),
+			eth_max_fee_per_gas: default_eth_max_fee_per_gas(),
+			eth_max_priority_fee: default_eth_max_priority_fee(),
 
 			asset: default_asset(),
 
 			rest_connection_timeout_secs: rest_connection_timeout_secs(),
+
+			eth_confirmations_required: default_eth_confirmations_required(),
 		}
 	}
 }
@@ -12,6 +12,12 @@ const DEFAULT_REST_LISTENER_HOSTNAME: &str = "0.0.0.0";
 const DEFAULT_GRPC_LISTENER_HOSTNAME: &str = "0.0.0.0";
 const DEFAULT_GRPC_LISTENER_PORT: u16 = 50051;
 const DEFAULT_REST_LISTENER_PORT: u16 = 30883;
+const DEFAULT_NODE_READY_TIMEOUT_SECS: u64 = 60;
+const DEFAULT_CONFIRM_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_TIME_LOCK_MIN_SECS: u64 = 1;
+const DEFAULT_TIME_LOCK_MAX_SECS: u64 = 7 * 24 * 60 * 60;
+const DEFAULT_PACKAGE_BUILD_DIR: &str = "move-modules/build/bridge-modules";
+const DEFAULT_MVT_FAUCET_REQUESTS_PER_MINUTE: u32 = 60;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MovementConfig {
@@ -52,6 +58,31 @@ pub struct MovementConfig {
 	pub grpc_port: u16,
 	#[serde(default = "rest_connection_timeout_secs")]
 	pub rest_connection_timeout_secs: u64,
+	/// How long the client waits for the node to answer when it's built.
+	#[serde(default = "default_node_ready_timeout_secs")]
+	pub node_ready_timeout_secs: u64,
+	/// How long the client waits for a submitted transaction to be confirmed.
+	#[serde(default = "default_confirm_timeout_secs")]
+	pub confirm_timeout_secs: u64,
+	/// Bounds accepted by the client when setting the bridge time lock durations.
+	#[serde(default = "default_time_lock_min_secs")]
+	pub time_lock_min_secs: u64,
+	#[serde(default = "default_time_lock_max_secs")]
+	pub time_lock_max_secs: u64,
+	/// Allows building the client before `movement_native_address` is set.
+	/// Otherwise a zero native address is rejected when the client is built.
+	#[serde(default = "default_lazy_init")]
+	pub lazy_init: bool,
+	/// Build output of the bridge Move package, used to deploy the counterparty module.
+	/// A relative path is resolved against the `.movement` directory.
+	#[serde(default = "default_package_build_dir")]
+	pub package_build_dir: String,
+	/// Maximum number of faucet requests sent per minute, to stay under the faucet rate limit.
+	#[serde(default = "default_mvt_faucet_requests_per_minute")]
+	pub mvt_faucet_requests_per_minute: u32,
+	/// Simulates each transaction before submitting it, and doesn't submit it if it would fail.
+	#[serde(default = "default_simulate_on_submit")]
+	pub simulate_on_submit: bool,
 }
 
 env_default!(
@@ -61,6 +92,35 @@ env_default!(
 	DEFAULT_REST_CONNECTION_TIMEOUT
 );
 
+env_default!(
+	default_node_ready_timeout_secs,
+	"MVT_NODE_READY_TIMEOUT",
+	u64,
+	DEFAULT_NODE_READY_TIMEOUT_SECS
+);
+
+env_default!(
+	default_confirm_timeout_secs,
+	"MVT_CONFIRM_TIMEOUT",
+	u64,
+	DEFAULT_CONFIRM_TIMEOUT_SECS
+);
+
+env_default!(default_time_lock_min_secs, "MVT_TIME_LOCK_MIN_SECS", u64, DEFAULT_TIME_LOCK_MIN_SECS);
+
+env_default!(default_time_lock_max_secs, "MVT_TIME_LOCK_MAX_SECS", u64, DEFAULT_TIME_LOCK_MAX_SECS);
+
+env_default!(default_lazy_init, "MVT_LAZY_INIT", bool, false);
+
+env_default!(default_simulate_on_submit, "MVT_SIMULATE_ON_SUBMIT", bool, false);
+
+env_default!(
+	default_package_build_dir,
+	"MVT_PACKAGE_BUILD_DIR",
+	String,
+	DEFAULT_PACKAGE_BUILD_DIR.to_string()
+);
+
 // The default private key
 pub fn default_movement_signer_key() -> Ed25519PrivateKey {
 	match std::env::var("MOVEMENT_SIGNER_KEY") {
@@ -143,6 +203,13 @@ env_default!(
 	DEFAULT_MVT_FAUCET_CONNECTION_PORT
 );
 
+env_default!(
+	default_mvt_faucet_requests_per_minute,
+	"MVT_FAUCET_REQUESTS_PER_MINUTE",
+	u32,
+	DEFAULT_MVT_FAUCET_REQUESTS_PER_MINUTE
+);
+
 env_default!(default_mvt_init_network, "MVT_FAUCET_INIT_NETWORK", String, "local".to_string());
 
 impl MovementConfig {
@@ -185,6 +252,14 @@ impl MovementConfig {
 			grpc_listener_hostname: default_grpc_listener_hostname(),
 			grpc_port: default_grpc_listener_port(),
 			rest_connection_timeout_secs: rest_connection_timeout_secs(),
+			node_ready_timeout_secs: default_node_ready_timeout_secs(),
+			confirm_timeout_secs: default_confirm_timeout_secs(),
+			time_lock_min_secs: default_time_lock_min_secs(),
+			time_lock_max_secs: default_time_lock_max_secs(),
+			lazy_init: default_lazy_init(),
+			package_build_dir: default_package_build_dir(),
+			mvt_faucet_requests_per_minute: default_mvt_faucet_requests_per_minute(),
+			simulate_on_submit: default_simulate_on_submit(),
 		}
 	}
 }
@@ -207,6 +282,14 @@ impl Default for MovementConfig {
 			grpc_listener_hostname: default_grpc_listener_hostname(),
 			grpc_port: default_grpc_listener_port(),
 			rest_connection_timeout_secs: rest_connection_timeout_secs(),
+			node_ready_timeout_secs: default_node_ready_timeout_secs(),
+			confirm_timeout_secs: default_confirm_timeout_secs(),
+			time_lock_min_secs: default_time_lock_min_secs(),
+			time_lock_max_secs: default_time_lock_max_secs(),
+			lazy_init: default_lazy_init(),
+			package_build_dir: default_package_build_dir(),
+			mvt_faucet_requests_per_minute: default_mvt_faucet_requests_per_minute(),
+			simulate_on_submit: default_simulate_on_submit(),
 		}
 	}
 }
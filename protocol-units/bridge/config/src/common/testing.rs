@@ -32,3 +32,22 @@ impl TestingConfig {
 			.expect("Testing config initiator private key parsing error")
 	}
 }
+
+/// Overrides applied on top of a base [`crate::Config`] by
+/// [`crate::Config::merge_with_testing_config`]. Only the fields set to `Some` are replaced.
+#[derive(Debug, Clone, Default)]
+pub struct BridgeTestConfig {
+	pub eth_rpc_connection_hostname: Option<String>,
+	pub eth_rpc_connection_port: Option<u16>,
+	pub eth_ws_connection_hostname: Option<String>,
+	pub eth_ws_connection_port: Option<u16>,
+	pub eth_chain_id: Option<u64>,
+	pub eth_signer_private_key: Option<String>,
+	pub eth_time_lock_secs: Option<u64>,
+	pub mvt_rpc_connection_hostname: Option<String>,
+	pub mvt_rpc_connection_port: Option<u16>,
+	pub mvt_faucet_connection_hostname: Option<String>,
+	pub mvt_faucet_connection_port: Option<u16>,
+	pub mvt_init_network: Option<String>,
+	pub eth_well_known_account_private_keys: Option<Vec<String>>,
+}
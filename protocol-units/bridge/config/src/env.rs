@@ -0,0 +1,341 @@
+//! Reading and writing the bridge [`Config`] from and to `BRIDGE_`-prefixed environment variables.
+
+use crate::Config;
+use anyhow::Context;
+use aptos_crypto::{ed25519::Ed25519PrivateKey, ValidCryptoMaterialStringExt};
+use std::env;
+use std::str::FromStr;
+
+// Eth
+pub const BRIDGE_ETH_RPC_URL: &str = "BRIDGE_ETH_RPC_URL";
+pub const BRIDGE_ETH_WS_URL: &str = "BRIDGE_ETH_WS_URL";
+pub const BRIDGE_ETH_CHAIN_ID: &str = "BRIDGE_ETH_CHAIN_ID";
+pub const BRIDGE_ETH_INITIATOR_CONTRACT: &str = "BRIDGE_ETH_INITIATOR_CONTRACT";
+pub const BRIDGE_ETH_COUNTERPARTY_CONTRACT: &str = "BRIDGE_ETH_COUNTERPARTY_CONTRACT";
+pub const BRIDGE_ETH_WETH_CONTRACT: &str = "BRIDGE_ETH_WETH_CONTRACT";
+pub const BRIDGE_ETH_MOVE_TOKEN_CONTRACT: &str = "BRIDGE_ETH_MOVE_TOKEN_CONTRACT";
+pub const BRIDGE_ETH_SIGNER_KEY: &str = "BRIDGE_ETH_SIGNER_KEY";
+pub const BRIDGE_ETH_TIME_LOCK_SECS: &str = "BRIDGE_ETH_TIME_LOCK_SECS";
+pub const BRIDGE_ETH_GAS_LIMIT: &str = "BRIDGE_ETH_GAS_LIMIT";
+pub const BRIDGE_ETH_GAS_ESTIMATE_MULTIPLIER: &str = "BRIDGE_ETH_GAS_ESTIMATE_MULTIPLIER";
+pub const BRIDGE_ETH_TRANSACTION_SEND_RETRIES: &str = "BRIDGE_ETH_TRANSACTION_SEND_RETRIES";
+pub const BRIDGE_ETH_ASSET: &str = "BRIDGE_ETH_ASSET";
+pub const BRIDGE_ETH_REST_CONNECTION_TIMEOUT_SECS: &str = "BRIDGE_ETH_REST_CONNECTION_TIMEOUT_SECS";
+pub const BRIDGE_ETH_CONFIRMATIONS_REQUIRED: &str = "BRIDGE_ETH_CONFIRMATIONS_REQUIRED";
+pub const BRIDGE_ETH_MAX_FEE_PER_GAS: &str = "BRIDGE_ETH_MAX_FEE_PER_GAS";
+pub const BRIDGE_ETH_MAX_PRIORITY_FEE: &str = "BRIDGE_ETH_MAX_PRIORITY_FEE";
+
+// Movement
+pub const BRIDGE_MOVEMENT_SIGNER_KEY: &str = "BRIDGE_MOVEMENT_SIGNER_KEY";
+pub const BRIDGE_MOVEMENT_NATIVE_ADDRESS: &str = "BRIDGE_MOVEMENT_NATIVE_ADDRESS";
+pub const BRIDGE_MOVEMENT_RPC_URL: &str = "BRIDGE_MOVEMENT_RPC_URL";
+pub const BRIDGE_MOVEMENT_FAUCET_URL: &str = "BRIDGE_MOVEMENT_FAUCET_URL";
+pub const BRIDGE_MOVEMENT_INIT_NETWORK: &str = "BRIDGE_MOVEMENT_INIT_NETWORK";
+pub const BRIDGE_MOVEMENT_REST_LISTENER_HOSTNAME: &str = "BRIDGE_MOVEMENT_REST_LISTENER_HOSTNAME";
+pub const BRIDGE_MOVEMENT_REST_PORT: &str = "BRIDGE_MOVEMENT_REST_PORT";
+pub const BRIDGE_MOVEMENT_GRPC_PROTOCOL: &str = "BRIDGE_MOVEMENT_GRPC_PROTOCOL";
+pub const BRIDGE_MOVEMENT_GRPC_LISTENER_HOSTNAME: &str = "BRIDGE_MOVEMENT_GRPC_LISTENER_HOSTNAME";
+pub const BRIDGE_MOVEMENT_GRPC_PORT: &str = "BRIDGE_MOVEMENT_GRPC_PORT";
+pub const BRIDGE_MOVEMENT_REST_CONNECTION_TIMEOUT_SECS: &str =
+	"BRIDGE_MOVEMENT_REST_CONNECTION_TIMEOUT_SECS";
+pub const BRIDGE_MOVEMENT_NODE_READY_TIMEOUT_SECS: &str = "BRIDGE_MOVEMENT_NODE_READY_TIMEOUT_SECS";
+pub const BRIDGE_MOVEMENT_CONFIRM_TIMEOUT_SECS: &str = "BRIDGE_MOVEMENT_CONFIRM_TIMEOUT_SECS";
+pub const BRIDGE_MOVEMENT_TIME_LOCK_MIN_SECS: &str = "BRIDGE_MOVEMENT_TIME_LOCK_MIN_SECS";
+pub const BRIDGE_MOVEMENT_TIME_LOCK_MAX_SECS: &str = "BRIDGE_MOVEMENT_TIME_LOCK_MAX_SECS";
+pub const BRIDGE_MOVEMENT_LAZY_INIT: &str = "BRIDGE_MOVEMENT_LAZY_INIT";
+pub const BRIDGE_MOVEMENT_PACKAGE_BUILD_DIR: &str = "BRIDGE_MOVEMENT_PACKAGE_BUILD_DIR";
+
+// Testing
+pub const BRIDGE_TESTING_ETH_PRIVATE_KEYS: &str = "BRIDGE_TESTING_ETH_PRIVATE_KEYS";
+
+/// Looks up the variable with `var` and parses it if it is set.
+fn read_var<T>(
+	var: &impl Fn(&str) -> Option<String>,
+	name: &str,
+) -> Result<Option<T>, anyhow::Error>
+where
+	T: FromStr,
+	T::Err: std::fmt::Display,
+{
+	match var(name) {
+		Some(value) => value
+			.parse::<T>()
+			.map(Some)
+			.map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", name, e)),
+		None => Ok(None),
+	}
+}
+
+/// Splits a `protocol://hostname:port` url into its parts.
+fn split_url(name: &str, url: &str) -> Result<(String, String, u16), anyhow::Error> {
+	let (protocol, rest) = url
+		.split_once("://")
+		.with_context(|| format!("{} has no protocol: {}", name, url))?;
+	let (hostname, port) = rest
+		.rsplit_once(':')
+		.with_context(|| format!("{} has no port: {}", name, url))?;
+	let port = port
+		.trim_end_matches('/')
+		.parse()
+		.with_context(|| format!("{} has an invalid port: {}", name, url))?;
+	Ok((protocol.to_string(), hostname.to_string(), port))
+}
+
+impl Config {
+	/// Builds the config from the `BRIDGE_*` environment variables.
+	/// Fields whose variable is not set keep their default value.
+	pub fn from_env() -> Result<Config, anyhow::Error> {
+		Self::from_vars(|name| env::var(name).ok())
+	}
+
+	/// Builds the config from the `BRIDGE_*` variables looked up with `var`.
+	fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Config, anyhow::Error> {
+		let var = &var;
+		let mut config = Config::default();
+
+		// eth
+		let eth = &mut config.eth;
+		if let Some(url) = read_var::<String>(var, BRIDGE_ETH_RPC_URL)? {
+			(
+				eth.eth_rpc_connection_protocol,
+				eth.eth_rpc_connection_hostname,
+				eth.eth_rpc_connection_port,
+			) = split_url(BRIDGE_ETH_RPC_URL, &url)?;
+		}
+		if let Some(url) = read_var::<String>(var, BRIDGE_ETH_WS_URL)? {
+			(
+				eth.eth_ws_connection_protocol,
+				eth.eth_ws_connection_hostname,
+				eth.eth_ws_connection_port,
+			) = split_url(BRIDGE_ETH_WS_URL, &url)?;
+		}
+		if let Some(chain_id) = read_var(var, BRIDGE_ETH_CHAIN_ID)? {
+			eth.eth_chain_id = chain_id;
+		}
+		if let Some(contract) = read_var(var, BRIDGE_ETH_INITIATOR_CONTRACT)? {
+			eth.eth_initiator_contract = contract;
+		}
+		if let Some(contract) = read_var(var, BRIDGE_ETH_COUNTERPARTY_CONTRACT)? {
+			eth.eth_counterparty_contract = contract;
+		}
+		if let Some(contract) = read_var(var, BRIDGE_ETH_WETH_CONTRACT)? {
+			eth.eth_weth_contract = contract;
+		}
+		if let Some(contract) = read_var(var, BRIDGE_ETH_MOVE_TOKEN_CONTRACT)? {
+			eth.eth_move_token_contract = contract;
+		}
+		if let Some(key) = read_var(var, BRIDGE_ETH_SIGNER_KEY)? {
+			eth.signer_private_key = key;
+		}
+		if let Some(time_lock_secs) = read_var(var, BRIDGE_ETH_TIME_LOCK_SECS)? {
+			eth.time_lock_secs = time_lock_secs;
+		}
+		if let Some(gas_limit) = read_var(var, BRIDGE_ETH_GAS_LIMIT)? {
+			eth.gas_limit = gas_limit;
+		}
+		if let Some(multiplier) = read_var(var, BRIDGE_ETH_GAS_ESTIMATE_MULTIPLIER)? {
+			eth.eth_gas_estimate_multiplier = multiplier;
+		}
+		if let Some(retries) = read_var(var, BRIDGE_ETH_TRANSACTION_SEND_RETRIES)? {
+			eth.transaction_send_retries = retries;
+		}
+		if let Some(asset) = read_var(var, BRIDGE_ETH_ASSET)? {
+			eth.asset = asset;
+		}
+		if let Some(timeout) = read_var(var, BRIDGE_ETH_REST_CONNECTION_TIMEOUT_SECS)? {
+			eth.rest_connection_timeout_secs = timeout;
+		}
+		if let Some(confirmations) = read_var(var, BRIDGE_ETH_CONFIRMATIONS_REQUIRED)? {
+			eth.eth_confirmations_required = confirmations;
+		}
+		if let Some(max_fee_per_gas) = read_var(var, BRIDGE_ETH_MAX_FEE_PER_GAS)? {
+			eth.eth_max_fee_per_gas = max_fee_per_gas;
+		}
+		if let Some(max_priority_fee) = read_var(var, BRIDGE_ETH_MAX_PRIORITY_FEE)? {
+			eth.eth_max_priority_fee = max_priority_fee;
+		}
+
+		// movement
+		let movement = &mut config.movement;
+		if let Some(key) = read_var::<String>(var, BRIDGE_MOVEMENT_SIGNER_KEY)? {
+			movement.movement_signer_key = Ed25519PrivateKey::from_encoded_string(&key)
+				.with_context(|| format!("Failed to parse {}", BRIDGE_MOVEMENT_SIGNER_KEY))?;
+		}
+		if let Some(address) = read_var(var, BRIDGE_MOVEMENT_NATIVE_ADDRESS)? {
+			movement.movement_native_address = address;
+		}
+		if let Some(url) = read_var::<String>(var, BRIDGE_MOVEMENT_RPC_URL)? {
+			(
+				movement.mvt_rpc_connection_protocol,
+				movement.mvt_rpc_connection_hostname,
+				movement.mvt_rpc_connection_port,
+			) = split_url(BRIDGE_MOVEMENT_RPC_URL, &url)?;
+		}
+		if let Some(url) = read_var::<String>(var, BRIDGE_MOVEMENT_FAUCET_URL)? {
+			(
+				movement.mvt_faucet_connection_protocol,
+				movement.mvt_faucet_connection_hostname,
+				movement.mvt_faucet_connection_port,
+			) = split_url(BRIDGE_MOVEMENT_FAUCET_URL, &url)?;
+		}
+		if let Some(network) = read_var(var, BRIDGE_MOVEMENT_INIT_NETWORK)? {
+			movement.mvt_init_network = network;
+		}
+		if let Some(hostname) = read_var(var, BRIDGE_MOVEMENT_REST_LISTENER_HOSTNAME)? {
+			movement.rest_listener_hostname = hostname;
+		}
+		if let Some(port) = read_var(var, BRIDGE_MOVEMENT_REST_PORT)? {
+			movement.rest_port = port;
+		}
+		if let Some(protocol) = read_var(var, BRIDGE_MOVEMENT_GRPC_PROTOCOL)? {
+			movement.grpc_protocol = protocol;
+		}
+		if let Some(hostname) = read_var(var, BRIDGE_MOVEMENT_GRPC_LISTENER_HOSTNAME)? {
+			movement.grpc_listener_hostname = hostname;
+		}
+		if let Some(port) = read_var(var, BRIDGE_MOVEMENT_GRPC_PORT)? {
+			movement.grpc_port = port;
+		}
+		if let Some(timeout) = read_var(var, BRIDGE_MOVEMENT_REST_CONNECTION_TIMEOUT_SECS)? {
+			movement.rest_connection_timeout_secs = timeout;
+		}
+		if let Some(timeout) = read_var(var, BRIDGE_MOVEMENT_NODE_READY_TIMEOUT_SECS)? {
+			movement.node_ready_timeout_secs = timeout;
+		}
+		if let Some(timeout) = read_var(var, BRIDGE_MOVEMENT_CONFIRM_TIMEOUT_SECS)? {
+			movement.confirm_timeout_secs = timeout;
+		}
+		if let Some(time_lock) = read_var(var, BRIDGE_MOVEMENT_TIME_LOCK_MIN_SECS)? {
+			movement.time_lock_min_secs = time_lock;
+		}
+		if let Some(time_lock) = read_var(var, BRIDGE_MOVEMENT_TIME_LOCK_MAX_SECS)? {
+			movement.time_lock_max_secs = time_lock;
+		}
+		if let Some(lazy_init) = read_var(var, BRIDGE_MOVEMENT_LAZY_INIT)? {
+			movement.lazy_init = lazy_init;
+		}
+		if let Some(dir) = read_var(var, BRIDGE_MOVEMENT_PACKAGE_BUILD_DIR)? {
+			movement.package_build_dir = dir;
+		}
+
+		// testing
+		if let Some(keys) = read_var::<String>(var, BRIDGE_TESTING_ETH_PRIVATE_KEYS)? {
+			config.testing.eth_well_known_account_private_keys =
+				keys.split(',').filter(|key| !key.is_empty()).map(ToString::to_string).collect();
+		}
+
+		Ok(config)
+	}
+
+	/// Exports every field of the config to the `BRIDGE_*` environment variables,
+	/// so that [`Config::from_env`] builds the same config back.
+	pub fn write_to_env(&self) -> Result<(), anyhow::Error> {
+		for (name, value) in self.to_vars()? {
+			env::set_var(name, value);
+		}
+		Ok(())
+	}
+
+	/// The `BRIDGE_*` variables holding every field of the config.
+	fn to_vars(&self) -> Result<Vec<(&'static str, String)>, anyhow::Error> {
+		let eth = &self.eth;
+		let movement = &self.movement;
+		Ok(vec![
+			(BRIDGE_ETH_RPC_URL, eth.eth_rpc_connection_url()),
+			(BRIDGE_ETH_WS_URL, eth.eth_ws_connection_url()),
+			(BRIDGE_ETH_CHAIN_ID, eth.eth_chain_id.to_string()),
+			(BRIDGE_ETH_INITIATOR_CONTRACT, eth.eth_initiator_contract.clone()),
+			(BRIDGE_ETH_COUNTERPARTY_CONTRACT, eth.eth_counterparty_contract.clone()),
+			(BRIDGE_ETH_WETH_CONTRACT, eth.eth_weth_contract.clone()),
+			(BRIDGE_ETH_MOVE_TOKEN_CONTRACT, eth.eth_move_token_contract.clone()),
+			(BRIDGE_ETH_SIGNER_KEY, eth.signer_private_key.clone()),
+			(BRIDGE_ETH_TIME_LOCK_SECS, eth.time_lock_secs.to_string()),
+			(BRIDGE_ETH_GAS_LIMIT, eth.gas_limit.to_string()),
+			(BRIDGE_ETH_GAS_ESTIMATE_MULTIPLIER, eth.eth_gas_estimate_multiplier.to_string()),
+			(BRIDGE_ETH_TRANSACTION_SEND_RETRIES, eth.transaction_send_retries.to_string()),
+			(BRIDGE_ETH_ASSET, eth.asset.clone()),
+			(BRIDGE_ETH_REST_CONNECTION_TIMEOUT_SECS, eth.rest_connection_timeout_secs.to_string()),
+			(BRIDGE_ETH_CONFIRMATIONS_REQUIRED, eth.eth_confirmations_required.to_string()),
+			(BRIDGE_ETH_MAX_FEE_PER_GAS, eth.eth_max_fee_per_gas.to_string()),
+			(BRIDGE_ETH_MAX_PRIORITY_FEE, eth.eth_max_priority_fee.to_string()),
+			(
+				BRIDGE_MOVEMENT_SIGNER_KEY,
+				movement
+					.movement_signer_key
+					.to_encoded_string()
+					.context("Failed to encode the movement signer key")?,
+			),
+			(BRIDGE_MOVEMENT_NATIVE_ADDRESS, movement.movement_native_address.clone()),
+			(BRIDGE_MOVEMENT_RPC_URL, movement.mvt_rpc_connection_url()),
+			(BRIDGE_MOVEMENT_FAUCET_URL, movement.mvt_faucet_connection_url()),
+			(BRIDGE_MOVEMENT_INIT_NETWORK, movement.mvt_init_network.clone()),
+			(BRIDGE_MOVEMENT_REST_LISTENER_HOSTNAME, movement.rest_listener_hostname.clone()),
+			(BRIDGE_MOVEMENT_REST_PORT, movement.rest_port.to_string()),
+			(BRIDGE_MOVEMENT_GRPC_PROTOCOL, movement.grpc_protocol.clone()),
+			(BRIDGE_MOVEMENT_GRPC_LISTENER_HOSTNAME, movement.grpc_listener_hostname.clone()),
+			(BRIDGE_MOVEMENT_GRPC_PORT, movement.grpc_port.to_string()),
+			(
+				BRIDGE_MOVEMENT_REST_CONNECTION_TIMEOUT_SECS,
+				movement.rest_connection_timeout_secs.to_string(),
+			),
+			(BRIDGE_MOVEMENT_NODE_READY_TIMEOUT_SECS, movement.node_ready_timeout_secs.to_string()),
+			(BRIDGE_MOVEMENT_CONFIRM_TIMEOUT_SECS, movement.confirm_timeout_secs.to_string()),
+			(BRIDGE_MOVEMENT_TIME_LOCK_MIN_SECS, movement.time_lock_min_secs.to_string()),
+			(BRIDGE_MOVEMENT_TIME_LOCK_MAX_SECS, movement.time_lock_max_secs.to_string()),
+			(BRIDGE_MOVEMENT_LAZY_INIT, movement.lazy_init.to_string()),
+			(BRIDGE_MOVEMENT_PACKAGE_BUILD_DIR, movement.package_build_dir.clone()),
+			(
+				BRIDGE_TESTING_ETH_PRIVATE_KEYS,
+				self.testing.eth_well_known_account_private_keys.join(","),
+			),
+		])
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::collections::HashMap;
+
+	#[test]
+	fn test_to_vars_and_from_vars_roundtrip() -> Result<(), anyhow::Error> {
+		let mut config = Config::suzuka();
+		config.eth.eth_rpc_connection_hostname = "eth.example".to_string();
+		config.eth.eth_rpc_connection_port = 9545;
+		config.eth.eth_chain_id = 3073;
+		config.eth.eth_confirmations_required = 3;
+		config.eth.eth_max_fee_per_gas = 50_000_000_000;
+		config.testing.eth_well_known_account_private_keys =
+			vec!["0xaa".to_string(), "0xbb".to_string()];
+
+		let vars: HashMap<_, _> = config.to_vars()?.into_iter().collect();
+		let read_config = Config::from_vars(|name| vars.get(name).cloned())?;
+
+		assert_eq!(read_config.eth.eth_rpc_connection_url(), "http://eth.example:9545");
+		assert_eq!(read_config.eth.eth_chain_id, 3073);
+		assert_eq!(read_config.eth.eth_confirmations_required, 3);
+		assert_eq!(read_config.eth.eth_max_fee_per_gas, 50_000_000_000);
+		assert_eq!(read_config.eth.eth_max_priority_fee, config.eth.eth_max_priority_fee);
+		assert_eq!(read_config.eth.signer_private_key, config.eth.signer_private_key);
+		assert_eq!(read_config.movement.movement_signer_key, config.movement.movement_signer_key);
+		assert_eq!(
+			read_config.movement.mvt_rpc_connection_url(),
+			config.movement.mvt_rpc_connection_url()
+		);
+		assert_eq!(
+			read_config.testing.eth_well_known_account_private_keys,
+			config.testing.eth_well_known_account_private_keys
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn test_split_url() {
+		assert_eq!(
+			split_url("TEST", "ws://localhost:8545").unwrap(),
+			("ws".to_string(), "localhost".to_string(), 8545)
+		);
+		assert!(split_url("TEST", "localhost:8545").is_err());
+		assert!(split_url("TEST", "http://localhost").is_err());
+	}
+}
@@ -1,9 +1,16 @@
 use serde::{Deserialize, Serialize};
 
 pub mod common;
+pub mod env;
 
 pub const BRIDGE_CONF_FOLDER: &str = "bridge";
 
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum ConfigError {
+	#[error("The counterparty address must be set in the config unless lazy_init is enabled")]
+	MissingCounterpartyAddress,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Config {
 	/// The ETH connection configuration.
@@ -43,4 +50,86 @@ impl Config {
 			testing: common::testing::TestingConfig::default(),
 		}
 	}
+
+	/// Returns `base` with every field set in `test_overrides` replaced.
+	pub fn merge_with_testing_config(
+		base: Config,
+		test_overrides: common::testing::BridgeTestConfig,
+	) -> Config {
+		fn merge<T>(field: &mut T, value: Option<T>) {
+			if let Some(value) = value {
+				*field = value;
+			}
+		}
+
+		let common::testing::BridgeTestConfig {
+			eth_rpc_connection_hostname,
+			eth_rpc_connection_port,
+			eth_ws_connection_hostname,
+			eth_ws_connection_port,
+			eth_chain_id,
+			eth_signer_private_key,
+			eth_time_lock_secs,
+			mvt_rpc_connection_hostname,
+			mvt_rpc_connection_port,
+			mvt_faucet_connection_hostname,
+			mvt_faucet_connection_port,
+			mvt_init_network,
+			eth_well_known_account_private_keys,
+		} = test_overrides;
+		let mut config = base;
+
+		let eth = &mut config.eth;
+		merge(&mut eth.eth_rpc_connection_hostname, eth_rpc_connection_hostname);
+		merge(&mut eth.eth_rpc_connection_port, eth_rpc_connection_port);
+		merge(&mut eth.eth_ws_connection_hostname, eth_ws_connection_hostname);
+		merge(&mut eth.eth_ws_connection_port, eth_ws_connection_port);
+		merge(&mut eth.eth_chain_id, eth_chain_id);
+		merge(&mut eth.signer_private_key, eth_signer_private_key);
+		merge(&mut eth.time_lock_secs, eth_time_lock_secs);
+
+		let movement = &mut config.movement;
+		merge(&mut movement.mvt_rpc_connection_hostname, mvt_rpc_connection_hostname);
+		merge(&mut movement.mvt_rpc_connection_port, mvt_rpc_connection_port);
+		merge(&mut movement.mvt_faucet_connection_hostname, mvt_faucet_connection_hostname);
+		merge(&mut movement.mvt_faucet_connection_port, mvt_faucet_connection_port);
+		merge(&mut movement.mvt_init_network, mvt_init_network);
+
+		merge(
+			&mut config.testing.eth_well_known_account_private_keys,
+			eth_well_known_account_private_keys,
+		);
+
+		config
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use common::testing::BridgeTestConfig;
+
+	#[test]
+	fn test_merge_with_testing_config_only_overrides_set_fields() {
+		let base = Config::suzuka();
+		let merged = Config::merge_with_testing_config(
+			base.clone(),
+			BridgeTestConfig {
+				eth_chain_id: Some(3073),
+				eth_time_lock_secs: Some(15),
+				mvt_init_network: Some("custom".to_string()),
+				..Default::default()
+			},
+		);
+
+		assert_eq!(merged.eth.eth_chain_id, 3073);
+		assert_eq!(merged.eth.time_lock_secs, 15);
+		assert_eq!(merged.movement.mvt_init_network, "custom");
+		assert_eq!(merged.eth.eth_rpc_connection_url(), base.eth.eth_rpc_connection_url());
+		assert_eq!(
+			merged.movement.mvt_rpc_connection_url(),
+			base.movement.mvt_rpc_connection_url()
+		);
+		assert_eq!(merged.eth.signer_private_key, base.eth.signer_private_key);
+	}
 }
@@ -52,6 +52,7 @@ impl Client {
 				initiator,
 				recipient,
 				amount,
+				..
 			} => {
 				diesel::insert_into(lock_bridge_transfers::table)
 					.values(NewLockBridgeTransfer {
@@ -64,7 +65,11 @@ impl Client {
 					})
 					.execute(&mut self.conn)?;
 			}
-			TransferActionType::WaitAndCompleteInitiator(wait_time_secs, hash_lock_pre_image) => {
+			TransferActionType::WaitAndCompleteInitiator(
+				wait_time_secs,
+				hash_lock_pre_image,
+				..,
+			) => {
 				diesel::insert_into(wait_and_complete_initiators::table)
 					.values(NewWaitAndCompleteInitiator {
 						wait_time_secs: wait_time_secs as i64,
@@ -169,6 +174,8 @@ impl Client {
 					})
 					.execute(&mut self.conn)?;
 			}
+			// The orphaned event was never relayed nor indexed.
+			BridgeContractEvent::Reorged(_) => (),
 		}
 
 		Ok(())
@@ -258,6 +265,7 @@ pub mod test {
 			initiator: BridgeAddress::<Vec<u8>>::test(),
 			recipient: BridgeAddress::<Vec<u8>>::test(),
 			amount: Amount::test(),
+			relayer_fee: Amount(0),
 		};
 		client.insert_transfer_action(action_type)?;
 
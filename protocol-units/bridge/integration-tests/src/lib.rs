@@ -6,10 +6,11 @@ use alloy::{
 };
 use alloy_network::EthereumWallet;
 use aptos_sdk::{
-	rest_client::{aptos_api_types::Transaction as AptosTransaction, Client, FaucetClient},
+	coin_client::CoinClient,
+	rest_client::{aptos_api_types::Transaction as AptosTransaction, Client},
 	types::{account_address::AccountAddress, LocalAccount},
 };
-use bridge_config::Config;
+use bridge_config::{common::testing::BridgeTestConfig, Config};
 use bridge_service::chains::ethereum::types::MockMOVEToken;
 use bridge_service::chains::ethereum::types::{
 	AtomicBridgeCounterpartyMOVE, AtomicBridgeInitiatorMOVE,
@@ -28,13 +29,15 @@ use bridge_service::{
 		},
 		movement::{
 			client_framework::{MovementClientFramework, FRAMEWORK_ADDRESS},
+			faucet::RateLimitedFaucetClient,
 			utils::{self as movement_utils, MovementAddress, MovementHash},
 		},
 	},
-	types::{BridgeTransferId, HashLockPreImage},
+	types::{derive_bridge_transfer_id, BridgeTransferId, HashLockPreImage, Nonce},
 };
 use godfig::{backend::config_file::ConfigFile, Godfig};
-use rand::{distributions::Alphanumeric, thread_rng, Rng, SeedableRng};
+use prometheus::Registry;
+use rand::{thread_rng, Rng, SeedableRng};
 use std::{
 	convert::TryInto,
 	str::FromStr,
@@ -67,62 +70,54 @@ pub struct MovementToEthCallArgs {
 
 impl Default for EthToMovementCallArgs {
 	fn default() -> Self {
-		// Generate 6 random alphanumeric characters
-		let random_suffix: String =
-			thread_rng().sample_iter(&Alphanumeric).take(6).map(char::from).collect();
-
-		// Construct the bridge_transfer_id with the random suffix
-		let mut bridge_transfer_id = b"00000000000000000000000tra".to_vec();
-		bridge_transfer_id.extend_from_slice(random_suffix.as_bytes());
+		// Actual Eth address
+		let initiator = hex::decode("3c44cdddb6a900fa2b585dd299e03d12fa4293bc")
+			.expect("Expected a hex encoded Eth address");
+		// Dummy recipient address
+		let recipient = MovementAddress(AccountAddress::new(*b"0x00000000000000000000000000face"));
+		let amount = 100;
+		// A random nonce gives each transfer its own id
+		let bridge_transfer_id = derive_bridge_transfer_id(
+			&initiator,
+			recipient.0.as_slice(),
+			Amount(amount),
+			Nonce(thread_rng().gen()),
+		);
 
 		Self {
-			// Dummy valid EIP-55 address used in framework modules
-			// initiator: b"32Be343B94f860124dC4fEe278FDCBD38C102D88".to_vec(),
-			// Actual Eth address
-			initiator: b"0x3c44cdddb6a900fa2b585dd299e03d12fa4293bc".to_vec(),
-			// All lowercase version:
-			//initiator: b"0x32be343b94f860124dc4fee278fdcbd38c102d88".to_vec(),
-			// Dummy recipient address
-			recipient: MovementAddress(AccountAddress::new(*b"0x00000000000000000000000000face")),
-			// Convert to [u8; 32] with explicit type annotation
-			bridge_transfer_id: MovementHash(
-				bridge_transfer_id
-					.as_slice()
-					.try_into()
-					.expect("Expected bridge_transfer_id to be 32 bytes"),
-			),
+			initiator,
+			recipient,
+			bridge_transfer_id: MovementHash(bridge_transfer_id.0),
 			hash_lock: MovementHash(*keccak256(b"secret")),
 			time_lock: 3600,
-			amount: 100,
+			amount,
 		}
 	}
 }
 
 impl Default for MovementToEthCallArgs {
 	fn default() -> Self {
-		// Generate a 6-character random alphanumeric suffix
-		let random_suffix: String =
-			thread_rng().sample_iter(&Alphanumeric).take(6).map(char::from).collect();
-
-		// Construct the bridge_transfer_id with the random suffix
-		let mut bridge_transfer_id = b"00000000000000000000000tra".to_vec();
-		bridge_transfer_id.extend_from_slice(random_suffix.as_bytes());
+		let initiator = MovementAddress(AccountAddress::new(*b"0x000000000000000000000000A55018"));
+		let recipient = b"32Be343B94f860124dC4fEe278FDCBD38C102D88".to_vec();
+		let amount = 100;
+		// A random nonce gives each transfer its own id
+		let bridge_transfer_id = derive_bridge_transfer_id(
+			initiator.0.as_slice(),
+			&recipient,
+			Amount(amount),
+			Nonce(thread_rng().gen()),
+		);
 
 		// Generate a random 32-byte secret
 		let pre_image: [u8; 32] = thread_rng().gen();
 
 		Self {
-			initiator: MovementAddress(AccountAddress::new(*b"0x000000000000000000000000A55018")),
-			recipient: b"32Be343B94f860124dC4fEe278FDCBD38C102D88".to_vec(),
-			bridge_transfer_id: EthHash(
-				bridge_transfer_id
-					.as_slice()
-					.try_into()
-					.expect("Expected bridge_transfer_id to be 32 bytes"),
-			),
+			initiator,
+			recipient,
+			bridge_transfer_id: EthHash(bridge_transfer_id.0),
 			hash_lock: EthHash(*keccak256(&pre_image)), // Hash the secret for the hash lock
 			time_lock: 3600,
-			amount: 100,
+			amount,
 			pre_image, // Store the generated secret in the struct
 		}
 	}
@@ -144,7 +139,9 @@ impl HarnessEthClient {
 			.parse::<PrivateKeySigner>()
 			.expect("Error during parsing signer private key?");
 
-		let eth_client = EthClient::new(&config.eth).await.expect("Failed to create EthClient");
+		let eth_client = EthClient::new(&config.eth, &Registry::new())
+			.await
+			.expect("Failed to create EthClient");
 		HarnessEthClient { eth_client, eth_rpc_url, signer_private_key }
 	}
 
@@ -298,8 +295,8 @@ pub struct HarnessMvtClient {
 	pub movement_client: MovementClientFramework,
 	///The Apotos Rest Client
 	pub rest_client: Client,
-	/// The Aptos Faucet Client
-	pub faucet_client: Arc<RwLock<FaucetClient>>,
+	/// The Aptos Faucet Client, rate limited to stay under the limit of the faucet
+	pub faucet_client: Arc<RwLock<RateLimitedFaucetClient>>,
 }
 
 impl HarnessMvtClient {
@@ -324,9 +321,10 @@ impl HarnessMvtClient {
 
 		let faucet_url = Url::from_str(&config.movement.mvt_faucet_connection_url())
 			.expect("Bad movement faucet url in config");
-		let faucet_client = Arc::new(RwLock::new(FaucetClient::new(
+		let faucet_client = Arc::new(RwLock::new(RateLimitedFaucetClient::new(
 			faucet_url.clone(),
 			node_connection_url.clone(),
+			config.movement.mvt_faucet_requests_per_minute,
 		)));
 
 		HarnessMvtClient { movement_client, rest_client, faucet_client }
@@ -343,6 +341,33 @@ impl HarnessMvtClient {
 		account
 	}
 
+	/// Funds the signer of the framework client and checks its balance grew by `amount`.
+	///
+	/// In framework mode the bridge modules are published at the framework address, so only
+	/// the signer sending the calls needs funds. [`utils::fund_and_check_balance`] additionally
+	/// funds the module account of a standalone bridge deployment.
+	pub async fn fund_signer_and_check_balance_framework(
+		&mut self,
+		amount: u64,
+	) -> Result<(), anyhow::Error> {
+		let address = self.movement_client.signer().address();
+		let coin_client = CoinClient::new(&self.rest_client);
+		let balance_before = coin_client.get_account_balance(&address).await?;
+
+		self.faucet_client.write().unwrap().fund(address, amount).await?;
+
+		let balance_after = coin_client.get_account_balance(&address).await?;
+		anyhow::ensure!(
+			balance_after >= balance_before + amount,
+			"Framework signer {} balance is {} after funding {} on top of {}",
+			address,
+			balance_after,
+			amount,
+			balance_before
+		);
+		Ok(())
+	}
+
 	pub async fn init_set_timelock(&mut self, timelock: u64) -> Result<(), BridgeContractError> {
 		self.movement_client.initiator_set_timelock(timelock).await?;
 		Ok(())
@@ -374,6 +399,7 @@ impl HarnessMvtClient {
 			&self.movement_client.rest_client,
 			initiator,
 			payload,
+			self.movement_client.confirm_timeout_secs(),
 		)
 		.await
 		.map_err(|_| BridgeContractError::InitiateTransferError)?;
@@ -411,9 +437,10 @@ impl HarnessMvtClient {
 			&self.rest_client,
 			&recipient_privatekey,
 			payload,
+			self.movement_client.confirm_timeout_secs(),
 		)
 		.await
-		.map_err(|_| BridgeContractError::CompleteTransferError)
+		.map_err(|e| BridgeContractError::CompleteTransferError(e.to_string()))
 	}
 }
 
@@ -441,15 +468,27 @@ impl TestHarness {
 		Ok((test_eth_harness, test_mvt_harness, config))
 	}
 
-	pub async fn new_with_movement() -> Result<(HarnessMvtClient, Config), anyhow::Error> {
-		let config = TestHarness::read_bridge_config().await?;
+	/// Builds the Movement harness from the bridge config file with `test_overrides` applied.
+	pub async fn new_with_movement(
+		test_overrides: BridgeTestConfig,
+	) -> Result<(HarnessMvtClient, Config), anyhow::Error> {
+		let config = Config::merge_with_testing_config(
+			TestHarness::read_bridge_config().await?,
+			test_overrides,
+		);
 		let test_harness = HarnessMvtClient::build(&config).await;
 
 		Ok((test_harness, config))
 	}
 
-	pub async fn new_only_eth() -> Result<(HarnessEthClient, Config), anyhow::Error> {
-		let config = TestHarness::read_bridge_config().await?;
+	/// Builds the Ethereum harness from the bridge config file with `test_overrides` applied.
+	pub async fn new_with_eth(
+		test_overrides: BridgeTestConfig,
+	) -> Result<(HarnessEthClient, Config), anyhow::Error> {
+		let config = Config::merge_with_testing_config(
+			TestHarness::read_bridge_config().await?,
+			test_overrides,
+		);
 		let test_harness = HarnessEthClient::build(&config).await;
 		Ok((test_harness, config))
 	}
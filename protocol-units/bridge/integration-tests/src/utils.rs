@@ -4,13 +4,20 @@ use anyhow::Result;
 use aptos_sdk::{
 	coin_client::CoinClient, rest_client::Transaction, types::account_address::AccountAddress,
 };
-use bridge_service::chains::bridge_contracts::{BridgeContract, BridgeContractError};
+use bridge_service::chains::bridge_contracts::{
+	BridgeContract, BridgeContractError, BridgeContractEvent, BridgeContractEventType,
+	BridgeContractResult,
+};
 use bridge_service::chains::movement::client_framework::MovementClientFramework;
+use bridge_service::chains::movement::event_monitoring::BridgeInitEventData;
 use bridge_service::chains::movement::utils::{
 	self as movement_utils, MovementAddress, MovementHash,
 };
-use bridge_service::types::{Amount, BridgeAddress, BridgeTransferDetails, HashLock};
+use bridge_service::types::{
+	Amount, BridgeAddress, BridgeTransferDetails, BridgeTransferId, HashLock,
+};
 use serde_json::Value;
+use std::fmt::Debug;
 use tracing::debug;
 
 const FRAMEWORK_ADDRESS: AccountAddress = AccountAddress::new([
@@ -49,6 +56,24 @@ pub fn assert_counterparty_bridge_transfer_details_framework(
 	assert_eq!(details.time_lock.0, expected_time_lock);
 }
 
+/// Asserts that each `(transfer id, event type)` pair of `expected_sequence` appears in `events`
+/// in the given order. Other events can be interleaved between them.
+pub fn assert_event_ordering<A: Debug>(
+	events: &[BridgeContractEvent<A>],
+	expected_sequence: &[(BridgeTransferId, BridgeContractEventType)],
+) {
+	let mut remaining = events.iter();
+	for (transfer_id, event_type) in expected_sequence {
+		let found = remaining.by_ref().any(|event| {
+			event.bridge_transfer_id() == *transfer_id && event.event_type() == *event_type
+		});
+		assert!(
+			found,
+			"Event {event_type:?} for transfer {transfer_id} not found in order in {events:?}"
+		);
+	}
+}
+
 pub async fn fetch_bridge_transfer_details(
 	movement_client: &mut MovementClientFramework,
 	bridge_transfer_id: Vec<u8>,
@@ -86,6 +111,27 @@ pub async fn fetch_bridge_transfer_details(
 	Err(anyhow::Error::msg("No matching bridge transfer details found"))
 }
 
+/// Returns the details of every `BridgeTransferInitiatedEvent` emitted by the transactions,
+/// in emission order. A single transaction can initiate several transfers (batch scripts).
+pub fn extract_bridge_transfer_details(
+	txns: &[Transaction],
+) -> BridgeContractResult<Vec<BridgeTransferDetails<MovementAddress>>> {
+	let mut details = Vec::new();
+	for txn in txns {
+		if let Transaction::UserTransaction(user_txn) = txn {
+			for event in &user_txn.events {
+				if !event.typ.to_string().contains("BridgeTransferInitiatedEvent") {
+					continue;
+				}
+				let data: BridgeInitEventData = serde_json::from_value(event.data.clone())
+					.map_err(|_| BridgeContractError::SerializationError)?;
+				details.push(BridgeTransferDetails::try_from(data)?);
+			}
+		}
+	}
+	Ok(details)
+}
+
 pub async fn fund_and_check_balance(
 	movement_harness: &mut HarnessMvtClient,
 	expected_balance: u64,
@@ -110,27 +156,6 @@ pub async fn fund_and_check_balance(
 	Ok(())
 }
 
-pub async fn fund_and_check_balance_framework(
-	movement_harness: &mut HarnessMvtClient,
-	expected_balance: u64,
-) -> Result<()> {
-	let movement_client_signer = movement_harness.movement_client.signer();
-	let rest_client = movement_harness.rest_client.clone();
-	let coin_client = CoinClient::new(&rest_client);
-	let faucet_client = movement_harness.faucet_client.write().unwrap();
-	faucet_client.fund(movement_client_signer.address(), expected_balance).await?;
-
-	let balance = coin_client.get_account_balance(&movement_client_signer.address()).await?;
-	assert!(
-		balance >= expected_balance,
-		"Expected Movement Client to have at least {}, but found {}",
-		expected_balance,
-		balance
-	);
-
-	Ok(())
-}
-
 pub async fn initiate_bridge_transfer_helper(
 	movement_client: &mut MovementClientFramework,
 	initiator: AccountAddress,
@@ -165,6 +190,7 @@ pub async fn initiate_bridge_transfer_helper(
 		&movement_client.rest_client(),
 		movement_client.signer(),
 		mint_payload,
+		movement_client.confirm_timeout_secs(),
 	)
 	.await
 	.map_err(|_| BridgeContractError::MintError)?;
@@ -1,6 +1,7 @@
 use bridge_config::Config;
 use bridge_service::rest::BridgeRest;
 use poem::test::TestClient;
+use prometheus::{IntCounter, Registry};
 use std::sync::Arc;
 use tracing_subscriber::EnvFilter;
 
@@ -50,3 +51,23 @@ async fn test_rest_service_health_endpoint() -> Result<(), anyhow::Error> {
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn test_rest_service_metrics_endpoint() -> Result<(), anyhow::Error> {
+	let registry = Registry::new();
+	let counter = IntCounter::new("bridge_test_total", "Counter registered by the test")?;
+	registry.register(Box::new(counter.clone()))?;
+	counter.inc();
+
+	let (health_tx, _health_rx) = tokio::sync::mpsc::channel(10);
+	let rest_service =
+		BridgeRest::new(&Config::default().movement, health_tx)?.with_metrics_registry(registry);
+	let client = TestClient::new(rest_service.create_routes());
+
+	let response = client.get("/metrics").send().await;
+	response.assert_status_is_ok();
+	let body = response.0.into_body().into_string().await?;
+	assert!(body.contains("bridge_test_total 1"));
+
+	Ok(())
+}
@@ -1,11 +1,18 @@
 use alloy::primitives::keccak256;
 use alloy::primitives::Address;
+use alloy::primitives::U256;
 use anyhow::Result;
+use bridge_config::common::testing::BridgeTestConfig;
 use bridge_integration_tests::HarnessEthClient;
 use bridge_integration_tests::HarnessMvtClient;
 use bridge_integration_tests::TestHarness;
-use bridge_service::chains::bridge_contracts::{BridgeContract, BridgeContractEvent};
-use bridge_service::chains::ethereum::{event_monitoring::EthMonitoring, types::EthAddress};
+use bridge_service::chains::bridge_contracts::{
+	BridgeContract, BridgeContractError, BridgeContractEvent,
+};
+use bridge_service::chains::ethereum::{
+	event_monitoring::EthMonitoring,
+	types::{EthAddress, MockMOVEToken},
+};
 use bridge_service::types::{Amount, BridgeAddress, BridgeTransferId, HashLock, HashLockPreImage};
 use futures::StreamExt;
 use std::str::FromStr;
@@ -14,14 +21,15 @@ use tokio::{self};
 #[tokio::test]
 async fn test_eth_client_counterparty_complete_transfer() {
 	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
-	let (mut eth_client_harness, config) =
-		TestHarness::new_only_eth().await.expect("Bridge config file not set");
+	let (mut eth_client_harness, config) = TestHarness::new_with_eth(BridgeTestConfig::default())
+		.await
+		.expect("Bridge config file not set");
 
 	let hash_lock_pre_image = HashLockPreImage::random();
 	let hash_lock = HashLock(From::from(keccak256(hash_lock_pre_image)));
 	let amount = Amount(1);
 
-	let transfer_id = BridgeTransferId::gen_unique_hash(&mut rand::rngs::OsRng);
+	let transfer_id = BridgeTransferId::random(&mut rand::rngs::OsRng);
 	let res = BridgeContract::lock_bridge_transfer(
 		&mut eth_client_harness.eth_client,
 		transfer_id,
@@ -45,14 +53,15 @@ async fn test_eth_client_counterparty_complete_transfer() {
 #[tokio::test]
 async fn test_eth_client_lock_transfer() -> Result<(), anyhow::Error> {
 	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
-	let (mut eth_client_harness, config) =
-		TestHarness::new_only_eth().await.expect("Bridge config file not set");
+	let (mut eth_client_harness, config) = TestHarness::new_with_eth(BridgeTestConfig::default())
+		.await
+		.expect("Bridge config file not set");
 
 	// Call lock transfer Eth
 	let hash_lock_pre_image = HashLockPreImage::random();
 	let hash_lock = HashLock(From::from(keccak256(hash_lock_pre_image)));
 	let amount = Amount(1);
-	let transfer_id = BridgeTransferId::gen_unique_hash(&mut rand::rngs::OsRng);
+	let transfer_id = BridgeTransferId::random(&mut rand::rngs::OsRng);
 
 	let res = eth_client_harness
 		.eth_client
@@ -70,11 +79,42 @@ async fn test_eth_client_lock_transfer() -> Result<(), anyhow::Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn test_eth_client_transfer_metrics() -> Result<(), anyhow::Error> {
+	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
+	let (mut eth_client_harness, config) = TestHarness::new_with_eth(BridgeTestConfig::default())
+		.await
+		.expect("Bridge config file not set");
+
+	for _ in 0..2 {
+		let hash_lock_pre_image = HashLockPreImage::random();
+		let hash_lock = HashLock(From::from(keccak256(hash_lock_pre_image)));
+		eth_client_harness
+			.eth_client
+			.lock_bridge_transfer(
+				BridgeTransferId::random(&mut rand::rngs::OsRng),
+				hash_lock,
+				BridgeAddress(vec![3; 32]),
+				BridgeAddress(EthAddress(HarnessEthClient::get_recipeint_address(&config))),
+				Amount(2),
+			)
+			.await?;
+	}
+
+	let metrics = eth_client_harness.eth_client.metrics();
+	assert_eq!(metrics.transfer_amount_total.with_label_values(&["counterparty"]).get(), 4.0);
+	assert!(metrics.gas_spent_total.get() > 0.0);
+	assert!(metrics.gas_used_total.get() > 0.0);
+	assert_eq!(metrics.rpc_errors_total.with_label_values(&["lock_bridge_transfer"]).get(), 0);
+	Ok(())
+}
+
 #[tokio::test]
 async fn test_eth_client_initiate_transfer() {
 	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
-	let (eth_client_harness, config) =
-		TestHarness::new_only_eth().await.expect("Bridge config file not set");
+	let (eth_client_harness, config) = TestHarness::new_with_eth(BridgeTestConfig::default())
+		.await
+		.expect("Bridge config file not set");
 
 	let recipient = HarnessMvtClient::gen_aptos_account();
 	let hash_lock_pre_image = HashLockPreImage::random();
@@ -96,8 +136,9 @@ async fn test_eth_client_initiate_transfer() {
 async fn test_eth_client_initiator_complete_transfer() {
 	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
 
-	let (mut eth_client_harness, config) =
-		TestHarness::new_only_eth().await.expect("Bridge config file not set");
+	let (mut eth_client_harness, config) = TestHarness::new_with_eth(BridgeTestConfig::default())
+		.await
+		.expect("Bridge config file not set");
 	let (_eth_health_tx, eth_health_rx) = tokio::sync::mpsc::channel(10);
 	let mut eth_monitoring = EthMonitoring::build(&config.eth, eth_health_rx).await.unwrap();
 
@@ -142,14 +183,18 @@ async fn test_eth_client_initiator_complete_transfer() {
 		.await;
 
 	assert!(res.is_ok(), "initiator_complete_bridge_transfer failed: {:?}", res.unwrap_err());
+	let metrics = eth_client_harness.eth_client.metrics();
+	assert_eq!(metrics.complete_bridge_transfer_duration_ms.get_sample_count(), 1);
+	assert!(metrics.complete_bridge_transfer_duration_ms.get_sample_sum() > 0.0);
 }
 
 #[tokio::test]
 async fn test_eth_client_refund_transfer() {
 	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
 
-	let (mut eth_client_harness, config) =
-		TestHarness::new_only_eth().await.expect("Bridge config file not set");
+	let (mut eth_client_harness, config) = TestHarness::new_with_eth(BridgeTestConfig::default())
+		.await
+		.expect("Bridge config file not set");
 	let (_, eth_health_rx) = tokio::sync::mpsc::channel(10);
 	let mut eth_monitoring = EthMonitoring::build(&config.eth, eth_health_rx).await.unwrap();
 
@@ -207,4 +252,107 @@ async fn test_eth_client_refund_transfer() {
 	let res = eth_client_harness.eth_client.refund_bridge_transfer(bridge_transfer_id).await;
 
 	assert!(res.is_ok(), "initiator_complete_bridge_transfer failed: {:?}", res.unwrap_err());
+
+	// A refunded transfer can't be refunded again
+	let res = eth_client_harness.eth_client.refund_bridge_transfer(bridge_transfer_id).await;
+	assert!(
+		matches!(res, Err(BridgeContractError::RefundTransferError(_))),
+		"second refund didn't fail: {:?}",
+		res
+	);
+}
+
+#[tokio::test]
+async fn test_eth_client_refund_unknown_transfer() {
+	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
+
+	let (mut eth_client_harness, _config) = TestHarness::new_with_eth(BridgeTestConfig::default())
+		.await
+		.expect("Bridge config file not set");
+
+	let transfer_id = BridgeTransferId::random(&mut rand::rngs::OsRng);
+	let res = eth_client_harness.eth_client.refund_bridge_transfer(transfer_id).await;
+	assert!(
+		matches!(res, Err(BridgeContractError::RefundTransferError(_))),
+		"refund of an unknown transfer didn't fail: {:?}",
+		res
+	);
+}
+
+#[tokio::test]
+async fn test_eth_client_estimate_bridge_transfer_gas() {
+	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
+
+	let (eth_client_harness, config) = TestHarness::new_with_eth(BridgeTestConfig::default())
+		.await
+		.expect("Bridge config file not set");
+	let eth_client = &eth_client_harness.eth_client;
+
+	let mock_move_token = MockMOVEToken::new(
+		Address::from_str(&config.eth.eth_move_token_contract).unwrap(),
+		eth_client.rpc_provider.clone(),
+	);
+	let call = mock_move_token.approve(eth_client.initiator_contract_address(), U256::from(1));
+
+	let gas = eth_client
+		.estimate_bridge_transfer_gas(&call)
+		.await
+		.expect("Failed to estimate the gas");
+	assert!(gas > 0);
+
+	// the estimate is cached for the function
+	let cached_gas = eth_client
+		.estimate_bridge_transfer_gas(&call)
+		.await
+		.expect("Failed to estimate the gas");
+	assert_eq!(gas, cached_gas);
+}
+
+#[cfg(feature = "integration-tests")]
+#[tokio::test]
+async fn test_eth_client_subscribe_bridge_events() {
+	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
+
+	let (eth_client_harness, config) = TestHarness::new_with_eth(BridgeTestConfig::default())
+		.await
+		.expect("Bridge config file not set");
+	assert!(eth_client_harness.eth_client.config.ws_rpc_url.is_some());
+	let events = eth_client_harness
+		.eth_client
+		.subscribe_bridge_events()
+		.await
+		.expect("Failed to subscribe to bridge events");
+	futures::pin_mut!(events);
+
+	let recipient = HarnessMvtClient::gen_aptos_account();
+	let hash_lock_pre_image = HashLockPreImage::random();
+	let hash_lock = HashLock(From::from(keccak256(hash_lock_pre_image)));
+
+	let res = eth_client_harness
+		.initiate_eth_bridge_transfer(
+			&config,
+			HarnessEthClient::get_initiator_private_key(&config),
+			bridge_service::chains::movement::utils::MovementAddress(recipient.address()),
+			hash_lock,
+			Amount(1),
+		)
+		.await;
+	assert!(res.is_ok(), "initiate_bridge_transfer failed: {:?}", res.unwrap_err());
+
+	// Wait for the initiation event of this transfer
+	let initiated = tokio::time::timeout(std::time::Duration::from_secs(30), async {
+		while let Some(event) = events.next().await {
+			if let BridgeContractEvent::Initiated(details) = event {
+				if details.hash_lock == hash_lock {
+					return Some(details);
+				}
+			}
+		}
+		None
+	})
+	.await
+	.expect("Timeout while waiting for the ETH Initiated event");
+
+	let details = initiated.expect("Event stream ended before the ETH Initiated event");
+	assert_eq!(details.amount, Amount(1));
 }
@@ -2,14 +2,19 @@ use alloy::primitives::keccak256;
 use anyhow::Result;
 use aptos_sdk::coin_client::CoinClient;
 use aptos_sdk::types::account_address::AccountAddress;
+use bridge_config::common::testing::BridgeTestConfig;
 use bridge_integration_tests::utils as test_utils;
 use bridge_integration_tests::{EthToMovementCallArgs, MovementToEthCallArgs, TestHarness};
-use bridge_service::chains::bridge_contracts::BridgeContractEvent;
+use bridge_service::chains::bridge_contracts::{BridgeContractEvent, BridgeContractEventType};
+use bridge_service::chains::movement::compiled_package;
 use bridge_service::chains::movement::event_monitoring::MovementMonitoring;
 use bridge_service::{
 	chains::{
 		bridge_contracts::BridgeContract,
-		movement::utils::{MovementAddress, MovementHash},
+		movement::{
+			client_framework::MovementClientFramework,
+			utils::{MovementAddress, MovementHash},
+		},
 	},
 	types::{Amount, BridgeAddress, BridgeTransferId, HashLock, HashLockPreImage},
 };
@@ -23,12 +28,14 @@ use tracing::info;
 async fn test_movement_client_counterparty_complete_transfer() -> Result<(), anyhow::Error> {
 	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
 	let (mut mvt_client_harness, _config) =
-		TestHarness::new_with_movement().await.expect("Bridge config file not set");
+		TestHarness::new_with_movement(BridgeTestConfig::default())
+			.await
+			.expect("Bridge config file not set");
 	let hash_lock_pre_image = HashLockPreImage::random();
 	let hash_lock = HashLock(From::from(keccak256(hash_lock_pre_image)));
 	let amount = Amount(1);
-	let transfer_id = BridgeTransferId::gen_unique_hash(&mut rand::rngs::OsRng);
-	let initiator = b"32Be343B94f860124dC4fEe278FDCBD38C102D88".to_vec();
+	let transfer_id = BridgeTransferId::random(&mut rand::rngs::OsRng);
+	let initiator = hex::decode("32Be343B94f860124dC4fEe278FDCBD38C102D88")?;
 	let recipient = AccountAddress::new(*b"0x00000000000000000000000000face");
 
 	let coin_client = CoinClient::new(&mvt_client_harness.rest_client);
@@ -68,10 +75,6 @@ async fn test_movement_client_counterparty_complete_transfer() -> Result<(), any
 	assert_eq!(details.state, 1, "Bridge transfer should be pending.");
 	info!("Bridge transfer details: {:?}", details);
 
-	let secret = b"secret";
-	let mut padded_secret = [0u8; 32];
-	padded_secret[..secret.len()].copy_from_slice(secret);
-
 	BridgeContract::counterparty_complete_bridge_transfer(
 		&mut mvt_client_harness.movement_client,
 		transfer_id,
@@ -102,11 +105,14 @@ async fn test_movement_client_counterparty_complete_transfer() -> Result<(), any
 async fn test_movement_client_initiate_transfer() -> Result<(), anyhow::Error> {
 	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
 	let (mut mvt_client_harness, config) =
-		TestHarness::new_with_movement().await.expect("Bridge config file not set");
+		TestHarness::new_with_movement(BridgeTestConfig::default())
+			.await
+			.expect("Bridge config file not set");
 	let args = MovementToEthCallArgs::default();
 
 	let test_result = async {
-		test_utils::fund_and_check_balance_framework(&mut mvt_client_harness, 100_000_000_000)
+		mvt_client_harness
+			.fund_signer_and_check_balance_framework(100_000_000_000)
 			.await?;
 
 		{
@@ -154,6 +160,21 @@ async fn test_movement_client_initiate_transfer() -> Result<(), anyhow::Error> {
 
 		assert_eq!(details.state, 1, "Bridge transfer should be initiated.");
 
+		// The initiation transaction of the signer emitted the monitored transfer.
+		let signer_address = mvt_client_harness.movement_client.signer().address();
+		let txns = mvt_client_harness
+			.rest_client
+			.get_account_transactions(signer_address, None, None)
+			.await?
+			.into_inner();
+		let initiated = test_utils::extract_bridge_transfer_details(&txns)?;
+		let initiated = initiated
+			.iter()
+			.find(|initiated| initiated.bridge_transfer_id == bridge_transfer_id)
+			.expect("Initiated transfer not found in the signer transactions");
+		assert_eq!(initiated.hash_lock, details.hash_lock);
+		assert_eq!(initiated.amount, details.amount);
+
 		test_utils::assert_counterparty_bridge_transfer_details_framework(
 			&details,
 			details.initiator.to_string(),
@@ -174,7 +195,9 @@ async fn test_movement_client_initiate_transfer() -> Result<(), anyhow::Error> {
 async fn test_movement_client_abort_transfer() -> Result<(), anyhow::Error> {
 	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
 	let (mut mvt_client_harness, _config) =
-		TestHarness::new_with_movement().await.expect("Bridge config file not set");
+		TestHarness::new_with_movement(BridgeTestConfig::default())
+			.await
+			.expect("Bridge config file not set");
 	let args = EthToMovementCallArgs::default();
 	let coin_client = CoinClient::new(&mvt_client_harness.rest_client);
 	let movement_client_signer = mvt_client_harness.movement_client.signer();
@@ -215,10 +238,6 @@ async fn test_movement_client_abort_transfer() -> Result<(), anyhow::Error> {
 
 	sleep(Duration::from_secs(20)).await;
 
-	let secret = b"secret";
-	let mut padded_secret = [0u8; 32];
-	padded_secret[..secret.len()].copy_from_slice(secret);
-
 	BridgeContract::abort_bridge_transfer(
 		&mut mvt_client_harness.movement_client,
 		BridgeTransferId(args.bridge_transfer_id.0),
@@ -249,9 +268,13 @@ async fn test_movement_client_abort_transfer() -> Result<(), anyhow::Error> {
 async fn test_movement_client_initiator_complete_transfer() -> Result<(), anyhow::Error> {
 	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
 	let (mut mvt_client_harness, config) =
-		TestHarness::new_with_movement().await.expect("Bridge config file not set");
+		TestHarness::new_with_movement(BridgeTestConfig::default())
+			.await
+			.expect("Bridge config file not set");
 	let args = MovementToEthCallArgs::default();
-	test_utils::fund_and_check_balance_framework(&mut mvt_client_harness, 100_000_000_000).await?;
+	mvt_client_harness
+		.fund_signer_and_check_balance_framework(100_000_000_000)
+		.await?;
 	{
 		let res = BridgeContract::initiate_bridge_transfer(
 			&mut mvt_client_harness.movement_client,
@@ -278,12 +301,13 @@ async fn test_movement_client_initiator_complete_transfer() -> Result<(), anyhow
 			.expect("Timeout while waiting for the Movement Initiated event");
 
 	// Check if we received an event (Option) and handle the Result inside it
-	let bridge_transfer_id = match event_option {
-		Some(Ok(BridgeContractEvent::Initiated(detail))) => detail.bridge_transfer_id,
+	let initiated_event = match event_option {
+		Some(Ok(event @ BridgeContractEvent::Initiated(_))) => event,
 		Some(Err(e)) => panic!("Error in bridge contract event: {:?}", e),
 		None => panic!("No event received"),
 		_ => panic!("Not a an Initiated event: {:?}", event_option),
 	};
+	let bridge_transfer_id = initiated_event.bridge_transfer_id();
 
 	tracing::info!("Received bridge_transfer_id: {:?}", bridge_transfer_id);
 
@@ -316,6 +340,27 @@ async fn test_movement_client_initiator_complete_transfer() -> Result<(), anyhow
 
 	assert_eq!(details.state, 2, "Bridge transfer should be completed.");
 
+	// The monitor reports the completion after the initiation.
+	let mut events = vec![initiated_event];
+	while !matches!(events.last(), Some(BridgeContractEvent::InitiatorCompleted(_))) {
+		let event_option =
+			tokio::time::timeout(std::time::Duration::from_secs(30), mvt_monitoring.next())
+				.await
+				.expect("Timeout while waiting for the Movement InitiatorCompleted event");
+		match event_option {
+			Some(Ok(event)) => events.push(event),
+			Some(Err(e)) => panic!("Error in bridge contract event: {:?}", e),
+			None => panic!("No event received"),
+		}
+	}
+	test_utils::assert_event_ordering(
+		&events,
+		&[
+			(bridge_transfer_id, BridgeContractEventType::Initiated),
+			(bridge_transfer_id, BridgeContractEventType::InitiatorCompleted),
+		],
+	);
+
 	Ok(())
 }
 
@@ -323,9 +368,13 @@ async fn test_movement_client_initiator_complete_transfer() -> Result<(), anyhow
 async fn test_movement_client_refund_transfer() -> Result<(), anyhow::Error> {
 	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
 	let (mut mvt_client_harness, config) =
-		TestHarness::new_with_movement().await.expect("Bridge config file not set");
+		TestHarness::new_with_movement(BridgeTestConfig::default())
+			.await
+			.expect("Bridge config file not set");
 	let args = MovementToEthCallArgs::default();
-	test_utils::fund_and_check_balance_framework(&mut mvt_client_harness, 100_000_000_000).await?;
+	mvt_client_harness
+		.fund_signer_and_check_balance_framework(100_000_000_000)
+		.await?;
 
 	{
 		let res = BridgeContract::initiate_bridge_transfer(
@@ -387,3 +436,73 @@ async fn test_movement_client_refund_transfer() -> Result<(), anyhow::Error> {
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn test_movement_client_new_with_signer() -> Result<(), anyhow::Error> {
+	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
+	let (mvt_client_harness, config) = TestHarness::new_with_movement(BridgeTestConfig::default())
+		.await
+		.expect("Bridge config file not set");
+
+	let first_account = mvt_client_harness.fund_account().await;
+	let second_account = mvt_client_harness.fund_account().await;
+
+	let first_client = MovementClientFramework::new_with_signer(
+		first_account.private_key().clone(),
+		&config.movement,
+	)
+	.await?;
+	let second_client = MovementClientFramework::new_with_signer(
+		second_account.private_key().clone(),
+		&config.movement,
+	)
+	.await?;
+
+	assert_eq!(first_client.signer().address(), first_account.address());
+	assert_eq!(second_client.signer().address(), second_account.address());
+	assert_ne!(first_client.signer().address(), second_client.signer().address());
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_movement_client_ensure_module_published_once() -> Result<(), anyhow::Error> {
+	let _ = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO).try_init();
+	let (mut mvt_client_harness, config) =
+		TestHarness::new_with_movement(BridgeTestConfig::default())
+			.await
+			.expect("Bridge config file not set");
+	let package_build_dir = compiled_package::package_build_dir(&config.movement.package_build_dir);
+	let signer_address = mvt_client_harness.movement_client.signer().address();
+
+	let first_address = mvt_client_harness
+		.movement_client
+		.ensure_module_published(&package_build_dir)
+		.await?;
+	let sequence_number = mvt_client_harness
+		.rest_client
+		.get_account(signer_address)
+		.await?
+		.into_inner()
+		.sequence_number;
+
+	let second_address = mvt_client_harness
+		.movement_client
+		.ensure_module_published(&package_build_dir)
+		.await?;
+	let sequence_number_after = mvt_client_harness
+		.rest_client
+		.get_account(signer_address)
+		.await?
+		.into_inner()
+		.sequence_number;
+
+	assert_eq!(first_address, signer_address);
+	assert_eq!(second_address, signer_address);
+	assert_eq!(
+		sequence_number, sequence_number_after,
+		"The unchanged package should not be published again"
+	);
+
+	Ok(())
+}
@@ -0,0 +1,127 @@
+use bridge_util::chains::bridge_contracts::{BridgeContract, BridgeContractResult};
+use bridge_util::types::{
+	Amount, BridgeAddress, BridgeTransferDetails, BridgeTransferDetailsCounterparty,
+	BridgeTransferId, HashLock, HashLockPreImage,
+};
+use std::fmt::Debug;
+
+/// Wraps a bridge client so that state changing calls are only logged.
+/// Read-only calls are forwarded to the inner client.
+#[derive(Clone)]
+pub struct DryRunClient<C> {
+	inner: C,
+	chain: &'static str,
+}
+
+impl<C> DryRunClient<C> {
+	pub fn new(inner: C, chain: &'static str) -> Self {
+		DryRunClient { inner, chain }
+	}
+}
+
+#[async_trait::async_trait]
+impl<A, C> BridgeContract<A> for DryRunClient<C>
+where
+	A: Debug + Send + Sync + 'static,
+	C: BridgeContract<A>,
+{
+	async fn initiate_bridge_transfer(
+		&mut self,
+		initiator: BridgeAddress<A>,
+		recipient: BridgeAddress<Vec<u8>>,
+		hash_lock: HashLock,
+		amount: Amount,
+	) -> BridgeContractResult<()> {
+		tracing::info!(
+			"[dry-run][{}] initiate_bridge_transfer initiator:{initiator:?} recipient:{recipient:?} hash_lock:{hash_lock:?} amount:{amount:?}",
+			self.chain
+		);
+		Ok(())
+	}
+
+	async fn initiator_complete_bridge_transfer(
+		&mut self,
+		bridge_transfer_id: BridgeTransferId,
+		_secret: HashLockPreImage,
+	) -> BridgeContractResult<()> {
+		tracing::info!(
+			"[dry-run][{}] initiator_complete_bridge_transfer transfer id:{bridge_transfer_id}",
+			self.chain
+		);
+		Ok(())
+	}
+
+	async fn counterparty_complete_bridge_transfer(
+		&mut self,
+		bridge_transfer_id: BridgeTransferId,
+		_secret: HashLockPreImage,
+	) -> BridgeContractResult<()> {
+		tracing::info!(
+			"[dry-run][{}] counterparty_complete_bridge_transfer transfer id:{bridge_transfer_id}",
+			self.chain
+		);
+		Ok(())
+	}
+
+	async fn refund_bridge_transfer(
+		&mut self,
+		bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<()> {
+		tracing::info!(
+			"[dry-run][{}] refund_bridge_transfer transfer id:{bridge_transfer_id}",
+			self.chain
+		);
+		Ok(())
+	}
+
+	async fn get_bridge_transfer_details_initiator(
+		&mut self,
+		bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<Option<BridgeTransferDetails<A>>> {
+		self.inner.get_bridge_transfer_details_initiator(bridge_transfer_id).await
+	}
+
+	async fn get_bridge_transfer_details_counterparty(
+		&mut self,
+		bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<Option<BridgeTransferDetailsCounterparty<A>>> {
+		self.inner.get_bridge_transfer_details_counterparty(bridge_transfer_id).await
+	}
+
+	async fn lock_bridge_transfer(
+		&mut self,
+		bridge_transfer_id: BridgeTransferId,
+		hash_lock: HashLock,
+		initiator: BridgeAddress<Vec<u8>>,
+		recipient: BridgeAddress<A>,
+		amount: Amount,
+	) -> BridgeContractResult<()> {
+		tracing::info!(
+			"[dry-run][{}] lock_bridge_transfer transfer id:{bridge_transfer_id} hash_lock:{hash_lock:?} initiator:{initiator:?} recipient:{recipient:?} amount:{amount:?}",
+			self.chain
+		);
+		Ok(())
+	}
+
+	async fn abort_bridge_transfer(
+		&mut self,
+		bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<()> {
+		tracing::info!(
+			"[dry-run][{}] abort_bridge_transfer transfer id:{bridge_transfer_id}",
+			self.chain
+		);
+		Ok(())
+	}
+
+	async fn claim_relayer_fee(
+		&mut self,
+		bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<()> {
+		tracing::info!(
+			"[dry-run][{}] claim_relayer_fee transfer id:{bridge_transfer_id}",
+			self.chain
+		);
+		Ok(())
+	}
+}
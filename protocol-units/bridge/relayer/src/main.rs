@@ -0,0 +1,197 @@
+use anyhow::Result;
+use bridge_config::Config;
+use bridge_indexer_db::client::Client;
+use bridge_service::{
+	chains::{
+		ethereum::{client::EthClient, event_monitoring::EthMonitoring},
+		movement::{
+			client_framework::MovementClientFramework, event_monitoring::MovementMonitoring,
+			faucet::RateLimitedFaucetClient,
+		},
+	},
+	circuit_breaker::CircuitBreakerConfig,
+	event_log::{EventLogger, EVENT_LOG_FILE},
+	nonce::{NonceRegistry, NONCE_REGISTRY_FOLDER},
+	types::Amount,
+};
+use clap::Parser;
+use dry_run::DryRunClient;
+use godfig::{backend::config_file::ConfigFile, Godfig};
+use prometheus::Registry;
+use std::{path::PathBuf, time::Duration};
+use tokio::task::JoinSet;
+
+mod dry_run;
+
+// Backoff applied between two restarts of the relayer loop.
+const INITIAL_RESTART_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug, Parser)]
+#[clap(name = "bridge-relayer", about = "Relays bridge transfers between Ethereum and Movement")]
+pub struct Args {
+	/// Log the actions the relayer would execute without submitting any transaction.
+	#[clap(long)]
+	dry_run: bool,
+
+	/// Maximum number of times the relayer loop is restarted after a crash.
+	#[clap(long, default_value_t = 10)]
+	max_restarts: u32,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+	use tracing_subscriber::EnvFilter;
+
+	tracing_subscriber::fmt()
+		.with_env_filter(
+			EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+		)
+		.init();
+
+	let args = Args::parse();
+	tracing::info!("Start Bridge relayer with args: {args:?}");
+
+	// Define bridge config path
+	let mut dot_movement = dot_movement::DotMovement::try_from_env()?;
+	let pathbuff = bridge_config::get_config_path(&dot_movement);
+	dot_movement.set_path(pathbuff);
+
+	let config_file = dot_movement.try_get_or_create_config_file().await?;
+
+	// Get a matching godfig object
+	let godfig: Godfig<Config, ConfigFile> = Godfig::new(ConfigFile::new(config_file), vec![]);
+	let bridge_config: Config = godfig.try_wait_for_ready().await?;
+
+	tracing::info!("Bridge config loaded: {bridge_config:?}");
+
+	let nonce_registry_path = dot_movement.get_path().join(NONCE_REGISTRY_FOLDER);
+	let event_log_path = dot_movement.get_path().join(EVENT_LOG_FILE);
+
+	let mut restarts = 0;
+	let mut backoff = INITIAL_RESTART_BACKOFF;
+	loop {
+		let mut relayer_tasks = JoinSet::new();
+		relayer_tasks.spawn(run_relayer(
+			bridge_config.clone(),
+			nonce_registry_path.clone(),
+			event_log_path.clone(),
+			args.dry_run,
+		));
+
+		tokio::select! {
+			Some(res) = relayer_tasks.join_next() => {
+				match res {
+					Ok(Ok(())) => {
+						tracing::info!("Relayer loop exited normally.");
+						return Ok(());
+					}
+					Ok(Err(err)) => tracing::error!("Relayer loop exit with an error: {err}"),
+					Err(err) if err.is_panic() => tracing::error!("Relayer loop panicked: {err}"),
+					Err(err) => return Err(err.into()),
+				}
+			}
+			_ = tokio::signal::ctrl_c() => {
+				tracing::info!("Shutdown requested, stopping the relayer.");
+				relayer_tasks.shutdown().await;
+				return Ok(());
+			}
+		}
+
+		restarts += 1;
+		if restarts > args.max_restarts {
+			anyhow::bail!("Relayer crashed {restarts} times, max restarts exceeded.");
+		}
+		tracing::warn!(
+			"Restarting relayer in {backoff:?} (restart {restarts}/{}).",
+			args.max_restarts
+		);
+		tokio::time::sleep(backoff).await;
+		backoff = (backoff * 2).min(MAX_RESTART_BACKOFF);
+	}
+}
+
+/// Builds the clients and monitoring streams from the config and runs the relayer loop.
+async fn run_relayer(
+	bridge_config: Config,
+	nonce_registry_path: PathBuf,
+	event_log_path: PathBuf,
+	dry_run: bool,
+) -> Result<()> {
+	let (eth_health_tx, eth_health_rx) = tokio::sync::mpsc::channel(10);
+	let one_stream = EthMonitoring::build(&bridge_config.eth, eth_health_rx).await?;
+	// Not served yet, the metrics are only collected.
+	let metrics_registry = Registry::new();
+	let one_client = EthClient::new(&bridge_config.eth, &metrics_registry).await?;
+	let (mvt_health_tx, mvt_health_rx) = tokio::sync::mpsc::channel(10);
+	let two_stream = MovementMonitoring::build(&bridge_config.movement, mvt_health_rx).await?;
+	let two_client = MovementClientFramework::new(&bridge_config.movement).await?;
+	// Funds the Movement recipients of the transfers before their lock.
+	let faucet = RateLimitedFaucetClient::try_from_config(&bridge_config.movement)?;
+
+	// The relayer has no REST service, keep the sender alive so the health check channel stays open.
+	let (_health_tx, health_rx) = tokio::sync::mpsc::channel(10);
+
+	let indexer_db_client = match Client::from_env() {
+		Ok(mut client) => {
+			client.run_migrations()?;
+			Some(client)
+		}
+		Err(e) => {
+			tracing::warn!("Failed to create indexer db client: {e:?}");
+			None
+		}
+	};
+
+	// A dry run submits nothing, so it must not mark any completion as done.
+	let nonce_registry = if dry_run {
+		NonceRegistry::in_memory()
+	} else {
+		NonceRegistry::open(nonce_registry_path)?
+	};
+	let event_logger = EventLogger::open(event_log_path)?;
+
+	tracing::info!("Bridge Eth and Movement Inited. Starting relayer loop.");
+	if dry_run {
+		bridge_service::run_bridge(
+			DryRunClient::new(one_client, "eth"),
+			one_stream,
+			DryRunClient::new(two_client, "movement"),
+			two_stream,
+			health_rx,
+			indexer_db_client,
+			Some(event_logger),
+			nonce_registry,
+			eth_health_tx,
+			mvt_health_tx,
+			CircuitBreakerConfig::default(),
+			&metrics_registry,
+			None,
+			None,
+			Amount(bridge_config.eth.eth_relayer_fee),
+			// a dry run funds no recipient
+			None,
+		)
+		.await
+	} else {
+		bridge_service::run_bridge(
+			one_client,
+			one_stream,
+			two_client,
+			two_stream,
+			health_rx,
+			indexer_db_client,
+			Some(event_logger),
+			nonce_registry,
+			eth_health_tx,
+			mvt_health_tx,
+			CircuitBreakerConfig::default(),
+			&metrics_registry,
+			None,
+			None,
+			Amount(bridge_config.eth.eth_relayer_fee),
+			Some(faucet),
+		)
+		.await
+	}
+}
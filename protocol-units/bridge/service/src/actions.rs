@@ -1,17 +1,140 @@
-use crate::chains::movement::utils as movement_utils;
+use crate::chains::movement::{faucet::RateLimitedFaucetClient, utils as movement_utils};
+use crate::circuit_breaker::CircuitBreaker;
+use crate::executor::ActionFuture;
+use crate::nonce::NonceRegistry;
 use bridge_util::chains::bridge_contracts::BridgeContract;
 use bridge_util::chains::bridge_contracts::BridgeContractError;
-use bridge_util::types::BridgeAddress;
+use bridge_util::chains::bridge_contracts::BridgeContractErrorCode;
+use bridge_util::types::{Amount, BridgeAddress, Nonce};
 use bridge_util::ActionExecError;
 use bridge_util::TransferAction;
 use bridge_util::TransferActionType;
 use std::future::Future;
-use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
 
+/// Serializes the calls made with the clients of a chain: only one client can be used at a time.
+/// It is only held during the calls, so the waits of the actions run concurrently.
+pub type ClientLock = Arc<Mutex<()>>;
+
+/// Delays between the attempts to complete a transfer on the initiator chain.
+#[derive(Debug, Clone, Copy)]
+struct Backoff {
+	/// The delay before the first retry, doubled after each retry.
+	initial_interval: Duration,
+	/// The maximum delay between two attempts.
+	max_interval: Duration,
+	/// No retry is started once this duration has elapsed since the first attempt.
+	max_elapsed_time: Duration,
+}
+
+const COMPLETE_INITIATOR_BACKOFF: Backoff = Backoff {
+	initial_interval: Duration::from_secs(1),
+	max_interval: Duration::from_secs(60),
+	max_elapsed_time: Duration::from_secs(10 * 60),
+};
+
+/// Whether the call may succeed if it is retried.
+fn is_transient(err: &BridgeContractError) -> bool {
+	matches!(
+		err.code(),
+		BridgeContractErrorCode::TransportError | BridgeContractErrorCode::TimeoutError
+	)
+}
+
+/// Calls `call` until it succeeds or fails with an error that is not transient.
+/// Gives up with [`BridgeContractError::RetriesExhausted`] after `max_retries` retries
+/// or once the max elapsed time of the backoff is reached.
+async fn retry_transient<F, Fut>(
+	backoff: Backoff,
+	max_retries: u32,
+	mut call: F,
+) -> Result<(), BridgeContractError>
+where
+	F: FnMut() -> Fut,
+	Fut: Future<Output = Result<(), BridgeContractError>>,
+{
+	let start = Instant::now();
+	let mut interval = backoff.initial_interval;
+	let mut attempts = 0;
+	loop {
+		attempts += 1;
+		match call().await {
+			Ok(()) => return Ok(()),
+			Err(err) if !is_transient(&err) => return Err(err),
+			Err(err) => {
+				if attempts > max_retries || start.elapsed() + interval > backoff.max_elapsed_time {
+					return Err(BridgeContractError::RetriesExhausted {
+						attempts,
+						elapsed_secs: start.elapsed().as_secs(),
+						last_error: err.to_string(),
+					});
+				}
+				tracing::warn!(
+					"Transient error: {err}, attempt {attempts} retried in {interval:?}"
+				);
+				tokio::time::sleep(interval).await;
+				interval = (interval * 2).min(backoff.max_interval);
+			}
+		}
+	}
+}
+
+/// Claims the fee of the relayer once the action paying it succeeded. No fee is claimed when
+/// it is zero. The action is already done on chain, so a failed claim is only logged.
+async fn claim_relayer_fee<A>(
+	client: &mut impl BridgeContract<A>,
+	action: &TransferAction,
+	relayer_fee: Amount,
+) {
+	if relayer_fee.is_zero() {
+		return;
+	}
+	if let Err(err) = client.claim_relayer_fee(action.transfer_id).await {
+		tracing::warn!("Failed to claim relayer fee {relayer_fee} for action:{action} err:{err}");
+	}
+}
+
+/// Creates the execution of the action, guarded by the circuit breaker of the action's chain.
+/// The breaker is checked when the execution starts: while it is open, the execution is
+/// deferred until the breaker lets it through.
+/// Only the errors of an unavailable chain count towards opening the breaker.
 pub fn process_action<A>(
+	action: TransferAction,
+	client: impl BridgeContract<A> + 'static,
+	client_lock: ClientLock,
+	nonce_registry: NonceRegistry,
+	circuit_breaker: CircuitBreaker,
+	faucet: Option<RateLimitedFaucetClient>,
+) -> Option<ActionFuture>
+where
+	A: Clone + Send + TryFrom<Vec<u8>>,
+{
+	let deferred_action = action.clone();
+	let future = build_action_future(action, client, client_lock, nonce_registry, faucet)?;
+	Some(Box::pin(async move {
+		if !circuit_breaker.try_acquire() {
+			tracing::warn!("Circuit breaker open, action deferred:{deferred_action}");
+			circuit_breaker.acquire().await;
+		}
+		let result = future.await;
+		match &result {
+			Ok(()) => circuit_breaker.record_success(),
+			Err(ActionExecError(_, err)) if is_transient(err) => circuit_breaker.record_failure(),
+			Err(_) => circuit_breaker.record_ignored(),
+		}
+		result
+	}))
+}
+
+fn build_action_future<A>(
 	action: TransferAction,
 	mut client: impl BridgeContract<A> + 'static,
-) -> Option<Pin<Box<dyn Future<Output = Result<(), ActionExecError>> + Send>>>
+	client_lock: ClientLock,
+	nonce_registry: NonceRegistry,
+	faucet: Option<RateLimitedFaucetClient>,
+) -> Option<ActionFuture>
 where
 	A: Clone + Send + TryFrom<Vec<u8>>,
 {
@@ -23,14 +146,16 @@ where
 			initiator,
 			recipient,
 			amount,
+			relayer_fee,
 		} => {
 			let future = async move {
-				if recipient.0.len() == 32 {
-					if let Err(e) = movement_utils::fund_recipient(&recipient).await {
+				if let (Some(faucet), 32) = (&faucet, recipient.0.len()) {
+					if let Err(e) = movement_utils::fund_recipient(faucet, &recipient).await {
 						return Err(ActionExecError(action.clone(), e));
 					}
 				}
 				tracing::info!("Before client.lock_bridge_transfer");
+				let _lock = client_lock.lock().await;
 				client
 					.lock_bridge_transfer(
 						bridge_transfer_id,
@@ -45,24 +170,290 @@ where
 						amount,
 					)
 					.await
-					.map_err(|err| ActionExecError(action, err))
+					.map_err(|err| ActionExecError(action.clone(), err))?;
+				claim_relayer_fee(&mut client, &action, relayer_fee).await;
+				Ok(())
 			};
 			Some(Box::pin(future))
 		}
-		TransferActionType::WaitAndCompleteInitiator(wait_time_sec, secret) => {
+		TransferActionType::WaitAndCompleteInitiator(
+			wait_time_sec,
+			secret,
+			hash_lock,
+			max_retries,
+			relayer_fee,
+		) => {
 			let future = async move {
 				if wait_time_sec != 0 {
-					let _ = tokio::time::sleep(tokio::time::Duration::from_secs(wait_time_sec));
+					tokio::time::sleep(Duration::from_secs(wait_time_sec)).await;
+				}
+				// the contract would revert the completion, don't spend gas on it
+				if !hash_lock.verify_preimage(&secret) {
+					let transfer_id = action.transfer_id;
+					return Err(ActionExecError(
+						action,
+						BridgeContractError::InvalidPreImage(transfer_id),
+					));
+				}
+				let nonce = Nonce::from(&secret);
+				// a concurrent completion with the same nonce is rejected until this one fails
+				let transfer_id = action.transfer_id;
+				if !nonce_registry.reserve(transfer_id, nonce) {
+					return Err(ActionExecError(
+						action,
+						BridgeContractError::NonceAlreadyUsed(transfer_id, nonce),
+					));
 				}
+				// The lock is taken for each attempt, the other actions run during the backoff.
+				let completed = retry_transient(COMPLETE_INITIATOR_BACKOFF, max_retries, || {
+					let mut client = client.clone();
+					let client_lock = client_lock.clone();
+					async move {
+						let _lock = client_lock.lock().await;
+						client.initiator_complete_bridge_transfer(transfer_id, secret).await
+					}
+				})
+				.await;
+				if let Err(err) = completed {
+					nonce_registry.release(transfer_id, nonce);
+					return Err(ActionExecError(action, err));
+				}
+				// The completion is already on chain, a failure here only loses the replay guard.
+				if let Err(err) = nonce_registry.register(action.transfer_id, nonce) {
+					tracing::warn!("Failed to register nonce for action:{action} err:{err}");
+				}
+				let _lock = client_lock.lock().await;
+				claim_relayer_fee(&mut client, &action, relayer_fee).await;
+				Ok(())
+			};
+			Some(Box::pin(future))
+		}
+		TransferActionType::RefundInitiator => {
+			let future = async move {
+				let _lock = client_lock.lock().await;
 				client
-					.initiator_complete_bridge_transfer(action.transfer_id, secret)
+					.refund_bridge_transfer(action.transfer_id)
 					.await
 					.map_err(|err| ActionExecError(action, err))
 			};
 			Some(Box::pin(future))
 		}
-		TransferActionType::RefundInitiator => None,
 		TransferActionType::TransferDone => None,
 		TransferActionType::NoAction => None,
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::circuit_breaker::{CircuitBreakerConfig, CircuitBreakerState};
+	use crate::mock::MockClient;
+	use bridge_util::types::{BridgeTransferId, ChainId, HashLock, HashLockPreImage};
+	use std::sync::atomic::{AtomicU32, Ordering};
+
+	const TEST_BACKOFF: Backoff = Backoff {
+		initial_interval: Duration::from_millis(1),
+		max_interval: Duration::from_millis(4),
+		max_elapsed_time: Duration::from_secs(60),
+	};
+
+	/// Calls failing with `error` the first `failures` times.
+	async fn call_failing(
+		calls: &AtomicU32,
+		failures: u32,
+		error: BridgeContractError,
+	) -> Result<(), BridgeContractError> {
+		if calls.fetch_add(1, Ordering::SeqCst) < failures {
+			return Err(error);
+		}
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_succeeds_after_transient_failures() {
+		let calls = AtomicU32::new(0);
+		let error = BridgeContractError::TransportError("connection reset".to_string());
+		retry_transient(TEST_BACKOFF, 5, || call_failing(&calls, 3, error.clone()))
+			.await
+			.unwrap();
+		assert_eq!(calls.load(Ordering::SeqCst), 4);
+	}
+
+	#[tokio::test]
+	async fn test_gives_up_after_max_retries() {
+		let calls = AtomicU32::new(0);
+		let error = BridgeContractError::TransportError("connection reset".to_string());
+		let err = retry_transient(TEST_BACKOFF, 2, || call_failing(&calls, 3, error.clone()))
+			.await
+			.unwrap_err();
+		assert_eq!(err.code(), BridgeContractErrorCode::TimeoutError);
+		assert!(matches!(err, BridgeContractError::RetriesExhausted { attempts: 3, .. }));
+		assert_eq!(calls.load(Ordering::SeqCst), 3);
+	}
+
+	#[tokio::test]
+	async fn test_reverts_are_not_retried() {
+		let calls = AtomicU32::new(0);
+		let error = BridgeContractError::ContractRevert("already completed".to_string());
+		let err = retry_transient(TEST_BACKOFF, 5, || call_failing(&calls, 3, error.clone()))
+			.await
+			.unwrap_err();
+		assert_eq!(err, error);
+		assert_eq!(calls.load(Ordering::SeqCst), 1);
+	}
+
+	fn complete_initiator_action(relayer_fee: Amount) -> TransferAction {
+		let secret = HashLockPreImage([4; 32]);
+		TransferAction {
+			chain: ChainId::ONE,
+			transfer_id: BridgeTransferId([1; 32]),
+			kind: TransferActionType::WaitAndCompleteInitiator(
+				0,
+				secret,
+				HashLock::from_preimage(&secret),
+				0,
+				relayer_fee,
+			),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_claims_relayer_fee_after_completion() {
+		let client = MockClient::default();
+		let action = complete_initiator_action(Amount(10));
+		let transfer_id = action.transfer_id;
+		build_action_future(
+			action,
+			client.clone(),
+			ClientLock::default(),
+			NonceRegistry::in_memory(),
+			None,
+		)
+		.unwrap()
+		.await
+		.unwrap();
+		assert_eq!(client.completed(), vec![transfer_id]);
+		assert_eq!(client.fee_claims(), vec![transfer_id]);
+	}
+
+	#[tokio::test]
+	async fn test_no_relayer_fee_claimed_when_zero() {
+		let client = MockClient::default();
+		let action = complete_initiator_action(Amount(0));
+		let transfer_id = action.transfer_id;
+		build_action_future(
+			action,
+			client.clone(),
+			ClientLock::default(),
+			NonceRegistry::in_memory(),
+			None,
+		)
+		.unwrap()
+		.await
+		.unwrap();
+		assert_eq!(client.completed(), vec![transfer_id]);
+		assert!(client.fee_claims().is_empty());
+	}
+
+	#[tokio::test]
+	async fn test_circuit_breaker_defers_the_action() {
+		let client = MockClient::default();
+		let breaker =
+			CircuitBreaker::new(CircuitBreakerConfig { failure_threshold: 1, cooldown_secs: 0 });
+		let action = complete_initiator_action(Amount(0));
+		let transfer_id = action.transfer_id;
+		let future = process_action(
+			action,
+			client.clone(),
+			ClientLock::default(),
+			NonceRegistry::in_memory(),
+			breaker.clone(),
+			None,
+		)
+		.unwrap();
+
+		// The breaker opens while the action waits in the executor, and another action probes.
+		breaker.record_failure();
+		assert!(breaker.try_acquire());
+		let action_jh = tokio::spawn(future);
+		tokio::time::sleep(Duration::from_millis(50)).await;
+		assert!(client.completed().is_empty());
+
+		// The action is executed once the probe succeeded, rather than failed.
+		breaker.record_success();
+		tokio::time::timeout(Duration::from_secs(5), action_jh)
+			.await
+			.unwrap()
+			.unwrap()
+			.unwrap();
+		assert_eq!(client.completed(), vec![transfer_id]);
+	}
+
+	#[tokio::test]
+	async fn test_circuit_breaker_ignores_deterministic_errors() {
+		let client = MockClient::default();
+		let breaker =
+			CircuitBreaker::new(CircuitBreakerConfig { failure_threshold: 1, cooldown_secs: 3600 });
+		let secret = HashLockPreImage([4; 32]);
+		let action = TransferAction {
+			chain: ChainId::ONE,
+			transfer_id: BridgeTransferId([1; 32]),
+			kind: TransferActionType::WaitAndCompleteInitiator(
+				0,
+				secret,
+				// not the hash lock of the secret
+				HashLock([0; 32]),
+				0,
+				Amount(0),
+			),
+		};
+		let future = process_action(
+			action,
+			client,
+			ClientLock::default(),
+			NonceRegistry::in_memory(),
+			breaker.clone(),
+			None,
+		)
+		.unwrap();
+
+		let err = future.await.unwrap_err();
+		assert!(matches!(err.1, BridgeContractError::InvalidPreImage(_)), "{err}");
+		assert_eq!(breaker.state(), CircuitBreakerState::Closed);
+	}
+
+	#[tokio::test]
+	async fn test_client_lock_is_released_during_the_backoff() {
+		let client = MockClient::default().with_completion_failures(1);
+		let client_lock = ClientLock::default();
+		let secret = HashLockPreImage([4; 32]);
+		let action = TransferAction {
+			chain: ChainId::ONE,
+			transfer_id: BridgeTransferId([1; 32]),
+			kind: TransferActionType::WaitAndCompleteInitiator(
+				0,
+				secret,
+				HashLock::from_preimage(&secret),
+				1,
+				Amount(0),
+			),
+		};
+		let transfer_id = action.transfer_id;
+		let future = build_action_future(
+			action,
+			client.clone(),
+			client_lock.clone(),
+			NonceRegistry::in_memory(),
+			None,
+		)
+		.unwrap();
+		let action_jh = tokio::spawn(future);
+
+		// The first attempt failed, the retry waits for the initial backoff interval.
+		tokio::time::sleep(COMPLETE_INITIATOR_BACKOFF.initial_interval / 2).await;
+		assert!(client_lock.try_lock().is_ok());
+
+		action_jh.await.unwrap().unwrap();
+		assert_eq!(client.completed(), vec![transfer_id]);
+	}
+}
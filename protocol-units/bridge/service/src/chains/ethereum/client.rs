@@ -1,40 +1,67 @@
+use super::gas_estimate_cache::GasEstimateCache;
+use super::metrics::EthClientMetrics;
+use super::nonce_cache::NonceCache;
 use super::types::{
 	AlloyProvider, AssetKind, AtomicBridgeCounterpartyMOVE, AtomicBridgeInitiatorMOVE,
-	CounterpartyContract, EthAddress, InitiatorContract,
+	CounterpartyContract, EthAddress, IRelayerFee, InitiatorContract,
+};
+use super::utils::{
+	calculate_counterparty_storage_slot, calculate_storage_slot, log_timestamp, map_rpc_error,
+	map_send_transaction_error, send_transaction, send_transaction_rules,
+	send_transaction_with_gas, TransactionType, COUNTERPARTY_BRIDGE_TRANSFER_SLOTS,
 };
-use super::utils::{calculate_storage_slot, send_transaction, send_transaction_rules};
 use alloy::{
-	network::EthereumWallet,
-	primitives::{Address, FixedBytes, U256},
+	contract::{CallBuilder, CallDecoder},
+	network::{Ethereum, EthereumWallet},
+	primitives::{Address, FixedBytes, Selector, U256},
 	providers::{Provider, ProviderBuilder},
 	rlp::{RlpDecodable, RlpEncodable},
+	rpc::types::{Log, TransactionReceipt},
 	signers::local::PrivateKeySigner,
+	transports::BoxTransport,
 };
 use alloy_primitives::Uint;
 use alloy_rlp::Decodable;
 use bridge_config::common::eth::EthConfig;
 use bridge_grpc::bridge_server::BridgeServer;
-use bridge_util::chains::bridge_contracts::{BridgeContractError, BridgeContractResult};
+use bridge_util::address::derive_movement_recipient;
+use bridge_util::chains::bridge_contracts::{
+	BridgeContractError, BridgeContractEvent, BridgeContractResult,
+};
 use bridge_util::types::{
-	Amount, BridgeAddress, BridgeTransferDetails, BridgeTransferDetailsCounterparty,
-	BridgeTransferId, HashLock, HashLockPreImage, TimeLock,
+	current_timestamp_secs, Amount, BridgeAddress, BridgeTransferDetails,
+	BridgeTransferDetailsCounterparty, BridgeTransferId, HashLock, HashLockPreImage, TimeLock,
 };
-use std::{fmt::Debug, net::SocketAddr};
+use futures::stream::{self, BoxStream, Stream, StreamExt};
+use prometheus::Registry;
+use std::{fmt::Debug, net::SocketAddr, time::Instant};
 use tonic::transport::Server;
-use tracing::info;
+use tracing::{info, warn};
 use url::Url;
 
 /// Configuration for the Ethereum Bridge Client
 #[derive(Clone, Debug)]
 pub struct Config {
 	pub rpc_url: Url,
+	/// The WebSocket endpoint to subscribe to contract events.
+	/// Events are polled from `rpc_url` if it is not set.
+	pub ws_rpc_url: Option<Url>,
 	pub signer_private_key: PrivateKeySigner,
 	pub initiator_contract: Address,
 	pub counterparty_contract: Address,
 	pub movetoken_contract: Address,
+	/// The contract paying the relayer fees, the zero address if there is none.
+	pub relayer_fee_contract: Address,
+	/// The maximum fee of a transaction in wei, 0 to estimate the gas of each transaction.
 	pub gas_limit: u128,
+	/// The factor applied to the node's gas estimates.
+	pub gas_estimate_multiplier: f64,
 	pub transaction_send_retries: u32,
+	/// The type of the transactions sent by the client.
+	pub transaction_type: TransactionType,
 	pub asset: AssetKind,
+	/// The number of blocks an event's block must be buried under before the event is relayed.
+	pub confirmations_required: u64,
 }
 impl TryFrom<&EthConfig> for Config {
 	type Error = anyhow::Error;
@@ -42,20 +69,81 @@ impl TryFrom<&EthConfig> for Config {
 	fn try_from(conf: &EthConfig) -> Result<Self, Self::Error> {
 		let signer_private_key = conf.signer_private_key.parse::<PrivateKeySigner>()?;
 		let rpc_url = conf.eth_rpc_connection_url().parse()?;
+		let ws_rpc_url = (!conf.eth_ws_connection_hostname.is_empty())
+			.then(|| conf.eth_ws_connection_url().parse())
+			.transpose()?;
 
 		Ok(Config {
 			rpc_url,
+			ws_rpc_url,
 			signer_private_key,
 			initiator_contract: conf.eth_initiator_contract.parse()?,
 			counterparty_contract: conf.eth_counterparty_contract.parse()?,
 			movetoken_contract: conf.eth_move_token_contract.parse()?,
+			relayer_fee_contract: conf.eth_relayer_fee_contract.parse()?,
 			gas_limit: conf.gas_limit.into(),
+			gas_estimate_multiplier: conf.eth_gas_estimate_multiplier,
 			transaction_send_retries: conf.transaction_send_retries,
+			transaction_type: match conf.eth_max_fee_per_gas {
+				0 => TransactionType::Legacy,
+				max_fee_per_gas => TransactionType::Eip1559 {
+					max_fee_per_gas: max_fee_per_gas.into(),
+					max_priority_fee: conf.eth_max_priority_fee.into(),
+				},
+			},
 			asset: conf.asset.clone().into(),
+			confirmations_required: conf.eth_confirmations_required,
 		})
 	}
 }
 
+type InitiatedLog =
+	alloy::sol_types::Result<(AtomicBridgeInitiatorMOVE::BridgeTransferInitiated, Log)>;
+type CompletedLog =
+	alloy::sol_types::Result<(AtomicBridgeInitiatorMOVE::BridgeTransferCompleted, Log)>;
+
+/// Decodes the initiator contract events into [`BridgeContractEvent`]s.
+/// `provider` is queried for the timestamps of the initiation blocks.
+fn decode_bridge_events<P>(
+	provider: P,
+	initiated: impl Stream<Item = InitiatedLog> + Send + 'static,
+	completed: impl Stream<Item = CompletedLog> + Send + 'static,
+) -> BoxStream<'static, BridgeContractEvent<EthAddress>>
+where
+	P: Provider<BoxTransport, Ethereum> + Clone + 'static,
+{
+	let initiated = initiated.then(move |event| {
+		let provider = provider.clone();
+		async move {
+			let (initiated, log) = event?;
+			let details = BridgeTransferDetails {
+				bridge_transfer_id: BridgeTransferId(*initiated._bridgeTransferId),
+				initiator: BridgeAddress(EthAddress(Address::from(initiated._originator))),
+				recipient: BridgeAddress(initiated._recipient.to_vec()),
+				hash_lock: HashLock(*initiated._hashLock),
+				time_lock: initiated._timeLock.into(),
+				amount: initiated.amount.into(),
+				state: 0,
+				initiated_at: log_timestamp(&provider, &log).await,
+			};
+			Ok::<_, alloy::sol_types::Error>(BridgeContractEvent::Initiated(details))
+		}
+	});
+	let completed = completed.map(|event| {
+		event.map(|(completed, _log)| {
+			BridgeContractEvent::InitiatorCompleted(BridgeTransferId(*completed._bridgeTransferId))
+		})
+	});
+	stream::select(initiated, completed)
+		.filter_map(|event| async move {
+			event.map_err(|e| warn!("Failed to decode bridge transfer event: {}", e)).ok()
+		})
+		.boxed()
+}
+
+/// `MessageState.INITIALIZED` of the initiator contract.
+const INITIATOR_STATE_INITIALIZED: u8 = 0;
+
 #[derive(RlpDecodable, RlpEncodable)]
 struct EthBridgeTransferDetails {
 	pub amount: U256,
@@ -66,16 +154,6 @@ struct EthBridgeTransferDetails {
 	pub state: u8,
 }
 
-#[derive(RlpDecodable, RlpEncodable)]
-struct EthBridgeTransferDetailsCounterparty {
-	pub amount: U256,
-	pub originator: [u8; 32],
-	pub recipient: EthAddress,
-	pub hash_lock: [u8; 32],
-	pub time_lock: U256,
-	pub state: u8,
-}
-
 #[derive(Clone)]
 pub struct EthClient {
 	pub rpc_provider: AlloyProvider,
@@ -83,15 +161,54 @@ pub struct EthClient {
 	counterparty_contract: CounterpartyContract,
 	pub config: Config,
 	signer_address: Address,
+	nonce_cache: NonceCache,
+	gas_estimates: GasEstimateCache,
+	metrics: EthClientMetrics,
 }
 
 impl EthClient {
-	pub async fn new(config: &EthConfig) -> Result<Self, anyhow::Error> {
+	/// Creates a client registering its metrics with `metrics_registry`.
+	pub async fn new(
+		config: &EthConfig,
+		metrics_registry: &Registry,
+	) -> Result<Self, anyhow::Error> {
 		let config: Config = config.try_into()?;
 		let signer_address = config.signer_private_key.address();
+		let wallet = EthereumWallet::from(config.signer_private_key.clone());
+		Self::build(config, wallet, signer_address, metrics_registry).await
+	}
+
+	/// Creates a client that signs with a Ledger hardware wallet
+	/// instead of the configured `signer_private_key`.
+	/// `derivation_path` is the HD path of the account to use on the device.
+	#[cfg(feature = "ledger-signer")]
+	pub async fn with_ledger_signer(
+		config: &EthConfig,
+		derivation_path: &str,
+		metrics_registry: &Registry,
+	) -> Result<Self, anyhow::Error> {
+		use alloy::signers::Signer;
+		use alloy_signer_ledger::{HDPath, LedgerSigner};
+
+		let chain_id = (config.eth_chain_id != 0).then_some(config.eth_chain_id);
+		let config: Config = config.try_into()?;
+		let ledger_signer =
+			LedgerSigner::new(HDPath::Other(derivation_path.to_string()), chain_id).await?;
+		let signer_address = ledger_signer.address();
+		info!("Using Ledger signer with address: {}", signer_address);
+		let wallet = EthereumWallet::from(ledger_signer);
+		Self::build(config, wallet, signer_address, metrics_registry).await
+	}
+
+	async fn build(
+		config: Config,
+		wallet: EthereumWallet,
+		signer_address: Address,
+		metrics_registry: &Registry,
+	) -> Result<Self, anyhow::Error> {
 		let rpc_provider = ProviderBuilder::new()
 			.with_recommended_fillers()
-			.wallet(EthereumWallet::from(config.signer_private_key.clone()))
+			.wallet(wallet)
 			.on_builtin(config.rpc_url.as_str())
 			.await?;
 
@@ -100,15 +217,129 @@ impl EthClient {
 		let counterparty_contract =
 			AtomicBridgeCounterpartyMOVE::new(config.counterparty_contract, rpc_provider.clone());
 
+		let nonce_cache = NonceCache::new();
+		// the pending nonce also counts the transactions of the signer still in the mempool
+		let nonce = rpc_provider.get_transaction_count(signer_address).pending().await?;
+		nonce_cache.set(signer_address, nonce);
+
+		let metrics = EthClientMetrics::try_new(metrics_registry)?;
+
 		Ok(EthClient {
 			rpc_provider,
 			initiator_contract,
 			counterparty_contract,
-			config: config.clone(),
+			config,
 			signer_address,
+			nonce_cache,
+			gas_estimates: GasEstimateCache::new(),
+			metrics,
 		})
 	}
 
+	pub fn metrics(&self) -> &EthClientMetrics {
+		&self.metrics
+	}
+
+	/// Estimates the gas of `call` with the RPC, increased by the configured multiplier.
+	/// The estimates are cached per contract function for a few blocks.
+	pub async fn estimate_bridge_transfer_gas<D: CallDecoder + Clone>(
+		&self,
+		call: &CallBuilder<BoxTransport, &AlloyProvider, D, Ethereum>,
+	) -> Result<u128, anyhow::Error> {
+		let selector = call.calldata().get(..4).map(Selector::from_slice);
+		let block_number = self.rpc_provider.get_block_number().await?;
+		if let Some(gas) =
+			selector.and_then(|selector| self.gas_estimates.get(&selector, block_number))
+		{
+			return Ok(gas);
+		}
+
+		// the sender must be set, the estimate fails if the call reverts
+		let estimate = call.clone().from(self.signer_address).estimate_gas().await?;
+		let gas = (estimate as f64 * self.config.gas_estimate_multiplier).ceil() as u128;
+		info!("Estimated gas: {} ({} from the RPC)", gas, estimate);
+		if let Some(selector) = selector {
+			self.gas_estimates.insert(selector, block_number, gas);
+		}
+		Ok(gas)
+	}
+
+	/// Sends a transaction of the signer, using a nonce from the nonce cache
+	/// instead of querying it from the RPC, and records the fees paid.
+	/// A failure is recorded under the client method `method` sending the transaction.
+	/// The gas is estimated when the configured `gas_limit` is 0.
+	async fn send_call<D: CallDecoder + Clone>(
+		&self,
+		method: &str,
+		call: CallBuilder<BoxTransport, &AlloyProvider, D, Ethereum>,
+	) -> Result<TransactionReceipt, anyhow::Error> {
+		let receipt = self.count_rpc_error(method, self.send_call_with_nonce(call).await)?;
+		self.metrics.record_receipt(&receipt);
+		Ok(receipt)
+	}
+
+	/// Records `result` under the client method `method` if the RPC call failed.
+	fn count_rpc_error<T, E>(&self, method: &str, result: Result<T, E>) -> Result<T, E> {
+		if result.is_err() {
+			self.metrics.record_rpc_error(method);
+		}
+		result
+	}
+
+	async fn send_call_with_nonce<D: CallDecoder + Clone>(
+		&self,
+		call: CallBuilder<BoxTransport, &AlloyProvider, D, Ethereum>,
+	) -> Result<TransactionReceipt, anyhow::Error> {
+		let rules = send_transaction_rules();
+		let rules = &rules;
+		let gas = match self.config.gas_limit {
+			0 => Some(self.estimate_bridge_transfer_gas(&call).await?),
+			_ => None,
+		};
+		self.nonce_cache
+			.send_with_nonce(
+				self.signer_address,
+				|| async {
+					Ok(self
+						.rpc_provider
+						.get_transaction_count(self.signer_address)
+						.pending()
+						.await?)
+				},
+				|nonce| {
+					let call = call.clone().nonce(nonce);
+					async move {
+						match gas {
+							Some(gas) => {
+								send_transaction_with_gas(
+									call,
+									self.signer_address,
+									rules,
+									self.config.transaction_send_retries,
+									self.config.gas_limit,
+									gas,
+									self.config.transaction_type,
+								)
+								.await
+							}
+							None => {
+								send_transaction_with_type(
+									call,
+									self.signer_address,
+									rules,
+									self.config.transaction_send_retries,
+									self.config.gas_limit,
+									self.config.transaction_type,
+								)
+								.await
+							}
+						}
+					}
+				},
+			)
+			.await
+	}
+
 	/// Start the gRPC server
 	/// internally this passes a cloned self `EthClient` as the service.
 	pub async fn serve_grpc(
@@ -139,27 +370,59 @@ impl EthClient {
 			contract.initialize(self.signer_address, initiator_address, U256::from(timelock.0));
 
 		// Send the transaction
-		send_transaction(
-			call.to_owned(),
-			self.signer_address,
-			&send_transaction_rules(),
-			self.config.transaction_send_retries,
-			self.config.gas_limit,
-		)
-		.await?;
+		self.send_call("initialize_counterparty_contract", call).await?;
 
 		Ok(())
 	}
 
+	/// Subscribes to the `BridgeTransferInitiated` and `BridgeTransferCompleted` events
+	/// of the initiator contract. The events are pushed over a WebSocket connection
+	/// if `ws_rpc_url` is configured, and polled from `rpc_url` otherwise.
+	/// Logs that fail to decode are skipped.
+	pub async fn subscribe_bridge_events(
+		&self,
+	) -> Result<impl Stream<Item = BridgeContractEvent<EthAddress>>, anyhow::Error> {
+		let Some(ws_rpc_url) = &self.config.ws_rpc_url else {
+			let initiated =
+				self.initiator_contract.BridgeTransferInitiated_filter().watch().await?;
+			let completed =
+				self.initiator_contract.BridgeTransferCompleted_filter().watch().await?;
+			return Ok(decode_bridge_events(
+				self.rpc_provider.clone(),
+				initiated.into_stream(),
+				completed.into_stream(),
+			));
+		};
+
+		let ws_provider = ProviderBuilder::new().on_builtin(ws_rpc_url.as_str()).await?;
+		let contract =
+			AtomicBridgeInitiatorMOVE::new(self.config.initiator_contract, ws_provider.clone());
+		let initiated = contract.BridgeTransferInitiated_filter().subscribe().await?;
+		let completed = contract.BridgeTransferCompleted_filter().subscribe().await?;
+		let events = decode_bridge_events(
+			ws_provider.clone(),
+			initiated.into_stream(),
+			completed.into_stream(),
+		);
+
+		// The subscriptions end with the WebSocket connection,
+		// so the provider is kept alive for as long as the stream.
+		Ok(async_stream::stream! {
+			let _ws_provider = ws_provider;
+			for await event in events {
+				yield event;
+			}
+		}
+		.boxed())
+	}
+
 	pub async fn get_block_number(&self) -> Result<u64, anyhow::Error> {
-		self.rpc_provider
-			.get_block_number()
-			.await
+		self.count_rpc_error("get_block_number", self.rpc_provider.get_block_number().await)
 			.map_err(|e| anyhow::anyhow!("Failed to get block number: {}", e))
 	}
 
 	pub fn get_signer_address(&self) -> Address {
-		self.config.signer_private_key.address()
+		self.signer_address
 	}
 
 	pub fn set_initiator_contract(&mut self, contract: InitiatorContract) {
@@ -187,11 +450,7 @@ impl bridge_util::chains::bridge_contracts::BridgeContract<EthAddress> for EthCl
 		hash_lock: HashLock,
 		amount: Amount, // the ETH amount
 	) -> BridgeContractResult<()> {
-		let recipient_bytes: [u8; 32] = recipient.0.try_into().map_err(|e| {
-			BridgeContractError::ConversionFailed(format!(
-				"Failed to convert in [u8; 32] recipient: {e:?}"
-			))
-		})?;
+		let recipient_bytes = derive_movement_recipient(&recipient.0)?.into_bytes();
 		let contract = AtomicBridgeInitiatorMOVE::new(
 			self.config.initiator_contract,
 			self.rpc_provider.clone(),
@@ -203,17 +462,14 @@ impl bridge_util::chains::bridge_contracts::BridgeContract<EthAddress> for EthCl
 				FixedBytes(hash_lock.0),
 			)
 			.from(*initiator.0);
-		let _ = send_transaction(
-			call,
-			self.signer_address,
-			&send_transaction_rules(),
-			self.config.transaction_send_retries,
-			self.config.gas_limit,
-		)
-		.await
-		.map_err(|e| {
-			BridgeContractError::GenericError(format!("Failed to send transaction: {}", e))
+		let start = Instant::now();
+		self.send_call("initiate_bridge_transfer", call).await.map_err(|e| {
+			map_send_transaction_error(e, |e| {
+				BridgeContractError::OnChainError(format!("Failed to send transaction: {}", e))
+			})
 		})?;
+		self.metrics.record_initiate_duration(start.elapsed());
+		self.metrics.record_initiated(amount);
 
 		Ok(())
 	}
@@ -223,15 +479,8 @@ impl bridge_util::chains::bridge_contracts::BridgeContract<EthAddress> for EthCl
 		bridge_transfer_id: BridgeTransferId,
 		pre_image: HashLockPreImage,
 	) -> BridgeContractResult<()> {
-		// The Alloy generated type for smart contract`pre_image` arg is `FixedBytes<32>`
-		// so it must be converted to `[u8; 32]`.
-		let generic_error = |desc| BridgeContractError::GenericError(String::from(desc));
-		let pre_image: [u8; 32] = pre_image
-			.0
-			.get(0..32)
-			.ok_or(generic_error("Could not get required slice from pre-image"))?
-			.try_into()
-			.map_err(|_| generic_error("Could not convert pre-image to [u8; 32]"))?;
+		// The Alloy generated type for smart contract`pre_image` arg is `FixedBytes<32>`.
+		let pre_image = pre_image.0;
 		info! {"Pre-image: {:?}", pre_image};
 		let contract = AtomicBridgeInitiatorMOVE::new(
 			self.config.initiator_contract,
@@ -239,17 +488,13 @@ impl bridge_util::chains::bridge_contracts::BridgeContract<EthAddress> for EthCl
 		);
 		let call = contract
 			.completeBridgeTransfer(FixedBytes(bridge_transfer_id.0), FixedBytes(pre_image));
-		send_transaction(
-			call,
-			self.signer_address,
-			&send_transaction_rules(),
-			self.config.transaction_send_retries,
-			self.config.gas_limit,
-		)
-		.await
-		.map_err(|e| {
-			BridgeContractError::OnChainError(format!("Failed to send transaction: {}", e))
+		let start = Instant::now();
+		self.send_call("initiator_complete_bridge_transfer", call).await.map_err(|e| {
+			map_send_transaction_error(e, |e| {
+				BridgeContractError::OnChainError(format!("Failed to send transaction: {}", e))
+			})
 		})?;
+		self.metrics.record_complete_duration(start.elapsed());
 
 		Ok(())
 	}
@@ -259,15 +504,8 @@ impl bridge_util::chains::bridge_contracts::BridgeContract<EthAddress> for EthCl
 		bridge_transfer_id: BridgeTransferId,
 		pre_image: HashLockPreImage,
 	) -> BridgeContractResult<()> {
-		// The Alloy generated type for smart contract`pre_image` arg is `FixedBytes<32>`
-		// so it must be converted to `[u8; 32]`.
-		let generic_error = |desc| BridgeContractError::GenericError(String::from(desc));
-		let pre_image: [u8; 32] = pre_image
-			.0
-			.get(0..32)
-			.ok_or(generic_error("Could not get required slice from pre-image"))?
-			.try_into()
-			.map_err(|_| generic_error("Could not convert pre-image to [u8; 32]"))?;
+		// The Alloy generated type for smart contract`pre_image` arg is `FixedBytes<32>`.
+		let pre_image = pre_image.0;
 
 		let contract = AtomicBridgeCounterpartyMOVE::new(
 			self.config.counterparty_contract,
@@ -276,17 +514,15 @@ impl bridge_util::chains::bridge_contracts::BridgeContract<EthAddress> for EthCl
 
 		let call = contract
 			.completeBridgeTransfer(FixedBytes(bridge_transfer_id.0), FixedBytes(pre_image));
-		send_transaction(
-			call,
-			self.signer_address,
-			&send_transaction_rules(),
-			self.config.transaction_send_retries,
-			self.config.gas_limit,
-		)
-		.await
-		.map_err(|e| {
-			BridgeContractError::OnChainError(format!("Failed to send transaction: {}", e))
-		})?;
+		let start = Instant::now();
+		self.send_call("counterparty_complete_bridge_transfer", call)
+			.await
+			.map_err(|e| {
+				map_send_transaction_error(e, |e| {
+					BridgeContractError::OnChainError(format!("Failed to send transaction: {}", e))
+				})
+			})?;
+		self.metrics.record_complete_duration(start.elapsed());
 
 		Ok(())
 	}
@@ -300,18 +536,38 @@ impl bridge_util::chains::bridge_contracts::BridgeContract<EthAddress> for EthCl
 			self.rpc_provider.clone(),
 		);
 		tracing::info!("Bridge transfer ID: {:?}", bridge_transfer_id);
+
+		// Only an initialized transfer can be refunded, don't spend gas on a reverting call.
+		let transfer = contract.bridgeTransfers(FixedBytes(bridge_transfer_id.0)).call().await;
+		let transfer = self.count_rpc_error("refund_bridge_transfer", transfer).map_err(|e| {
+			let on_other = |e| {
+				BridgeContractError::RefundTransferError(format!(
+					"Failed to read transfer {bridge_transfer_id}: {e}"
+				))
+			};
+			match e {
+				alloy::contract::Error::TransportError(e) => map_rpc_error(e, on_other),
+				e => on_other(e.to_string()),
+			}
+		})?;
+		if transfer.originator == Address::ZERO {
+			return Err(BridgeContractError::RefundTransferError(format!(
+				"Transfer {bridge_transfer_id} not found"
+			)));
+		}
+		if transfer.state != INITIATOR_STATE_INITIALIZED {
+			return Err(BridgeContractError::RefundTransferError(format!(
+				"Transfer {bridge_transfer_id} is not initialized, state: {}",
+				transfer.state
+			)));
+		}
+
 		let call = contract.refundBridgeTransfer(FixedBytes(bridge_transfer_id.0));
 
-		send_transaction(
-			call,
-			self.signer_address,
-			&send_transaction_rules(),
-			self.config.transaction_send_retries,
-			self.config.gas_limit,
-		)
-		.await
-		.map_err(|e| {
-			BridgeContractError::OnChainError(format!("Failed to send transaction: {}", e))
+		self.send_call("refund_bridge_transfer", call).await.map_err(|e| {
+			map_send_transaction_error(e, |e| {
+				BridgeContractError::OnChainError(format!("Failed to send transaction: {}", e))
+			})
 		})?;
 
 		Ok(())
@@ -346,19 +602,14 @@ impl bridge_util::chains::bridge_contracts::BridgeContract<EthAddress> for EthCl
 			self.signer_address
 		);
 
-		let receipt = send_transaction(
-			call,
-			self.signer_address,
-			&send_transaction_rules(),
-			self.config.transaction_send_retries,
-			self.config.gas_limit,
-		)
-		.await
-		.map_err(|e| {
-			BridgeContractError::OnChainError(format!("Failed to send transaction: {}", e))
+		let receipt = self.send_call("lock_bridge_transfer", call).await.map_err(|e| {
+			map_send_transaction_error(e, |e| {
+				BridgeContractError::OnChainError(format!("Failed to send transaction: {}", e))
+			})
 		})?;
 
 		tracing::info!("LockBridgeTransfer receipt: {:?}", receipt);
+		self.metrics.record_locked(amount);
 
 		Ok(())
 	}
@@ -372,53 +623,61 @@ impl bridge_util::chains::bridge_contracts::BridgeContract<EthAddress> for EthCl
 			self.rpc_provider.clone(),
 		);
 		let call = contract.abortBridgeTransfer(FixedBytes(bridge_transfer_id.0));
-		send_transaction(
-			call,
-			self.signer_address,
-			&send_transaction_rules(),
-			self.config.transaction_send_retries,
-			self.config.gas_limit,
-		)
-		.await
-		.map_err(|e| {
-			BridgeContractError::OnChainError(format!("Failed to send transaction: {}", e))
+		self.send_call("abort_bridge_transfer", call).await.map_err(|e| {
+			map_send_transaction_error(e, |e| {
+				BridgeContractError::OnChainError(format!("Failed to send transaction: {}", e))
+			})
 		})?;
 		let call = contract.abortBridgeTransfer(FixedBytes(bridge_transfer_id.0));
-		send_transaction(
-			call,
-			self.signer_address,
-			&send_transaction_rules(),
-			self.config.transaction_send_retries,
-			self.config.gas_limit,
-		)
-		.await
-		.map_err(|e| {
-			BridgeContractError::OnChainError(format!("Failed to send transaction: {}", e))
+		self.send_call("abort_bridge_transfer", call).await.map_err(|e| {
+			map_send_transaction_error(e, |e| {
+				BridgeContractError::OnChainError(format!("Failed to send transaction: {}", e))
+			})
 		})?;
 
 		Ok(())
 	}
 
+	async fn claim_relayer_fee(
+		&mut self,
+		bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<()> {
+		// No fee contract, no fee to claim.
+		if self.config.relayer_fee_contract == Address::ZERO {
+			return Ok(());
+		}
+		let contract =
+			IRelayerFee::new(self.config.relayer_fee_contract, self.rpc_provider.clone());
+		let call = contract.claimRelayerFee(FixedBytes(bridge_transfer_id.0));
+		self.send_call("claim_relayer_fee", call).await.map_err(|e| {
+			map_send_transaction_error(e, |e| {
+				BridgeContractError::OnChainError(format!("Failed to send transaction: {}", e))
+			})
+		})?;
+		Ok(())
+	}
+
 	async fn get_bridge_transfer_details_initiator(
 		&mut self,
 		bridge_transfer_id: BridgeTransferId,
 	) -> BridgeContractResult<Option<BridgeTransferDetails<EthAddress>>> {
-		let generic_error = |desc| BridgeContractError::GenericError(String::from(desc));
-
 		let mapping_slot = U256::from(0); // the mapping is the zeroth slot in the contract
 		let key = bridge_transfer_id.0.clone();
 		let storage_slot = calculate_storage_slot(key, mapping_slot);
-		let storage: U256 = self
+		let storage = self
 			.rpc_provider
 			.get_storage_at(self.initiator_contract_address(), storage_slot)
-			.await
-			.map_err(|_| generic_error("could not find storage"))?;
+			.await;
+		let storage: U256 = self
+			.count_rpc_error("get_bridge_transfer_details_initiator", storage)
+			.map_err(|e| map_rpc_error(e, BridgeContractError::TransportError))?;
 		let storage_bytes = storage.to_be_bytes::<32>();
 
 		println!("storage_bytes: {:?}", storage_bytes);
 		let mut storage_slice = &storage_bytes[..];
-		let eth_details = EthBridgeTransferDetails::decode(&mut storage_slice)
-			.map_err(|_| generic_error("could not decode storage"))?;
+		let eth_details = EthBridgeTransferDetails::decode(&mut storage_slice).map_err(|_| {
+			BridgeContractError::DecodingError("could not decode storage".to_string())
+		})?;
 
 		Ok(Some(BridgeTransferDetails {
 			bridge_transfer_id,
@@ -428,6 +687,8 @@ impl bridge_util::chains::bridge_contracts::BridgeContract<EthAddress> for EthCl
 			time_lock: TimeLock(eth_details.time_lock.wrapping_to::<u64>()),
 			amount: eth_details.amount.into(),
 			state: eth_details.state,
+			// The contract doesn't store when the transfer was initiated.
+			initiated_at: current_timestamp_secs(),
 		}))
 	}
 
@@ -435,33 +696,48 @@ impl bridge_util::chains::bridge_contracts::BridgeContract<EthAddress> for EthCl
 		&mut self,
 		bridge_transfer_id: BridgeTransferId,
 	) -> BridgeContractResult<Option<BridgeTransferDetailsCounterparty<EthAddress>>> {
-		let generic_error = |desc| BridgeContractError::GenericError(String::from(desc));
-
-		let mapping_slot = U256::from(0); // the mapping is the zeroth slot in the contract
-		let key = bridge_transfer_id.0.clone();
-		let storage_slot = calculate_storage_slot(key, mapping_slot);
-		let storage: U256 = self
-			.rpc_provider
-			.get_storage_at(self.initiator_contract_address(), storage_slot)
-			.await
-			.map_err(|_| generic_error("could not find storage"))?;
-		let storage_bytes = storage.to_be_bytes::<32>();
+		// the fields of the entry are stored in consecutive slots
+		let first_slot = calculate_counterparty_storage_slot(bridge_transfer_id.0);
+		let mut slots = [U256::ZERO; COUNTERPARTY_BRIDGE_TRANSFER_SLOTS as usize];
+		for (offset, slot) in (0..).zip(slots.iter_mut()) {
+			let storage = self
+				.rpc_provider
+				.get_storage_at(
+					self.counterparty_contract_address(),
+					first_slot + U256::from(offset),
+				)
+				.await;
+			*slot = self
+				.count_rpc_error("get_bridge_transfer_details_counterparty", storage)
+				.map_err(|e| map_rpc_error(e, BridgeContractError::TransportError))?;
+		}
 
-		println!("storage_bytes: {:?}", storage_bytes);
-		let mut storage_slice = &storage_bytes[..];
-		let eth_details = EthBridgeTransferDetailsCounterparty::decode(&mut storage_slice)
-			.map_err(|_| generic_error("could not decode storage"))?;
+		Ok(decode_counterparty_details(bridge_transfer_id, &slots))
+	}
+}
 
-		Ok(Some(BridgeTransferDetailsCounterparty {
-			bridge_transfer_id,
-			initiator: BridgeAddress(eth_details.originator.to_vec()),
-			recipient: BridgeAddress(eth_details.recipient),
-			hash_lock: HashLock(eth_details.hash_lock),
-			time_lock: TimeLock(eth_details.time_lock.wrapping_to::<u64>()),
-			amount: eth_details.amount.into(),
-			state: eth_details.state,
-		}))
+/// Decodes the storage slots of a `bridgeTransfers` entry of the counterparty contract,
+/// `None` if the contract has no transfer with this id.
+fn decode_counterparty_details(
+	bridge_transfer_id: BridgeTransferId,
+	slots: &[U256; COUNTERPARTY_BRIDGE_TRANSFER_SLOTS as usize],
+) -> Option<BridgeTransferDetailsCounterparty<EthAddress>> {
+	let [originator, recipient, amount, hash_lock, time_lock, state] = slots;
+	// the address is right aligned in its slot
+	let recipient = Address::from_slice(&recipient.to_be_bytes::<32>()[12..]);
+	if recipient == Address::ZERO {
+		return None;
 	}
+
+	Some(BridgeTransferDetailsCounterparty {
+		bridge_transfer_id,
+		initiator: BridgeAddress(originator.to_be_bytes::<32>().to_vec()),
+		recipient: BridgeAddress(EthAddress(recipient)),
+		hash_lock: HashLock(hash_lock.to_be_bytes::<32>()),
+		time_lock: TimeLock(time_lock.wrapping_to::<u64>()),
+		amount: (*amount).into(),
+		state: state.wrapping_to::<u8>(),
+	})
 }
 
 #[cfg(test)]
@@ -472,6 +748,8 @@ fn test_wrapping_to(a: &U256, b: u64) {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use bridge_util::chains::bridge_contracts::BridgeContract;
+	use std::collections::HashMap;
 	use std::time::{SystemTime, UNIX_EPOCH};
 
 	#[test]
@@ -507,4 +785,120 @@ mod tests {
 			test_wrapping_to(&eth_details.time_lock, current_time + additional_time);
 		}
 	}
+
+	#[test]
+	fn test_counterparty_details_storage_roundtrip() {
+		let bridge_transfer_id = BridgeTransferId([3; 32]);
+		// the storage of the counterparty contract holding a single transfer
+		let first_slot = calculate_counterparty_storage_slot(bridge_transfer_id.0);
+		let mut recipient = [0u8; 32];
+		recipient[12..].copy_from_slice(Address::repeat_byte(2).as_slice());
+		let storage: HashMap<U256, U256> = [
+			U256::from_be_bytes([1; 32]),
+			U256::from_be_bytes(recipient),
+			U256::from(42u64),
+			U256::from_be_bytes([4; 32]),
+			U256::from(1_700_000_000u64),
+			U256::from(1u8),
+		]
+		.into_iter()
+		.enumerate()
+		.map(|(offset, value)| (first_slot + U256::from(offset), value))
+		.collect();
+		let read = |first_slot: U256| {
+			std::array::from_fn(|offset| {
+				storage.get(&(first_slot + U256::from(offset))).copied().unwrap_or_default()
+			})
+		};
+
+		let details = decode_counterparty_details(bridge_transfer_id, &read(first_slot));
+		assert_eq!(
+			details,
+			Some(BridgeTransferDetailsCounterparty {
+				bridge_transfer_id,
+				initiator: BridgeAddress(vec![1; 32]),
+				recipient: BridgeAddress(EthAddress(Address::repeat_byte(2))),
+				hash_lock: HashLock([4; 32]),
+				time_lock: TimeLock(1_700_000_000),
+				amount: Amount(42),
+				state: 1,
+			})
+		);
+
+		// the entry of an unknown transfer is zeroed
+		let unknown = calculate_counterparty_storage_slot([9; 32]);
+		assert_eq!(decode_counterparty_details(BridgeTransferId([9; 32]), &read(unknown)), None);
+	}
+
+	/// A client whose RPC endpoint is not reachable, built without querying the signer's nonce.
+	async fn test_client() -> EthClient {
+		let signer_private_key = PrivateKeySigner::random();
+		let signer_address = signer_private_key.address();
+		let config = Config {
+			rpc_url: "http://127.0.0.1:1".parse().unwrap(),
+			ws_rpc_url: None,
+			signer_private_key: signer_private_key.clone(),
+			initiator_contract: Address::repeat_byte(0xee),
+			counterparty_contract: Address::repeat_byte(0xcc),
+			movetoken_contract: Address::repeat_byte(0xe2),
+			relayer_fee_contract: Address::ZERO,
+			gas_limit: 0,
+			gas_estimate_multiplier: 1.0,
+			transaction_send_retries: 0,
+			transaction_type: TransactionType::Legacy,
+			asset: AssetKind::Move,
+			confirmations_required: 0,
+		};
+		let rpc_provider = ProviderBuilder::new()
+			.with_recommended_fillers()
+			.wallet(EthereumWallet::from(signer_private_key))
+			.on_builtin(config.rpc_url.as_str())
+			.await
+			.unwrap();
+
+		EthClient {
+			initiator_contract: AtomicBridgeInitiatorMOVE::new(
+				config.initiator_contract,
+				rpc_provider.clone(),
+			),
+			counterparty_contract: AtomicBridgeCounterpartyMOVE::new(
+				config.counterparty_contract,
+				rpc_provider.clone(),
+			),
+			rpc_provider,
+			config,
+			signer_address,
+			nonce_cache: NonceCache::new(),
+			gas_estimates: GasEstimateCache::new(),
+			metrics: EthClientMetrics::try_new(&Registry::new()).unwrap(),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_initiate_bridge_transfer_rejects_invalid_recipient() {
+		let mut client = test_client().await;
+
+		let err = client
+			.initiate_bridge_transfer(
+				BridgeAddress(EthAddress(Address::repeat_byte(1))),
+				BridgeAddress(vec![3; 20]),
+				HashLock([2; 32]),
+				Amount(1),
+			)
+			.await
+			.unwrap_err();
+
+		assert!(
+			matches!(err, BridgeContractError::InvalidRecipientLength { expected: 32, actual: 20 }),
+			"{:?}",
+			err
+		);
+	}
+
+	#[test]
+	fn test_unknown_counterparty_transfer() {
+		let details =
+			counterparty_details(BridgeTransferId([3; 32]), counterparty_transfer(Address::ZERO));
+		assert_eq!(details, None);
+	}
 }
@@ -0,0 +1,235 @@
+use alloy::primitives::B256;
+use bridge_util::chains::bridge_contracts::BridgeContractEvent;
+use std::{
+	collections::{HashMap, VecDeque},
+	future::Future,
+};
+
+/// An event waiting for its block to be confirmed.
+struct PendingEvent<A> {
+	block_number: u64,
+	block_hash: B256,
+	event: BridgeContractEvent<A>,
+}
+
+/// Holds back the contract events until their block is `confirmations_required` blocks deep,
+/// so that an event of an orphaned block is never relayed.
+pub struct ConfirmationBuffer<A> {
+	confirmations_required: u64,
+	/// The pending events, ordered by block number.
+	pending: VecDeque<PendingEvent<A>>,
+	/// The lowest block whose events were reorged since the last [`Self::take_reorged_from`].
+	reorged_from: Option<u64>,
+}
+
+impl<A> ConfirmationBuffer<A> {
+	pub fn new(confirmations_required: u64) -> Self {
+		ConfirmationBuffer { confirmations_required, pending: VecDeque::new(), reorged_from: None }
+	}
+
+	/// The number of events waiting for confirmations.
+	pub fn len(&self) -> usize {
+		self.pending.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.pending.is_empty()
+	}
+
+	/// Buffers an event emitted in the block `block_number` with hash `block_hash`.
+	/// An event already pending for the same block is ignored, e.g. when a range is queried again.
+	pub fn push(&mut self, block_number: u64, block_hash: B256, event: BridgeContractEvent<A>)
+	where
+		A: PartialEq,
+	{
+		if self
+			.pending
+			.iter()
+			.any(|pending| pending.block_hash == block_hash && pending.event == event)
+		{
+			return;
+		}
+		let index = self.pending.partition_point(|pending| pending.block_number <= block_number);
+		self.pending.insert(index, PendingEvent { block_number, block_hash, event });
+	}
+
+	/// Releases the events whose block is deep enough below `head`.
+	/// The block of each buffered event is compared with the canonical chain using `block_hash`,
+	/// the events of a block that was reorged are dropped and reported with
+	/// [`BridgeContractEvent::Reorged`] instead.
+	/// The buffer is left unchanged if a block hash can't be fetched.
+	/// The range of the reorged blocks must be queried again, from [`Self::take_reorged_from`].
+	pub async fn poll<F, Fut>(
+		&mut self,
+		head: u64,
+		block_hash: F,
+	) -> Result<Vec<BridgeContractEvent<A>>, anyhow::Error>
+	where
+		F: Fn(u64) -> Fut,
+		Fut: Future<Output = Result<Option<B256>, anyhow::Error>>,
+	{
+		let mut canonical_hashes = HashMap::new();
+		for pending in &self.pending {
+			if !canonical_hashes.contains_key(&pending.block_number) {
+				let hash = block_hash(pending.block_number).await?;
+				canonical_hashes.insert(pending.block_number, hash);
+			}
+		}
+
+		let mut released = Vec::new();
+		let mut still_pending = VecDeque::with_capacity(self.pending.len());
+		for pending in self.pending.drain(..) {
+			if canonical_hashes[&pending.block_number] != Some(pending.block_hash) {
+				tracing::warn!(
+					"Block {} ({}) was reorged, dropping event: {}",
+					pending.block_number,
+					pending.block_hash,
+					pending.event
+				);
+				released.push(BridgeContractEvent::Reorged(pending.event.bridge_transfer_id()));
+				self.reorged_from = Some(match self.reorged_from {
+					Some(reorged_from) => reorged_from.min(pending.block_number),
+					None => pending.block_number,
+				});
+			} else if head.saturating_sub(pending.block_number) >= self.confirmations_required {
+				released.push(pending.event);
+			} else {
+				still_pending.push_back(pending);
+			}
+		}
+		self.pending = still_pending;
+		Ok(released)
+	}
+
+	/// The lowest block whose events were reorged since the last call, if any.
+	/// The events of the new chain from this block have not been queried yet.
+	pub fn take_reorged_from(&mut self) -> Option<u64> {
+		self.reorged_from.take()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bridge_util::types::BridgeTransferId;
+	use std::sync::Mutex;
+
+	/// Mocks the RPC: serves the hash of the blocks of the canonical chain.
+	struct MockProvider {
+		block_hashes: Mutex<Vec<B256>>,
+	}
+
+	impl MockProvider {
+		fn new(head: u64) -> Self {
+			let block_hashes = (0..=head).map(|number| block_hash(number, 0)).collect();
+			MockProvider { block_hashes: Mutex::new(block_hashes) }
+		}
+
+		fn head(&self) -> u64 {
+			self.block_hashes.lock().unwrap().len() as u64 - 1
+		}
+
+		fn mine(&self) {
+			let mut block_hashes = self.block_hashes.lock().unwrap();
+			let number = block_hashes.len() as u64;
+			block_hashes.push(block_hash(number, 0));
+		}
+
+		/// Replaces the last `depth` blocks with the blocks of a fork.
+		fn reorg(&self, depth: u64) {
+			let mut block_hashes = self.block_hashes.lock().unwrap();
+			let head = block_hashes.len() as u64 - 1;
+			for number in head + 1 - depth..=head {
+				block_hashes[number as usize] = block_hash(number, 1);
+			}
+		}
+
+		async fn get_block_hash(&self, number: u64) -> Result<Option<B256>, anyhow::Error> {
+			Ok(self.block_hashes.lock().unwrap().get(number as usize).copied())
+		}
+	}
+
+	fn block_hash(number: u64, fork: u8) -> B256 {
+		let mut hash = [fork; 32];
+		hash[..8].copy_from_slice(&number.to_be_bytes());
+		B256::from(hash)
+	}
+
+	fn event(id: u8) -> BridgeContractEvent<()> {
+		BridgeContractEvent::InitiatorCompleted(BridgeTransferId([id; 32]))
+	}
+
+	#[tokio::test]
+	async fn test_events_are_released_once_confirmed() -> Result<(), anyhow::Error> {
+		let provider = MockProvider::new(10);
+		let mut buffer = ConfirmationBuffer::new(3);
+		buffer.push(9, block_hash(9, 0), event(2));
+		buffer.push(8, block_hash(8, 0), event(1));
+
+		let released = buffer.poll(provider.head(), |n| provider.get_block_hash(n)).await?;
+		assert!(released.is_empty());
+		assert_eq!(buffer.len(), 2);
+
+		provider.mine();
+		let released = buffer.poll(provider.head(), |n| provider.get_block_hash(n)).await?;
+		assert_eq!(released, vec![event(1)]);
+
+		provider.mine();
+		let released = buffer.poll(provider.head(), |n| provider.get_block_hash(n)).await?;
+		assert_eq!(released, vec![event(2)]);
+		assert!(buffer.is_empty());
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_reorged_events_are_dropped() -> Result<(), anyhow::Error> {
+		let provider = MockProvider::new(10);
+		let mut buffer = ConfirmationBuffer::new(3);
+		buffer.push(8, block_hash(8, 0), event(1));
+		buffer.push(9, block_hash(9, 0), event(2));
+		buffer.push(10, block_hash(10, 0), event(3));
+
+		// blocks 9 and 10 are orphaned, the fork is one block longer
+		provider.reorg(2);
+		provider.mine();
+		let released = buffer.poll(provider.head(), |n| provider.get_block_hash(n)).await?;
+		assert_eq!(
+			released,
+			vec![
+				event(1),
+				BridgeContractEvent::Reorged(BridgeTransferId([2; 32])),
+				BridgeContractEvent::Reorged(BridgeTransferId([3; 32])),
+			]
+		);
+		assert!(buffer.is_empty());
+		// the orphaned range is queried again
+		assert_eq!(buffer.take_reorged_from(), Some(9));
+		assert_eq!(buffer.take_reorged_from(), None);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_requeried_event_is_buffered_once() -> Result<(), anyhow::Error> {
+		let provider = MockProvider::new(10);
+		let mut buffer = ConfirmationBuffer::new(0);
+		buffer.push(9, block_hash(9, 0), event(1));
+		buffer.push(9, block_hash(9, 0), event(1));
+		assert_eq!(buffer.len(), 1);
+
+		let released = buffer.poll(provider.head(), |n| provider.get_block_hash(n)).await?;
+		assert_eq!(released, vec![event(1)]);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_failed_hash_query_keeps_the_events() -> Result<(), anyhow::Error> {
+		let mut buffer = ConfirmationBuffer::new(0);
+		buffer.push(8, block_hash(8, 0), event(1));
+
+		let result =
+			buffer.poll(10, |_| async { Err(anyhow::anyhow!("connection refused")) }).await;
+		assert!(result.is_err());
+		assert_eq!(buffer.len(), 1);
+		Ok(())
+	}
+}
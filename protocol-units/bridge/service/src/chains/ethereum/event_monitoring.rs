@@ -1,10 +1,13 @@
+use super::confirmation_buffer::ConfirmationBuffer;
 use super::types::EthAddress;
+use super::utils::log_timestamp;
 use crate::chains::ethereum::types::AtomicBridgeCounterpartyMOVE;
 use crate::chains::ethereum::types::AtomicBridgeInitiatorMOVE;
 use alloy::eips::BlockNumberOrTag;
 use alloy::primitives::Address;
 use alloy::providers::Provider;
 use alloy::providers::ProviderBuilder;
+use alloy::rpc::types::Log;
 use alloy_network::EthereumWallet;
 use bridge_config::common::eth::EthConfig;
 use bridge_util::chains::bridge_contracts::BridgeContractError;
@@ -60,7 +63,10 @@ impl EthMonitoring {
 					config.eth_counterparty_contract.parse().unwrap(), //If unwrap start fail. Config must be updated.
 					rpc_provider.clone(),
 				);
-				let mut last_processed_block = 0;
+				// The first block not queried yet, the monitoring starts at the chain head.
+				let mut next_block = None;
+				let mut confirmation_buffer =
+					ConfirmationBuffer::new(client_config.confirmations_required);
 				loop {
 					//Check if there's a health check request
 					match health_check_rx.try_recv() {
@@ -114,29 +120,36 @@ impl EthMonitoring {
 							continue;
 						}
 					};
-					if last_processed_block < block_number {
-						last_processed_block = block_number;
+					let from_block = next_block.unwrap_or(block_number);
+					if from_block <= block_number {
+						next_block = Some(block_number + 1);
 						let initiator_initiate_event_filter = initiator_contract
 							.BridgeTransferInitiated_filter()
-							.from_block(BlockNumberOrTag::Number(last_processed_block));
+							.from_block(BlockNumberOrTag::Number(from_block))
+							.to_block(BlockNumberOrTag::Number(block_number));
 						// event BridgeTransferCompleted(bytes32 indexed _bridgeTransferId, bytes32 pre_image);
 						let initiator_trcompleted_event_filter = initiator_contract
 							.BridgeTransferCompleted_filter()
-							.from_block(BlockNumberOrTag::Number(last_processed_block));
+							.from_block(BlockNumberOrTag::Number(from_block))
+							.to_block(BlockNumberOrTag::Number(block_number));
 						// event BridgeTransferRefunded(bytes32 indexed _bridgeTransferId);
 						let initiator_trrefund_event_filter = initiator_contract
 							.BridgeTransferRefunded_filter()
-							.from_block(BlockNumberOrTag::Number(last_processed_block));
+							.from_block(BlockNumberOrTag::Number(from_block))
+							.to_block(BlockNumberOrTag::Number(block_number));
 						let counterpart_trlocked_event_filter = counterpart_contract
 							.BridgeTransferLocked_filter()
-							.from_block(BlockNumberOrTag::Number(last_processed_block));
+							.from_block(BlockNumberOrTag::Number(from_block))
+							.to_block(BlockNumberOrTag::Number(block_number));
 						let counterpart_trcompleted_event_filter = counterpart_contract
 							.BridgeTransferCompleted_filter()
-							.from_block(BlockNumberOrTag::Number(last_processed_block));
+							.from_block(BlockNumberOrTag::Number(from_block))
+							.to_block(BlockNumberOrTag::Number(block_number));
 						//event BridgeTransferAborted(bytes32 indexed bridgeTransferId);
 						let counterpart_trcaborted_event_filter = counterpart_contract
 							.BridgeTransferAborted_filter()
-							.from_block(BlockNumberOrTag::Number(last_processed_block));
+							.from_block(BlockNumberOrTag::Number(from_block))
+							.to_block(BlockNumberOrTag::Number(block_number));
 
 						//Initiator event stream
 						match tokio::time::timeout(
@@ -146,7 +159,7 @@ impl EthMonitoring {
 						.await
 						{
 							Ok(Ok(events)) => {
-								for (initiated, _log) in events {
+								for (initiated, log) in events {
 									let event = {
 										// BridgeTransferInitiated(bridgeTransferId, originator, recipient, totalAmount, hashLock, initiatorTimeLockDuration);
 										let details: BridgeTransferDetails<EthAddress> =
@@ -164,13 +177,12 @@ impl EthMonitoring {
 												time_lock: initiated._timeLock.into(),
 												amount: initiated.amount.into(),
 												state: 0,
+												initiated_at: log_timestamp(&rpc_provider, &log)
+													.await,
 											};
 										BridgeContractEvent::Initiated(details)
 									};
-									if sender.send(Ok(event)).await.is_err() {
-										tracing::error!("Failed to send event to listener channel");
-										break;
-									}
+									buffer_event(&mut confirmation_buffer, &log, event);
 								}
 							}
 							Ok(Err(_)) => {
@@ -197,17 +209,11 @@ impl EthMonitoring {
 						match tokio::time::timeout(
 							tokio::time::Duration::from_secs(config.rest_connection_timeout_secs),initiator_trcompleted_event_filter.query()).await {
 							Ok(Ok(events)) => {
-								for (completed, _log) in events {
-									if sender
-										.send(Ok(BridgeContractEvent::InitiatorCompleted(
-											BridgeTransferId(*completed._bridgeTransferId),
-										)))
-										.await
-										.is_err()
-									{
-										tracing::error!("Failed to send event to listener channel");
-										break;
-									}
+								for (completed, log) in events {
+									let event = BridgeContractEvent::InitiatorCompleted(
+										BridgeTransferId(*completed._bridgeTransferId),
+									);
+									buffer_event(&mut confirmation_buffer, &log, event);
 								}
 							}
 							Ok(Err(_)) => {
@@ -238,17 +244,11 @@ impl EthMonitoring {
 						.await
 						{
 							Ok(Ok(events)) => {
-								for (refund, _log) in events {
-									if sender
-										.send(Ok(BridgeContractEvent::Refunded(BridgeTransferId(
-											*refund._bridgeTransferId,
-										))))
-										.await
-										.is_err()
-									{
-										tracing::error!("Failed to send event to listener channel");
-										break;
-									}
+								for (refund, log) in events {
+									let event = BridgeContractEvent::Refunded(BridgeTransferId(
+										*refund._bridgeTransferId,
+									));
+									buffer_event(&mut confirmation_buffer, &log, event);
 								}
 							}
 							Ok(Err(_)) => {
@@ -285,7 +285,7 @@ impl EthMonitoring {
 									"Query completed for counterpart_trlocked_event_filter in {:?}",
 									start_time.elapsed()
 								);
-								for (trlocked, _log) in events {
+								for (trlocked, log) in events {
 									let event = {
 										// BridgeTransferLocked(bridgeTransferId, originator, recipient, totalAmount, hashLock, initiatorTimeLockDuration);
 										let details: LockDetails<EthAddress> = LockDetails {
@@ -302,10 +302,7 @@ impl EthMonitoring {
 										};
 										BridgeContractEvent::Locked(details)
 									};
-									if sender.send(Ok(event)).await.is_err() {
-										tracing::error!("Failed to send event to listener channel");
-										break;
-									}
+									buffer_event(&mut confirmation_buffer, &log, event);
 								}
 							}
 							Ok(Err(_)) => {
@@ -336,18 +333,12 @@ impl EthMonitoring {
 						.await
 						{
 							Ok(Ok(events)) => {
-								for (completed, _log) in events {
-									if sender
-										.send(Ok(BridgeContractEvent::CounterPartyCompleted(
-											BridgeTransferId(*completed.bridgeTransferId),
-											HashLockPreImage(*completed.pre_image),
-										)))
-										.await
-										.is_err()
-									{
-										tracing::error!("Failed to send event to listener channel");
-										break;
-									}
+								for (completed, log) in events {
+									let event = BridgeContractEvent::CounterPartyCompleted(
+										BridgeTransferId(*completed.bridgeTransferId),
+										HashLockPreImage(*completed.pre_image),
+									);
+									buffer_event(&mut confirmation_buffer, &log, event);
 								}
 							}
 							Ok(Err(_)) => {
@@ -378,17 +369,11 @@ impl EthMonitoring {
 						.await
 						{
 							Ok(Ok(events)) => {
-								for (aborted, _log) in events {
-									if sender
-										.send(Ok(BridgeContractEvent::Cancelled(BridgeTransferId(
-											*aborted.bridgeTransferId,
-										))))
-										.await
-										.is_err()
-									{
-										tracing::error!("Failed to send event to listener channel");
-										break;
-									}
+								for (aborted, log) in events {
+									let event = BridgeContractEvent::Cancelled(BridgeTransferId(
+										*aborted.bridgeTransferId,
+									));
+									buffer_event(&mut confirmation_buffer, &log, event);
 								}
 							}
 							Ok(Err(_)) => {
@@ -414,6 +399,56 @@ impl EthMonitoring {
 						}
 					} // end match
 
+					// Relay the events that are deep enough, and the reorged ones.
+					if !confirmation_buffer.is_empty() {
+						let released = confirmation_buffer
+							.poll(block_number, |number| {
+								let rpc_provider = rpc_provider.clone();
+								async move {
+									let block = rpc_provider
+										.get_block_by_number(
+											BlockNumberOrTag::Number(number),
+											false,
+										)
+										.await?;
+									Ok(block.and_then(|block| block.header.hash))
+								}
+							})
+							.await;
+						match released {
+							Ok(events) => {
+								// Query the reorged range again, for the events of the new chain.
+								if let Some(reorged_from) = confirmation_buffer.take_reorged_from()
+								{
+									next_block = next_block.map(|next| next.min(reorged_from));
+								}
+								let mut listener_closed = false;
+								for event in events {
+									if sender.send(Ok(event)).await.is_err() {
+										tracing::error!("Failed to send event to listener channel");
+										listener_closed = true;
+										break;
+									}
+								}
+								if listener_closed {
+									break;
+								}
+							}
+							Err(err) => {
+								if sender
+									.send(Err(BridgeContractError::OnChainError(format!(
+										"Eth monitoring block hash query failed: {err}"
+									))))
+									.await
+									.is_err()
+								{
+									tracing::error!("Failed to send event to listener channel");
+									break;
+								}
+							}
+						}
+					}
+
 					let _ = tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
 				} // end loop
 			} // End spawn
@@ -423,6 +458,20 @@ impl EthMonitoring {
 	}
 }
 
+/// Buffers the event of `log` until its block is confirmed.
+fn buffer_event(
+	confirmation_buffer: &mut ConfirmationBuffer<EthAddress>,
+	log: &Log,
+	event: BridgeContractEvent<EthAddress>,
+) {
+	match (log.block_number, log.block_hash) {
+		(Some(block_number), Some(block_hash)) => {
+			confirmation_buffer.push(block_number, block_hash, event)
+		}
+		_ => tracing::warn!("Dropping event of a pending block: {event}"),
+	}
+}
+
 impl Stream for EthMonitoring {
 	type Item = BridgeContractResult<BridgeContractEvent<EthAddress>>;
 
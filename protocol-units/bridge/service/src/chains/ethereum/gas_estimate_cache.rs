@@ -0,0 +1,71 @@
+use alloy::primitives::Selector;
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex},
+};
+
+/// The number of blocks a gas estimate is reused for.
+pub const GAS_ESTIMATE_TTL_BLOCKS: u64 = 10;
+
+/// Caches the gas estimates per contract function, so that sending a transaction
+/// does not need to estimate its gas with the RPC each time.
+#[derive(Clone, Default)]
+pub struct GasEstimateCache {
+	/// The estimated gas and the block number of the estimate, by function selector.
+	estimates: Arc<Mutex<HashMap<Selector, (u128, u64)>>>,
+}
+
+impl GasEstimateCache {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the gas estimate of `selector` if it was made less than
+	/// [`GAS_ESTIMATE_TTL_BLOCKS`] blocks before `block_number`.
+	pub fn get(&self, selector: &Selector, block_number: u64) -> Option<u128> {
+		let estimates = self.estimates.lock().expect("gas estimate cache lock poisoned");
+		estimates
+			.get(selector)
+			.filter(|(_, estimated_at)| {
+				block_number.saturating_sub(*estimated_at) < GAS_ESTIMATE_TTL_BLOCKS
+			})
+			.map(|(gas, _)| *gas)
+	}
+
+	/// Caches the gas of `selector` estimated at `block_number`.
+	pub fn insert(&self, selector: Selector, block_number: u64, gas: u128) {
+		self.estimates
+			.lock()
+			.expect("gas estimate cache lock poisoned")
+			.insert(selector, (gas, block_number));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_estimate_expires_after_ttl() {
+		let cache = GasEstimateCache::new();
+		let selector = Selector::from([1, 2, 3, 4]);
+		cache.insert(selector, 100, 50_000);
+
+		assert_eq!(cache.get(&selector, 100), Some(50_000));
+		assert_eq!(cache.get(&selector, 100 + GAS_ESTIMATE_TTL_BLOCKS - 1), Some(50_000));
+		assert_eq!(cache.get(&selector, 100 + GAS_ESTIMATE_TTL_BLOCKS), None);
+	}
+
+	#[test]
+	fn test_estimates_are_per_selector() {
+		let cache = GasEstimateCache::new();
+		let initiate = Selector::from([1, 2, 3, 4]);
+		let complete = Selector::from([5, 6, 7, 8]);
+		cache.insert(initiate, 100, 50_000);
+
+		assert_eq!(cache.get(&complete, 100), None);
+		cache.insert(complete, 101, 80_000);
+		assert_eq!(cache.get(&initiate, 101), Some(50_000));
+		assert_eq!(cache.get(&complete, 101), Some(80_000));
+	}
+}
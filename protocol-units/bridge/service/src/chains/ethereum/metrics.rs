@@ -0,0 +1,160 @@
+use alloy::rpc::types::TransactionReceipt;
+use bridge_util::types::Amount;
+use prometheus::{
+	exponential_buckets, Counter, CounterVec, Histogram, HistogramOpts, IntCounterVec, Opts,
+	Registry,
+};
+use std::time::Duration;
+
+/// Prometheus metrics of the [`EthClient`](super::client::EthClient).
+#[derive(Clone)]
+pub struct EthClientMetrics {
+	/// The amount of the transfers sent to the bridge contracts, by contract.
+	pub transfer_amount_total: CounterVec,
+	/// The fees paid by the signer for the bridge transactions, in wei.
+	pub gas_spent_total: Counter,
+	/// The gas used by the bridge transactions of the signer.
+	pub gas_used_total: Counter,
+	/// The time to send and confirm a transfer initiation, in milliseconds.
+	pub initiate_bridge_transfer_duration_ms: Histogram,
+	/// The time to send and confirm a transfer completion on either contract, in milliseconds.
+	pub complete_bridge_transfer_duration_ms: Histogram,
+	/// The failed RPC calls, transactions and reads, by client method.
+	pub rpc_errors_total: IntCounterVec,
+}
+
+impl EthClientMetrics {
+	/// Creates the metrics and registers them with `registry`.
+	pub fn try_new(registry: &Registry) -> Result<Self, prometheus::Error> {
+		let transfer_amount_total = CounterVec::new(
+			Opts::new(
+				"bridge_transfer_amount_total",
+				"Amount of the transfers sent to the bridge contracts",
+			),
+			&["direction"],
+		)?;
+		let gas_spent_total = Counter::new(
+			"bridge_gas_spent_total",
+			"Fees paid for the bridge transactions, in wei",
+		)?;
+		let gas_used_total =
+			Counter::new("bridge_gas_used_total", "Gas used by the bridge transactions")?;
+		// From 100ms to about 51s, transactions wait for the confirmation of their block.
+		let duration_buckets = exponential_buckets(100.0, 2.0, 10)?;
+		let initiate_bridge_transfer_duration_ms = Histogram::with_opts(
+			HistogramOpts::new(
+				"bridge_initiate_bridge_transfer_duration_ms",
+				"Time to send and confirm a transfer initiation, in milliseconds",
+			)
+			.buckets(duration_buckets.clone()),
+		)?;
+		let complete_bridge_transfer_duration_ms = Histogram::with_opts(
+			HistogramOpts::new(
+				"bridge_complete_bridge_transfer_duration_ms",
+				"Time to send and confirm a transfer completion, in milliseconds",
+			)
+			.buckets(duration_buckets),
+		)?;
+		let rpc_errors_total = IntCounterVec::new(
+			Opts::new("bridge_rpc_errors_total", "Failed RPC calls of the bridge client"),
+			&["method"],
+		)?;
+
+		registry.register(Box::new(transfer_amount_total.clone()))?;
+		registry.register(Box::new(gas_spent_total.clone()))?;
+		registry.register(Box::new(gas_used_total.clone()))?;
+		registry.register(Box::new(initiate_bridge_transfer_duration_ms.clone()))?;
+		registry.register(Box::new(complete_bridge_transfer_duration_ms.clone()))?;
+		registry.register(Box::new(rpc_errors_total.clone()))?;
+
+		Ok(Self {
+			transfer_amount_total,
+			gas_spent_total,
+			gas_used_total,
+			initiate_bridge_transfer_duration_ms,
+			complete_bridge_transfer_duration_ms,
+			rpc_errors_total,
+		})
+	}
+
+	/// Records a transfer initiated on the initiator contract.
+	pub fn record_initiated(&self, amount: Amount) {
+		self.transfer_amount_total
+			.with_label_values(&["initiator"])
+			.inc_by(amount.0 as f64);
+	}
+
+	/// Records a transfer locked on the counterparty contract.
+	pub fn record_locked(&self, amount: Amount) {
+		self.transfer_amount_total
+			.with_label_values(&["counterparty"])
+			.inc_by(amount.0 as f64);
+	}
+
+	/// Records the gas used and the fees paid for the transaction of `receipt`.
+	pub fn record_receipt(&self, receipt: &TransactionReceipt) {
+		let fee_wei = receipt.gas_used.saturating_mul(receipt.effective_gas_price);
+		self.gas_spent_total.inc_by(fee_wei as f64);
+		self.gas_used_total.inc_by(receipt.gas_used as f64);
+	}
+
+	/// Records the time taken by a successful transfer initiation.
+	pub fn record_initiate_duration(&self, duration: Duration) {
+		self.initiate_bridge_transfer_duration_ms
+			.observe(duration.as_secs_f64() * 1000.0);
+	}
+
+	/// Records the time taken by a successful transfer completion.
+	pub fn record_complete_duration(&self, duration: Duration) {
+		self.complete_bridge_transfer_duration_ms
+			.observe(duration.as_secs_f64() * 1000.0);
+	}
+
+	/// Records a failed RPC call of the client method `method`.
+	pub fn record_rpc_error(&self, method: &str) {
+		self.rpc_errors_total.with_label_values(&[method]).inc();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_transfer_amount_by_direction() -> Result<(), prometheus::Error> {
+		let registry = Registry::new();
+		let metrics = EthClientMetrics::try_new(&registry)?;
+		metrics.record_initiated(Amount(3));
+		metrics.record_initiated(Amount(4));
+		metrics.record_locked(Amount(5));
+
+		assert_eq!(metrics.transfer_amount_total.with_label_values(&["initiator"]).get(), 7.0);
+		assert_eq!(metrics.transfer_amount_total.with_label_values(&["counterparty"]).get(), 5.0);
+		// Registering the metrics of a second client in the same registry fails.
+		assert!(EthClientMetrics::try_new(&registry).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn test_durations_and_errors() -> Result<(), prometheus::Error> {
+		let registry = Registry::new();
+		let metrics = EthClientMetrics::try_new(&registry)?;
+		metrics.record_initiate_duration(Duration::from_millis(250));
+		metrics.record_complete_duration(Duration::from_millis(300));
+		metrics.record_complete_duration(Duration::from_millis(500));
+		metrics.record_rpc_error("lock_bridge_transfer");
+		metrics.record_rpc_error("lock_bridge_transfer");
+		metrics.record_rpc_error("refund_bridge_transfer");
+
+		assert_eq!(metrics.initiate_bridge_transfer_duration_ms.get_sample_count(), 1);
+		assert_eq!(metrics.initiate_bridge_transfer_duration_ms.get_sample_sum(), 250.0);
+		assert_eq!(metrics.complete_bridge_transfer_duration_ms.get_sample_count(), 2);
+		assert_eq!(metrics.complete_bridge_transfer_duration_ms.get_sample_sum(), 800.0);
+		assert_eq!(metrics.rpc_errors_total.with_label_values(&["lock_bridge_transfer"]).get(), 2);
+		assert_eq!(
+			metrics.rpc_errors_total.with_label_values(&["refund_bridge_transfer"]).get(),
+			1
+		);
+		Ok(())
+	}
+}
@@ -1,4 +1,8 @@
 pub mod client;
+pub mod confirmation_buffer;
 pub mod event_monitoring;
+pub mod gas_estimate_cache;
+pub mod metrics;
+pub mod nonce_cache;
 pub mod types;
 pub mod utils;
@@ -0,0 +1,200 @@
+use alloy::primitives::Address;
+use std::{
+	collections::HashMap,
+	future::Future,
+	sync::{Arc, Mutex},
+};
+
+/// Caches the next nonce of the sending accounts, so that sending a transaction
+/// does not need to query the account nonce from the RPC each time.
+#[derive(Clone, Default)]
+pub struct NonceCache {
+	nonces: Arc<Mutex<HashMap<Address, u64>>>,
+}
+
+impl NonceCache {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets the next nonce of `address`.
+	pub fn set(&self, address: Address, nonce: u64) {
+		self.nonces.lock().expect("nonce cache lock poisoned").insert(address, nonce);
+	}
+
+	/// Forgets the next nonce of `address`, it is fetched again on the next reservation.
+	pub fn invalidate(&self, address: &Address) {
+		self.nonces.lock().expect("nonce cache lock poisoned").remove(address);
+	}
+
+	/// Returns the next nonce of `address` and increments it optimistically.
+	/// The nonce is fetched with `fetch_nonce` if it is not cached.
+	pub async fn reserve<F, Fut>(
+		&self,
+		address: Address,
+		fetch_nonce: F,
+	) -> Result<u64, anyhow::Error>
+	where
+		F: FnOnce() -> Fut,
+		Fut: Future<Output = Result<u64, anyhow::Error>>,
+	{
+		{
+			let mut nonces = self.nonces.lock().expect("nonce cache lock poisoned");
+			if let Some(next) = nonces.get_mut(&address) {
+				let nonce = *next;
+				*next += 1;
+				return Ok(nonce);
+			}
+		}
+		let fetched = fetch_nonce().await?;
+
+		// the lock is not held while fetching, another reservation may have filled the entry
+		let mut nonces = self.nonces.lock().expect("nonce cache lock poisoned");
+		let next = nonces.entry(address).or_insert(fetched);
+		let nonce = *next;
+		*next += 1;
+		Ok(nonce)
+	}
+
+	/// Sends a transaction of `address` with a reserved nonce.
+	/// If the RPC rejects the nonce as too low, the cache entry is invalidated
+	/// and the transaction is sent once more with a fresh on-chain nonce.
+	/// On any other failure the entry is invalidated, as the nonce may not have been used.
+	pub async fn send_with_nonce<F, FetchFut, S, SendFut, R>(
+		&self,
+		address: Address,
+		fetch_nonce: F,
+		send: S,
+	) -> Result<R, anyhow::Error>
+	where
+		F: Fn() -> FetchFut,
+		FetchFut: Future<Output = Result<u64, anyhow::Error>>,
+		S: Fn(u64) -> SendFut,
+		SendFut: Future<Output = Result<R, anyhow::Error>>,
+	{
+		let nonce = self.reserve(address, &fetch_nonce).await?;
+		let result = match send(nonce).await {
+			Err(err) if is_nonce_too_low(&err) => {
+				tracing::warn!(
+					"Nonce {} of {} is too low, retrying with a fresh nonce",
+					nonce,
+					address
+				);
+				self.invalidate(&address);
+				let nonce = self.reserve(address, &fetch_nonce).await?;
+				send(nonce).await
+			}
+			result => result,
+		};
+		if result.is_err() {
+			self.invalidate(&address);
+		}
+		result
+	}
+}
+
+fn is_nonce_too_low(err: &anyhow::Error) -> bool {
+	err.to_string().to_lowercase().contains("nonce too low")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::atomic::{AtomicU64, Ordering};
+
+	/// Mocks the RPC: counts the nonce queries and rejects the nonces below `on_chain_nonce`.
+	struct MockProvider {
+		on_chain_nonce: AtomicU64,
+		nonce_queries: AtomicU64,
+		sent: Mutex<Vec<u64>>,
+	}
+
+	impl MockProvider {
+		fn new(on_chain_nonce: u64) -> Self {
+			MockProvider {
+				on_chain_nonce: AtomicU64::new(on_chain_nonce),
+				nonce_queries: AtomicU64::new(0),
+				sent: Mutex::new(Vec::new()),
+			}
+		}
+
+		async fn get_transaction_count(&self) -> Result<u64, anyhow::Error> {
+			self.nonce_queries.fetch_add(1, Ordering::SeqCst);
+			Ok(self.on_chain_nonce.load(Ordering::SeqCst))
+		}
+
+		async fn send_transaction(&self, nonce: u64) -> Result<u64, anyhow::Error> {
+			if nonce < self.on_chain_nonce.load(Ordering::SeqCst) {
+				anyhow::bail!(
+					"server returned an error response: error code -32003: nonce too low"
+				);
+			}
+			self.on_chain_nonce.store(nonce + 1, Ordering::SeqCst);
+			self.sent.lock().unwrap().push(nonce);
+			Ok(nonce)
+		}
+	}
+
+	#[tokio::test]
+	async fn test_nonce_is_incremented_from_the_cache() -> Result<(), anyhow::Error> {
+		let provider = MockProvider::new(5);
+		let cache = NonceCache::new();
+		let address = Address::repeat_byte(1);
+
+		for expected in 5..8 {
+			let nonce = cache
+				.send_with_nonce(
+					address,
+					|| provider.get_transaction_count(),
+					|nonce| provider.send_transaction(nonce),
+				)
+				.await?;
+			assert_eq!(nonce, expected);
+		}
+
+		// the nonce was only queried once
+		assert_eq!(provider.nonce_queries.load(Ordering::SeqCst), 1);
+		assert_eq!(*provider.sent.lock().unwrap(), vec![5, 6, 7]);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_nonce_too_low_invalidates_and_retries() -> Result<(), anyhow::Error> {
+		let provider = MockProvider::new(10);
+		let cache = NonceCache::new();
+		let address = Address::repeat_byte(2);
+
+		// the cached nonce is stale, e.g. transactions were sent from another process
+		cache.set(address, 3);
+		let nonce = cache
+			.send_with_nonce(
+				address,
+				|| provider.get_transaction_count(),
+				|nonce| provider.send_transaction(nonce),
+			)
+			.await?;
+		assert_eq!(nonce, 10);
+		assert_eq!(provider.nonce_queries.load(Ordering::SeqCst), 1);
+
+		// the refreshed nonce is cached for the next transaction
+		assert_eq!(cache.nonces.lock().unwrap().get(&address), Some(&11));
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_failed_send_invalidates_the_nonce() -> Result<(), anyhow::Error> {
+		let cache = NonceCache::new();
+		let address = Address::repeat_byte(3);
+
+		let result: Result<(), _> = cache
+			.send_with_nonce(
+				address,
+				|| async { Ok(0) },
+				|_nonce| async { Err(anyhow::anyhow!("insufficient funds")) },
+			)
+			.await;
+		assert!(result.is_err());
+		assert!(cache.nonces.lock().unwrap().get(&address).is_none());
+		Ok(())
+	}
+}
@@ -15,6 +15,7 @@ use bridge_util::types::HashLock;
 use bridge_util::types::HashLockPreImage;
 use bridge_util::types::LockDetails;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::hash::{DefaultHasher, Hash, Hasher};
 
 pub const ETH_ADDRESS_LEN: usize = 20;
@@ -40,6 +41,15 @@ alloy::sol!(
 	"abis/MockMOVEToken.json"
 );
 
+// The bridge contracts don't pay the relayer yet, the fee contract only has to expose this call.
+alloy::sol!(
+	#[allow(missing_docs)]
+	#[sol(rpc)]
+	interface IRelayerFee {
+		function claimRelayerFee(bytes32 bridgeTransferId) external;
+	}
+);
+
 /// Specifies the kind of asset being transferred,
 /// This will associate the client with its respective ABIs
 #[derive(Debug, Clone, Default)]
@@ -125,7 +135,7 @@ pub type AlloyProvider = FillProvider<
 	Ethereum,
 >;
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, RlpEncodable, RlpDecodable)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, RlpEncodable, RlpDecodable, Serialize, Deserialize)]
 pub struct EthAddress(pub Address);
 
 impl From<EthAddress> for Vec<u8> {
@@ -1,12 +1,17 @@
 use crate::chains::ethereum::types::EthAddress;
 use alloy::{
 	contract::{CallBuilder, CallDecoder},
+	eips::BlockNumberOrTag,
 	network::Ethereum,
 	primitives::{Address, U256},
 	providers::Provider,
 	rlp::{Encodable, RlpEncodable},
-	rpc::types::TransactionReceipt,
-	transports::Transport,
+	rpc::types::{Log, TransactionReceipt},
+	transports::{RpcError, Transport, TransportError},
+};
+use bridge_util::{
+	chains::bridge_contracts::BridgeContractError,
+	types::{current_timestamp_secs, ChainId},
 };
 use keccak_hash::keccak;
 use mcr_settlement_client::send_eth_transaction::{
@@ -14,7 +19,7 @@ use mcr_settlement_client::send_eth_transaction::{
 };
 use std::str::FromStr;
 use thiserror::Error;
-use tracing::info;
+use tracing::{info, warn};
 
 #[derive(Debug, Error)]
 pub enum EthUtilError {
@@ -30,6 +35,48 @@ pub enum EthUtilError {
 	GasLimitExceed(u128, u128),
 	#[error("RpcTransactionExecution: {0}")]
 	RpcTransactionExecution(String),
+	#[error("The latest block has no base fee, EIP-1559 transactions are not supported")]
+	MissingBaseFee,
+}
+
+/// Position of the Ethereum side in the relayer, reported in `ChainUnavailable` errors.
+pub const ETH_BRIDGE_CHAIN: ChainId = ChainId::ONE;
+
+/// Transport failures (connection refused, timeouts, HTTP 5xx, ...) mean the node can't be reached.
+fn is_unavailable(err: &TransportError) -> bool {
+	matches!(err, RpcError::Transport(_))
+}
+
+/// Maps a provider rpc error to `ChainUnavailable` when the node can't be reached,
+/// otherwise the error message is passed to `on_other`.
+pub fn map_rpc_error(
+	err: TransportError,
+	on_other: impl FnOnce(String) -> BridgeContractError,
+) -> BridgeContractError {
+	if is_unavailable(&err) {
+		BridgeContractError::chain_unavailable(ETH_BRIDGE_CHAIN, err)
+	} else {
+		on_other(err.to_string())
+	}
+}
+
+/// Same as `map_rpc_error` for the errors returned by `send_transaction`.
+pub fn map_send_transaction_error(
+	err: anyhow::Error,
+	on_other: impl FnOnce(String) -> BridgeContractError,
+) -> BridgeContractError {
+	let unavailable = match err.downcast_ref::<EthUtilError>() {
+		Some(EthUtilError::SendTxError(alloy::contract::Error::TransportError(e))) => {
+			is_unavailable(e)
+		}
+		Some(_) => false,
+		None => err.downcast_ref::<TransportError>().map_or(false, is_unavailable),
+	};
+	if unavailable {
+		BridgeContractError::chain_unavailable(ETH_BRIDGE_CHAIN, err)
+	} else {
+		on_other(err.to_string())
+	}
 }
 
 impl FromStr for EthAddress {
@@ -63,12 +110,121 @@ pub fn calculate_storage_slot(key: [u8; 32], mapping_slot: U256) -> U256 {
 	U256::from_be_slice(&hash.0)
 }
 
+/// Storage slot of the `bridgeTransfers` mapping of the counterparty contract.
+/// `atomicBridgeInitiatorMOVE` and `rateLimiter` are declared before it in slots 0 and 1,
+/// `OwnableUpgradeable` keeps its state in namespaced storage.
+pub const COUNTERPARTY_BRIDGE_TRANSFERS_SLOT: u64 = 2;
+
+/// Number of storage slots of a counterparty `BridgeTransferDetails` entry,
+/// one per field as none of them can be packed with the next one.
+pub const COUNTERPARTY_BRIDGE_TRANSFER_SLOTS: u64 = 6;
+
+/// Storage slot of the first field of the counterparty `bridgeTransfers` entry of a transfer,
+/// `keccak256(bridge_transfer_id . COUNTERPARTY_BRIDGE_TRANSFERS_SLOT)` as laid out by Solidity.
+pub fn calculate_counterparty_storage_slot(bridge_transfer_id: [u8; 32]) -> U256 {
+	let mut buffer = [0u8; 64];
+	buffer[..32].copy_from_slice(&bridge_transfer_id);
+	buffer[32..]
+		.copy_from_slice(&U256::from(COUNTERPARTY_BRIDGE_TRANSFERS_SLOT).to_be_bytes::<32>());
+	U256::from_be_slice(&keccak(buffer).0)
+}
+
 pub fn send_transaction_rules() -> Vec<Box<dyn VerifyRule>> {
 	let rule1: Box<dyn VerifyRule> = Box::new(SendTransactionErrorRule::<UnderPriced>::new());
 	let rule2: Box<dyn VerifyRule> = Box::new(SendTransactionErrorRule::<InsufficentFunds>::new());
 	vec![rule1, rule2]
 }
 
+/// The type of the transactions sent by `send_transaction_with_type`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransactionType {
+	/// Legacy transactions paying the gas price reported by the node.
+	#[default]
+	Legacy,
+	/// EIP-1559 transactions paying the base fee plus the `max_priority_fee` tip,
+	/// never more than `max_fee_per_gas`.
+	Eip1559 { max_fee_per_gas: u128, max_priority_fee: u128 },
+}
+
+/// The gas fees set on a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GasFees {
+	Legacy { gas_price: u128 },
+	Eip1559 { max_fee_per_gas: u128, max_priority_fee_per_gas: u128 },
+}
+
+impl GasFees {
+	/// Computes the EIP-1559 fees for the given base fee, leaving room for the base fee
+	/// to double before the transaction is included.
+	fn eip1559(base_fee: u128, max_fee_per_gas: u128, max_priority_fee: u128) -> Self {
+		let max_fee_per_gas =
+			base_fee.saturating_mul(2).saturating_add(max_priority_fee).min(max_fee_per_gas);
+		GasFees::Eip1559 {
+			max_fee_per_gas,
+			max_priority_fee_per_gas: max_priority_fee.min(max_fee_per_gas),
+		}
+	}
+
+	/// The highest price per gas the transaction can pay.
+	fn max_gas_price(&self) -> u128 {
+		match self {
+			GasFees::Legacy { gas_price } => *gas_price,
+			GasFees::Eip1559 { max_fee_per_gas, .. } => *max_fee_per_gas,
+		}
+	}
+}
+
+impl TransactionType {
+	/// Queries the node for the current gas price or base fee.
+	async fn gas_fees<P: Provider<T, Ethereum>, T: Transport + Clone>(
+		&self,
+		provider: &P,
+	) -> Result<GasFees, anyhow::Error> {
+		match self {
+			TransactionType::Legacy => {
+				Ok(GasFees::Legacy { gas_price: provider.get_gas_price().await? })
+			}
+			TransactionType::Eip1559 { max_fee_per_gas, max_priority_fee } => {
+				let block = provider
+					.get_block_by_number(BlockNumberOrTag::Latest, false)
+					.await?
+					.ok_or_else(|| {
+						EthUtilError::RpcTransactionExecution("No latest block".to_string())
+					})?;
+				let base_fee = block.header.base_fee_per_gas.ok_or(EthUtilError::MissingBaseFee)?;
+				Ok(GasFees::eip1559(base_fee, *max_fee_per_gas, *max_priority_fee))
+			}
+		}
+	}
+}
+
+/// Unix timestamp in seconds of the block of `log`.
+/// The block is queried when the node leaves the timestamp out of the log,
+/// and the current time is used when the block can't be fetched.
+pub async fn log_timestamp<P: Provider<T, Ethereum>, T: Transport + Clone>(
+	provider: &P,
+	log: &Log,
+) -> u64 {
+	if let Some(timestamp) = log.block_timestamp {
+		return timestamp;
+	}
+	let Some(block_number) = log.block_number else {
+		return current_timestamp_secs();
+	};
+	match provider
+		.get_block_by_number(BlockNumberOrTag::Number(block_number), false)
+		.await
+	{
+		Ok(Some(block)) => block.header.timestamp,
+		Ok(None) => current_timestamp_secs(),
+		Err(err) => {
+			warn!("Failed to fetch the timestamp of block {block_number}: {err}");
+			current_timestamp_secs()
+		}
+	}
+}
+
+/// Sends a legacy transaction, see `send_transaction_with_type`.
 pub async fn send_transaction<
 	P: Provider<T, Ethereum> + Clone,
 	T: Transport + Clone,
@@ -80,30 +236,90 @@ pub async fn send_transaction<
 	number_retry: u32,
 	gas_limit: u128,
 ) -> Result<TransactionReceipt, anyhow::Error> {
-	info!("base_call_builder: {:?}", base_call_builder);
-	info!("Sending transaction with gas limit: {}", gas_limit);
+	send_transaction_with_type(
+		base_call_builder,
+		signer_address,
+		send_transaction_error_rules,
+		number_retry,
+		gas_limit,
+		TransactionType::Legacy,
+	)
+	.await
+}
 
-	// set signer address as from for gas_estimation.
-	// The gas estimate need to set teh from before calling.
-	let base_call_builder = base_call_builder.from(signer_address);
+pub async fn send_transaction_with_type<
+	P: Provider<T, Ethereum> + Clone,
+	T: Transport + Clone,
+	D: CallDecoder + Clone,
+>(
+	base_call_builder: CallBuilder<T, &P, D, Ethereum>,
+	signer_address: Address,
+	send_transaction_error_rules: &[Box<dyn VerifyRule>],
+	number_retry: u32,
+	gas_limit: u128,
+	transaction_type: TransactionType,
+) -> Result<TransactionReceipt, anyhow::Error> {
 	//validate gas price.
 	let mut estimate_gas = 300000; //base_call_builder.estimate_gas().await?;
 							   // Add 20% because initial gas estimate are too low.
 	estimate_gas += (estimate_gas * 20) / 100;
 	estimate_gas *= 2;
 
+	send_transaction_with_gas(
+		base_call_builder,
+		signer_address,
+		send_transaction_error_rules,
+		number_retry,
+		gas_limit,
+		estimate_gas,
+		transaction_type,
+	)
+	.await
+}
+
+/// Same as `send_transaction_with_type`, starting with `gas` as the transaction gas.
+/// A `gas_limit` of 0 doesn't cap the transaction fee.
+pub async fn send_transaction_with_gas<
+	P: Provider<T, Ethereum> + Clone,
+	T: Transport + Clone,
+	D: CallDecoder + Clone,
+>(
+	base_call_builder: CallBuilder<T, &P, D, Ethereum>,
+	signer_address: Address,
+	send_transaction_error_rules: &[Box<dyn VerifyRule>],
+	number_retry: u32,
+	gas_limit: u128,
+	gas: u128,
+	transaction_type: TransactionType,
+) -> Result<TransactionReceipt, anyhow::Error> {
+	info!("base_call_builder: {:?}", base_call_builder);
+	info!("Sending transaction with gas limit: {}", gas_limit);
+
+	// set signer address as from for gas_estimation.
+	// The gas estimate need to set teh from before calling.
+	let base_call_builder = base_call_builder.from(signer_address);
+	let mut estimate_gas = gas;
+
 	// Sending Transaction automatically can lead to errors that depend on the state for Eth.
 	// It's convenient to manage some of them automatically to avoid to fail commitment Transaction.
 	// I define a first one but other should be added depending on the test with mainnet.
 	for _ in 0..number_retry {
 		let call_builder = base_call_builder.clone().gas(estimate_gas);
 
+		//set the fees of the transaction type.
+		let gas_fees = transaction_type.gas_fees(call_builder.provider).await?;
+		let call_builder = match gas_fees {
+			GasFees::Legacy { gas_price } => call_builder.gas_price(gas_price),
+			GasFees::Eip1559 { max_fee_per_gas, max_priority_fee_per_gas } => call_builder
+				.max_fee_per_gas(max_fee_per_gas)
+				.max_priority_fee_per_gas(max_priority_fee_per_gas),
+		};
+
 		tracing::info!("Eth send_transaction: {:?}", call_builder);
 
 		//detect if the gas price doesn't execeed the limit.
-		let gas_price = call_builder.provider.get_gas_price().await?;
-		let transaction_fee_wei = estimate_gas * gas_price;
-		if transaction_fee_wei > gas_limit {
+		let transaction_fee_wei = estimate_gas * gas_fees.max_gas_price();
+		if gas_limit != 0 && transaction_fee_wei > gas_limit {
 			return Err(EthUtilError::GasLimitExceed(transaction_fee_wei, gas_limit).into());
 		}
 
@@ -159,3 +375,49 @@ pub async fn send_transaction<
 	)
 	.into())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_counterparty_storage_slot() {
+		// keccak256(abi.encode(bytes32(0), uint256(2)))
+		assert_eq!(
+			calculate_counterparty_storage_slot([0; 32]),
+			U256::from_str("0xac33ff75c19e70fe83507db0d683fd3465c996598dc972688b7ace676c89077b")
+				.unwrap()
+		);
+		assert_ne!(
+			calculate_counterparty_storage_slot([7; 32]),
+			calculate_counterparty_storage_slot([0; 32])
+		);
+	}
+
+	#[test]
+	fn test_legacy_is_the_default_transaction_type() {
+		assert_eq!(TransactionType::default(), TransactionType::Legacy);
+		assert_eq!(GasFees::Legacy { gas_price: 7 }.max_gas_price(), 7);
+	}
+
+	#[test]
+	fn test_eip1559_fees_add_the_tip_to_the_base_fee() {
+		let fees = GasFees::eip1559(100, 1_000, 2);
+		assert_eq!(fees, GasFees::Eip1559 { max_fee_per_gas: 202, max_priority_fee_per_gas: 2 });
+		assert_eq!(fees.max_gas_price(), 202);
+	}
+
+	#[test]
+	fn test_eip1559_fees_are_capped() {
+		let fees = GasFees::eip1559(100, 150, 2);
+		assert_eq!(fees, GasFees::Eip1559 { max_fee_per_gas: 150, max_priority_fee_per_gas: 2 });
+
+		// the tip cannot exceed the max fee
+		let fees = GasFees::eip1559(0, 5, 10);
+		assert_eq!(fees, GasFees::Eip1559 { max_fee_per_gas: 5, max_priority_fee_per_gas: 5 });
+
+		// no overflow on extreme base fees
+		let fees = GasFees::eip1559(u128::MAX, 150, 2);
+		assert_eq!(fees.max_gas_price(), 150);
+	}
+}
@@ -1,24 +1,45 @@
-use super::utils::{self, MovementAddress};
+use super::{
+	compiled_package::{self, read_compiled_package},
+	keystore,
+	module_cache::{self, ModuleCache},
+	utils::{self, MovementAddress},
+};
 use anyhow::{Context, Result};
 use aptos_api_types::{EntryFunctionId, MoveModuleId, ViewRequest};
 use aptos_sdk::{
+	crypto::ed25519::Ed25519PrivateKey,
 	move_types::identifier::Identifier,
-	rest_client::{Client, Response},
-	types::LocalAccount,
+	rest_client::{aptos_api_types::Transaction as AptosTransaction, Client, Response},
+	types::{transaction::TransactionPayload, LocalAccount},
 };
 use aptos_types::account_address::AccountAddress;
-use bridge_config::common::movement::MovementConfig;
+use bridge_config::{
+	common::movement::{
+		default_confirm_timeout_secs, default_package_build_dir, default_simulate_on_submit,
+		default_time_lock_max_secs, default_time_lock_min_secs, MovementConfig,
+	},
+	ConfigError,
+};
 use bridge_util::{
+	address::derive_eth_recipient,
 	chains::bridge_contracts::{BridgeContract, BridgeContractError, BridgeContractResult},
 	types::{
-		Amount, BridgeAddress, BridgeTransferDetails, BridgeTransferDetailsCounterparty,
-		BridgeTransferId, HashLock, HashLockPreImage, TimeLock,
+		current_timestamp_secs, Amount, BridgeAddress, BridgeTransferDetails,
+		BridgeTransferDetailsCounterparty, BridgeTransferId, ChainId, HashLock, HashLockPreImage,
+		TimeLock,
 	},
 };
 use hex;
 use rand::prelude::*;
-use std::{path::Path, str::FromStr, sync::Arc};
-use tracing::{debug, info};
+use std::{
+	path::{Path, PathBuf},
+	str::FromStr,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+};
+use tracing::{debug, info, warn};
 use url::Url;
 
 pub const FRAMEWORK_ADDRESS: AccountAddress = AccountAddress::new([
@@ -27,8 +48,24 @@ pub const FRAMEWORK_ADDRESS: AccountAddress = AccountAddress::new([
 
 pub const INITIATOR_MODULE_NAME: &str = "atomic_bridge_initiator";
 pub const COUNTERPARTY_MODULE_NAME: &str = "atomic_bridge_counterparty";
+/// Position of the Movement side in the relayer, reported in `ChainUnavailable` errors.
+pub const MOVEMENT_BRIDGE_CHAIN: ChainId = ChainId::TWO;
 const DUMMY_ADDRESS: AccountAddress = AccountAddress::new([0; 32]);
 
+/// Parses the native address from the config. An unset (empty or zero) address is only
+/// accepted when `lazy_init` is enabled, in which case it is replaced later.
+fn native_address_from_config(config: &MovementConfig) -> Result<AccountAddress, anyhow::Error> {
+	let native_address = if config.movement_native_address.is_empty() {
+		DUMMY_ADDRESS
+	} else {
+		AccountAddress::from_hex_literal(&config.movement_native_address)?
+	};
+	if native_address == DUMMY_ADDRESS && !config.lazy_init {
+		return Err(ConfigError::MissingCounterpartyAddress.into());
+	}
+	Ok(native_address)
+}
+
 #[allow(dead_code)]
 enum Call {
 	Lock,
@@ -46,33 +83,222 @@ pub struct MovementClientFramework {
 	pub rest_client: Client,
 	///The signer account
 	signer: Arc<LocalAccount>,
+	/// How long to wait for a submitted transaction to be confirmed
+	confirm_timeout_secs: u64,
+	/// Minimum and maximum time lock durations the client accepts
+	timelock_bounds: (TimeLock, TimeLock),
+	/// Build output of the bridge Move package, published by `deploy_counterparty_module`
+	package_build_dir: PathBuf,
+	/// Address of the initiator contract on the other chain
+	initiator_address: Vec<u8>,
+	/// Whether the transactions are simulated before being submitted
+	simulate_on_submit: bool,
+	/// Whether a bridge call has already warned about the dummy native address
+	dummy_address_warned: Arc<AtomicBool>,
 }
 
 impl MovementClientFramework {
 	pub async fn new(config: &MovementConfig) -> Result<Self, anyhow::Error> {
+		Self::new_with_signer(config.movement_signer_key.clone(), config).await
+	}
+
+	/// Builds a client that signs with `signing_key` instead of `movement_signer_key`,
+	/// so the key can come from a key management system.
+	pub async fn new_with_signer(
+		signing_key: Ed25519PrivateKey,
+		config: &MovementConfig,
+	) -> Result<Self, anyhow::Error> {
 		let node_connection_url = Url::from_str(config.mvt_rpc_connection_url().as_str())
 			.map_err(|_| BridgeContractError::SerializationError)?;
 
 		let rest_client = Client::new(node_connection_url.clone());
+		utils::wait_for_node_ready(&rest_client, config.node_ready_timeout_secs).await?;
+
+		let signer = utils::create_local_account(signing_key, &rest_client).await?;
+		let native_address = native_address_from_config(config)?;
+		Ok(MovementClientFramework {
+			native_address,
+			rest_client,
+			signer: Arc::new(signer),
+			confirm_timeout_secs: config.confirm_timeout_secs,
+			timelock_bounds: (
+				TimeLock(config.time_lock_min_secs),
+				TimeLock(config.time_lock_max_secs),
+			),
+			package_build_dir: compiled_package::package_build_dir(&config.package_build_dir),
+			initiator_address: Vec::new(),
+			simulate_on_submit: config.simulate_on_submit,
+			dummy_address_warned: Arc::new(AtomicBool::new(false)),
+		})
+	}
 
-		let signer =
-			utils::create_local_account(config.movement_signer_key.clone(), &rest_client).await?;
-		let native_address = AccountAddress::from_hex_literal(&config.movement_native_address)?;
-		Ok(MovementClientFramework { native_address, rest_client, signer: Arc::new(signer) })
+	/// Builds a client whose signer is loaded from a password encrypted keystore file holding
+	/// an Aptos CLI private key (see [`keystore`]) instead of `movement_signer_key`.
+	pub async fn new_with_keystore(
+		keystore_path: &Path,
+		keystore_password: &str,
+		config: &MovementConfig,
+	) -> Result<Self, anyhow::Error> {
+		let private_key = keystore::read_key(keystore_path, keystore_password)
+			.with_context(|| format!("Failed to decrypt keystore {}", keystore_path.display()))?;
+		Self::new_with_signer(private_key, config).await
 	}
 
 	pub fn rest_client(&self) -> &Client {
 		&self.rest_client
 	}
 
+	/// Dry runs the transaction of `payload` sent by the signer.
+	pub async fn simulate_transaction(
+		&self,
+		payload: TransactionPayload,
+	) -> Result<utils::SimulationResult, BridgeContractError> {
+		utils::simulate_transaction(&self.rest_client, self.signer.as_ref(), payload).await
+	}
+
+	/// Sends the transaction of `payload` and waits for its confirmation.
+	/// When `simulate_on_submit` is set, a transaction whose simulation fails isn't submitted.
+	async fn submit_transaction(
+		&self,
+		payload: TransactionPayload,
+	) -> Result<AptosTransaction, BridgeContractError> {
+		if self.simulate_on_submit {
+			let simulation = self.simulate_transaction(payload.clone()).await?;
+			debug!("Transaction simulation used {} gas", simulation.gas_used);
+			simulation.ensure_success()?;
+		}
+		utils::send_and_confirm_aptos_transaction(
+			&self.rest_client,
+			self.signer.as_ref(),
+			payload,
+			self.confirm_timeout_secs,
+		)
+		.await
+	}
+
+	/// Points the bridge calls at the counterparty module published at `address`,
+	/// e.g. once it is known after a lazy init.
+	pub fn set_native_address(&mut self, address: AccountAddress) {
+		self.native_address = address;
+	}
+
+	/// Sets the address of the initiator contract on the other chain.
+	pub fn set_initiator_address(&mut self, address: Vec<u8>) {
+		self.initiator_address = address;
+	}
+
+	pub fn initiator_address(&self) -> &[u8] {
+		&self.initiator_address
+	}
+
+	/// Publishes the compiled bridge package found in `package_build_dir` under the signer
+	/// account, then uses that account as the native address. The package has to be compiled
+	/// with `--save-metadata` and its named addresses set to the signer address.
+	pub async fn deploy_counterparty_module(&mut self) -> Result<AccountAddress, anyhow::Error> {
+		let (metadata, modules) = read_compiled_package(&self.package_build_dir)?;
+		self.publish_package(&metadata, &modules).await
+	}
+
+	/// Publishes the compiled package found in `module_path` like `deploy_counterparty_module`,
+	/// unless the same modules are already published under the signer account. The hash of
+	/// the published package is kept in a [`ModuleCache`], so a package known to differ from
+	/// the published one is published without querying the chain.
+	pub async fn ensure_module_published(
+		&mut self,
+		module_path: &Path,
+	) -> Result<AccountAddress, anyhow::Error> {
+		let (metadata, modules) = read_compiled_package(module_path)?;
+		let address = self.signer.address();
+		let hash = module_cache::package_hash(&modules);
+		let mut cache = ModuleCache::open_default()?;
+
+		let cached = cache.get(address);
+		if cached.map_or(true, |cached| cached == hash)
+			&& self.are_modules_published(address, &modules).await
+		{
+			info!("Modules of {} already published at {}", module_path.display(), address);
+			if cached.is_none() {
+				cache.record(address, hash)?;
+			}
+			self.set_native_address(address);
+			return Ok(address);
+		}
+
+		let address = self.publish_package(&metadata, &modules).await?;
+		cache.record(address, hash)?;
+		Ok(address)
+	}
+
+	/// Whether all of `modules` are published with the same bytecode under `address`.
+	async fn are_modules_published(&self, address: AccountAddress, modules: &[Vec<u8>]) -> bool {
+		match self.rest_client.get_account_modules(address).await {
+			Ok(response) => {
+				let published = response.into_inner();
+				modules
+					.iter()
+					.all(|module| published.iter().any(|published| published.bytecode.0 == *module))
+			}
+			Err(e) => {
+				debug!("Failed to get the modules of {}: {}", address, e);
+				false
+			}
+		}
+	}
+
+	async fn publish_package(
+		&mut self,
+		metadata: &[u8],
+		modules: &[Vec<u8>],
+	) -> Result<AccountAddress, anyhow::Error> {
+		let args = vec![utils::serialize_vec(metadata)?, utils::serialize_vec(modules)?];
+
+		let payload = utils::make_aptos_payload(
+			FRAMEWORK_ADDRESS,
+			"code",
+			"publish_package_txn",
+			Vec::new(),
+			args,
+		);
+
+		self.submit_transaction(payload).await?;
+
+		let address = self.signer.address();
+		info!("Counterparty module deployed at {}", address.to_hex_literal());
+		self.set_native_address(address);
+		Ok(address)
+	}
+
+	/// Warns, once per client, when a bridge call runs before the native address has been set.
+	fn warn_if_dummy_native_address(&self, call: &str) {
+		if self.native_address == DUMMY_ADDRESS
+			&& !self.dummy_address_warned.swap(true, Ordering::Relaxed)
+		{
+			warn!("{} called while the native address is still the dummy address", call);
+		}
+	}
+
 	pub fn signer(&self) -> &LocalAccount {
 		&self.signer
 	}
 
+	pub fn confirm_timeout_secs(&self) -> u64 {
+		self.confirm_timeout_secs
+	}
+
+	/// Rejects a time lock duration outside of the configured bounds.
+	fn check_time_lock(&self, time_lock: u64) -> Result<(), BridgeContractError> {
+		let (TimeLock(min), TimeLock(max)) = self.timelock_bounds;
+		if time_lock < min || time_lock > max {
+			return Err(BridgeContractError::InvalidTimeLock { time_lock, min, max });
+		}
+		Ok(())
+	}
+
 	pub async fn initiator_set_timelock(
 		&mut self,
 		time_lock: u64,
 	) -> Result<(), BridgeContractError> {
+		self.check_time_lock(time_lock)?;
 		let args = vec![utils::serialize_u64(&time_lock)?];
 
 		let payload = utils::make_aptos_payload(
@@ -83,9 +309,9 @@ impl MovementClientFramework {
 			args,
 		);
 
-		utils::send_and_confirm_aptos_transaction(&self.rest_client, self.signer.as_ref(), payload)
+		self.submit_transaction(payload)
 			.await
-			.map_err(|_| BridgeContractError::CallError)?;
+			.map_err(|e| e.unless_chain_unavailable(BridgeContractError::CallError))?;
 
 		Ok(())
 	}
@@ -94,6 +320,7 @@ impl MovementClientFramework {
 		&mut self,
 		time_lock: u64,
 	) -> Result<(), BridgeContractError> {
+		self.check_time_lock(time_lock)?;
 		let args = vec![utils::serialize_u64(&time_lock)?];
 
 		let payload = utils::make_aptos_payload(
@@ -104,9 +331,9 @@ impl MovementClientFramework {
 			args,
 		);
 
-		utils::send_and_confirm_aptos_transaction(&self.rest_client, self.signer.as_ref(), payload)
+		self.submit_transaction(payload)
 			.await
-			.map_err(|_| BridgeContractError::CallError)?;
+			.map_err(|e| e.unless_chain_unavailable(BridgeContractError::CallError))?;
 
 		Ok(())
 	}
@@ -121,7 +348,9 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 		hash_lock: HashLock,
 		amount: Amount,
 	) -> BridgeContractResult<()> {
+		self.warn_if_dummy_native_address("initiate_bridge_transfer");
 		debug!("Amount value: {:?}", amount);
+		derive_eth_recipient(&recipient.0)?;
 
 		let serialized_hash_lock = utils::serialize_vec_initiator(&hash_lock.0[..])?;
 
@@ -139,13 +368,10 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 			args,
 		);
 
-		let _ = utils::send_and_confirm_aptos_transaction(
-			&self.rest_client,
-			self.signer.as_ref(),
-			payload,
-		)
-		.await
-		.map_err(|_| BridgeContractError::InitiateTransferError)?;
+		let _ = self
+			.submit_transaction(payload)
+			.await
+			.map_err(|e| e.unless_chain_unavailable(BridgeContractError::InitiateTransferError))?;
 
 		Ok(())
 	}
@@ -155,6 +381,7 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 		bridge_transfer_id: BridgeTransferId,
 		preimage: HashLockPreImage,
 	) -> BridgeContractResult<()> {
+		self.warn_if_dummy_native_address("initiator_complete_bridge_transfer");
 		let unpadded_preimage = {
 			let mut end = preimage.0.len();
 			while end > 0 && preimage.0[end - 1] == 0 {
@@ -176,13 +403,9 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 			args2,
 		);
 
-		let _ = utils::send_and_confirm_aptos_transaction(
-			&self.rest_client,
-			self.signer.as_ref(),
-			payload,
-		)
-		.await
-		.map_err(|_| BridgeContractError::CompleteTransferError);
+		self.submit_transaction(payload).await.map_err(|e| {
+			e.wrap_unless_chain_unavailable(BridgeContractError::CompleteTransferError)
+		})?;
 
 		Ok(())
 	}
@@ -192,6 +415,7 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 		bridge_transfer_id: BridgeTransferId,
 		preimage: HashLockPreImage,
 	) -> BridgeContractResult<()> {
+		self.warn_if_dummy_native_address("counterparty_complete_bridge_transfer");
 		let unpadded_preimage = {
 			let mut end = preimage.0.len();
 			while end > 0 && preimage.0[end - 1] == 0 {
@@ -212,13 +436,9 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 			args2,
 		);
 
-		let result = utils::send_and_confirm_aptos_transaction(
-			&self.rest_client,
-			self.signer.as_ref(),
-			payload,
-		)
-		.await
-		.map_err(|_| BridgeContractError::CompleteTransferError);
+		let result = self.submit_transaction(payload).await.map_err(|e| {
+			e.wrap_unless_chain_unavailable(BridgeContractError::CompleteTransferError)
+		});
 
 		match &result {
 			Ok(tx_result) => {
@@ -228,6 +448,7 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 				debug!("Transaction failed: {:?}", err);
 			}
 		}
+		result?;
 
 		Ok(())
 	}
@@ -240,11 +461,12 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 		recipient: BridgeAddress<MovementAddress>,
 		amount: Amount,
 	) -> BridgeContractResult<()> {
+		self.warn_if_dummy_native_address("lock_bridge_transfer");
 		debug!("Starting lock bridge transfer");
 		debug!("Initiator: {:?}", initiator.0);
 
 		let args = vec![
-			utils::serialize_vec(&initiator.0)?,
+			utils::serialize_vec(initiator.try_as_eth_address()?.as_slice())?,
 			utils::serialize_vec(&bridge_transfer_id.0[..])?,
 			utils::serialize_vec(&hash_lock.0[..])?,
 			utils::serialize_vec(&recipient.0)?,
@@ -259,13 +481,10 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 			args,
 		);
 
-		let _ = utils::send_and_confirm_aptos_transaction(
-			&self.rest_client,
-			self.signer.as_ref(),
-			payload,
-		)
-		.await
-		.map_err(|_| BridgeContractError::LockTransferError)?;
+		let _ = self
+			.submit_transaction(payload)
+			.await
+			.map_err(|e| e.wrap_unless_chain_unavailable(BridgeContractError::LockTransferError))?;
 
 		Ok(())
 	}
@@ -274,6 +493,7 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 		&mut self,
 		bridge_transfer_id: BridgeTransferId,
 	) -> BridgeContractResult<()> {
+		self.warn_if_dummy_native_address("refund_bridge_transfer");
 		let args = vec![utils::serialize_vec_initiator(&bridge_transfer_id.0[..])?];
 
 		let payload = utils::make_aptos_payload(
@@ -284,9 +504,7 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 			args,
 		);
 
-		utils::send_and_confirm_aptos_transaction(&self.rest_client, self.signer.as_ref(), payload)
-			.await
-			.map_err(|err| BridgeContractError::OnChainError(err.to_string()))?;
+		self.submit_transaction(payload).await?;
 
 		Ok(())
 	}
@@ -295,6 +513,7 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 		&mut self,
 		bridge_transfer_id: BridgeTransferId,
 	) -> BridgeContractResult<()> {
+		self.warn_if_dummy_native_address("abort_bridge_transfer");
 		let args3 = vec![utils::serialize_vec(&bridge_transfer_id.0[..])?];
 		let payload = utils::make_aptos_payload(
 			FRAMEWORK_ADDRESS,
@@ -303,9 +522,9 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 			Vec::new(),
 			args3,
 		);
-		utils::send_and_confirm_aptos_transaction(&self.rest_client, self.signer.as_ref(), payload)
-			.await
-			.map_err(|_| BridgeContractError::AbortTransferError)?;
+		self.submit_transaction(payload).await.map_err(|e| {
+			e.wrap_unless_chain_unavailable(BridgeContractError::AbortTransferError)
+		})?;
 		Ok(())
 	}
 
@@ -313,6 +532,7 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 		&mut self,
 		bridge_transfer_id: BridgeTransferId,
 	) -> BridgeContractResult<Option<BridgeTransferDetails<MovementAddress>>> {
+		self.warn_if_dummy_native_address("get_bridge_transfer_details_initiator");
 		let bridge_transfer_id_hex = format!("0x{}", hex::encode(bridge_transfer_id.0));
 
 		let view_request = ViewRequest {
@@ -337,7 +557,7 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 			.rest_client
 			.view(&view_request, None)
 			.await
-			.map_err(|_| BridgeContractError::CallError)?;
+			.map_err(|e| utils::map_rest_error(e, |_| BridgeContractError::CallError))?;
 
 		let values = response.inner();
 
@@ -390,6 +610,8 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 			hash_lock: HashLock(hash_lock_array),
 			time_lock: TimeLock(time_lock),
 			state,
+			// The contract doesn't store when the transfer was initiated.
+			initiated_at: current_timestamp_secs(),
 		};
 
 		Ok(Some(details))
@@ -399,6 +621,7 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 		&mut self,
 		bridge_transfer_id: BridgeTransferId,
 	) -> BridgeContractResult<Option<BridgeTransferDetailsCounterparty<MovementAddress>>> {
+		self.warn_if_dummy_native_address("get_bridge_transfer_details_counterparty");
 		let bridge_transfer_id_hex = format!("0x{}", hex::encode(bridge_transfer_id.0));
 
 		let view_request = ViewRequest {
@@ -423,7 +646,7 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 			.rest_client
 			.view(&view_request, None)
 			.await
-			.map_err(|_| BridgeContractError::CallError)?;
+			.map_err(|e| utils::map_rest_error(e, |_| BridgeContractError::CallError))?;
 
 		let values = response.inner();
 
@@ -487,7 +710,6 @@ impl BridgeContract<MovementAddress> for MovementClientFramework {
 use std::{
 	env, fs,
 	io::Write,
-	path::PathBuf,
 	process::{Command, Stdio},
 };
 use tokio::{
@@ -949,8 +1171,331 @@ impl MovementClientFramework {
 				native_address: DUMMY_ADDRESS,
 				rest_client,
 				signer: Arc::new(LocalAccount::generate(&mut rng)),
+				confirm_timeout_secs: default_confirm_timeout_secs(),
+				timelock_bounds: (
+					TimeLock(default_time_lock_min_secs()),
+					TimeLock(default_time_lock_max_secs()),
+				),
+				package_build_dir: compiled_package::package_build_dir(&default_package_build_dir()),
+				initiator_address: Vec::new(),
+				simulate_on_submit: default_simulate_on_submit(),
+				dummy_address_warned: Arc::new(AtomicBool::new(false)),
 			},
 			child,
 		))
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bridge_util::chains::bridge_contracts::BridgeContractErrorCode;
+
+	fn test_client(url: &str, timelock_bounds: (TimeLock, TimeLock)) -> MovementClientFramework {
+		let mut rng = ::rand::rngs::StdRng::from_seed([3u8; 32]);
+		MovementClientFramework {
+			native_address: FRAMEWORK_ADDRESS,
+			rest_client: Client::new(Url::from_str(url).unwrap()),
+			signer: Arc::new(LocalAccount::generate(&mut rng)),
+			confirm_timeout_secs: 1,
+			timelock_bounds,
+			package_build_dir: compiled_package::package_build_dir(&default_package_build_dir()),
+			initiator_address: Vec::new(),
+			simulate_on_submit: false,
+			dummy_address_warned: Arc::new(AtomicBool::new(false)),
+		}
+	}
+
+	#[test]
+	fn test_dummy_native_address_warning_is_shared_by_clones() {
+		let mut client = test_client("http://127.0.0.1:1", (TimeLock(1), TimeLock(2)));
+		client.warn_if_dummy_native_address("lock_bridge_transfer");
+		assert!(!client.dummy_address_warned.load(Ordering::Relaxed));
+
+		client.set_native_address(DUMMY_ADDRESS);
+		let clone = client.clone();
+		clone.warn_if_dummy_native_address("lock_bridge_transfer");
+		assert!(client.dummy_address_warned.load(Ordering::Relaxed));
+	}
+
+	#[test]
+	fn test_native_address_from_config_requires_address_unless_lazy() {
+		let mut config = MovementConfig::for_test();
+		assert!(native_address_from_config(&config).is_ok());
+
+		config.movement_native_address = "0x0".to_string();
+		let err = native_address_from_config(&config).unwrap_err();
+		assert_eq!(err.downcast_ref(), Some(&ConfigError::MissingCounterpartyAddress));
+
+		config.movement_native_address = String::new();
+		assert!(native_address_from_config(&config).is_err());
+
+		config.lazy_init = true;
+		assert_eq!(native_address_from_config(&config).unwrap(), DUMMY_ADDRESS);
+	}
+
+	#[test]
+	fn test_clone_shares_signer_and_connection() {
+		let client = test_client("http://127.0.0.1:8080", (TimeLock(1), TimeLock(60)));
+
+		let cloned = client.clone();
+		assert!(Arc::ptr_eq(&client.signer, &cloned.signer));
+		assert_eq!(cloned.signer().address(), client.signer().address());
+		assert_eq!(cloned.native_address, client.native_address);
+		assert_eq!(cloned.confirm_timeout_secs(), client.confirm_timeout_secs());
+	}
+
+	#[test]
+	fn test_check_time_lock_bounds() {
+		let client = test_client("http://127.0.0.1:8080", (TimeLock(10), TimeLock(60)));
+
+		assert_eq!(
+			client.check_time_lock(9),
+			Err(BridgeContractError::InvalidTimeLock { time_lock: 9, min: 10, max: 60 })
+		);
+		assert_eq!(
+			client.check_time_lock(61),
+			Err(BridgeContractError::InvalidTimeLock { time_lock: 61, min: 10, max: 60 })
+		);
+		assert_eq!(client.check_time_lock(10), Ok(()));
+		assert_eq!(client.check_time_lock(60), Ok(()));
+	}
+
+	#[tokio::test]
+	async fn test_lock_bridge_transfer_keeps_transport_error() {
+		// Nothing listens on this port, so fetching the chain id before submitting fails.
+		let mut client = test_client("http://127.0.0.1:1", (TimeLock(1), TimeLock(60)));
+
+		let err = client
+			.lock_bridge_transfer(
+				BridgeTransferId([1; 32]),
+				HashLock([2; 32]),
+				BridgeAddress(vec![3; 20]),
+				BridgeAddress(MovementAddress(FRAMEWORK_ADDRESS)),
+				Amount(1),
+			)
+			.await
+			.unwrap_err();
+
+		match err {
+			BridgeContractError::LockTransferError(message) => {
+				assert!(message.contains("Failed in getting chain id"), "{}", message)
+			}
+			err => panic!("Expected a LockTransferError, got {:?}", err),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_initiate_bridge_transfer_rejects_invalid_recipient() {
+		let mut client = test_client("http://127.0.0.1:1", (TimeLock(1), TimeLock(60)));
+
+		let err = client
+			.initiate_bridge_transfer(
+				BridgeAddress(MovementAddress(FRAMEWORK_ADDRESS)),
+				BridgeAddress(vec![3; 32]),
+				HashLock([2; 32]),
+				Amount(1),
+			)
+			.await
+			.unwrap_err();
+
+		assert!(
+			matches!(err, BridgeContractError::InvalidRecipientLength { expected: 20, actual: 32 }),
+			"{:?}",
+			err
+		);
+	}
+
+	#[test]
+	fn test_simulation_result_ensure_success() {
+		let success = utils::SimulationResult {
+			gas_used: 42,
+			success: true,
+			vm_status: "Executed successfully".to_string(),
+		};
+		assert_eq!(success.clone().ensure_success().unwrap(), success);
+
+		let failure = utils::SimulationResult {
+			gas_used: 7,
+			success: false,
+			vm_status: "Move abort: EINSUFFICIENT_BALANCE".to_string(),
+		};
+		let err = failure.ensure_success().unwrap_err();
+		assert_eq!(err.code(), BridgeContractErrorCode::ContractRevert);
+		assert!(err.to_string().contains("EINSUFFICIENT_BALANCE"), "{}", err);
+	}
+
+	/// The requests received by the mock REST server, as `METHOD /path`.
+	type ReceivedRequests = Arc<std::sync::Mutex<Vec<String>>>;
+
+	const FAILED_SIMULATION_VM_STATUS: &str = "Move abort: EINSUFFICIENT_BALANCE";
+
+	/// Answers the Aptos REST API calls made to submit a transaction: the ledger information,
+	/// the account of the sender and the simulation, which fails. Anything else is not found.
+	#[poem::handler]
+	fn mock_rest_api(
+		request: &poem::Request,
+		poem::web::Data(received): poem::web::Data<&ReceivedRequests>,
+	) -> poem::Response {
+		use aptos_api_types::{
+			X_APTOS_BLOCK_HEIGHT, X_APTOS_CHAIN_ID, X_APTOS_EPOCH, X_APTOS_LEDGER_OLDEST_VERSION,
+			X_APTOS_LEDGER_TIMESTAMP, X_APTOS_LEDGER_VERSION, X_APTOS_OLDEST_BLOCK_HEIGHT,
+		};
+
+		let path = request.uri().path().trim_end_matches('/').to_string();
+		received.lock().unwrap().push(format!("{} {}", request.method(), path));
+		let accepts_bcs =
+			request.header(poem::http::header::ACCEPT).unwrap_or_default().contains("bcs");
+		let hash = format!("0x{}", "00".repeat(32));
+		let (content_type, body) = match (request.method().as_str(), path.as_str()) {
+			("GET", "/v1") if accepts_bcs => {
+				// chain id, epoch, ledger version, oldest ledger version, ledger timestamp,
+				// full node role, oldest block height, block height and no git hash
+				let mut index = vec![4u8];
+				for value in [1u64, 1, 0, 1] {
+					index.extend(value.to_le_bytes());
+				}
+				index.push(1);
+				for value in [0u64, 1] {
+					index.extend(value.to_le_bytes());
+				}
+				index.push(0);
+				("application/x-bcs", index)
+			}
+			("GET", "/v1") => (
+				"application/json",
+				serde_json::json!({
+					"chain_id": 4,
+					"epoch": "1",
+					"ledger_version": "1",
+					"oldest_ledger_version": "0",
+					"ledger_timestamp": "1",
+					"node_role": "full_node",
+					"oldest_block_height": "0",
+					"block_height": "1",
+				})
+				.to_string()
+				.into_bytes(),
+			),
+			("GET", path) if path.starts_with("/v1/accounts/") => (
+				"application/json",
+				serde_json::json!({ "sequence_number": "0", "authentication_key": hash })
+					.to_string()
+					.into_bytes(),
+			),
+			("POST", "/v1/transactions/simulate") => (
+				"application/json",
+				serde_json::json!([{
+					"version": "1",
+					"hash": hash,
+					"state_change_hash": hash,
+					"event_root_hash": hash,
+					"state_checkpoint_hash": null,
+					"gas_used": "7",
+					"success": false,
+					"vm_status": FAILED_SIMULATION_VM_STATUS,
+					"accumulator_root_hash": hash,
+					"changes": [],
+					"sender": "0x1",
+					"sequence_number": "0",
+					"max_gas_amount": "100",
+					"gas_unit_price": "100",
+					"expiration_timestamp_secs": "1",
+					"payload": {
+						"type": "entry_function_payload",
+						"function": "0x1::atomic_bridge_counterparty::lock_bridge_transfer",
+						"type_arguments": [],
+						"arguments": [],
+					},
+					"signature": null,
+					"events": [],
+					"timestamp": "1",
+				}])
+				.to_string()
+				.into_bytes(),
+			),
+			_ => {
+				return poem::Response::builder()
+					.status(poem::http::StatusCode::NOT_FOUND)
+					.content_type("application/json")
+					.body(
+						serde_json::json!({
+							"message": format!("{} not found", path),
+							"error_code": "web_framework_error",
+							"vm_error_code": null,
+						})
+						.to_string(),
+					)
+			}
+		};
+		poem::Response::builder()
+			.content_type(content_type)
+			.header(X_APTOS_CHAIN_ID, "4")
+			.header(X_APTOS_EPOCH, "1")
+			.header(X_APTOS_LEDGER_VERSION, "1")
+			.header(X_APTOS_LEDGER_OLDEST_VERSION, "0")
+			.header(X_APTOS_LEDGER_TIMESTAMP, "1")
+			.header(X_APTOS_BLOCK_HEIGHT, "1")
+			.header(X_APTOS_OLDEST_BLOCK_HEIGHT, "0")
+			.body(body)
+	}
+
+	/// Serves [`mock_rest_api`] on a free local port.
+	/// Returns the url of the server and the requests it receives.
+	async fn spawn_mock_rest_server() -> (String, ReceivedRequests) {
+		use poem::{listener::Acceptor, listener::Listener, EndpointExt};
+
+		let acceptor = poem::listener::TcpListener::bind("127.0.0.1:0")
+			.into_acceptor()
+			.await
+			.expect("Failed to bind the mock REST server");
+		let address = acceptor.local_addr()[0]
+			.as_socket_addr()
+			.copied()
+			.expect("The mock REST server listens on a socket address");
+		let received = ReceivedRequests::default();
+		let app = poem::Route::new().at("/*path", mock_rest_api).data(received.clone());
+		tokio::spawn(poem::Server::new_with_acceptor(acceptor).run(app));
+		(format!("http://{}", address), received)
+	}
+
+	#[tokio::test]
+	async fn test_failed_simulation_is_not_submitted() {
+		let (url, received) = spawn_mock_rest_server().await;
+		let mut client = test_client(&url, (TimeLock(1), TimeLock(60)));
+		client.simulate_on_submit = true;
+
+		let payload = utils::make_aptos_payload(
+			FRAMEWORK_ADDRESS,
+			"atomic_bridge_counterparty",
+			"lock_bridge_transfer",
+			Vec::new(),
+			Vec::new(),
+		);
+		let err = client.submit_transaction(payload).await.unwrap_err();
+
+		assert_eq!(err, BridgeContractError::SimulationFailed(FAILED_SIMULATION_VM_STATUS.into()));
+		let received = received.lock().unwrap();
+		assert!(received.contains(&"POST /v1/transactions/simulate".to_string()), "{:?}", received);
+		assert!(!received.contains(&"POST /v1/transactions".to_string()), "{:?}", received);
+	}
+
+	#[test]
+	fn test_set_native_address() {
+		let mut client = test_client("http://127.0.0.1:8080", (TimeLock(1), TimeLock(60)));
+		let address = AccountAddress::from_hex_literal("0xcafe").unwrap();
+
+		client.set_native_address(address);
+		assert_eq!(client.native_address, address);
+	}
+
+	#[test]
+	fn test_set_initiator_address() {
+		let mut client = test_client("http://127.0.0.1:8080", (TimeLock(1), TimeLock(60)));
+		assert!(client.initiator_address().is_empty());
+
+		let address = vec![0xab; 20];
+		client.set_initiator_address(address.clone());
+		assert_eq!(client.initiator_address(), address.as_slice());
+	}
+}
@@ -0,0 +1,265 @@
+use anyhow::{Context, Result};
+use aptos_sdk::types::account_address::AccountAddress;
+use std::{
+	collections::BTreeMap,
+	fs,
+	path::{Path, PathBuf},
+};
+
+/// Magic bytes starting the bytecode of a Move module.
+const MOVE_MAGIC: [u8; 4] = [0xA1, 0x1C, 0xEB, 0x0B];
+/// First bytecode version storing the index of the module's own handle after the tables.
+const VERSION_WITH_SELF_HANDLE: u32 = 5;
+const TABLE_MODULE_HANDLES: u8 = 0x1;
+const TABLE_IDENTIFIERS: u8 = 0x7;
+const TABLE_ADDRESS_IDENTIFIERS: u8 = 0x8;
+
+/// Resolves the configured `package_build_dir`. A relative directory is taken from the
+/// `.movement` directory rather than from the working directory of the service.
+pub fn package_build_dir(dir: &str) -> PathBuf {
+	let dot_movement = dot_movement::DotMovement::try_from_env()
+		.unwrap_or(dot_movement::DotMovement::new(".movement"));
+	resolve_package_build_dir(dir, dot_movement.get_path())
+}
+
+fn resolve_package_build_dir(dir: &str, base: &Path) -> PathBuf {
+	let dir = Path::new(dir);
+	if dir.is_absolute() {
+		dir.to_path_buf()
+	} else {
+		base.join(dir)
+	}
+}
+
+/// Reads the package metadata and the module bytecode written into `build_dir` by
+/// `movement move compile --save-metadata`. Modules are returned in dependency order,
+/// as the modules of a package have to be published after the modules they use.
+pub fn read_compiled_package(build_dir: &Path) -> Result<(Vec<u8>, Vec<Vec<u8>>)> {
+	let metadata_path = build_dir.join("package-metadata.bcs");
+	let metadata = fs::read(&metadata_path)
+		.with_context(|| format!("Failed to read {}", metadata_path.display()))?;
+
+	let modules_dir = build_dir.join("bytecode_modules");
+	let mut module_paths = fs::read_dir(&modules_dir)
+		.with_context(|| format!("Failed to read {}", modules_dir.display()))?
+		.map(|entry| entry.map(|entry| entry.path()))
+		.collect::<Result<Vec<_>, _>>()?;
+	module_paths.retain(|path| path.extension().map_or(false, |ext| ext == "mv"));
+	module_paths.sort();
+	anyhow::ensure!(!module_paths.is_empty(), "No compiled module in {}", modules_dir.display());
+
+	let modules = module_paths
+		.iter()
+		.map(|path| fs::read(path).with_context(|| format!("Failed to read {}", path.display())))
+		.collect::<Result<Vec<_>>>()?;
+	Ok((metadata, sort_by_dependencies(modules)?))
+}
+
+/// Orders `modules` so that each module comes after the modules of the package it uses.
+/// Modules without a dependency between them keep their order.
+fn sort_by_dependencies(modules: Vec<Vec<u8>>) -> Result<Vec<Vec<u8>>> {
+	let mut pending = BTreeMap::new();
+	for (position, module) in modules.into_iter().enumerate() {
+		let (id, dependencies) = read_module_handles(&module)?;
+		pending.insert(id, (position, dependencies, module));
+	}
+
+	let mut sorted = Vec::with_capacity(pending.len());
+	while !pending.is_empty() {
+		// the first module, in the original order, whose dependencies are all sorted
+		let next = pending
+			.iter()
+			.filter(|(_, (_, dependencies, _))| {
+				dependencies.iter().all(|dependency| !pending.contains_key(dependency))
+			})
+			.min_by_key(|(_, (position, _, _))| *position)
+			.map(|(id, _)| id.clone());
+		let Some(id) = next else {
+			anyhow::bail!("Cyclic dependencies between the modules of the package");
+		};
+		let (_, _, module) = pending.remove(&id).expect("module is pending");
+		sorted.push(module);
+	}
+	Ok(sorted)
+}
+
+/// Reads the address and name of a module, and of the modules it uses, from its bytecode.
+fn read_module_handles(
+	bytecode: &[u8],
+) -> Result<((AccountAddress, String), Vec<(AccountAddress, String)>)> {
+	anyhow::ensure!(bytecode.starts_with(&MOVE_MAGIC), "Not a Move module bytecode");
+	let version = bytecode
+		.get(4..8)
+		.map(|bytes| u32::from_le_bytes(bytes.try_into().expect("4 bytes")))
+		.context("Truncated module bytecode")?;
+	let mut cursor = 8;
+
+	let mut tables = BTreeMap::new();
+	let mut contents_len = 0;
+	for _ in 0..read_uleb128(bytecode, &mut cursor)? {
+		let kind = *bytecode.get(cursor).context("Truncated module bytecode")?;
+		cursor += 1;
+		let offset = read_uleb128(bytecode, &mut cursor)? as usize;
+		let len = read_uleb128(bytecode, &mut cursor)? as usize;
+		tables.insert(kind, (offset, len));
+		contents_len = contents_len.max(offset + len);
+	}
+	let contents = bytecode
+		.get(cursor..cursor + contents_len)
+		.context("Truncated module bytecode")?;
+	let table = |kind| {
+		let (offset, len) = tables.get(&kind).copied().unwrap_or((0, 0));
+		&contents[offset..offset + len]
+	};
+
+	let mut identifiers = Vec::new();
+	let (bytes, mut position) = (table(TABLE_IDENTIFIERS), 0);
+	while position < bytes.len() {
+		let len = read_uleb128(bytes, &mut position)? as usize;
+		let identifier =
+			bytes.get(position..position + len).context("Truncated module identifier")?;
+		identifiers.push(String::from_utf8(identifier.to_vec())?);
+		position += len;
+	}
+
+	let addresses = table(TABLE_ADDRESS_IDENTIFIERS)
+		.chunks(AccountAddress::LENGTH)
+		.map(AccountAddress::from_bytes)
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let mut handles = Vec::new();
+	let (bytes, mut position) = (table(TABLE_MODULE_HANDLES), 0);
+	while position < bytes.len() {
+		let address = read_uleb128(bytes, &mut position)? as usize;
+		let name = read_uleb128(bytes, &mut position)? as usize;
+		handles.push((
+			*addresses.get(address).context("Invalid module handle address")?,
+			identifiers.get(name).context("Invalid module handle name")?.clone(),
+		));
+	}
+
+	let self_handle = if version & 0xFFFF >= VERSION_WITH_SELF_HANDLE {
+		let mut position = cursor + contents_len;
+		read_uleb128(bytecode, &mut position)? as usize
+	} else {
+		0
+	};
+	anyhow::ensure!(self_handle < handles.len(), "Invalid self module handle");
+	let id = handles.remove(self_handle);
+	Ok((id, handles))
+}
+
+fn read_uleb128(bytes: &[u8], position: &mut usize) -> Result<u64> {
+	let mut value = 0u64;
+	for shift in (0..64).step_by(7) {
+		let byte = *bytes.get(*position).context("Truncated module bytecode")?;
+		*position += 1;
+		value |= u64::from(byte & 0x7F) << shift;
+		if byte & 0x80 == 0 {
+			return Ok(value);
+		}
+	}
+	anyhow::bail!("Invalid ULEB128 integer in module bytecode")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn write_uleb128(bytes: &mut Vec<u8>, mut value: usize) {
+		while value >= 0x80 {
+			bytes.push((value as u8 & 0x7F) | 0x80);
+			value >>= 7;
+		}
+		bytes.push(value as u8);
+	}
+
+	/// The bytecode of a module `name` at `0xcafe` that uses the modules `dependencies`,
+	/// with only the tables read by `read_module_handles`.
+	fn module_bytecode(name: &str, dependencies: &[&str]) -> Vec<u8> {
+		let mut identifiers = Vec::new();
+		let mut handles = Vec::new();
+		for (index, name) in dependencies.iter().chain([&name]).enumerate() {
+			write_uleb128(&mut identifiers, name.len());
+			identifiers.extend_from_slice(name.as_bytes());
+			write_uleb128(&mut handles, 0);
+			write_uleb128(&mut handles, index);
+		}
+		let addresses = AccountAddress::from_hex_literal("0xcafe").unwrap().to_vec();
+
+		let mut bytecode = MOVE_MAGIC.to_vec();
+		bytecode.extend_from_slice(&6u32.to_le_bytes());
+		write_uleb128(&mut bytecode, 3);
+		let mut offset = 0;
+		for (kind, table) in [
+			(TABLE_MODULE_HANDLES, &handles),
+			(TABLE_IDENTIFIERS, &identifiers),
+			(TABLE_ADDRESS_IDENTIFIERS, &addresses),
+		] {
+			bytecode.push(kind);
+			write_uleb128(&mut bytecode, offset);
+			write_uleb128(&mut bytecode, table.len());
+			offset += table.len();
+		}
+		bytecode.extend_from_slice(&handles);
+		bytecode.extend_from_slice(&identifiers);
+		bytecode.extend_from_slice(&addresses);
+		// the module's own handle is the last one
+		write_uleb128(&mut bytecode, dependencies.len());
+		bytecode
+	}
+
+	#[test]
+	fn test_resolve_package_build_dir() {
+		let base = Path::new("/home/user/.movement");
+		assert_eq!(
+			resolve_package_build_dir("move-modules/build/bridge-modules", base),
+			PathBuf::from("/home/user/.movement/move-modules/build/bridge-modules")
+		);
+		assert_eq!(
+			resolve_package_build_dir("/opt/bridge-modules", base),
+			PathBuf::from("/opt/bridge-modules")
+		);
+	}
+
+	#[test]
+	fn test_read_module_handles() {
+		let cafe = AccountAddress::from_hex_literal("0xcafe").unwrap();
+		let (id, dependencies) =
+			read_module_handles(&module_bytecode("bridge", &["config", "token"])).unwrap();
+		assert_eq!(id, (cafe, "bridge".to_string()));
+		assert_eq!(dependencies, vec![(cafe, "config".to_string()), (cafe, "token".to_string())]);
+
+		assert!(read_module_handles(&[0, 1, 2, 3]).is_err());
+	}
+
+	#[test]
+	fn test_read_compiled_package() {
+		let build_dir = tempfile::tempdir().unwrap();
+		let modules_dir = build_dir.path().join("bytecode_modules");
+		fs::create_dir_all(modules_dir.join("dependencies")).unwrap();
+		fs::write(build_dir.path().join("package-metadata.bcs"), [1, 2]).unwrap();
+		// sorted by file name, a_bridge would come before the modules it uses
+		let bridge = module_bytecode("a_bridge", &["b_config", "c_token"]);
+		let config = module_bytecode("b_config", &["c_token"]);
+		let token = module_bytecode("c_token", &[]);
+		fs::write(modules_dir.join("a_bridge.mv"), &bridge).unwrap();
+		fs::write(modules_dir.join("b_config.mv"), &config).unwrap();
+		fs::write(modules_dir.join("c_token.mv"), &token).unwrap();
+
+		let (metadata, modules) = read_compiled_package(build_dir.path()).unwrap();
+		assert_eq!(metadata, vec![1, 2]);
+		assert_eq!(modules, vec![token, config, bridge]);
+
+		for name in ["a_bridge.mv", "b_config.mv", "c_token.mv"] {
+			fs::remove_file(modules_dir.join(name)).unwrap();
+		}
+		assert!(read_compiled_package(build_dir.path()).is_err());
+	}
+
+	#[test]
+	fn test_cyclic_dependencies_are_rejected() {
+		let modules = vec![module_bytecode("a", &["b"]), module_bytecode("b", &["a"])];
+		assert!(sort_by_dependencies(modules).is_err());
+	}
+}
@@ -8,8 +8,8 @@ use crate::{
 		BridgeContractMonitoring, BridgeContractResult,
 	},
 	types::{
-		Amount, BridgeAddress, BridgeTransferDetails, BridgeTransferId, HashLock, HashLockPreImage,
-		LockDetails, TimeLock,
+		current_timestamp_secs, Amount, BridgeAddress, BridgeTransferDetails, BridgeTransferId,
+		HashLock, HashLockPreImage, LockDetails, TimeLock,
 	},
 };
 use anyhow::Result;
@@ -115,6 +115,8 @@ impl MvtPullingState {
 					self.initiator_refund = sequence_number + 1
 				}
 			}
+			// Movement events are final, they are never reorged.
+			BridgeContractEvent::Reorged(_) => (),
 		}
 	}
 
@@ -129,6 +131,7 @@ impl MvtPullingState {
 				BridgeContractEventType::CounterPartyCompleted => self.counterpart_complete += 1,
 				BridgeContractEventType::Cancelled => self.counterpart_cancel += 1,
 				BridgeContractEventType::Refunded => self.initiator_refund += 1,
+				BridgeContractEventType::Reorged => (),
 			},
 			_ => (),
 		}
@@ -281,15 +284,21 @@ async fn pool_initiator_contract(
 	.map(|e| {
 		let data: BridgeInitEventData = serde_json::from_str(&e.data.to_string())?;
 		let transfer_details = BridgeTransferDetails::try_from(data)?;
-		Ok((BridgeContractEvent::Initiated(transfer_details), e.sequence_number.into()))
+		Ok((transfer_details, e.sequence_number.into(), e.version.into()))
 	})
-	.collect::<Result<Vec<_>>>()
+	.collect::<Result<Vec<(_, u64, u64)>>>()
 	.map_err(|e| {
 		BridgeContractError::EventDeserializingFail(
 			format!("MVT bridge_transfer_initiated_events de-serialization error:{}", e),
 			BridgeContractEventType::Initiated,
 		)
 	})?;
+	let mut initiated = Vec::with_capacity(initiated_events.len());
+	for (mut transfer_details, sequence_number, version) in initiated_events {
+		transfer_details.initiated_at =
+			get_transaction_timestamp(rest_url, version, timeout_sec).await;
+		initiated.push((BridgeContractEvent::Initiated(transfer_details), sequence_number));
+	}
 
 	// Get completed events
 	let completed_events = get_account_events(
@@ -351,7 +360,7 @@ async fn pool_initiator_contract(
 		)
 	})?;
 
-	let total_events = initiated_events
+	let total_events = initiated
 		.into_iter()
 		.chain(completed_events.into_iter())
 		.chain(refunded_events.into_iter())
@@ -541,6 +550,9 @@ impl TryFrom<BridgeInitEventData> for BridgeTransferDetails<MovementAddress> {
 			time_lock: TimeLock(data.time_lock),
 			amount: Amount(data.amount),
 			state: 0,
+			// The events of the Movement node carry no timestamp,
+			// it is set from the transaction of the event.
+			initiated_at: 0,
 		})
 	}
 }
@@ -667,3 +679,36 @@ async fn get_account_events(
 		)))
 	}
 }
+
+/// Unix timestamp in seconds of the transaction at `version`.
+/// The current time is returned when the transaction can't be fetched.
+async fn get_transaction_timestamp(rest_url: &str, version: u64, timeout_sec: u64) -> u64 {
+	let url = format!("{}/v1/transactions/by_version/{}", rest_url, version);
+	let response = tokio::time::timeout(
+		tokio::time::Duration::from_secs(timeout_sec),
+		reqwest::Client::new().get(&url).send(),
+	)
+	.await;
+	let transaction: Result<serde_json::Value, String> = match response {
+		Ok(Ok(response)) => match response.text().await {
+			Ok(body) => serde_json::from_str(&body).map_err(|e| e.to_string()),
+			Err(e) => Err(e.to_string()),
+		},
+		Ok(Err(e)) => Err(e.to_string()),
+		Err(e) => Err(e.to_string()),
+	};
+	// The timestamps of the REST API are in microseconds.
+	let timestamp = transaction.and_then(|transaction| {
+		transaction["timestamp"]
+			.as_str()
+			.and_then(|timestamp| timestamp.parse::<u64>().ok())
+			.ok_or_else(|| format!("no timestamp in {transaction}"))
+	});
+	match timestamp {
+		Ok(timestamp) => timestamp / 1_000_000,
+		Err(e) => {
+			tracing::warn!("MVT failed to get the timestamp of transaction {version}: {e}");
+			current_timestamp_secs()
+		}
+	}
+}
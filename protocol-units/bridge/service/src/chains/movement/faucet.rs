@@ -0,0 +1,92 @@
+use aptos_sdk::{rest_client::FaucetClient, types::account_address::AccountAddress};
+use bridge_config::common::movement::MovementConfig;
+use std::sync::Arc;
+use tokio::{
+	sync::Mutex,
+	time::{Duration, Instant},
+};
+use url::Url;
+
+/// Spaces the permits evenly so that at most `requests_per_minute` are handed out per minute.
+#[derive(Debug)]
+pub struct RateLimiter {
+	interval: Duration,
+	next_permit: Mutex<Instant>,
+}
+
+impl RateLimiter {
+	/// A limit of 0 requests per minute is handled as 1 request per minute.
+	pub fn per_minute(requests_per_minute: u32) -> Self {
+		RateLimiter {
+			interval: Duration::from_secs(60) / requests_per_minute.max(1),
+			next_permit: Mutex::new(Instant::now()),
+		}
+	}
+
+	/// Waits until the next permit is available.
+	pub async fn acquire(&self) {
+		let mut next_permit = self.next_permit.lock().await;
+		tokio::time::sleep_until(*next_permit).await;
+		*next_permit = Instant::now() + self.interval;
+	}
+}
+
+/// A faucet client waiting for a permit of its rate limiter before each request,
+/// so that bursts of funding don't hit the rate limit of the faucet.
+#[derive(Clone)]
+pub struct RateLimitedFaucetClient {
+	inner: Arc<FaucetClient>,
+	limiter: Arc<RateLimiter>,
+}
+
+impl RateLimitedFaucetClient {
+	pub fn new(faucet_url: Url, rest_url: Url, requests_per_minute: u32) -> Self {
+		RateLimitedFaucetClient {
+			inner: Arc::new(FaucetClient::new(faucet_url, rest_url)),
+			limiter: Arc::new(RateLimiter::per_minute(requests_per_minute)),
+		}
+	}
+
+	/// Builds the client of the faucet of `config`, within its `mvt_faucet_requests_per_minute`.
+	pub fn try_from_config(config: &MovementConfig) -> Result<Self, url::ParseError> {
+		Ok(Self::new(
+			Url::parse(&config.mvt_faucet_connection_url())?,
+			Url::parse(&config.mvt_rpc_connection_url())?,
+			config.mvt_faucet_requests_per_minute,
+		))
+	}
+
+	pub async fn fund(&self, address: AccountAddress, amount: u64) -> Result<(), anyhow::Error> {
+		self.limiter.acquire().await;
+		self.inner.fund(address, amount).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn test_permits_are_spaced() {
+		// One permit every 50ms.
+		let limiter = RateLimiter::per_minute(1200);
+		let start = Instant::now();
+		for _ in 0..3 {
+			limiter.acquire().await;
+		}
+		assert!(start.elapsed() >= Duration::from_millis(100));
+	}
+
+	#[tokio::test]
+	async fn test_permit_is_acquired_before_each_fund() -> Result<(), anyhow::Error> {
+		// Nothing listens on the port, each call fails right after it got its permit.
+		let url = Url::parse("http://127.0.0.1:1")?;
+		let faucet = RateLimitedFaucetClient::new(url.clone(), url, 1200);
+		let start = Instant::now();
+		for _ in 0..3 {
+			assert!(faucet.fund(AccountAddress::ONE, 1).await.is_err());
+		}
+		assert!(start.elapsed() >= Duration::from_millis(100));
+		Ok(())
+	}
+}
@@ -0,0 +1,243 @@
+use aes::cipher::{generic_array::GenericArray, KeyIvInit, StreamCipher};
+use anyhow::{Context, Result};
+use aptos_sdk::crypto::{ed25519::Ed25519PrivateKey, HashValue, ValidCryptoMaterialStringExt};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+use subtle::ConstantTimeEq as _;
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+const KEYSTORE_VERSION: u32 = 1;
+const CIPHER: &str = "aes-128-ctr";
+const KDF: &str = "pbkdf2";
+const PRF: &str = "hmac-sha256";
+/// PBKDF2 rounds of the keystores written by [`write_key`].
+const DEFAULT_ROUNDS: u32 = 262_144;
+const DERIVED_KEY_LEN: usize = 32;
+/// Prefix of the AIP-80 private key strings, older Aptos CLI key files only hold the hex.
+const AIP_80_PREFIX: &str = "ed25519-priv-";
+
+/// A signing key encrypted with a password. The plaintext is the private key encoded as the
+/// Aptos CLI writes it in its key files and `config.yaml`, so a key can be moved between
+/// the CLI and the bridge without converting it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyStore {
+	version: u32,
+	crypto: KeyStoreCrypto,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct KeyStoreCrypto {
+	cipher: String,
+	cipherparams: CipherParams,
+	#[serde(with = "hex")]
+	ciphertext: Vec<u8>,
+	kdf: String,
+	kdfparams: KdfParams,
+	/// SHA3-256 of the second half of the derived key followed by the ciphertext
+	#[serde(with = "hex")]
+	mac: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CipherParams {
+	#[serde(with = "hex")]
+	iv: [u8; 16],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct KdfParams {
+	c: u32,
+	dklen: usize,
+	prf: String,
+	#[serde(with = "hex")]
+	salt: [u8; 32],
+}
+
+/// Reads and decrypts the signing key of the keystore at `path`.
+pub fn read_key(path: &Path, password: &str) -> Result<Ed25519PrivateKey> {
+	let json = fs::read_to_string(path)
+		.with_context(|| format!("Failed to read keystore {}", path.display()))?;
+	let keystore: KeyStore = serde_json::from_str(&json)
+		.with_context(|| format!("Invalid keystore {}", path.display()))?;
+	keystore.decrypt(password)
+}
+
+/// Encrypts `key` with `password` into a new keystore at `path`.
+pub fn write_key<R: RngCore + CryptoRng>(
+	rng: &mut R,
+	path: &Path,
+	key: &Ed25519PrivateKey,
+	password: &str,
+) -> Result<()> {
+	let keystore = KeyStore::encrypt(rng, key, password, DEFAULT_ROUNDS)?;
+	fs::write(path, serde_json::to_string_pretty(&keystore)?)
+		.with_context(|| format!("Failed to write keystore {}", path.display()))
+}
+
+impl KeyStore {
+	fn encrypt<R: RngCore + CryptoRng>(
+		rng: &mut R,
+		key: &Ed25519PrivateKey,
+		password: &str,
+		rounds: u32,
+	) -> Result<Self> {
+		let mut salt = [0u8; 32];
+		rng.fill_bytes(&mut salt);
+		let mut iv = [0u8; 16];
+		rng.fill_bytes(&mut iv);
+
+		let derived_key = derive_key(password, &salt, rounds);
+		let mut ciphertext = key.to_encoded_string()?.into_bytes();
+		apply_cipher(&derived_key, &iv, &mut ciphertext);
+		let mac = mac(&derived_key, &ciphertext);
+
+		Ok(Self {
+			version: KEYSTORE_VERSION,
+			crypto: KeyStoreCrypto {
+				cipher: CIPHER.to_string(),
+				cipherparams: CipherParams { iv },
+				ciphertext,
+				kdf: KDF.to_string(),
+				kdfparams: KdfParams {
+					c: rounds,
+					dklen: DERIVED_KEY_LEN,
+					prf: PRF.to_string(),
+					salt,
+				},
+				mac,
+			},
+		})
+	}
+
+	fn decrypt(&self, password: &str) -> Result<Ed25519PrivateKey> {
+		let crypto = &self.crypto;
+		anyhow::ensure!(
+			self.version == KEYSTORE_VERSION,
+			"Unsupported keystore version {}",
+			self.version
+		);
+		anyhow::ensure!(crypto.cipher == CIPHER, "Unsupported keystore cipher {}", crypto.cipher);
+		anyhow::ensure!(
+			crypto.kdf == KDF && crypto.kdfparams.prf == PRF,
+			"Unsupported keystore key derivation {} {}",
+			crypto.kdf,
+			crypto.kdfparams.prf
+		);
+		anyhow::ensure!(
+			crypto.kdfparams.dklen == DERIVED_KEY_LEN,
+			"Unsupported keystore derived key length {}",
+			crypto.kdfparams.dklen
+		);
+
+		let derived_key = derive_key(password, &crypto.kdfparams.salt, crypto.kdfparams.c);
+		let mac = mac(&derived_key, &crypto.ciphertext);
+		anyhow::ensure!(
+			bool::from(mac.as_slice().ct_eq(crypto.mac.as_slice())),
+			"Wrong keystore password or corrupted keystore"
+		);
+
+		let mut plaintext = crypto.ciphertext.clone();
+		apply_cipher(&derived_key, &crypto.cipherparams.iv, &mut plaintext);
+		let encoded = String::from_utf8(plaintext).context("Keystore key is not a string")?;
+		let encoded = encoded.trim();
+		Ed25519PrivateKey::from_encoded_string(
+			encoded.strip_prefix(AIP_80_PREFIX).unwrap_or(encoded),
+		)
+		.context("Keystore does not contain a valid Ed25519 private key")
+	}
+}
+
+fn derive_key(password: &str, salt: &[u8], rounds: u32) -> [u8; DERIVED_KEY_LEN] {
+	let mut derived_key = [0u8; DERIVED_KEY_LEN];
+	pbkdf2::pbkdf2_hmac::<sha2::Sha256>(password.as_bytes(), salt, rounds, &mut derived_key);
+	derived_key
+}
+
+/// Encrypts or decrypts `data` with the first half of the derived key.
+fn apply_cipher(derived_key: &[u8; DERIVED_KEY_LEN], iv: &[u8; 16], data: &mut [u8]) {
+	Aes128Ctr::new(GenericArray::from_slice(&derived_key[..16]), GenericArray::from_slice(iv))
+		.apply_keystream(data);
+}
+
+fn mac(derived_key: &[u8; DERIVED_KEY_LEN], ciphertext: &[u8]) -> Vec<u8> {
+	HashValue::sha3_256_of(&[&derived_key[16..], ciphertext].concat()).to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::SeedableRng;
+
+	/// Encrypts `0x0101..01` with the password `bridge`, a salt of `0x02` bytes, an IV of
+	/// `0x03` bytes and 1024 rounds.
+	const KEYSTORE: &str = r#"{
+		"version": 1,
+		"crypto": {
+			"cipher": "aes-128-ctr",
+			"cipherparams": { "iv": "03030303030303030303030303030303" },
+			"ciphertext": "06c978bd29479a9ade6828dc8a5b4f5d94ba0df0d412a05e28d98371dda75eb4020fc059a6ec9dcf1d0f31bf6fc2ab3c52ab1672f605d89165315135fc921b35ce4c",
+			"kdf": "pbkdf2",
+			"kdfparams": {
+				"c": 1024,
+				"dklen": 32,
+				"prf": "hmac-sha256",
+				"salt": "0202020202020202020202020202020202020202020202020202020202020202"
+			},
+			"mac": "463bc4cf595b3ba04293c46b4ee968fd2ca33c999ce2c3b1c58cc43a83ef1e06"
+		}
+	}"#;
+
+	fn test_key() -> Ed25519PrivateKey {
+		Ed25519PrivateKey::try_from([1u8; 32].as_slice()).unwrap()
+	}
+
+	#[test]
+	fn test_read_key() -> Result<()> {
+		let dir = tempfile::tempdir()?;
+		let path = dir.path().join("keystore.json");
+		fs::write(&path, KEYSTORE)?;
+
+		assert_eq!(read_key(&path, "bridge")?.to_bytes(), test_key().to_bytes());
+		assert!(read_key(&path, "not the password").is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn test_round_trip() -> Result<()> {
+		let mut rng = rand::rngs::StdRng::from_seed([4u8; 32]);
+		let key = test_key();
+		let keystore = KeyStore::encrypt(&mut rng, &key, "bridge", 1024)?;
+
+		let dir = tempfile::tempdir()?;
+		let path = dir.path().join("keystore.json");
+		fs::write(&path, serde_json::to_string(&keystore)?)?;
+		assert_eq!(read_key(&path, "bridge")?.to_bytes(), key.to_bytes());
+
+		// the salt and IV are random
+		let other = KeyStore::encrypt(&mut rng, &key, "bridge", 1024)?;
+		assert_ne!(other.crypto.ciphertext, keystore.crypto.ciphertext);
+		assert_eq!(other.decrypt("bridge")?.to_bytes(), key.to_bytes());
+		Ok(())
+	}
+
+	#[test]
+	fn test_aip_80_key_is_read() -> Result<()> {
+		let mut keystore = KeyStore::encrypt(
+			&mut rand::rngs::StdRng::from_seed([5u8; 32]),
+			&test_key(),
+			"bridge",
+			1024,
+		)?;
+		let derived_key = derive_key("bridge", &keystore.crypto.kdfparams.salt, 1024);
+		let mut ciphertext =
+			format!("{}{}", AIP_80_PREFIX, test_key().to_encoded_string()?).into_bytes();
+		apply_cipher(&derived_key, &keystore.crypto.cipherparams.iv, &mut ciphertext);
+		keystore.crypto.mac = mac(&derived_key, &ciphertext);
+		keystore.crypto.ciphertext = ciphertext;
+
+		assert_eq!(keystore.decrypt("bridge")?.to_bytes(), test_key().to_bytes());
+		Ok(())
+	}
+}
@@ -1,3 +1,7 @@
 pub mod client_framework;
+pub mod compiled_package;
 pub mod event_monitoring;
+pub mod faucet;
+pub mod keystore;
+pub mod module_cache;
 pub mod utils;
@@ -0,0 +1,93 @@
+use anyhow::{Context, Result};
+use aptos_sdk::types::account_address::AccountAddress;
+use std::{collections::BTreeMap, fs, path::PathBuf};
+use tiny_keccak::{Hasher, Keccak};
+
+const MODULE_CACHE_FILE_NAME: &str = "module_cache.json";
+
+/// Hash of the last package published under each account, stored in a JSON file
+/// so that a package isn't published again when it didn't change.
+#[derive(Debug)]
+pub struct ModuleCache {
+	path: PathBuf,
+	published: BTreeMap<String, String>,
+}
+
+impl ModuleCache {
+	/// Opens the cache stored at `path`, which starts empty if the file doesn't exist.
+	pub fn open(path: PathBuf) -> Result<Self> {
+		let published = if path.exists() {
+			let json = fs::read_to_string(&path)
+				.with_context(|| format!("Failed to read {}", path.display()))?;
+			serde_json::from_str(&json)
+				.with_context(|| format!("Failed to parse {}", path.display()))?
+		} else {
+			BTreeMap::new()
+		};
+		Ok(Self { path, published })
+	}
+
+	/// Opens the cache stored in the bridge folder of the `.movement` directory.
+	pub fn open_default() -> Result<Self> {
+		let dot_movement = dot_movement::DotMovement::try_from_env()
+			.unwrap_or(dot_movement::DotMovement::new(".movement"));
+		Self::open(bridge_config::get_config_path(&dot_movement).join(MODULE_CACHE_FILE_NAME))
+	}
+
+	/// Hash of the package last published under `address`, if any.
+	pub fn get(&self, address: AccountAddress) -> Option<&str> {
+		self.published.get(&address.to_hex_literal()).map(String::as_str)
+	}
+
+	/// Records that the package of hash `hash` has been published under `address`.
+	pub fn record(&mut self, address: AccountAddress, hash: String) -> Result<()> {
+		self.published.insert(address.to_hex_literal(), hash);
+		if let Some(parent) = self.path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+		let json = serde_json::to_string_pretty(&self.published)?;
+		fs::write(&self.path, json)
+			.with_context(|| format!("Failed to write {}", self.path.display()))
+	}
+}
+
+/// Keccak hash of the compiled modules of a package, hex encoded.
+pub fn package_hash(modules: &[Vec<u8>]) -> String {
+	let mut hasher = Keccak::v256();
+	for module in modules {
+		hasher.update(&(module.len() as u64).to_le_bytes());
+		hasher.update(module);
+	}
+	let mut output = [0u8; 32];
+	hasher.finalize(&mut output);
+	hex::encode(output)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_module_cache_persists_records() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("bridge").join(MODULE_CACHE_FILE_NAME);
+		let address = AccountAddress::from_hex_literal("0xcafe").unwrap();
+		let hash = package_hash(&[vec![1, 2, 3]]);
+
+		let mut cache = ModuleCache::open(path.clone()).unwrap();
+		assert_eq!(cache.get(address), None);
+		cache.record(address, hash.clone()).unwrap();
+
+		let reopened = ModuleCache::open(path).unwrap();
+		assert_eq!(reopened.get(address), Some(hash.as_str()));
+		assert_eq!(reopened.get(AccountAddress::ONE), None);
+	}
+
+	#[test]
+	fn test_package_hash_depends_on_module_boundaries() {
+		let hash = package_hash(&[vec![1, 2], vec![3]]);
+		assert_eq!(hash, package_hash(&[vec![1, 2], vec![3]]));
+		assert_ne!(hash, package_hash(&[vec![1], vec![2, 3]]));
+		assert_ne!(hash, package_hash(&[vec![1, 2, 3]]));
+	}
+}
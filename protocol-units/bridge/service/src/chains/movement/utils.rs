@@ -11,13 +11,14 @@ use aptos_sdk::{
 			EntryFunctionId, MoveType, Transaction as AptosTransaction, TransactionInfo,
 			ViewRequest,
 		},
-		Client as RestClient, FaucetClient, Transaction,
+		error::RestError,
+		Client as RestClient, Transaction,
 	},
 	transaction_builder::TransactionFactory,
 	types::{
 		account_address::AccountAddress,
 		chain_id::ChainId,
-		transaction::{EntryFunction, SignedTransaction, TransactionPayload},
+		transaction::{EntryFunction, RawTransaction, SignedTransaction, TransactionPayload},
 		AccountKey, LocalAccount,
 	},
 };
@@ -30,18 +31,17 @@ use rand::{rngs::StdRng, Rng, RngCore, SeedableRng};
 use rand_chacha::ChaChaRng;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::str::FromStr;
+use std::{future::Future, str::FromStr, time::Duration};
 use thiserror::Error;
 use tiny_keccak::{Hasher, Keccak};
-use tracing::log::{error, info};
-use url::Url;
+use tracing::log::{error, info, warn};
 
-use super::client_framework::MovementClientFramework;
+use super::{
+	client_framework::{MovementClientFramework, MOVEMENT_BRIDGE_CHAIN},
+	faucet::RateLimitedFaucetClient,
+};
 pub type TestRng = StdRng;
 
-const MOVEMENT_RPC_URL: &str = "https://testnet.bardock.movementnetwork.xyz";
-const MOVEMENT_FAUCET_URL: &str = "https://faucet.testnet.bardock.movementnetwork.xyz";
-
 pub trait RngSeededClone: Rng + SeedableRng {
 	fn seeded_clone(&mut self) -> Self;
 }
@@ -164,44 +164,163 @@ pub struct Indexed<T> {
 	pub sequence: Option<u32>,
 }
 
-/// Send Aptos Transaction
+/// Maps a rest client error to `ChainUnavailable` when the node can't be reached or answers 503,
+/// otherwise the error message is passed to `on_other`.
+pub fn map_rest_error(
+	err: RestError,
+	on_other: impl FnOnce(String) -> BridgeContractError,
+) -> BridgeContractError {
+	let unavailable = match &err {
+		RestError::Http(status, _) => status.as_u16() == 503,
+		RestError::Api(response) => response.status_code.as_u16() == 503,
+		RestError::Timeout(_) => true,
+		_ => false,
+	};
+	if unavailable {
+		BridgeContractError::chain_unavailable(MOVEMENT_BRIDGE_CHAIN, err)
+	} else {
+		on_other(err.to_string())
+	}
+}
+
+/// Send Aptos Transaction, failing with `TransactionTimeout` if it is not confirmed
+/// within `confirm_timeout_secs`.
 pub async fn send_and_confirm_aptos_transaction(
 	rest_client: &RestClient,
 	signer: &LocalAccount,
 	payload: TransactionPayload,
-) -> Result<AptosTransaction, String> {
+	confirm_timeout_secs: u64,
+) -> Result<AptosTransaction, BridgeContractError> {
 	info!("Starting send_aptos_transaction");
+	let signed_tx = sign_with_latest_sequence_number(rest_client, signer, payload).await?;
+	rest_client.submit(&signed_tx).await.map_err(map_transaction_error)?;
+	confirm_aptos_transaction(rest_client, &signed_tx, confirm_timeout_secs).await
+}
+
+/// Delay before a transaction rejected for its sequence number is rebuilt and resubmitted.
+pub const SEQUENCE_NUMBER_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Same as [`send_and_confirm_aptos_transaction`], but when the submission is rejected
+/// because of a stale sequence number, the transaction is rebuilt with the latest sequence
+/// number of the signer and resubmitted, up to `max_retries` times.
+pub async fn send_and_confirm_aptos_transaction_with_retry(
+	rest_client: &RestClient,
+	signer: &LocalAccount,
+	payload: TransactionPayload,
+	confirm_timeout_secs: u64,
+	max_retries: u32,
+) -> Result<AptosTransaction, BridgeContractError> {
+	info!("Starting send_aptos_transaction with up to {} retries", max_retries);
+	let signed_tx =
+		retry_on_sequence_number_error(max_retries, SEQUENCE_NUMBER_RETRY_DELAY, || {
+			let payload = payload.clone();
+			async move {
+				let signed_tx =
+					sign_with_latest_sequence_number(rest_client, signer, payload).await?;
+				rest_client.submit(&signed_tx).await.map_err(map_transaction_error)?;
+				Ok(signed_tx)
+			}
+		})
+		.await?;
+	confirm_aptos_transaction(rest_client, &signed_tx, confirm_timeout_secs).await
+}
+
+/// Whether the transaction was rejected because its sequence number is behind or ahead of
+/// the one of the sender account.
+fn is_sequence_number_error(err: &BridgeContractError) -> bool {
+	let message = err.to_string();
+	message.contains("SEQUENCE_NUMBER_TOO_OLD") || message.contains("SEQUENCE_NUMBER_TOO_NEW")
+}
+
+async fn retry_on_sequence_number_error<T, F, Fut>(
+	max_retries: u32,
+	delay: Duration,
+	mut attempt: F,
+) -> Result<T, BridgeContractError>
+where
+	F: FnMut() -> Fut,
+	Fut: Future<Output = Result<T, BridgeContractError>>,
+{
+	let mut retries = 0;
+	loop {
+		match attempt().await {
+			Err(err) if retries < max_retries && is_sequence_number_error(&err) => {
+				retries += 1;
+				warn!("Sequence number rejected, retrying ({}/{}): {}", retries, max_retries, err);
+				tokio::time::sleep(delay).await;
+			}
+			result => return result,
+		}
+	}
+}
+
+/// Builds the transaction of `payload` with the latest sequence number of `signer`,
+/// and signs it.
+async fn sign_with_latest_sequence_number(
+	rest_client: &RestClient,
+	signer: &LocalAccount,
+	payload: TransactionPayload,
+) -> Result<SignedTransaction, BridgeContractError> {
+	let raw_tx = build_with_latest_sequence_number(rest_client, signer, payload).await?;
+	Ok(signer.sign_transaction(raw_tx))
+}
+
+/// Builds the transaction of `payload` sent by `signer` with its latest sequence number.
+async fn build_with_latest_sequence_number(
+	rest_client: &RestClient,
+	signer: &LocalAccount,
+	payload: TransactionPayload,
+) -> Result<RawTransaction, BridgeContractError> {
 	let state = rest_client
 		.get_ledger_information()
 		.await
-		.map_err(|e| format!("Failed in getting chain id: {}", e))?
+		.map_err(|e| {
+			map_rest_error(e, |e| {
+				BridgeContractError::OnChainError(format!("Failed in getting chain id: {}", e))
+			})
+		})?
 		.into_inner();
 
 	let transaction_factory = TransactionFactory::new(ChainId::new(state.chain_id))
-		.with_gas_unit_price(100)
+		.with_gas_unit_price(GAS_UNIT_PRICE)
 		.with_max_gas_amount(GAS_UNIT_LIMIT);
-	let latest_account_info = rest_client
-		.get_account(signer.address())
-		.await
-		.map_err(|e| format!("Failed to get account information: {}", e))?;
+	let latest_account_info = rest_client.get_account(signer.address()).await.map_err(|e| {
+		map_rest_error(e, |e| {
+			BridgeContractError::OnChainError(format!("Failed to get account information: {}", e))
+		})
+	})?;
 	let account = latest_account_info.into_inner();
 	let latest_sequence_number = account.sequence_number;
 
-	let raw_tx = transaction_factory
+	Ok(transaction_factory
 		.payload(payload)
 		.sender(signer.address())
 		.sequence_number(latest_sequence_number)
-		.build();
-
-	let signed_tx = signer.sign_transaction(raw_tx);
+		.build())
+}
 
-	//info!("Signed TX: {:?}", signed_tx);
+fn map_transaction_error(e: RestError) -> BridgeContractError {
+	let err_msg = format!("Transaction submission error: {}", e.to_string());
+	error!("Full error: {}", err_msg); // Log the error in detail
+	map_rest_error(e, |_| BridgeContractError::OnChainError(err_msg))
+}
 
-	let response = rest_client.submit_and_wait(&signed_tx).await.map_err(|e| {
-		let err_msg = format!("Transaction submission error: {}", e.to_string());
-		error!("Full error: {}", err_msg); // Log the error in detail
-		err_msg
-	})?;
+/// Waits for the submitted `signed_tx` to be committed, and checks that it succeeded.
+async fn confirm_aptos_transaction(
+	rest_client: &RestClient,
+	signed_tx: &SignedTransaction,
+	confirm_timeout_secs: u64,
+) -> Result<AptosTransaction, BridgeContractError> {
+	let response = tokio::time::timeout(
+		tokio::time::Duration::from_secs(confirm_timeout_secs),
+		rest_client.wait_for_signed_transaction(signed_tx),
+	)
+	.await
+	.map_err(|_| BridgeContractError::TransactionTimeout {
+		hash: signed_tx.committed_hash(),
+		elapsed_secs: confirm_timeout_secs,
+	})?
+	.map_err(map_transaction_error)?;
 
 	let txn = response.into_inner();
 	//info!("Response: {:?}", txn);
@@ -209,13 +328,16 @@ pub async fn send_and_confirm_aptos_transaction(
 	match &txn {
 		Transaction::UserTransaction(user_txn) => {
 			if !user_txn.info.success {
-				return Err(format!("Transaction failed with status: {}", user_txn.info.vm_status));
+				return Err(BridgeContractError::OnChainError(format!(
+					"Transaction failed with status: {}",
+					user_txn.info.vm_status
+				)));
 			}
 		}
 		_ => {
-			return Err(
-				"Expected a UserTransaction, but got a different transaction type.".to_string()
-			)
+			return Err(BridgeContractError::OnChainError(
+				"Expected a UserTransaction, but got a different transaction type.".to_string(),
+			))
 		}
 	}
 
@@ -294,42 +416,61 @@ pub fn serialize_vec_initiator<T: serde::Serialize + ?Sized>(
 	bcs::to_bytes(value).map_err(|_| BridgeContractError::SerializationError)
 }
 
-pub async fn simulate_aptos_transaction(
-	aptos_client: &MovementClientFramework,
-	signer: &mut LocalAccount,
-	payload: TransactionPayload,
-) -> Result<TransactionInfo> {
-	let state = aptos_client
-		.rest_client
-		.get_ledger_information()
-		.await
-		.context("Failed in getting chain id")?
-		.into_inner();
-
-	let transaction_factory = TransactionFactory::new(ChainId::new(state.chain_id))
-		.with_gas_unit_price(GAS_UNIT_PRICE)
-		.with_max_gas_amount(GAS_UNIT_LIMIT);
-
-	let latest_account_info = aptos_client.rest_client.get_account(signer.address()).await?;
-	let account = latest_account_info.into_inner();
-	let latest_sequence_number = account.sequence_number;
+/// Outcome of a transaction dry run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulationResult {
+	pub gas_used: u64,
+	pub success: bool,
+	pub vm_status: String,
+}
 
-	let raw_tx = transaction_factory
-		.payload(payload)
-		.sender(signer.address())
-		.sequence_number(latest_sequence_number)
-		.build();
+impl SimulationResult {
+	/// Fails with `SimulationFailed` when the simulated transaction would fail on chain.
+	pub fn ensure_success(self) -> Result<Self, BridgeContractError> {
+		if self.success {
+			Ok(self)
+		} else {
+			Err(BridgeContractError::SimulationFailed(self.vm_status))
+		}
+	}
+}
 
-	let signed_tx = SignedTransaction::new(
-		raw_tx,
-		signer.public_key().clone(),
-		Ed25519Signature::try_from([0u8; 64].as_ref())?,
-	);
+impl From<&TransactionInfo> for SimulationResult {
+	fn from(info: &TransactionInfo) -> Self {
+		SimulationResult {
+			gas_used: info.gas_used.0,
+			success: info.success,
+			vm_status: info.vm_status.clone(),
+		}
+	}
+}
 
-	let response_txns = aptos_client.rest_client.simulate(&signed_tx).await?.into_inner();
-	let response = response_txns[0].clone();
+/// Dry runs the transaction of `payload` sent by `signer` with the `/transactions/simulate`
+/// endpoint. The transaction is built like a submitted one, but signed with an invalid
+/// signature so it can't be submitted.
+pub async fn simulate_transaction(
+	rest_client: &RestClient,
+	signer: &LocalAccount,
+	payload: TransactionPayload,
+) -> Result<SimulationResult, BridgeContractError> {
+	let raw_tx = build_with_latest_sequence_number(rest_client, signer, payload).await?;
+	let signature = Ed25519Signature::try_from([0u8; 64].as_ref())
+		.map_err(|_| BridgeContractError::SerializationError)?;
+	let signed_tx = SignedTransaction::new(raw_tx, signer.public_key().clone(), signature);
 
-	Ok(response.info)
+	let response_txns = rest_client
+		.simulate(&signed_tx)
+		.await
+		.map_err(|e| {
+			map_rest_error(e, |e| {
+				BridgeContractError::OnChainError(format!("Failed to simulate transaction: {}", e))
+			})
+		})?
+		.into_inner();
+	let response = response_txns.first().ok_or_else(|| {
+		BridgeContractError::OnChainError("Empty simulation response".to_string())
+	})?;
+	Ok(SimulationResult::from(&response.info))
 }
 
 /// Make Aptos Transaction Payload
@@ -373,6 +514,26 @@ pub async fn send_view_request(
 	Ok(view_response.inner().clone())
 }
 
+/// Polls the node ledger information every 2 seconds until it answers or `timeout_secs` elapse.
+pub async fn wait_for_node_ready(client: &RestClient, timeout_secs: u64) -> Result<()> {
+	let poll_interval = tokio::time::Duration::from_secs(2);
+	let deadline = tokio::time::Instant::now() + tokio::time::Duration::from_secs(timeout_secs);
+	loop {
+		match client.get_ledger_information().await {
+			Ok(_) => return Ok(()),
+			Err(err) if tokio::time::Instant::now() + poll_interval < deadline => {
+				info!("Movement node not ready yet, retrying: {}", err);
+				tokio::time::sleep(poll_interval).await;
+			}
+			Err(err) => {
+				return Err(err).with_context(|| {
+					format!("Movement node not ready after {} seconds", timeout_secs)
+				});
+			}
+		}
+	}
+}
+
 pub async fn create_local_account(
 	private_key: Ed25519PrivateKey,
 	client: &RestClient,
@@ -425,15 +586,12 @@ pub fn to_eip55(address: &str) -> String {
 		.collect()
 }
 
-pub async fn fund_recipient(recipient: &BridgeAddress<Vec<u8>>) -> Result<(), BridgeContractError> {
-	// Parse URLs
-	let faucet_url =
-		Url::parse(MOVEMENT_FAUCET_URL).map_err(|_| BridgeContractError::InvalidUrl)?;
-	let rest_url = Url::parse(MOVEMENT_RPC_URL).map_err(|_| BridgeContractError::InvalidUrl)?;
-
-	// Create clients
-	let faucet_client = FaucetClient::new(faucet_url, rest_url);
-
+/// Funds the Movement account of `recipient` with `faucet_client`, so that it can pay
+/// the gas of its completion.
+pub async fn fund_recipient(
+	faucet_client: &RateLimitedFaucetClient,
+	recipient: &BridgeAddress<Vec<u8>>,
+) -> Result<(), BridgeContractError> {
 	// Convert recipient to AccountAddress
 	let recipient: [u8; 32] = recipient
 		.0
@@ -450,3 +608,62 @@ pub async fn fund_recipient(recipient: &BridgeAddress<Vec<u8>>) -> Result<(), Br
 
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::atomic::{AtomicU32, Ordering};
+
+	const TEST_DELAY: Duration = Duration::from_millis(1);
+
+	fn sequence_number_error(status: &str) -> BridgeContractError {
+		BridgeContractError::OnChainError(format!(
+			"Transaction submission error: API error Error(VmError): Invalid transaction: Type: Validation Code: {}",
+			status
+		))
+	}
+
+	#[tokio::test]
+	async fn test_retry_resubmits_after_sequence_number_error() {
+		for status in ["SEQUENCE_NUMBER_TOO_OLD", "SEQUENCE_NUMBER_TOO_NEW"] {
+			let attempts = AtomicU32::new(0);
+			let result = retry_on_sequence_number_error(3, TEST_DELAY, || async {
+				if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+					Err(sequence_number_error(status))
+				} else {
+					Ok("committed")
+				}
+			})
+			.await;
+
+			assert_eq!(result.unwrap(), "committed");
+			assert_eq!(attempts.load(Ordering::SeqCst), 2);
+		}
+	}
+
+	#[tokio::test]
+	async fn test_retry_gives_up_after_max_retries() {
+		let attempts = AtomicU32::new(0);
+		let result: Result<(), _> = retry_on_sequence_number_error(2, TEST_DELAY, || async {
+			attempts.fetch_add(1, Ordering::SeqCst);
+			Err(sequence_number_error("SEQUENCE_NUMBER_TOO_OLD"))
+		})
+		.await;
+
+		assert!(is_sequence_number_error(&result.unwrap_err()));
+		assert_eq!(attempts.load(Ordering::SeqCst), 3);
+	}
+
+	#[tokio::test]
+	async fn test_retry_does_not_retry_other_errors() {
+		let attempts = AtomicU32::new(0);
+		let result: Result<(), _> = retry_on_sequence_number_error(3, TEST_DELAY, || async {
+			attempts.fetch_add(1, Ordering::SeqCst);
+			Err(BridgeContractError::OnChainError("INSUFFICIENT_BALANCE".to_string()))
+		})
+		.await;
+
+		assert!(!is_sequence_number_error(&result.unwrap_err()));
+		assert_eq!(attempts.load(Ordering::SeqCst), 1);
+	}
+}
@@ -0,0 +1,249 @@
+use std::{
+	sync::{
+		atomic::{AtomicU32, Ordering},
+		Arc, Mutex,
+	},
+	time::{Duration, Instant},
+};
+use tokio::sync::Notify;
+
+/// When a [`CircuitBreaker`] opens and how long it stays open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+	/// Number of consecutive action failures opening the breaker.
+	pub failure_threshold: u32,
+	/// Seconds during which the actions are rejected before a probe is attempted.
+	pub cooldown_secs: u64,
+}
+
+impl Default for CircuitBreakerConfig {
+	fn default() -> Self {
+		CircuitBreakerConfig { failure_threshold: 5, cooldown_secs: 30 }
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitBreakerState {
+	/// Actions are executed.
+	Closed,
+	/// Actions are rejected since the given instant.
+	Open(Instant),
+	/// One probe action is being executed, the others are rejected until it completes.
+	HalfOpen,
+}
+
+/// Stops executing the actions of a chain after consecutive failures, so that the relayer
+/// doesn't spin on a chain that is unavailable.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+	config: CircuitBreakerConfig,
+	failures: Arc<AtomicU32>,
+	state: Arc<Mutex<CircuitBreakerState>>,
+	/// Wakes up the actions deferred by [`Self::acquire`] when the state changes.
+	changed: Arc<Notify>,
+}
+
+impl CircuitBreaker {
+	pub fn new(config: CircuitBreakerConfig) -> Self {
+		CircuitBreaker {
+			config,
+			failures: Arc::new(AtomicU32::new(0)),
+			state: Arc::new(Mutex::new(CircuitBreakerState::Closed)),
+			changed: Arc::new(Notify::new()),
+		}
+	}
+
+	pub fn state(&self) -> CircuitBreakerState {
+		*self.state.lock().expect("circuit breaker lock poisoned")
+	}
+
+	/// Returns whether an action can be executed.
+	/// Once the cooldown has elapsed, the first caller gets to execute the probe action.
+	pub fn try_acquire(&self) -> bool {
+		let mut state = self.state.lock().expect("circuit breaker lock poisoned");
+		match *state {
+			CircuitBreakerState::Closed => true,
+			CircuitBreakerState::Open(opened_at)
+				if opened_at.elapsed() >= Duration::from_secs(self.config.cooldown_secs) =>
+			{
+				tracing::info!("Circuit breaker half open, probing with one action");
+				*state = CircuitBreakerState::HalfOpen;
+				true
+			}
+			CircuitBreakerState::Open(_) | CircuitBreakerState::HalfOpen => false,
+		}
+	}
+
+	/// Waits until an action can be executed, see [`Self::try_acquire`].
+	/// While the breaker is open the actions are deferred rather than failed,
+	/// they are executed once the chain is available again.
+	pub async fn acquire(&self) {
+		loop {
+			let changed = self.changed.notified();
+			tokio::pin!(changed);
+			// registered before the state is checked, so that no change is missed
+			changed.as_mut().enable();
+			if self.try_acquire() {
+				return;
+			}
+			match self.state() {
+				CircuitBreakerState::Open(opened_at) => {
+					let cooldown = Duration::from_secs(self.config.cooldown_secs)
+						.saturating_sub(opened_at.elapsed());
+					tokio::select! {
+						_ = tokio::time::sleep(cooldown) => {}
+						_ = changed => {}
+					}
+				}
+				// Wait for the probe action to complete.
+				CircuitBreakerState::HalfOpen => changed.await,
+				CircuitBreakerState::Closed => {}
+			}
+		}
+	}
+
+	pub fn record_success(&self) {
+		let mut state = self.state.lock().expect("circuit breaker lock poisoned");
+		self.failures.store(0, Ordering::SeqCst);
+		if *state != CircuitBreakerState::Closed {
+			tracing::info!("Circuit breaker closed");
+			*state = CircuitBreakerState::Closed;
+		}
+		self.changed.notify_waiters();
+	}
+
+	/// Records an action that failed for a reason unrelated to the availability of the chain,
+	/// like an invalid pre-image: the count of consecutive failures is left unchanged.
+	/// A probe action is given back, so that the next action probes the chain.
+	pub fn record_ignored(&self) {
+		let mut state = self.state.lock().expect("circuit breaker lock poisoned");
+		if *state == CircuitBreakerState::HalfOpen {
+			// opened a cooldown ago, so that the next action is let through
+			let cooldown = Duration::from_secs(self.config.cooldown_secs);
+			let opened_at = Instant::now().checked_sub(cooldown).unwrap_or_else(Instant::now);
+			*state = CircuitBreakerState::Open(opened_at);
+		}
+		self.changed.notify_waiters();
+	}
+
+	pub fn record_failure(&self) {
+		let mut state = self.state.lock().expect("circuit breaker lock poisoned");
+		let failures = self.failures.fetch_add(1, Ordering::SeqCst).saturating_add(1);
+		let open = match *state {
+			CircuitBreakerState::Closed => failures >= self.config.failure_threshold,
+			// The probe failed, wait for another cooldown.
+			CircuitBreakerState::HalfOpen => true,
+			CircuitBreakerState::Open(_) => false,
+		};
+		if open {
+			tracing::warn!(
+				"Circuit breaker open after {failures} consecutive failures, rejecting actions for {}s",
+				self.config.cooldown_secs
+			);
+			*state = CircuitBreakerState::Open(Instant::now());
+		}
+		self.changed.notify_waiters();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn circuit_breaker(cooldown_secs: u64) -> CircuitBreaker {
+		CircuitBreaker::new(CircuitBreakerConfig { failure_threshold: 3, cooldown_secs })
+	}
+
+	#[test]
+	fn test_opens_after_consecutive_failures() {
+		let breaker = circuit_breaker(60);
+		breaker.record_failure();
+		breaker.record_failure();
+		// A success resets the count of consecutive failures.
+		breaker.record_success();
+		breaker.record_failure();
+		breaker.record_failure();
+		assert_eq!(breaker.state(), CircuitBreakerState::Closed);
+		assert!(breaker.try_acquire());
+
+		breaker.record_failure();
+		assert!(matches!(breaker.state(), CircuitBreakerState::Open(_)));
+		assert!(!breaker.try_acquire());
+	}
+
+	#[test]
+	fn test_probe_success_closes() {
+		let breaker = circuit_breaker(0);
+		for _ in 0..3 {
+			breaker.record_failure();
+		}
+		assert!(matches!(breaker.state(), CircuitBreakerState::Open(_)));
+
+		// Only one probe is allowed once the cooldown elapsed.
+		assert!(breaker.try_acquire());
+		assert_eq!(breaker.state(), CircuitBreakerState::HalfOpen);
+		assert!(!breaker.try_acquire());
+
+		breaker.record_success();
+		assert_eq!(breaker.state(), CircuitBreakerState::Closed);
+		assert!(breaker.try_acquire());
+	}
+
+	#[test]
+	fn test_probe_failure_reopens() {
+		let breaker = circuit_breaker(0);
+		for _ in 0..3 {
+			breaker.record_failure();
+		}
+		assert!(breaker.try_acquire());
+		breaker.record_failure();
+		assert!(matches!(breaker.state(), CircuitBreakerState::Open(_)));
+	}
+
+	#[test]
+	fn test_ignored_failures_are_not_counted() {
+		let breaker = circuit_breaker(60);
+		breaker.record_failure();
+		breaker.record_failure();
+		breaker.record_ignored();
+		assert_eq!(breaker.state(), CircuitBreakerState::Closed);
+
+		// The ignored failure didn't reset the count either.
+		breaker.record_failure();
+		assert!(matches!(breaker.state(), CircuitBreakerState::Open(_)));
+	}
+
+	#[test]
+	fn test_ignored_probe_lets_another_action_probe() {
+		let breaker = circuit_breaker(60 * 60);
+		{
+			let mut state = breaker.state.lock().unwrap();
+			*state = CircuitBreakerState::HalfOpen;
+		}
+		assert!(!breaker.try_acquire());
+
+		breaker.record_ignored();
+		assert!(breaker.try_acquire());
+		assert_eq!(breaker.state(), CircuitBreakerState::HalfOpen);
+	}
+
+	#[tokio::test]
+	async fn test_acquire_waits_for_the_probe() {
+		let breaker = circuit_breaker(0);
+		for _ in 0..3 {
+			breaker.record_failure();
+		}
+		// This caller executes the probe.
+		assert!(breaker.try_acquire());
+
+		let deferred = tokio::spawn({
+			let breaker = breaker.clone();
+			async move { breaker.acquire().await }
+		});
+		tokio::time::sleep(Duration::from_millis(50)).await;
+		assert!(!deferred.is_finished());
+
+		breaker.record_success();
+		tokio::time::timeout(Duration::from_secs(5), deferred).await.unwrap().unwrap();
+	}
+}
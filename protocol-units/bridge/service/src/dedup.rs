@@ -0,0 +1,181 @@
+use bridge_util::{
+	chains::bridge_contracts::BridgeContractEvent,
+	events::TransferEvent,
+	transfer_state::BridgeTransferState,
+	types::{BridgeTransferId, ChainId},
+};
+use lru::LruCache;
+use std::num::NonZeroUsize;
+
+/// Number of transfers whose last event state is remembered by default.
+pub const DEFAULT_DEDUPLICATOR_CAPACITY: usize = 10_000;
+
+/// Remembers the state reported by the last event of the recent transfers on each chain,
+/// to drop the events received twice, e.g. when a monitor replays its last blocks.
+///
+/// The state is kept per chain: both chains report a completion, which are two distinct events.
+pub struct TransferEventDeduplicator(LruCache<(BridgeTransferId, ChainId), BridgeTransferState>);
+
+impl TransferEventDeduplicator {
+	pub fn new(capacity: NonZeroUsize) -> Self {
+		Self(LruCache::new(capacity))
+	}
+
+	/// Whether an event reporting the same state for the same transfer was already recorded.
+	pub fn is_duplicate<A>(&self, event: &TransferEvent<A>) -> bool {
+		let id = event.contract_event.bridge_transfer_id();
+		match (event_state(&event.contract_event), self.0.peek(&(id, event.chain))) {
+			(Some(state), Some(recorded)) => state == *recorded,
+			_ => false,
+		}
+	}
+
+	/// Records the state reported by `event`. A reorged event is forgotten, so that it is
+	/// accepted again when it is included in the new chain.
+	pub fn record<A>(&mut self, event: &TransferEvent<A>) {
+		let key = (event.contract_event.bridge_transfer_id(), event.chain);
+		match event_state(&event.contract_event) {
+			Some(state) => {
+				self.0.put(key, state);
+			}
+			None => {
+				self.0.pop(&key);
+			}
+		}
+	}
+}
+
+impl Default for TransferEventDeduplicator {
+	fn default() -> Self {
+		Self::new(NonZeroUsize::new(DEFAULT_DEDUPLICATOR_CAPACITY).expect("capacity is not zero"))
+	}
+}
+
+/// State of the transfer on the chain emitting `event`, once the event is emitted.
+fn event_state<A>(event: &BridgeContractEvent<A>) -> Option<BridgeTransferState> {
+	match event {
+		BridgeContractEvent::Initiated(_) => Some(BridgeTransferState::Initiated),
+		BridgeContractEvent::Locked(_) => Some(BridgeTransferState::Locked),
+		BridgeContractEvent::InitiatorCompleted(_)
+		| BridgeContractEvent::CounterPartyCompleted(..) => Some(BridgeTransferState::Completed),
+		BridgeContractEvent::Refunded(_) => Some(BridgeTransferState::Refunded),
+		BridgeContractEvent::Cancelled(_) => Some(BridgeTransferState::Expired),
+		BridgeContractEvent::Reorged(_) => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bridge_util::types::{
+		Amount, BridgeAddress, BridgeTransferDetails, HashLock, HashLockPreImage, LockDetails,
+		TimeLock,
+	};
+
+	const ID: BridgeTransferId = BridgeTransferId([1; 32]);
+
+	fn event(
+		chain: ChainId,
+		contract_event: BridgeContractEvent<Vec<u8>>,
+	) -> TransferEvent<Vec<u8>> {
+		TransferEvent { chain, contract_event }
+	}
+
+	fn initiated() -> BridgeContractEvent<Vec<u8>> {
+		BridgeContractEvent::Initiated(BridgeTransferDetails {
+			bridge_transfer_id: ID,
+			initiator: BridgeAddress(vec![1]),
+			recipient: BridgeAddress(vec![2]),
+			hash_lock: HashLock([3; 32]),
+			time_lock: TimeLock(60),
+			amount: Amount(1),
+			state: 0,
+			initiated_at: 0,
+		})
+	}
+
+	fn locked() -> BridgeContractEvent<Vec<u8>> {
+		BridgeContractEvent::Locked(LockDetails {
+			bridge_transfer_id: ID,
+			initiator: BridgeAddress(vec![1]),
+			recipient: BridgeAddress(vec![2]),
+			hash_lock: HashLock([3; 32]),
+			time_lock: TimeLock(60),
+			amount: Amount(1),
+		})
+	}
+
+	/// Records `event` if it isn't a duplicate, and returns whether it was accepted.
+	fn accept(dedup: &mut TransferEventDeduplicator, event: &TransferEvent<Vec<u8>>) -> bool {
+		if dedup.is_duplicate(event) {
+			return false;
+		}
+		dedup.record(event);
+		true
+	}
+
+	#[test]
+	fn test_deduplicates_initiator_events() {
+		let mut dedup = TransferEventDeduplicator::default();
+		let initiated = event(ChainId::ONE, initiated());
+		let completed = event(ChainId::ONE, BridgeContractEvent::InitiatorCompleted(ID));
+
+		assert!(accept(&mut dedup, &initiated));
+		assert!(!accept(&mut dedup, &initiated));
+		assert!(accept(&mut dedup, &completed));
+		assert!(!accept(&mut dedup, &completed));
+	}
+
+	#[test]
+	fn test_deduplicates_counterparty_events() {
+		let mut dedup = TransferEventDeduplicator::default();
+		let locked = event(ChainId::TWO, locked());
+		let completed = event(
+			ChainId::TWO,
+			BridgeContractEvent::CounterPartyCompleted(ID, HashLockPreImage([4; 32])),
+		);
+
+		assert!(accept(&mut dedup, &locked));
+		assert!(!accept(&mut dedup, &locked));
+		assert!(accept(&mut dedup, &completed));
+		assert!(!accept(&mut dedup, &completed));
+	}
+
+	#[test]
+	fn test_completions_on_both_chains_are_not_duplicates() {
+		let mut dedup = TransferEventDeduplicator::default();
+
+		assert!(accept(
+			&mut dedup,
+			&event(
+				ChainId::TWO,
+				BridgeContractEvent::CounterPartyCompleted(ID, HashLockPreImage([4; 32]))
+			)
+		));
+		assert!(accept(
+			&mut dedup,
+			&event(ChainId::ONE, BridgeContractEvent::InitiatorCompleted(ID))
+		));
+	}
+
+	#[test]
+	fn test_reorged_event_is_accepted_again() {
+		let mut dedup = TransferEventDeduplicator::default();
+		let locked = event(ChainId::TWO, locked());
+
+		assert!(accept(&mut dedup, &locked));
+		assert!(accept(&mut dedup, &event(ChainId::TWO, BridgeContractEvent::Reorged(ID))));
+		assert!(accept(&mut dedup, &locked));
+	}
+
+	#[test]
+	fn test_evicts_least_recently_recorded_transfer() {
+		let mut dedup = TransferEventDeduplicator::new(NonZeroUsize::new(1).unwrap());
+		let first = event(ChainId::ONE, BridgeContractEvent::Refunded(ID));
+		let second = event(ChainId::ONE, BridgeContractEvent::Refunded(BridgeTransferId([2; 32])));
+
+		assert!(accept(&mut dedup, &first));
+		assert!(accept(&mut dedup, &second));
+		assert!(accept(&mut dedup, &first));
+	}
+}
@@ -0,0 +1,119 @@
+use anyhow::Context;
+use bridge_util::chains::bridge_contracts::BridgeContractEvent;
+use bridge_util::types::{current_timestamp_secs, ChainId};
+use serde::{Deserialize, Serialize};
+use std::{
+	fs::{File, OpenOptions},
+	io::{BufWriter, Write},
+	path::Path,
+};
+
+/// File of the event log, relative to the bridge config folder.
+pub const EVENT_LOG_FILE: &str = "bridge_events.log";
+
+/// One line of the audit log, `E` being a `BridgeContractEvent` or a reference to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoggedEvent<E> {
+	/// Unix timestamp in seconds at which the event was logged.
+	pub timestamp: u64,
+	pub chain: ChainId,
+	pub event: E,
+}
+
+/// Appends the bridge contract events to a file, one JSON object per line,
+/// so that they can be audited or replayed.
+pub struct EventLogger {
+	writer: BufWriter<File>,
+}
+
+impl EventLogger {
+	/// Opens (or creates) the log file at `path`. Events are appended to the existing ones.
+	pub fn open(path: impl AsRef<Path>) -> Result<Self, anyhow::Error> {
+		let path = path.as_ref();
+		let file = OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(path)
+			.with_context(|| format!("Failed to open event log at {}", path.display()))?;
+		Ok(EventLogger { writer: BufWriter::new(file) })
+	}
+
+	pub fn log<A: Serialize>(
+		&mut self,
+		chain: ChainId,
+		event: &BridgeContractEvent<A>,
+	) -> Result<(), anyhow::Error> {
+		let logged = LoggedEvent { timestamp: current_timestamp_secs(), chain, event };
+		serde_json::to_writer(&mut self.writer, &logged)?;
+		self.writer.write_all(b"\n")?;
+		Ok(())
+	}
+
+	pub fn flush(&mut self) -> Result<(), anyhow::Error> {
+		self.writer.flush()?;
+		Ok(())
+	}
+}
+
+impl Drop for EventLogger {
+	fn drop(&mut self) {
+		if let Err(err) = self.writer.flush() {
+			tracing::warn!("Failed to flush event log: {err}");
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bridge_util::types::{BridgeTransferId, HashLockPreImage};
+
+	fn read_events(path: &Path) -> Vec<LoggedEvent<BridgeContractEvent<Vec<u8>>>> {
+		std::fs::read_to_string(path)
+			.unwrap()
+			.lines()
+			.map(|line| serde_json::from_str(line).unwrap())
+			.collect()
+	}
+
+	#[test]
+	fn test_events_are_flushed_on_drop() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("events.log");
+		let completed = BridgeContractEvent::<Vec<u8>>::CounterPartyCompleted(
+			BridgeTransferId([1; 32]),
+			HashLockPreImage([2; 32]),
+		);
+		{
+			let mut logger = EventLogger::open(&path).unwrap();
+			logger.log(ChainId::ONE, &completed).unwrap();
+			logger
+				.log(ChainId::TWO, &BridgeContractEvent::Refunded(BridgeTransferId([3; 32])))
+				.unwrap();
+		}
+		let events = read_events(&path);
+		assert_eq!(events.len(), 2);
+		assert_eq!(events[0].chain, ChainId::ONE);
+		assert_eq!(events[0].event, completed);
+		assert_eq!(events[1].event, BridgeContractEvent::Refunded(BridgeTransferId([3; 32])));
+		assert!(events[0].timestamp > 0);
+	}
+
+	#[test]
+	fn test_events_are_appended() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("events.log");
+		for id in 0..2 {
+			let mut logger = EventLogger::open(&path).unwrap();
+			logger
+				.log(
+					ChainId::ONE,
+					&BridgeContractEvent::<Vec<u8>>::Cancelled(BridgeTransferId([id; 32])),
+				)
+				.unwrap();
+		}
+		let events = read_events(&path);
+		assert_eq!(events.len(), 2);
+		assert_eq!(events[1].event, BridgeContractEvent::Cancelled(BridgeTransferId([1; 32])));
+	}
+}
@@ -0,0 +1,172 @@
+use bridge_util::{actions::ActionExecError, types::ChainId};
+use prometheus::{IntGauge, IntGaugeVec, Opts, Registry};
+use std::{collections::VecDeque, future::Future, pin::Pin, sync::Arc};
+use tokio::{
+	sync::Semaphore,
+	task::{JoinError, JoinSet},
+};
+
+/// The execution of a transfer action, created by `process_action`.
+pub type ActionFuture = Pin<Box<dyn Future<Output = Result<(), ActionExecError>> + Send>>;
+
+/// Default number of actions of a chain executed at the same time.
+pub const DEFAULT_MAX_CONCURRENT_ACTIONS: usize = 8;
+
+/// Prometheus metrics of the [`ActionExecutor`]s, labelled by chain.
+#[derive(Clone)]
+pub struct ActionExecutorMetrics {
+	/// The number of actions waiting for an execution slot.
+	pub queue_depth: IntGaugeVec,
+	/// The number of actions being executed.
+	pub active_actions: IntGaugeVec,
+}
+
+impl ActionExecutorMetrics {
+	/// Creates the metrics and registers them with `registry`.
+	pub fn try_new(registry: &Registry) -> Result<Self, prometheus::Error> {
+		let queue_depth = IntGaugeVec::new(
+			Opts::new("bridge_action_queue_depth", "Number of actions waiting to be executed"),
+			&["chain"],
+		)?;
+		let active_actions = IntGaugeVec::new(
+			Opts::new("bridge_active_actions", "Number of actions being executed"),
+			&["chain"],
+		)?;
+
+		registry.register(Box::new(queue_depth.clone()))?;
+		registry.register(Box::new(active_actions.clone()))?;
+
+		Ok(Self { queue_depth, active_actions })
+	}
+}
+
+/// Executes the actions of a chain, at most `max_concurrent` at a time.
+/// The actions submitted while all the slots are taken are queued and executed in order.
+pub struct ActionExecutor {
+	semaphore: Arc<Semaphore>,
+	max_concurrent: usize,
+	queue: VecDeque<ActionFuture>,
+	running: JoinSet<Result<(), ActionExecError>>,
+	queue_depth: IntGauge,
+	active_actions: IntGauge,
+}
+
+impl ActionExecutor {
+	pub fn new(max_concurrent: usize, chain: ChainId, metrics: &ActionExecutorMetrics) -> Self {
+		let chain = chain.to_string();
+		ActionExecutor {
+			semaphore: Arc::new(Semaphore::new(max_concurrent)),
+			max_concurrent,
+			queue: VecDeque::new(),
+			running: JoinSet::new(),
+			queue_depth: metrics.queue_depth.with_label_values(&[&chain]),
+			active_actions: metrics.active_actions.with_label_values(&[&chain]),
+		}
+	}
+
+	pub fn max_concurrent(&self) -> usize {
+		self.max_concurrent
+	}
+
+	/// Number of actions being executed.
+	pub fn active(&self) -> usize {
+		self.running.len()
+	}
+
+	/// Number of actions waiting for an execution slot.
+	pub fn queued(&self) -> usize {
+		self.queue.len()
+	}
+
+	/// Executes the action as soon as a slot is free.
+	pub fn submit(&mut self, action: ActionFuture) {
+		self.queue.push_back(action);
+		self.spawn_queued();
+	}
+
+	/// Waits for the next action to complete and starts the queued actions it made room for.
+	/// Returns `None` when no action is being executed.
+	/// Cancel safe: an action completing after the cancellation is returned by the next call.
+	pub async fn join_next(&mut self) -> Option<Result<Result<(), ActionExecError>, JoinError>> {
+		let result = self.running.join_next().await;
+		self.spawn_queued();
+		result
+	}
+
+	fn spawn_queued(&mut self) {
+		while !self.queue.is_empty() {
+			let Ok(permit) = self.semaphore.clone().try_acquire_owned() else {
+				break;
+			};
+			if let Some(action) = self.queue.pop_front() {
+				self.running.spawn(async move {
+					let _permit = permit;
+					action.await
+				});
+			}
+		}
+		self.queue_depth.set(self.queue.len() as i64);
+		self.active_actions.set(self.running.len() as i64);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+	use std::time::Duration;
+
+	/// An action that counts its start and completes once `gate` hands it a permit.
+	fn gated_action(started: Arc<AtomicUsize>, gate: Arc<Semaphore>) -> ActionFuture {
+		Box::pin(async move {
+			started.fetch_add(1, Ordering::SeqCst);
+			gate.acquire().await.expect("gate closed").forget();
+			Ok(())
+		})
+	}
+
+	async fn wait_started(started: &AtomicUsize, count: usize) {
+		tokio::time::timeout(Duration::from_secs(5), async {
+			while started.load(Ordering::SeqCst) < count {
+				tokio::time::sleep(Duration::from_millis(1)).await;
+			}
+		})
+		.await
+		.expect("actions did not start");
+	}
+
+	#[tokio::test]
+	async fn test_max_concurrent_actions() -> Result<(), anyhow::Error> {
+		let metrics = ActionExecutorMetrics::try_new(&Registry::new())?;
+		let mut executor = ActionExecutor::new(2, ChainId::ONE, &metrics);
+		let started = Arc::new(AtomicUsize::new(0));
+		let gate = Arc::new(Semaphore::new(0));
+
+		for _ in 0..3 {
+			executor.submit(gated_action(started.clone(), gate.clone()));
+		}
+		wait_started(&started, 2).await;
+		// Give the third action a chance to start if it wrongly got a slot.
+		tokio::time::sleep(Duration::from_millis(20)).await;
+		assert_eq!(started.load(Ordering::SeqCst), 2);
+		assert_eq!(executor.active(), 2);
+		assert_eq!(executor.queued(), 1);
+		assert_eq!(metrics.queue_depth.with_label_values(&["ONE"]).get(), 1);
+		assert_eq!(metrics.active_actions.with_label_values(&["ONE"]).get(), 2);
+
+		// Completing one action starts the queued one.
+		gate.add_permits(1);
+		assert!(matches!(executor.join_next().await, Some(Ok(Ok(())))));
+		wait_started(&started, 3).await;
+		assert_eq!(executor.active(), 2);
+		assert_eq!(executor.queued(), 0);
+		assert_eq!(metrics.queue_depth.with_label_values(&["ONE"]).get(), 0);
+
+		gate.add_permits(2);
+		assert!(matches!(executor.join_next().await, Some(Ok(Ok(())))));
+		assert!(matches!(executor.join_next().await, Some(Ok(Ok(())))));
+		assert!(executor.join_next().await.is_none());
+		assert_eq!(metrics.active_actions.with_label_values(&["ONE"]).get(), 0);
+		Ok(())
+	}
+}
@@ -0,0 +1,224 @@
+use bridge_util::{
+	actions::{TransferAction, TransferActionType},
+	chains::bridge_contracts::BridgeContract,
+	types::{BridgeTransferId, ChainId},
+};
+use std::{collections::HashSet, marker::PhantomData, time::Duration};
+use tokio::sync::mpsc;
+
+/// Default interval between two checks of the tracked transfers.
+pub const DEFAULT_EXPIRY_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+const EXPIRY_ACTION_CHANNEL_SIZE: usize = 100;
+
+/// Polls the initiator contract of a chain for the transfers initiated on it, and emits a
+/// `RefundInitiator` action for the ones whose time lock has expired.
+pub struct ExpiryMonitor<A, C> {
+	client: C,
+	chain: ChainId,
+	poll_interval: Duration,
+	transfer_ids: HashSet<BridgeTransferId>,
+	transfer_rx: mpsc::UnboundedReceiver<TrackingUpdate>,
+	action_tx: mpsc::Sender<TransferAction>,
+	_address: PhantomData<fn() -> A>,
+}
+
+/// A change of the transfers tracked by an [`ExpiryMonitor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackingUpdate {
+	Track(BridgeTransferId),
+	Untrack(BridgeTransferId),
+}
+
+/// The relayer side of an [`ExpiryMonitor`]: the changes of the tracked transfers are sent on
+/// `transfer_tx`, and the refund actions are received on `action_rx`.
+/// The tracking channel is unbounded so that no transfer is lost while the monitor is busy
+/// checking the tracked ones.
+pub struct ExpiryMonitorChannels {
+	pub transfer_tx: mpsc::UnboundedSender<TrackingUpdate>,
+	pub action_rx: mpsc::Receiver<TransferAction>,
+}
+
+impl ExpiryMonitorChannels {
+	/// Starts tracking the transfer `transfer_id` initiated on the chain of the monitor.
+	pub fn track(&self, transfer_id: BridgeTransferId) {
+		self.send(TrackingUpdate::Track(transfer_id));
+	}
+
+	/// Stops tracking the transfer `transfer_id`, once it is no longer in progress.
+	pub fn untrack(&self, transfer_id: BridgeTransferId) {
+		self.send(TrackingUpdate::Untrack(transfer_id));
+	}
+
+	fn send(&self, update: TrackingUpdate) {
+		if let Err(err) = self.transfer_tx.send(update) {
+			tracing::warn!("Failed to update the expiry tracking, monitor stopped: {err}");
+		}
+	}
+}
+
+impl<A, C: BridgeContract<A>> ExpiryMonitor<A, C> {
+	pub fn new(
+		client: C,
+		chain: ChainId,
+		poll_interval: Duration,
+	) -> (Self, ExpiryMonitorChannels) {
+		let (transfer_tx, transfer_rx) = mpsc::unbounded_channel();
+		let (action_tx, action_rx) = mpsc::channel(EXPIRY_ACTION_CHANNEL_SIZE);
+		let monitor = ExpiryMonitor {
+			client,
+			chain,
+			poll_interval,
+			transfer_ids: HashSet::new(),
+			transfer_rx,
+			action_tx,
+			_address: PhantomData,
+		};
+		(monitor, ExpiryMonitorChannels { transfer_tx, action_rx })
+	}
+
+	/// Runs until the relayer drops its [`ExpiryMonitorChannels`].
+	pub async fn run(mut self) {
+		let mut interval = tokio::time::interval(self.poll_interval);
+		loop {
+			tokio::select! {
+				update = self.transfer_rx.recv() => match update {
+					Some(update) => self.apply(update),
+					None => return,
+				},
+				_ = interval.tick() => {
+					if self.refund_expired().await.is_err() {
+						return;
+					}
+				}
+			}
+		}
+	}
+
+	fn apply(&mut self, update: TrackingUpdate) {
+		match update {
+			TrackingUpdate::Track(transfer_id) => self.transfer_ids.insert(transfer_id),
+			TrackingUpdate::Untrack(transfer_id) => self.transfer_ids.remove(&transfer_id),
+		};
+	}
+
+	/// Emits a refund action for each tracked transfer that has expired, and stops tracking it.
+	/// The pending tracking changes are applied first, so a completed transfer is not refunded.
+	/// Fails when the relayer no longer receives the actions.
+	async fn refund_expired(&mut self) -> Result<(), mpsc::error::SendError<TransferAction>> {
+		while let Ok(update) = self.transfer_rx.try_recv() {
+			self.apply(update);
+		}
+		let transfer_ids: Vec<_> = self.transfer_ids.iter().copied().collect();
+		for transfer_id in transfer_ids {
+			match self.client.get_bridge_transfer_details_initiator(transfer_id).await {
+				Ok(Some(details)) if details.time_lock.has_expired() => {
+					tracing::info!("Transfer {transfer_id} expired on chain {}", self.chain);
+					self.transfer_ids.remove(&transfer_id);
+					self.action_tx
+						.send(TransferAction {
+							chain: self.chain,
+							transfer_id,
+							kind: TransferActionType::RefundInitiator,
+						})
+						.await?;
+				}
+				Ok(Some(_)) => (),
+				Ok(None) => {
+					tracing::warn!("Transfer {transfer_id} not found on chain {}", self.chain);
+					self.transfer_ids.remove(&transfer_id);
+				}
+				Err(err) => {
+					tracing::warn!("Failed to get the details of transfer {transfer_id}: {err}")
+				}
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::MockClient;
+	use bridge_util::types::{current_timestamp_secs, TimeLock};
+	use std::collections::HashMap;
+
+	#[tokio::test]
+	async fn test_emits_refund_for_expired_transfers() {
+		let expired = BridgeTransferId([1; 32]);
+		let pending = BridgeTransferId([2; 32]);
+		let unknown = BridgeTransferId([3; 32]);
+		let client = MockClient::with_time_locks(HashMap::from([
+			(expired, TimeLock(current_timestamp_secs() - 60)),
+			(pending, TimeLock::from_duration(Duration::from_secs(3600))),
+		]));
+		let (mut monitor, mut channels) =
+			ExpiryMonitor::new(client, ChainId::ONE, Duration::from_millis(10));
+		monitor.transfer_ids.extend([expired, pending, unknown]);
+
+		monitor.refund_expired().await.unwrap();
+
+		let action = channels.action_rx.try_recv().unwrap();
+		assert_eq!(action.chain, ChainId::ONE);
+		assert_eq!(action.transfer_id, expired);
+		assert!(matches!(action.kind, TransferActionType::RefundInitiator));
+		assert!(channels.action_rx.try_recv().is_err());
+		// Only the transfer that can still expire is tracked.
+		assert_eq!(monitor.transfer_ids, HashSet::from([pending]));
+	}
+
+	#[tokio::test]
+	async fn test_pending_tracking_changes_are_applied_before_refunding() {
+		let tracked = BridgeTransferId([1; 32]);
+		let completed = BridgeTransferId([2; 32]);
+		let client = MockClient::with_time_locks(HashMap::from([
+			(tracked, TimeLock(current_timestamp_secs() - 60)),
+			(completed, TimeLock(current_timestamp_secs() - 60)),
+		]));
+		let (mut monitor, mut channels) =
+			ExpiryMonitor::new(client, ChainId::ONE, Duration::from_millis(10));
+
+		// Tracking many transfers at once while the monitor is busy drops none of them.
+		for i in 0..=EXPIRY_ACTION_CHANNEL_SIZE as u8 {
+			channels.track(BridgeTransferId([i + 3; 32]));
+		}
+		channels.track(tracked);
+		channels.track(completed);
+		channels.untrack(completed);
+
+		monitor.refund_expired().await.unwrap();
+
+		let action = channels.action_rx.try_recv().unwrap();
+		assert_eq!(action.transfer_id, tracked);
+		assert!(channels.action_rx.try_recv().is_err());
+		// The unknown transfers are dropped, the completed one was never checked.
+		assert!(monitor.transfer_ids.is_empty());
+	}
+
+	#[tokio::test]
+	async fn test_run_refunds_tracked_transfers() {
+		let expired = BridgeTransferId([1; 32]);
+		let client = MockClient::with_time_locks(HashMap::from([(
+			expired,
+			TimeLock(current_timestamp_secs() - 60),
+		)]));
+		let (monitor, mut channels) =
+			ExpiryMonitor::new(client, ChainId::TWO, Duration::from_millis(10));
+		let monitor_jh = tokio::spawn(monitor.run());
+
+		channels.track(expired);
+		let action = tokio::time::timeout(Duration::from_secs(5), channels.action_rx.recv())
+			.await
+			.expect("No refund emitted")
+			.expect("Monitor stopped");
+		assert_eq!(action.transfer_id, expired);
+		assert_eq!(action.chain, ChainId::TWO);
+
+		drop(channels);
+		tokio::time::timeout(Duration::from_secs(5), monitor_jh)
+			.await
+			.expect("Monitor did not stop")
+			.unwrap();
+	}
+}
@@ -1,24 +1,41 @@
-use crate::actions::process_action;
+use crate::actions::{process_action, ClientLock};
+use crate::chains::movement::faucet::RateLimitedFaucetClient;
+use crate::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
+use crate::dedup::TransferEventDeduplicator;
+use crate::event_log::EventLogger;
+use crate::executor::{ActionExecutor, ActionExecutorMetrics, DEFAULT_MAX_CONCURRENT_ACTIONS};
+use crate::expiry::ExpiryMonitorChannels;
+use crate::nonce::NonceRegistry;
 use bridge_indexer_db::client::Client as IndexerClient;
 use bridge_util::{
 	actions::{ActionExecError, TransferAction, TransferActionType},
 	chains::bridge_contracts::{BridgeContract, BridgeContractEvent, BridgeContractMonitoring},
 	events::{InvalidEventError, TransferEvent},
 	states::{TransferState, TransferStateType},
-	types::{BridgeTransferId, ChainId},
+	types::{Amount, BridgeTransferId, ChainId},
 };
 use futures::stream::FuturesUnordered;
-use std::{collections::HashMap, sync::Arc};
+use prometheus::Registry;
+use serde::Serialize;
+use std::collections::HashMap;
+use tokio::select;
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
-use tokio::{select, sync::Mutex};
 use tokio_stream::StreamExt;
 
 pub use bridge_util::types;
 
 mod actions;
 pub mod chains;
+pub mod circuit_breaker;
+pub mod dedup;
+pub mod event_log;
+pub mod executor;
+pub mod expiry;
 pub mod grpc;
+#[cfg(test)]
+mod mock;
+pub mod nonce;
 pub mod rest;
 
 #[derive(Debug)]
@@ -44,8 +61,8 @@ impl HeathCheckStatus {
 }
 
 pub async fn run_bridge<
-	A1: Send + TryFrom<Vec<u8>> + std::clone::Clone + 'static + std::fmt::Debug,
-	A2: Send + TryFrom<Vec<u8>> + std::clone::Clone + 'static + std::fmt::Debug,
+	A1: Send + TryFrom<Vec<u8>> + std::clone::Clone + 'static + std::fmt::Debug + Serialize,
+	A2: Send + TryFrom<Vec<u8>> + std::clone::Clone + 'static + std::fmt::Debug + Serialize,
 >(
 	client_one: impl BridgeContract<A1> + 'static,
 	mut stream_one: impl BridgeContractMonitoring<Address = A1>,
@@ -53,22 +70,37 @@ pub async fn run_bridge<
 	mut stream_two: impl BridgeContractMonitoring<Address = A2>,
 	mut healthcheck_request_rx: mpsc::Receiver<oneshot::Sender<String>>,
 	indexer_db_client: Option<IndexerClient>,
+	event_logger: Option<EventLogger>,
+	nonce_registry: NonceRegistry,
 	healthcheck_tx_one: mpsc::Sender<oneshot::Sender<bool>>,
 	healthcheck_tx_two: mpsc::Sender<oneshot::Sender<bool>>,
+	circuit_breaker_config: CircuitBreakerConfig,
+	metrics_registry: &Registry,
+	mut expiry_one: Option<ExpiryMonitorChannels>,
+	mut expiry_two: Option<ExpiryMonitorChannels>,
+	relayer_fee: Amount,
+	faucet: Option<RateLimitedFaucetClient>,
 ) -> Result<(), anyhow::Error>
 where
 	Vec<u8>: From<A1>,
 	Vec<u8>: From<A2>,
 {
-	let mut state_runtime = Runtime::new(indexer_db_client);
+	let mut state_runtime = Runtime::new(indexer_db_client, event_logger, relayer_fee);
 
-	let mut client_exec_result_futures_one = FuturesUnordered::new();
-	let mut client_exec_result_futures_two = FuturesUnordered::new();
+	let executor_metrics = ActionExecutorMetrics::try_new(metrics_registry)?;
+	let mut executor_one =
+		ActionExecutor::new(DEFAULT_MAX_CONCURRENT_ACTIONS, ChainId::ONE, &executor_metrics);
+	let mut executor_two =
+		ActionExecutor::new(DEFAULT_MAX_CONCURRENT_ACTIONS, ChainId::TWO, &executor_metrics);
 	let mut health_check_result_futures = FuturesUnordered::new();
 
 	//only one client can use at a time.
-	let client_lock_one = Arc::new(Mutex::new(()));
-	let client_lock_two = Arc::new(Mutex::new(()));
+	let client_lock_one = ClientLock::default();
+	let client_lock_two = ClientLock::default();
+
+	// Stop executing the actions of a chain that keeps failing.
+	let circuit_breaker_one = CircuitBreaker::new(circuit_breaker_config);
+	let circuit_breaker_two = CircuitBreaker::new(circuit_breaker_config);
 
 	let mut tranfer_log_interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
 	let mut monitoring_health_check_interval =
@@ -129,8 +161,12 @@ where
 			_ = tranfer_log_interval.tick() => {
 				//format logs
 				let logs: Vec<_> = state_runtime.iter_state().map(|state| state.to_string()).collect();
+				let average_transfer_secs = state_runtime.average_transfer_secs();
 				tokio::spawn(async move {
 					tracing::info!("Bridge current transfer processing:{:#?}", logs);
+					if let Some(secs) = average_transfer_secs {
+						tracing::info!("Bridge average transfer time: {secs}s");
+					}
 				});
 			}
 			// Wait on chain one events.
@@ -139,35 +175,29 @@ where
 					Ok(event_one) => {
 						let event : TransferEvent<A1> = (event_one, ChainId::ONE).into();
 						tracing::info!("Receive event from chain ONE:{} ", event.contract_event);
+						let transfer_id = event.contract_event.bridge_transfer_id();
+						let initiated_id = event.contract_event.is_initiated_event().then_some(transfer_id);
 						match state_runtime.process_event(event) {
 							Ok(action) => {
+								if let (Some(id), Some(expiry)) = (initiated_id, &expiry_one) {
+									expiry.track(id);
+								}
+								if !state_runtime.is_in_progress(transfer_id) {
+									untrack_expiry(transfer_id, [&expiry_one, &expiry_two]);
+								}
 								//Execute action
 								match action.chain {
 									ChainId::ONE => {
-										let fut = process_action(action, client_one.clone());
+										let fut = process_action(action, client_one.clone(), client_lock_one.clone(), nonce_registry.clone(), circuit_breaker_one.clone(), faucet.clone());
 										if let Some(fut) = fut {
-											let jh = tokio::spawn({
-												let client_lock_clone = client_lock_one.clone();
-												async move {
-													let _lock = client_lock_clone.lock().await;
-													fut.await
-												}
-											});
-											client_exec_result_futures_one.push(jh);
+											executor_one.submit(fut);
 										}
 
 									},
 									ChainId::TWO => {
-										let fut = process_action(action, client_two.clone());
+										let fut = process_action(action, client_two.clone(), client_lock_two.clone(), nonce_registry.clone(), circuit_breaker_two.clone(), faucet.clone());
 										if let Some(fut) = fut {
-											let jh = tokio::spawn({
-												let client_lock_clone = client_lock_two.clone();
-												async move {
-													let _lock = client_lock_clone.lock().await;
-													fut.await
-												}
-											});
-											client_exec_result_futures_two.push(jh);
+											executor_two.submit(fut);
 										}
 									}
 								}
@@ -184,23 +214,29 @@ where
 					Ok(event_two) => {
 						let event : TransferEvent<A2> = (event_two, ChainId::TWO).into();
 						tracing::info!("Receive event from chain TWO :{}", event.contract_event);
+						let transfer_id = event.contract_event.bridge_transfer_id();
+						let initiated_id = event.contract_event.is_initiated_event().then_some(transfer_id);
 						match state_runtime.process_event(event) {
 							Ok(action) => {
+								if let (Some(id), Some(expiry)) = (initiated_id, &expiry_two) {
+									expiry.track(id);
+								}
+								if !state_runtime.is_in_progress(transfer_id) {
+									untrack_expiry(transfer_id, [&expiry_one, &expiry_two]);
+								}
 								//Execute action
 								match action.chain {
 									ChainId::ONE => {
-										let fut = process_action(action, client_one.clone());
+										let fut = process_action(action, client_one.clone(), client_lock_one.clone(), nonce_registry.clone(), circuit_breaker_one.clone(), faucet.clone());
 										if let Some(fut) = fut {
-											let jh = tokio::spawn(fut);
-											client_exec_result_futures_one.push(jh);
+											executor_one.submit(fut);
 										}
 
 									},
 									ChainId::TWO => {
-										let fut = process_action(action, client_two.clone());
+										let fut = process_action(action, client_two.clone(), client_lock_two.clone(), nonce_registry.clone(), circuit_breaker_two.clone(), faucet.clone());
 										if let Some(fut) = fut {
-											let jh = tokio::spawn(fut);
-											client_exec_result_futures_two.push(jh);
+											executor_two.submit(fut);
 										}
 									}
 								}
@@ -211,8 +247,26 @@ where
 					Err(err) => tracing::error!("Chain two event stream return an error:{err}"),
 				}
 			}
+			// Refund the transfers initiated on chain one that have expired.
+			action = recv_expired_refund(&mut expiry_one) => {
+				if let Some(action) = state_runtime.process_expired_transfer(action) {
+					let fut = process_action(action, client_one.clone(), client_lock_one.clone(), nonce_registry.clone(), circuit_breaker_one.clone(), faucet.clone());
+					if let Some(fut) = fut {
+						executor_one.submit(fut);
+					}
+				}
+			}
+			// Refund the transfers initiated on chain two that have expired.
+			action = recv_expired_refund(&mut expiry_two) => {
+				if let Some(action) = state_runtime.process_expired_transfer(action) {
+					let fut = process_action(action, client_two.clone(), client_lock_two.clone(), nonce_registry.clone(), circuit_breaker_two.clone(), faucet.clone());
+					if let Some(fut) = fut {
+						executor_two.submit(fut);
+					}
+				}
+			}
 			// Wait on client tx execution result.
-			Some(res) = client_exec_result_futures_one.next() => {
+			Some(res) = executor_one.join_next() => {
 				match res {
 					//Client execution ok.
 					Ok(Ok(_)) => (),
@@ -229,7 +283,7 @@ where
 					}
 				}
 			}
-			Some(res) = client_exec_result_futures_two.next() => {
+			Some(res) = executor_two.join_next() => {
 				match res {
 					//Client execution ok.
 					Ok(Ok(_)) => (),
@@ -250,6 +304,25 @@ where
 	}
 }
 
+/// Stops the expiry monitors tracking a transfer that is no longer in progress.
+fn untrack_expiry(transfer_id: BridgeTransferId, expiry: [&Option<ExpiryMonitorChannels>; 2]) {
+	for channels in expiry.into_iter().flatten() {
+		channels.untrack(transfer_id);
+	}
+}
+
+/// Next refund action of the expiry monitor, pending forever without a monitor.
+async fn recv_expired_refund(expiry: &mut Option<ExpiryMonitorChannels>) -> TransferAction {
+	if let Some(channels) = expiry {
+		if let Some(action) = channels.action_rx.recv().await {
+			return action;
+		}
+		tracing::warn!("Expiry monitor stopped, expired transfers are no longer refunded");
+		*expiry = None;
+	}
+	std::future::pending().await
+}
+
 async fn check_monitoring_loop_heath(
 	healthcheck_tx: mpsc::Sender<oneshot::Sender<bool>>,
 ) -> Result<bool, String> {
@@ -275,11 +348,42 @@ async fn check_monitoring_loop_heath(
 struct Runtime {
 	swap_state_map: HashMap<BridgeTransferId, TransferState>,
 	indexer_db_client: Option<IndexerClient>,
+	// Appends every received event to the audit log.
+	event_logger: Option<EventLogger>,
+	// Drops the events already received, e.g. replayed by a monitor.
+	deduplicator: TransferEventDeduplicator,
+	// Number of transfers done and their cumulated duration, to compute the average transfer time.
+	done_transfer_count: u64,
+	done_transfer_secs: u64,
+	// Fee claimed by the relayer for each new transfer.
+	relayer_fee: Amount,
 }
 
 impl Runtime {
-	pub fn new(indexer_db_client: Option<IndexerClient>) -> Self {
-		Runtime { swap_state_map: HashMap::new(), indexer_db_client }
+	pub fn new(
+		indexer_db_client: Option<IndexerClient>,
+		event_logger: Option<EventLogger>,
+		relayer_fee: Amount,
+	) -> Self {
+		Runtime {
+			swap_state_map: HashMap::new(),
+			indexer_db_client,
+			event_logger,
+			deduplicator: TransferEventDeduplicator::default(),
+			done_transfer_count: 0,
+			done_transfer_secs: 0,
+			relayer_fee,
+		}
+	}
+
+	/// Average duration in seconds of the transfers done since the relayer started.
+	pub fn average_transfer_secs(&self) -> Option<u64> {
+		(self.done_transfer_count != 0).then(|| self.done_transfer_secs / self.done_transfer_count)
+	}
+
+	/// Whether the transfer `transfer_id` was initiated and is not done yet.
+	pub fn is_in_progress(&self, transfer_id: BridgeTransferId) -> bool {
+		self.swap_state_map.contains_key(&transfer_id)
 	}
 
 	pub fn iter_state(&self) -> impl Iterator<Item = &TransferState> {
@@ -308,6 +412,16 @@ impl Runtime {
 		}
 	}
 
+	fn log_event<A: Serialize>(&mut self, event: &TransferEvent<A>) {
+		if let Some(ref mut logger) = self.event_logger {
+			// Flushed for each event, so that the log is complete if the relayer crashes.
+			match logger.log(event.chain, &event.contract_event).and_then(|()| logger.flush()) {
+				Ok(()) => (),
+				Err(err) => tracing::warn!("Fail to log event :{err}"),
+			}
+		}
+	}
+
 	pub fn index_transfer_action(
 		&mut self,
 		action: TransferAction,
@@ -334,9 +448,34 @@ impl Runtime {
 		event: TransferEvent<A>,
 	) -> Result<TransferAction, InvalidEventError>
 	where
-		A: Into<Vec<u8>> + std::clone::Clone + std::fmt::Debug,
+		A: Into<Vec<u8>> + std::clone::Clone + std::fmt::Debug + Serialize,
 	{
 		tracing::info!("Event received: {:?}", event);
+		self.log_event(&event);
+		// The orphaned event was never relayed, there is no state to roll back.
+		if let BridgeContractEvent::Reorged(id) = event.contract_event {
+			self.deduplicator.record(&event);
+			return Err(InvalidEventError::Reorged(id));
+		}
+		if self.deduplicator.is_duplicate(&event) {
+			return Err(InvalidEventError::DuplicateEvent(
+				event.contract_event.bridge_transfer_id(),
+			));
+		}
+		// Only a processed event is a duplicate, a rejected one can be received again.
+		let recorded = event.clone();
+		let action = self.process_new_event(event)?;
+		self.deduplicator.record(&recorded);
+		Ok(action)
+	}
+
+	fn process_new_event<A>(
+		&mut self,
+		event: TransferEvent<A>,
+	) -> Result<TransferAction, InvalidEventError>
+	where
+		A: Into<Vec<u8>> + std::clone::Clone + std::fmt::Debug,
+	{
 		self.validate_state(&event)?;
 		let indexer_event = event.clone();
 		self.index_event(indexer_event)?;
@@ -344,8 +483,12 @@ impl Runtime {
 		let state_opt = self.swap_state_map.remove(&event_transfer_id);
 		//create swap state if need
 		let mut state = if let BridgeContractEvent::Initiated(detail) = event.contract_event {
-			let (state, mut action) =
-				TransferState::transition_from_initiated(event.chain, event_transfer_id, detail);
+			let (state, mut action) = TransferState::transition_from_initiated(
+				event.chain,
+				event_transfer_id,
+				detail,
+				self.relayer_fee,
+			);
 			action.chain = state.init_chain.other();
 			self.swap_state_map.insert(state.transfer_id, state);
 			self.index_transfer_action(action.clone())?;
@@ -354,9 +497,10 @@ impl Runtime {
 			//tested before in validate_state() state can be unwrap
 			state_opt.unwrap()
 		};
+		state.contract_state = state.next_contract_state(&event.contract_event)?.into();
 
 		let (action_kind, chain_id) = match event.contract_event {
-			BridgeContractEvent::Initiated(_) => unreachable!(),
+			BridgeContractEvent::Initiated(_) | BridgeContractEvent::Reorged(_) => unreachable!(),
 			BridgeContractEvent::Locked(detail) => {
 				let (new_state, action_kind) =
 					state.transition_from_locked_done(event_transfer_id, detail);
@@ -399,6 +543,9 @@ impl Runtime {
 
 		if state.state != TransferStateType::Done {
 			self.swap_state_map.insert(state.transfer_id, state);
+		} else {
+			self.done_transfer_count += 1;
+			self.done_transfer_secs += state.age().as_secs();
 		}
 		Ok(action)
 	}
@@ -438,6 +585,33 @@ impl Runtime {
 		Ok(())
 	}
 
+	/// Moves a transfer reported expired by an expiry monitor to the refund state, unless its
+	/// secret was already revealed or it is no longer in progress.
+	fn process_expired_transfer(&mut self, action: TransferAction) -> Option<TransferAction> {
+		let state = match self.swap_state_map.get_mut(&action.transfer_id) {
+			Some(state) => state,
+			None => {
+				tracing::info!("Expired transfer {} is no longer in progress", action.transfer_id);
+				return None;
+			}
+		};
+		match state.state {
+			TransferStateType::Initialized | TransferStateType::Locked => {
+				let (new_state_type, action_kind) = state.transition_to_refund();
+				state.state = new_state_type;
+				Some(TransferAction {
+					chain: state.init_chain,
+					transfer_id: state.transfer_id,
+					kind: action_kind,
+				})
+			}
+			_ => {
+				tracing::info!("Expired transfer not refunded, state: {}", state);
+				None
+			}
+		}
+	}
+
 	fn process_action_exec_error(&mut self, action_err: ActionExecError) -> Option<TransferAction> {
 		// Manage Tx execution error
 		let (action, err) = action_err.inner();
@@ -482,3 +656,30 @@ impl Runtime {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::event_log::LoggedEvent;
+
+	#[test]
+	fn test_received_events_are_logged() -> Result<(), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+		let path = dir.path().join("events.log");
+		let mut runtime = Runtime::new(None, Some(EventLogger::open(&path)?), Amount(0));
+
+		// Rejected events are logged too, the log records everything the relayer received.
+		let event = BridgeContractEvent::<Vec<u8>>::Refunded(BridgeTransferId([1; 32]));
+		assert!(runtime.process_event((event.clone(), ChainId::ONE).into()).is_err());
+
+		let logged: Vec<LoggedEvent<BridgeContractEvent<Vec<u8>>>> =
+			std::fs::read_to_string(&path)?
+				.lines()
+				.map(serde_json::from_str)
+				.collect::<Result<_, _>>()?;
+		assert_eq!(logged.len(), 1);
+		assert_eq!(logged[0].chain, ChainId::ONE);
+		assert_eq!(logged[0].event, event);
+		Ok(())
+	}
+}
@@ -9,12 +9,19 @@ use bridge_service::{
 		ethereum::{client::EthClient, event_monitoring::EthMonitoring},
 		movement::{
 			client_framework::MovementClientFramework, event_monitoring::MovementMonitoring,
+			faucet::RateLimitedFaucetClient,
 		},
 	},
+	circuit_breaker::CircuitBreakerConfig,
+	event_log::{EventLogger, EVENT_LOG_FILE},
+	expiry::{ExpiryMonitor, DEFAULT_EXPIRY_POLL_INTERVAL},
 	grpc::HealthCheckService,
+	nonce::{NonceRegistry, NONCE_REGISTRY_FOLDER},
 	rest::BridgeRest,
+	types::{Amount, ChainId},
 };
 use godfig::{backend::config_file::ConfigFile, Godfig};
+use prometheus::Registry;
 use std::net::SocketAddr;
 use tonic::transport::Server;
 
@@ -45,8 +52,12 @@ async fn main() -> Result<()> {
 
 	let (eth_health_tx, eth_health_rx) = tokio::sync::mpsc::channel(10);
 	let one_stream = EthMonitoring::build(&bridge_config.eth, eth_health_rx).await.unwrap();
-	let one_client = EthClient::new(&bridge_config.eth).await.unwrap();
+	// Served by the REST service on /metrics.
+	let metrics_registry = Registry::new();
+	let one_client = EthClient::new(&bridge_config.eth, &metrics_registry).await.unwrap();
 	let two_client = MovementClientFramework::new(&bridge_config.movement).await.unwrap();
+	// Funds the Movement recipients of the transfers before their lock.
+	let faucet = RateLimitedFaucetClient::try_from_config(&bridge_config.movement)?;
 	let (mvt_health_tx, mvt_health_rx) = tokio::sync::mpsc::channel(10);
 	let two_stream =
 		MovementMonitoring::build(&bridge_config.movement, mvt_health_rx).await.unwrap();
@@ -77,7 +88,8 @@ async fn main() -> Result<()> {
 	let (health_tx, health_rx) = tokio::sync::mpsc::channel(10);
 	// Start the gRPC server on a specific address (e.g., localhost:50051)
 	// Create and run the REST service
-	let rest_service = BridgeRest::new(&bridge_config.movement, health_tx)?;
+	let rest_service = BridgeRest::new(&bridge_config.movement, health_tx)?
+		.with_metrics_registry(metrics_registry.clone());
 	let rest_service_future = rest_service.run_service();
 	let rest_jh = tokio::spawn(rest_service_future);
 
@@ -93,6 +105,17 @@ async fn main() -> Result<()> {
 		}
 	};
 
+	let nonce_registry = NonceRegistry::open(dot_movement.get_path().join(NONCE_REGISTRY_FOLDER))?;
+	let event_logger = EventLogger::open(dot_movement.get_path().join(EVENT_LOG_FILE))?;
+
+	// Refund the transfers initiated on each chain once their time lock has expired.
+	let (expiry_monitor_one, expiry_one) =
+		ExpiryMonitor::new(one_client.clone(), ChainId::ONE, DEFAULT_EXPIRY_POLL_INTERVAL);
+	let (expiry_monitor_two, expiry_two) =
+		ExpiryMonitor::new(two_client.clone(), ChainId::TWO, DEFAULT_EXPIRY_POLL_INTERVAL);
+	let expiry_one_jh = tokio::spawn(expiry_monitor_one.run());
+	let expiry_two_jh = tokio::spawn(expiry_monitor_two.run());
+
 	let loop_jh = tokio::spawn(async move {
 		bridge_service::run_bridge(
 			one_client,
@@ -101,8 +124,16 @@ async fn main() -> Result<()> {
 			two_stream,
 			health_rx,
 			indexer_db_client,
+			Some(event_logger),
+			nonce_registry,
 			eth_health_tx,
 			mvt_health_tx,
+			CircuitBreakerConfig::default(),
+			&metrics_registry,
+			Some(expiry_one),
+			Some(expiry_two),
+			Amount(bridge_config.eth.eth_relayer_fee),
+			Some(faucet),
 		)
 		.await
 	});
@@ -117,6 +148,12 @@ async fn main() -> Result<()> {
 		res = grpc_jh => {
 			tracing::error!("gRpc server exit because :{res:?}");
 		}
+		res = expiry_one_jh => {
+			tracing::error!("Chain one expiry monitor exit because :{res:?}");
+		}
+		res = expiry_two_jh => {
+			tracing::error!("Chain two expiry monitor exit because :{res:?}");
+		}
 	};
 
 	Ok(())
@@ -0,0 +1,145 @@
+//! In memory bridge client for the unit tests of the relayer.
+
+use bridge_util::{
+	chains::bridge_contracts::{BridgeContract, BridgeContractError, BridgeContractResult},
+	types::{
+		Amount, BridgeAddress, BridgeTransferDetails, BridgeTransferDetailsCounterparty,
+		BridgeTransferId, HashLock, HashLockPreImage, TimeLock,
+	},
+};
+use std::{
+	collections::HashMap,
+	sync::{
+		atomic::{AtomicU32, Ordering},
+		Arc, Mutex,
+	},
+};
+
+/// Client whose transfers only have a time lock, recording the calls changing their state.
+#[derive(Clone, Default)]
+pub struct MockClient {
+	time_locks: Arc<HashMap<BridgeTransferId, TimeLock>>,
+	completed: Arc<Mutex<Vec<BridgeTransferId>>>,
+	fee_claims: Arc<Mutex<Vec<BridgeTransferId>>>,
+	/// Number of completions still failing with a transport error before they succeed.
+	completion_failures: Arc<AtomicU32>,
+}
+
+impl MockClient {
+	pub fn with_time_locks(time_locks: HashMap<BridgeTransferId, TimeLock>) -> Self {
+		MockClient { time_locks: Arc::new(time_locks), ..Default::default() }
+	}
+
+	/// Fails the next `failures` completions with a transport error.
+	pub fn with_completion_failures(self, failures: u32) -> Self {
+		self.completion_failures.store(failures, Ordering::SeqCst);
+		self
+	}
+
+	pub fn completed(&self) -> Vec<BridgeTransferId> {
+		self.completed.lock().expect("mock client lock poisoned").clone()
+	}
+
+	pub fn fee_claims(&self) -> Vec<BridgeTransferId> {
+		self.fee_claims.lock().expect("mock client lock poisoned").clone()
+	}
+}
+
+#[async_trait::async_trait]
+impl BridgeContract<Vec<u8>> for MockClient {
+	async fn initiate_bridge_transfer(
+		&mut self,
+		_initiator: BridgeAddress<Vec<u8>>,
+		_recipient: BridgeAddress<Vec<u8>>,
+		_hash_lock: HashLock,
+		_amount: Amount,
+	) -> BridgeContractResult<()> {
+		unimplemented!()
+	}
+
+	async fn initiator_complete_bridge_transfer(
+		&mut self,
+		bridge_transfer_id: BridgeTransferId,
+		_secret: HashLockPreImage,
+	) -> BridgeContractResult<()> {
+		let failing = self
+			.completion_failures
+			.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |failures| failures.checked_sub(1))
+			.is_ok();
+		if failing {
+			return Err(BridgeContractError::TransportError("connection reset".to_string()));
+		}
+		self.completed
+			.lock()
+			.expect("mock client lock poisoned")
+			.push(bridge_transfer_id);
+		Ok(())
+	}
+
+	async fn counterparty_complete_bridge_transfer(
+		&mut self,
+		_bridge_transfer_id: BridgeTransferId,
+		_secret: HashLockPreImage,
+	) -> BridgeContractResult<()> {
+		unimplemented!()
+	}
+
+	async fn refund_bridge_transfer(
+		&mut self,
+		_bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<()> {
+		unimplemented!()
+	}
+
+	async fn get_bridge_transfer_details_initiator(
+		&mut self,
+		bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<Option<BridgeTransferDetails<Vec<u8>>>> {
+		Ok(self.time_locks.get(&bridge_transfer_id).map(|time_lock| BridgeTransferDetails {
+			bridge_transfer_id,
+			initiator: BridgeAddress(vec![1; 20]),
+			recipient: BridgeAddress(vec![2; 32]),
+			hash_lock: HashLock([3; 32]),
+			time_lock: *time_lock,
+			amount: Amount(1),
+			state: 0,
+			initiated_at: 0,
+		}))
+	}
+
+	async fn get_bridge_transfer_details_counterparty(
+		&mut self,
+		_bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<Option<BridgeTransferDetailsCounterparty<Vec<u8>>>> {
+		unimplemented!()
+	}
+
+	async fn lock_bridge_transfer(
+		&mut self,
+		_bridge_transfer_id: BridgeTransferId,
+		_hash_lock: HashLock,
+		_initiator: BridgeAddress<Vec<u8>>,
+		_recipient: BridgeAddress<Vec<u8>>,
+		_amount: Amount,
+	) -> BridgeContractResult<()> {
+		unimplemented!()
+	}
+
+	async fn abort_bridge_transfer(
+		&mut self,
+		_bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<()> {
+		unimplemented!()
+	}
+
+	async fn claim_relayer_fee(
+		&mut self,
+		bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<()> {
+		self.fee_claims
+			.lock()
+			.expect("mock client lock poisoned")
+			.push(bridge_transfer_id);
+		Ok(())
+	}
+}
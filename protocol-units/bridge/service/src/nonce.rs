@@ -0,0 +1,157 @@
+use anyhow::Context;
+use bridge_util::types::{BridgeTransferId, Nonce};
+use std::{
+	collections::HashSet,
+	path::Path,
+	sync::{Arc, Mutex},
+};
+
+/// Folder of the registry db, relative to the bridge config folder.
+pub const NONCE_REGISTRY_FOLDER: &str = "nonce_registry";
+
+const KEY_LEN: usize = 32 + 16;
+
+/// Keeps track of the `(BridgeTransferId, Nonce)` pairs already used to complete a transfer.
+/// Pairs are reserved in memory while their completion is sent, and persisted in a sled db
+/// once it succeeded so they survive a relayer restart.
+#[derive(Clone)]
+pub struct NonceRegistry {
+	/// The pairs used, or reserved by a completion in progress.
+	used: Arc<Mutex<HashSet<(BridgeTransferId, Nonce)>>>,
+	db: Option<sled::Db>,
+}
+
+impl NonceRegistry {
+	/// Opens (or creates) the registry stored at `path` and loads the pairs already used.
+	pub fn open(path: impl AsRef<Path>) -> Result<Self, anyhow::Error> {
+		let path = path.as_ref();
+		let db = sled::open(path)
+			.with_context(|| format!("Failed to open nonce registry at {}", path.display()))?;
+		let mut used = HashSet::new();
+		for entry in db.iter() {
+			let (key, _) = entry?;
+			used.insert(decode_key(&key)?);
+		}
+		tracing::info!("Nonce registry loaded with {} used nonces", used.len());
+		Ok(NonceRegistry { used: Arc::new(Mutex::new(used)), db: Some(db) })
+	}
+
+	/// Registry that is not persisted, used by tests and dry runs.
+	pub fn in_memory() -> Self {
+		NonceRegistry { used: Arc::new(Mutex::new(HashSet::new())), db: None }
+	}
+
+	pub fn contains(&self, transfer_id: BridgeTransferId, nonce: Nonce) -> bool {
+		self.used
+			.lock()
+			.expect("nonce registry lock poisoned")
+			.contains(&(transfer_id, nonce))
+	}
+
+	/// Reserves the pair for a completion. Returns false if it is already used or reserved,
+	/// the check and the reservation being a single insert.
+	pub fn reserve(&self, transfer_id: BridgeTransferId, nonce: Nonce) -> bool {
+		self.used
+			.lock()
+			.expect("nonce registry lock poisoned")
+			.insert((transfer_id, nonce))
+	}
+
+	/// Releases a reservation whose completion failed, so that it can be retried.
+	pub fn release(&self, transfer_id: BridgeTransferId, nonce: Nonce) {
+		self.used
+			.lock()
+			.expect("nonce registry lock poisoned")
+			.remove(&(transfer_id, nonce));
+	}
+
+	/// Records the pair as used once its completion is on chain.
+	pub fn register(
+		&self,
+		transfer_id: BridgeTransferId,
+		nonce: Nonce,
+	) -> Result<(), anyhow::Error> {
+		self.used
+			.lock()
+			.expect("nonce registry lock poisoned")
+			.insert((transfer_id, nonce));
+		if let Some(db) = &self.db {
+			db.insert(encode_key(transfer_id, nonce), &[])?;
+			db.flush()?;
+		}
+		Ok(())
+	}
+}
+
+fn encode_key(transfer_id: BridgeTransferId, nonce: Nonce) -> [u8; KEY_LEN] {
+	let mut key = [0u8; KEY_LEN];
+	key[..32].copy_from_slice(&transfer_id.0);
+	key[32..].copy_from_slice(&nonce.0.to_be_bytes());
+	key
+}
+
+fn decode_key(key: &[u8]) -> Result<(BridgeTransferId, Nonce), anyhow::Error> {
+	if key.len() != KEY_LEN {
+		anyhow::bail!("Invalid nonce registry key length: {}", key.len());
+	}
+	let mut transfer_id = [0u8; 32];
+	transfer_id.copy_from_slice(&key[..32]);
+	let mut nonce = [0u8; 16];
+	nonce.copy_from_slice(&key[32..]);
+	Ok((BridgeTransferId(transfer_id), Nonce(u128::from_be_bytes(nonce))))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_reserve_twice() {
+		let registry = NonceRegistry::in_memory();
+		let transfer_id = BridgeTransferId([1; 32]);
+		assert!(!registry.contains(transfer_id, Nonce(7)));
+		assert!(registry.reserve(transfer_id, Nonce(7)));
+		assert!(registry.contains(transfer_id, Nonce(7)));
+		assert!(!registry.reserve(transfer_id, Nonce(7)));
+		// Same transfer with another nonce is a different completion.
+		assert!(!registry.contains(transfer_id, Nonce(8)));
+	}
+
+	#[test]
+	fn test_released_reservation_can_be_reserved_again() {
+		let registry = NonceRegistry::in_memory();
+		let transfer_id = BridgeTransferId([1; 32]);
+		assert!(registry.reserve(transfer_id, Nonce(7)));
+		registry.release(transfer_id, Nonce(7));
+		assert!(registry.reserve(transfer_id, Nonce(7)));
+	}
+
+	#[test]
+	fn test_concurrent_reservations() {
+		let registry = NonceRegistry::in_memory();
+		let transfer_id = BridgeTransferId([3; 32]);
+		let reserved = std::thread::scope(|scope| {
+			let handles: Vec<_> = (0..8)
+				.map(|_| scope.spawn(|| registry.reserve(transfer_id, Nonce(7))))
+				.collect();
+			handles.into_iter().filter(|handle| handle.join().unwrap()).count()
+		});
+		assert_eq!(reserved, 1);
+	}
+
+	#[test]
+	fn test_registry_is_persisted() {
+		let dir = tempfile::tempdir().unwrap();
+		let transfer_id = BridgeTransferId([2; 32]);
+		{
+			let registry = NonceRegistry::open(dir.path()).unwrap();
+			assert!(registry.reserve(transfer_id, Nonce(u128::MAX)));
+			// a reservation alone is not persisted
+			assert!(registry.reserve(transfer_id, Nonce(1)));
+			registry.register(transfer_id, Nonce(u128::MAX)).unwrap();
+		}
+		let registry = NonceRegistry::open(dir.path()).unwrap();
+		assert!(registry.contains(transfer_id, Nonce(u128::MAX)));
+		assert!(!registry.contains(transfer_id, Nonce(1)));
+	}
+}
@@ -1,14 +1,30 @@
+use crate::chains::movement::{client_framework::MovementClientFramework, utils::MovementAddress};
 use anyhow::Error;
 use bridge_config::common::movement::MovementConfig;
+use bridge_util::{
+	chains::bridge_contracts::{BridgeContract, BridgeContractError},
+	encoding::hex_to_bytes,
+	types::{Amount, BridgeAddress, BridgeTransferId, HashLock, HashLockPreImage},
+};
 use futures::prelude::*;
 use poem::{
-	get, handler, listener::TcpListener, middleware::Tracing, web::Data, EndpointExt, IntoResponse,
-	Response, Route, Server,
+	get, handler,
+	http::StatusCode,
+	listener::TcpListener,
+	middleware::Tracing,
+	post,
+	web::{Data, Json, Path},
+	Endpoint, EndpointExt, IntoResponse, Request, Response, Route, Server,
 };
+use prometheus::{Encoder as _, Registry, TextEncoder};
+use serde::{Deserialize, Serialize};
 use std::future::Future;
+use std::str::FromStr;
 use std::sync::Arc;
+use subtle::ConstantTimeEq as _;
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
+use tokio::sync::Mutex;
 use tracing::info;
 
 struct RestContext {
@@ -18,6 +34,7 @@ struct RestContext {
 pub struct BridgeRest {
 	pub url: String,
 	context: Arc<RestContext>,
+	metrics_registry: Registry,
 }
 
 impl BridgeRest {
@@ -30,7 +47,13 @@ impl BridgeRest {
 		let url = format!("{}:{}", conf.rest_listener_hostname, conf.rest_port);
 
 		let context = RestContext { request_tx };
-		Ok(Self { url, context: Arc::new(context) })
+		Ok(Self { url, context: Arc::new(context), metrics_registry: Registry::new() })
+	}
+
+	/// Serves the metrics of `registry` on `/metrics`, no metric is served otherwise.
+	pub fn with_metrics_registry(mut self, registry: Registry) -> Self {
+		self.metrics_registry = registry;
+		self
 	}
 
 	pub fn run_service(&self) -> impl Future<Output = Result<(), Error>> + Send {
@@ -42,7 +65,11 @@ impl BridgeRest {
 	}
 
 	pub fn create_routes(&self) -> impl EndpointExt {
-		Route::new().at("/health", get(health)).with(Tracing).data(self.context.clone())
+		Route::new()
+			.at("/health", get(health))
+			.at("/metrics", get(metrics).data(self.metrics_registry.clone()))
+			.with(Tracing)
+			.data(self.context.clone())
 	}
 }
 
@@ -53,3 +80,257 @@ async fn health(context: Data<&Arc<RestContext>>) -> Result<Response, anyhow::Er
 	let resp = rx.await?;
 	Ok(resp.into_response())
 }
+
+/// Renders the registered metrics in the Prometheus text format.
+#[handler]
+fn metrics(Data(registry): Data<&Registry>) -> poem::Result<Vec<u8>> {
+	let mut buffer = Vec::new();
+	TextEncoder::new().encode(&registry.gather(), &mut buffer).map_err(|err| {
+		poem::Error::from_string(err.to_string(), StatusCode::INTERNAL_SERVER_ERROR)
+	})?;
+	Ok(buffer)
+}
+
+/// Header that must carry the configured API key on every [`MovementBridgeRouter`] request.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Rejects the requests whose [`API_KEY_HEADER`] does not carry `api_key`.
+/// The key is compared in constant time, so the response times do not leak it.
+fn require_api_key<E: Endpoint>(endpoint: E, api_key: Arc<String>) -> impl Endpoint {
+	endpoint.around(move |endpoint, request: Request| {
+		let api_key = api_key.clone();
+		async move {
+			match request.header(API_KEY_HEADER) {
+				Some(key) if bool::from(key.as_bytes().ct_eq(api_key.as_bytes())) => {
+					endpoint.call(request).await.map(IntoResponse::into_response)
+				}
+				_ => Err(poem::Error::from_status(StatusCode::UNAUTHORIZED)),
+			}
+		}
+	})
+}
+
+struct MovementRouterContext {
+	client: Mutex<MovementClientFramework>,
+}
+
+/// REST routes to trigger the Movement counterparty bridge operations.
+///
+/// Built on `poem` rather than `axum`: [`BridgeRest`] and the other services of the workspace
+/// already serve their endpoints with `poem`, which avoids a second HTTP framework.
+pub struct MovementBridgeRouter {
+	api_key: String,
+	context: Arc<MovementRouterContext>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LockRequest {
+	pub bridge_transfer_id: String,
+	pub hash_lock: String,
+	pub initiator: String,
+	pub recipient: String,
+	pub amount: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CompleteRequest {
+	pub bridge_transfer_id: String,
+	pub preimage: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AbortRequest {
+	pub bridge_transfer_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OperationResponse {
+	pub bridge_transfer_id: String,
+	pub status: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TransferDetailsResponse {
+	pub bridge_transfer_id: String,
+	pub initiator: String,
+	pub recipient: String,
+	pub hash_lock: String,
+	pub time_lock: u64,
+	pub amount: u64,
+	pub state: u8,
+}
+
+impl MovementBridgeRouter {
+	/// Fails if `api_key` is empty, as an empty header would then be accepted.
+	pub fn new(client: MovementClientFramework, api_key: String) -> Result<Self, Error> {
+		if api_key.is_empty() {
+			anyhow::bail!("The Movement bridge router API key must not be empty");
+		}
+		let context = MovementRouterContext { client: Mutex::new(client) };
+		Ok(Self { api_key, context: Arc::new(context) })
+	}
+
+	pub fn create_routes(&self) -> impl Endpoint {
+		let routes = Route::new()
+			.at("/bridge/movement/lock", post(movement_lock))
+			.at("/bridge/movement/complete", post(movement_complete))
+			.at("/bridge/movement/abort", post(movement_abort))
+			.at("/bridge/movement/transfer/:id", get(movement_transfer_details))
+			.with(Tracing)
+			.data(self.context.clone());
+		require_api_key(routes, Arc::new(self.api_key.clone()))
+	}
+}
+
+fn bad_request(field: &str) -> poem::Error {
+	poem::Error::from_string(format!("Invalid {field}"), StatusCode::BAD_REQUEST)
+}
+
+fn contract_error(err: BridgeContractError) -> poem::Error {
+	poem::Error::from_string(err.to_string(), StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+fn parse_transfer_id(s: &str) -> poem::Result<BridgeTransferId> {
+	BridgeTransferId::parse(s.trim_start_matches("0x"))
+		.map_err(|_| bad_request("bridge_transfer_id"))
+}
+
+fn parse_bytes32(s: &str, field: &str) -> poem::Result<[u8; 32]> {
+	hex_to_bytes(s)
+		.map_err(|_| bad_request(field))?
+		.try_into()
+		.map_err(|_| bad_request(field))
+}
+
+#[handler]
+async fn movement_lock(
+	Json(request): Json<LockRequest>,
+	context: Data<&Arc<MovementRouterContext>>,
+) -> poem::Result<Json<OperationResponse>> {
+	let bridge_transfer_id = parse_transfer_id(&request.bridge_transfer_id)?;
+	let hash_lock = HashLock(parse_bytes32(&request.hash_lock, "hash_lock")?);
+	let initiator = hex_to_bytes(&request.initiator).map_err(|_| bad_request("initiator"))?;
+	let recipient =
+		MovementAddress::from_str(&request.recipient).map_err(|_| bad_request("recipient"))?;
+
+	let mut client = context.client.lock().await;
+	client
+		.lock_bridge_transfer(
+			bridge_transfer_id,
+			hash_lock,
+			BridgeAddress(initiator),
+			BridgeAddress(recipient),
+			Amount(request.amount),
+		)
+		.await
+		.map_err(contract_error)?;
+
+	Ok(Json(OperationResponse {
+		bridge_transfer_id: request.bridge_transfer_id,
+		status: "locked".to_string(),
+	}))
+}
+
+#[handler]
+async fn movement_complete(
+	Json(request): Json<CompleteRequest>,
+	context: Data<&Arc<MovementRouterContext>>,
+) -> poem::Result<Json<OperationResponse>> {
+	let bridge_transfer_id = parse_transfer_id(&request.bridge_transfer_id)?;
+	let preimage = HashLockPreImage(parse_bytes32(&request.preimage, "preimage")?);
+
+	let mut client = context.client.lock().await;
+	client
+		.counterparty_complete_bridge_transfer(bridge_transfer_id, preimage)
+		.await
+		.map_err(contract_error)?;
+
+	Ok(Json(OperationResponse {
+		bridge_transfer_id: request.bridge_transfer_id,
+		status: "completed".to_string(),
+	}))
+}
+
+#[handler]
+async fn movement_abort(
+	Json(request): Json<AbortRequest>,
+	context: Data<&Arc<MovementRouterContext>>,
+) -> poem::Result<Json<OperationResponse>> {
+	let bridge_transfer_id = parse_transfer_id(&request.bridge_transfer_id)?;
+
+	let mut client = context.client.lock().await;
+	client.abort_bridge_transfer(bridge_transfer_id).await.map_err(contract_error)?;
+
+	Ok(Json(OperationResponse {
+		bridge_transfer_id: request.bridge_transfer_id,
+		status: "aborted".to_string(),
+	}))
+}
+
+#[handler]
+async fn movement_transfer_details(
+	Path(id): Path<String>,
+	context: Data<&Arc<MovementRouterContext>>,
+) -> poem::Result<Json<TransferDetailsResponse>> {
+	let bridge_transfer_id = parse_transfer_id(&id)?;
+
+	let mut client = context.client.lock().await;
+	let details = client
+		.get_bridge_transfer_details_counterparty(bridge_transfer_id)
+		.await
+		.map_err(contract_error)?
+		.ok_or_else(|| poem::Error::from_status(StatusCode::NOT_FOUND))?;
+
+	Ok(Json(TransferDetailsResponse {
+		bridge_transfer_id: hex::encode(details.bridge_transfer_id.0),
+		initiator: format!("0x{}", hex::encode(&details.initiator.0)),
+		recipient: details.recipient.0.to_string(),
+		hash_lock: hex::encode(details.hash_lock.0),
+		time_lock: details.time_lock.0,
+		amount: details.amount.0,
+		state: details.state,
+	}))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use poem::test::TestClient;
+
+	#[handler]
+	fn ok() -> &'static str {
+		"OK"
+	}
+
+	fn client() -> TestClient<impl Endpoint> {
+		TestClient::new(require_api_key(Route::new().at("/", get(ok)), Arc::new("key".to_string())))
+	}
+
+	#[tokio::test]
+	async fn test_requests_with_the_api_key_are_accepted() {
+		let response = client().get("/").header(API_KEY_HEADER, "key").send().await;
+		response.assert_status_is_ok();
+		response.assert_text("OK").await;
+	}
+
+	#[tokio::test]
+	async fn test_requests_without_the_api_key_are_rejected() {
+		let client = client();
+		client.get("/").send().await.assert_status(StatusCode::UNAUTHORIZED);
+		for key in ["", "ke", "keys", "KEY"] {
+			let response = client.get("/").header(API_KEY_HEADER, key).send().await;
+			response.assert_status(StatusCode::UNAUTHORIZED);
+		}
+	}
+
+	#[test]
+	fn test_parse_request_fields() {
+		let id = "0x".to_string() + &"ab".repeat(32);
+		assert_eq!(parse_transfer_id(&id).unwrap(), BridgeTransferId([0xab; 32]));
+		assert!(parse_transfer_id("0xzz").is_err());
+
+		assert_eq!(parse_bytes32(&"01".repeat(32), "hash_lock").unwrap(), [1; 32]);
+		let err = parse_bytes32(&"01".repeat(31), "hash_lock").unwrap_err();
+		assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+	}
+}
@@ -15,6 +15,8 @@ pub fn setup_eth(config: &mut EthConfig, testing_config: &mut TestingConfig) ->
 	//update config with Anvil address
 	let signer: PrivateKeySigner = anvil.keys()[1].clone().into();
 	config.signer_private_key = signer.to_bytes().to_string();
+	// Anvil only mines blocks on new transactions, don't wait for confirmations
+	config.eth_confirmations_required = 0;
 	for key in anvil.keys().iter().skip(2) {
 		let privkey: PrivateKeySigner = (key.clone()).into();
 		testing_config
@@ -39,8 +39,13 @@ pub enum TransferActionType {
 		initiator: BridgeAddress<Vec<u8>>,
 		recipient: BridgeAddress<Vec<u8>>,
 		amount: Amount,
+		/// Fee claimed by the relayer once the lock succeeded, none when zero.
+		relayer_fee: Amount,
 	},
-	WaitAndCompleteInitiator(u64, HashLockPreImage),
+	/// Completes the transfer on the initiator chain with the pre-image of its hash lock.
+	/// The fourth field is the number of times a transient failure of the completion is
+	/// retried, and the last one the fee claimed by the relayer once the completion succeeded.
+	WaitAndCompleteInitiator(u64, HashLockPreImage, HashLock, u32, Amount),
 	RefundInitiator,
 	TransferDone,
 	NoAction,
@@ -0,0 +1,61 @@
+//! Validation of the raw recipient addresses of bridge transfers, which are sent as bytes
+//! to the other chain.
+
+use crate::chains::bridge_contracts::BridgeContractError;
+use alloy::primitives::Address;
+use aptos_sdk::types::account_address::AccountAddress;
+
+/// Parses the recipient of a transfer to Ethereum, which must be a 20 bytes EVM address.
+pub fn derive_eth_recipient(raw: &[u8]) -> Result<Address, BridgeContractError> {
+	if raw.len() != Address::len_bytes() {
+		return Err(BridgeContractError::InvalidRecipientLength {
+			expected: Address::len_bytes(),
+			actual: raw.len(),
+		});
+	}
+	Ok(Address::from_slice(raw))
+}
+
+/// Parses the recipient of a transfer to Movement, which must be a 32 bytes account address.
+pub fn derive_movement_recipient(raw: &[u8]) -> Result<AccountAddress, BridgeContractError> {
+	AccountAddress::from_bytes(raw).map_err(|_| BridgeContractError::InvalidRecipientLength {
+		expected: AccountAddress::LENGTH,
+		actual: raw.len(),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::chains::bridge_contracts::BridgeContractErrorCode;
+
+	#[test]
+	fn test_derive_eth_recipient() {
+		assert_eq!(derive_eth_recipient(&[0x11; 20]).unwrap(), Address::repeat_byte(0x11));
+
+		for len in [0, 19, 21, 32] {
+			let err = derive_eth_recipient(&vec![0x11; len]).unwrap_err();
+			assert!(matches!(
+				err,
+				BridgeContractError::InvalidRecipientLength { expected: 20, actual } if actual == len
+			));
+			assert_eq!(err.code(), BridgeContractErrorCode::DecodingError);
+		}
+	}
+
+	#[test]
+	fn test_derive_movement_recipient() {
+		assert_eq!(
+			derive_movement_recipient(&[0x22; 32]).unwrap(),
+			AccountAddress::new([0x22; 32])
+		);
+
+		for len in [0, 20, 31, 33] {
+			let err = derive_movement_recipient(&vec![0x22; len]).unwrap_err();
+			assert!(matches!(
+				err,
+				BridgeContractError::InvalidRecipientLength { expected: 32, actual } if actual == len
+			));
+		}
+	}
+}
@@ -1,4 +1,6 @@
-use crate::types::{BridgeTransferDetailsCounterparty, LockDetails};
+use crate::types::{BridgeTransferDetailsCounterparty, ChainId, LockDetails, Nonce};
+use aptos_sdk::crypto::HashValue;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::fmt;
 use thiserror::Error;
 use tokio_stream::Stream;
@@ -29,8 +31,8 @@ pub enum BridgeContractError {
 	FunctionViewError,
 	#[error("Failed to initiate bridge transfer")]
 	InitiateTransferError,
-	#[error("Failed to complete bridge transfer")]
-	CompleteTransferError,
+	#[error("Failed to complete bridge transfer: {0}")]
+	CompleteTransferError(String),
 	#[error("Failed to parse preimage")]
 	ParsePreimageError,
 	#[error("Contract address parse error")]
@@ -43,10 +45,12 @@ pub enum BridgeContractError {
 	ModuleViewError,
 	#[error("Failed to serialize view args")]
 	ViewSerializationError,
-	#[error("Failed to lock bridge transfer")]
-	LockTransferError,
-	#[error("Failed to abort bridge transfer")]
-	AbortTransferError,
+	#[error("Failed to lock bridge transfer: {0}")]
+	LockTransferError(String),
+	#[error("Failed to abort bridge transfer: {0}")]
+	AbortTransferError(String),
+	#[error("Failed to refund bridge transfer: {0}")]
+	RefundTransferError(String),
 	#[error("Address not set")]
 	AddressNotSet,
 	#[error("Error getting the signer")]
@@ -57,14 +61,130 @@ pub enum BridgeContractError {
 	OnChainError(String),
 	#[error("Error during decoding address:{0}")]
 	BadAddressEncoding(String),
+	#[error("Invalid recipient address: expected {expected} bytes, got {actual}")]
+	InvalidRecipientLength { expected: usize, actual: usize },
 	#[error("Error during deserializing an event :{1:?} : {0}")]
 	EventDeserializingFail(String, BridgeContractEventType),
+	#[error("Chain {chain} unavailable: {reason}")]
+	ChainUnavailable { chain: ChainId, reason: String },
+	#[error("Nonce {1} already used for transfer {0}")]
+	NonceAlreadyUsed(BridgeTransferId, Nonce),
+	#[error("Transaction {hash:#x} not confirmed after {elapsed_secs}s")]
+	TransactionTimeout { hash: HashValue, elapsed_secs: u64 },
+	#[error("Time lock {time_lock} is outside of the allowed range [{min}, {max}]")]
+	InvalidTimeLock { time_lock: u64, min: u64, max: u64 },
+	#[error("Transport error: {0}")]
+	TransportError(String),
+	#[error("Contract call reverted: {0}")]
+	ContractRevert(String),
+	#[error("Failed to decode: {0}")]
+	DecodingError(String),
+	#[error("Not found: {0}")]
+	NotFound(String),
+	#[error("The pre-image does not match the hash lock of transfer {0}")]
+	InvalidPreImage(BridgeTransferId),
+	#[error("Transaction simulation failed with status: {0}")]
+	SimulationFailed(String),
+	#[error("Call still failing after {attempts} attempts in {elapsed_secs}s: {last_error}")]
+	RetriesExhausted { attempts: u32, elapsed_secs: u64, last_error: String },
+}
+
+/// The kind of a [`BridgeContractError`], for the callers handling errors programmatically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BridgeContractErrorCode {
+	/// The chain could not be reached or answered with a transport failure.
+	TransportError,
+	/// The contract call reverted.
+	ContractRevert,
+	/// A value could not be decoded or converted.
+	DecodingError,
+	/// A transaction was not confirmed in time.
+	TimeoutError,
+	/// The requested transfer or value does not exist.
+	NotFound,
+	/// Any other error.
+	Other,
 }
 
 impl BridgeContractError {
 	pub fn generic<E: std::error::Error>(e: E) -> Self {
 		Self::GenericError(e.to_string())
 	}
+
+	pub fn code(&self) -> BridgeContractErrorCode {
+		match self {
+			Self::ChainUnavailable { .. } | Self::TransportError(_) | Self::InvalidUrl => {
+				BridgeContractErrorCode::TransportError
+			}
+			Self::ContractRevert(_) | Self::SimulationFailed(_) => {
+				BridgeContractErrorCode::ContractRevert
+			}
+			Self::DecodingError(_)
+			| Self::SerializationError
+			| Self::ConversionFailed(_)
+			| Self::ParsePreimageError
+			| Self::BadAddressEncoding(_)
+			| Self::InvalidRecipientLength { .. }
+			| Self::EventDeserializingFail(..)
+			| Self::TransferIdExtractionError
+			| Self::InvalidResponseLength => BridgeContractErrorCode::DecodingError,
+			Self::TransactionTimeout { .. } | Self::RetriesExhausted { .. } => {
+				BridgeContractErrorCode::TimeoutError
+			}
+			Self::NotFound(_) | Self::AddressNotSet => BridgeContractErrorCode::NotFound,
+			_ => BridgeContractErrorCode::Other,
+		}
+	}
+
+	pub fn chain_unavailable(chain: ChainId, reason: impl ToString) -> Self {
+		Self::ChainUnavailable { chain, reason: reason.to_string() }
+	}
+
+	/// Network errors can be retried, unlike the other errors.
+	pub fn is_chain_unavailable(&self) -> bool {
+		matches!(self, Self::ChainUnavailable { .. })
+	}
+
+	/// Keeps a `ChainUnavailable` error so the caller can still retry it,
+	/// any other error is replaced by `other`.
+	pub fn unless_chain_unavailable(self, other: BridgeContractError) -> Self {
+		if self.is_chain_unavailable() {
+			self
+		} else {
+			other
+		}
+	}
+
+	/// Like [`Self::unless_chain_unavailable`], but builds the replacing error from
+	/// the message of the original one so it is not lost.
+	pub fn wrap_unless_chain_unavailable(self, wrap: impl FnOnce(String) -> Self) -> Self {
+		if self.is_chain_unavailable() {
+			self
+		} else {
+			wrap(self.to_string())
+		}
+	}
+}
+
+impl From<alloy::contract::Error> for BridgeContractError {
+	fn from(err: alloy::contract::Error) -> Self {
+		use alloy::contract::Error;
+		use alloy::transports::RpcError;
+
+		match &err {
+			Error::TransportError(RpcError::ErrorResp(payload))
+				if payload.message.contains("revert") =>
+			{
+				Self::ContractRevert(err.to_string())
+			}
+			// the node rejected the call for another reason, e.g. insufficient funds
+			Error::TransportError(RpcError::ErrorResp(_)) => Self::OnChainError(err.to_string()),
+			Error::TransportError(RpcError::SerError(_) | RpcError::DeserError { .. })
+			| Error::AbiError(_) => Self::DecodingError(err.to_string()),
+			Error::TransportError(_) => Self::TransportError(err.to_string()),
+			_ => Self::OnChainError(err.to_string()),
+		}
+	}
 }
 
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
@@ -93,9 +213,10 @@ pub enum BridgeContractEventType {
 	CounterPartyCompleted,
 	Cancelled,
 	Refunded,
+	Reorged,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BridgeContractEvent<A> {
 	Initiated(BridgeTransferDetails<A>),
 	Locked(LockDetails<A>),
@@ -103,9 +224,27 @@ pub enum BridgeContractEvent<A> {
 	CounterPartyCompleted(BridgeTransferId, HashLockPreImage),
 	Cancelled(BridgeTransferId),
 	Refunded(BridgeTransferId),
+	/// An event of the transfer was dropped before being relayed, its block was orphaned.
+	Reorged(BridgeTransferId),
 }
 
 impl<A> BridgeContractEvent<A> {
+	/// Serializes the event for the audit logs.
+	pub fn to_json(&self) -> Result<String, serde_json::Error>
+	where
+		A: Serialize,
+	{
+		serde_json::to_string(self)
+	}
+
+	/// Deserializes an event serialized with [`Self::to_json`].
+	pub fn from_json(s: &str) -> Result<Self, serde_json::Error>
+	where
+		A: DeserializeOwned,
+	{
+		serde_json::from_str(s)
+	}
+
 	pub fn bridge_transfer_id(&self) -> BridgeTransferId {
 		match self {
 			Self::Initiated(details) => details.bridge_transfer_id,
@@ -113,7 +252,20 @@ impl<A> BridgeContractEvent<A> {
 			Self::InitiatorCompleted(id)
 			| Self::CounterPartyCompleted(id, _)
 			| Self::Cancelled(id)
-			| Self::Refunded(id) => *id,
+			| Self::Refunded(id)
+			| Self::Reorged(id) => *id,
+		}
+	}
+
+	pub fn event_type(&self) -> BridgeContractEventType {
+		match self {
+			Self::Initiated(_) => BridgeContractEventType::Initiated,
+			Self::Locked(_) => BridgeContractEventType::Locked,
+			Self::InitiatorCompleted(_) => BridgeContractEventType::InitiatorCompleted,
+			Self::CounterPartyCompleted(..) => BridgeContractEventType::CounterPartyCompleted,
+			Self::Cancelled(_) => BridgeContractEventType::Cancelled,
+			Self::Refunded(_) => BridgeContractEventType::Refunded,
+			Self::Reorged(_) => BridgeContractEventType::Reorged,
 		}
 	}
 
@@ -135,6 +287,7 @@ impl<A> fmt::Display for BridgeContractEvent<A> {
 			Self::CounterPartyCompleted(_, _) => "CounterPartyCompleted",
 			Self::Cancelled(_) => "Cancelled",
 			Self::Refunded(_) => "Refunded",
+			Self::Reorged(_) => "Reorged",
 		};
 		write!(f, "Contract event: {}/ transfer id: {}", kind, self.bridge_transfer_id(),)
 	}
@@ -196,9 +349,105 @@ pub trait BridgeContract<A>: Clone + Unpin + Send + Sync {
 		&mut self,
 		bridge_transfer_id: BridgeTransferId,
 	) -> BridgeContractResult<()>;
+
+	/// Claims the relayer fee of a transfer. Does nothing on chains without relayer fees.
+	async fn claim_relayer_fee(
+		&mut self,
+		_bridge_transfer_id: BridgeTransferId,
+	) -> BridgeContractResult<()> {
+		Ok(())
+	}
 }
 
 #[async_trait::async_trait]
 pub trait BridgeContractWETH9: Clone + Unpin + Send + Sync {
 	async fn deposit_weth(&mut self, amount: Amount) -> BridgeContractWETH9Result<()>;
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::TimeLock;
+	use alloy::rpc::json_rpc::ErrorPayload;
+	use alloy::transports::{RpcError, TransportErrorKind};
+
+	#[test]
+	fn test_event_json_roundtrip() {
+		let details = BridgeTransferDetails {
+			bridge_transfer_id: BridgeTransferId([1; 32]),
+			initiator: BridgeAddress(vec![2; 20]),
+			recipient: BridgeAddress(vec![3; 32]),
+			hash_lock: HashLock([4; 32]),
+			time_lock: TimeLock(60),
+			amount: Amount(100),
+			state: 1,
+			initiated_at: 1700000000,
+		};
+		let lock_details = LockDetails {
+			bridge_transfer_id: BridgeTransferId([5; 32]),
+			initiator: BridgeAddress(vec![6; 20]),
+			recipient: BridgeAddress(vec![7; 32]),
+			hash_lock: HashLock([8; 32]),
+			time_lock: TimeLock(30),
+			amount: Amount(u64::MAX),
+		};
+		let events = [
+			BridgeContractEvent::Initiated(details),
+			BridgeContractEvent::Locked(lock_details),
+			BridgeContractEvent::InitiatorCompleted(BridgeTransferId([9; 32])),
+			BridgeContractEvent::CounterPartyCompleted(
+				BridgeTransferId([10; 32]),
+				HashLockPreImage([11; 32]),
+			),
+			BridgeContractEvent::Cancelled(BridgeTransferId([12; 32])),
+			BridgeContractEvent::Refunded(BridgeTransferId([13; 32])),
+			BridgeContractEvent::Reorged(BridgeTransferId([14; 32])),
+		];
+		for event in events {
+			let json = event.to_json().unwrap();
+			assert_eq!(BridgeContractEvent::<Vec<u8>>::from_json(&json).unwrap(), event, "{json}");
+		}
+	}
+
+	fn error_response(message: &str) -> alloy::contract::Error {
+		alloy::contract::Error::TransportError(RpcError::ErrorResp(ErrorPayload {
+			code: 3,
+			message: message.into(),
+			data: None,
+		}))
+	}
+
+	#[test]
+	fn test_contract_error_codes() {
+		let revert = BridgeContractError::from(error_response("execution reverted: not found"));
+		assert!(matches!(revert, BridgeContractError::ContractRevert(_)));
+		assert_eq!(revert.code(), BridgeContractErrorCode::ContractRevert);
+
+		let rejected = BridgeContractError::from(error_response("insufficient funds"));
+		assert_eq!(rejected.code(), BridgeContractErrorCode::Other);
+
+		let unreachable = BridgeContractError::from(alloy::contract::Error::TransportError(
+			TransportErrorKind::backend_gone(),
+		));
+		assert!(matches!(unreachable, BridgeContractError::TransportError(_)));
+		assert_eq!(unreachable.code(), BridgeContractErrorCode::TransportError);
+	}
+
+	#[test]
+	fn test_error_codes() {
+		let chain_unavailable = BridgeContractError::chain_unavailable(ChainId::ONE, "refused");
+		assert_eq!(chain_unavailable.code(), BridgeContractErrorCode::TransportError);
+		let timeout =
+			BridgeContractError::TransactionTimeout { hash: HashValue::zero(), elapsed_secs: 5 };
+		assert_eq!(timeout.code(), BridgeContractErrorCode::TimeoutError);
+		assert_eq!(
+			timeout.to_string(),
+			format!("Transaction 0x{} not confirmed after 5s", "0".repeat(64))
+		);
+		let decoding = BridgeContractError::DecodingError("could not decode storage".into());
+		assert_eq!(decoding.code(), BridgeContractErrorCode::DecodingError);
+		let not_found = BridgeContractError::NotFound("transfer".into());
+		assert_eq!(not_found.code(), BridgeContractErrorCode::NotFound);
+		assert_eq!(BridgeContractError::MintError.code(), BridgeContractErrorCode::Other);
+	}
+}
@@ -0,0 +1,52 @@
+//! Conversions between hex strings and the fixed size byte arrays used by the bridge contracts.
+
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum EncodingError {
+	#[error("Invalid hex string: {0}")]
+	InvalidHex(String),
+	#[error("Expected at most 32 bytes, got {0}")]
+	TooLong(usize),
+}
+
+/// Decodes a hex string, with or without a `0x` prefix.
+pub fn hex_to_bytes(hex: &str) -> Result<Vec<u8>, EncodingError> {
+	hex::decode(hex.trim_start_matches("0x")).map_err(|e| EncodingError::InvalidHex(e.to_string()))
+}
+
+/// Right pads `bytes` with zeros to 32 bytes.
+pub fn normalize_to_32_bytes(bytes: &[u8]) -> Result<[u8; 32], EncodingError> {
+	if bytes.len() > 32 {
+		return Err(EncodingError::TooLong(bytes.len()));
+	}
+	let mut normalized = [0u8; 32];
+	normalized[..bytes.len()].copy_from_slice(bytes);
+	Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_hex_to_bytes() {
+		assert_eq!(hex_to_bytes("0x0a0b"), Ok(vec![0x0a, 0x0b]));
+		assert_eq!(hex_to_bytes("0a0b"), Ok(vec![0x0a, 0x0b]));
+		assert_eq!(hex_to_bytes(""), Ok(vec![]));
+		assert!(matches!(hex_to_bytes("0x0a0"), Err(EncodingError::InvalidHex(_))));
+		assert!(matches!(hex_to_bytes("0xzz"), Err(EncodingError::InvalidHex(_))));
+	}
+
+	#[test]
+	fn test_normalize_to_32_bytes() {
+		assert_eq!(normalize_to_32_bytes(&[]), Ok([0u8; 32]));
+
+		let mut expected = [0u8; 32];
+		expected[..6].copy_from_slice(b"secret");
+		assert_eq!(normalize_to_32_bytes(b"secret"), Ok(expected));
+
+		assert_eq!(normalize_to_32_bytes(&[7u8; 32]), Ok([7u8; 32]));
+		assert_eq!(normalize_to_32_bytes(&[7u8; 33]), Err(EncodingError::TooLong(33)));
+	}
+}
@@ -1,5 +1,6 @@
 use crate::chains::bridge_contracts::BridgeContractEvent;
-use crate::types::ChainId;
+use crate::transfer_state::InvalidTransitionError;
+use crate::types::{BridgeTransferId, ChainId};
 use std::fmt;
 use thiserror::Error;
 
@@ -15,6 +16,12 @@ pub enum InvalidEventError {
 	StateNotFound,
 	#[error("Error during event indexing:{0}")]
 	IndexingFailed(String),
+	#[error("An event of transfer {0} was orphaned by a chain reorg")]
+	Reorged(BridgeTransferId),
+	#[error("Event doesn't match the transfer contract state: {0}")]
+	InvalidTransition(#[from] InvalidTransitionError),
+	#[error("Duplicate event received for transfer {0}")]
+	DuplicateEvent(BridgeTransferId),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -1,7 +1,10 @@
 pub mod actions;
+pub mod address;
 pub mod chains;
+pub mod encoding;
 pub mod events;
 pub mod states;
+pub mod transfer_state;
 pub mod types;
 
 pub use crate::actions::ActionExecError;
@@ -1,5 +1,6 @@
 use crate::chains::bridge_contracts::BridgeContractEvent;
 use crate::events::{InvalidEventError, TransferEvent};
+use crate::transfer_state::{self, BridgeTransferState};
 use crate::types::Amount;
 use crate::types::BridgeAddress;
 use crate::types::BridgeTransferDetails;
@@ -9,6 +10,10 @@ use crate::types::{BridgeTransferId, ChainId, HashLock, TimeLock};
 use crate::TransferAction;
 use crate::TransferActionType;
 use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Number of times a transient failure to complete a transfer on the initiator chain is retried.
+pub const COMPLETE_INITIATOR_MAX_RETRIES: u32 = 10;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TransferAddress(Vec<u8>);
@@ -63,6 +68,10 @@ pub struct TransferState {
 	pub contract_state: u8,
 	//Max number time action are retry for the whole transfer.
 	pub retry_on_error: usize,
+	// When the relayer received the initiation, to measure the duration of the transfer.
+	pub received_at: Instant,
+	// Fee claimed by the relayer for the transfer, set from the relayer's configuration.
+	pub relayer_fee: Amount,
 }
 
 impl fmt::Display for TransferState {
@@ -76,9 +85,31 @@ impl fmt::Display for TransferState {
 }
 
 impl TransferState {
+	/// Time elapsed since the relayer received the initiation.
+	pub fn age(&self) -> Duration {
+		self.received_at.elapsed()
+	}
+
+	/// The contract state reached by the transfer when `event` is applied.
+	pub fn next_contract_state<A>(
+		&self,
+		event: &BridgeContractEvent<A>,
+	) -> Result<BridgeTransferState, InvalidEventError> {
+		let current = BridgeTransferState::try_from(self.contract_state)?;
+		Ok(transfer_state::transition(current, event)?)
+	}
+
 	pub fn validate_event<A: std::fmt::Debug>(
 		&self,
 		event: &TransferEvent<A>,
+	) -> Result<(), InvalidEventError> {
+		self.validate_event_kind(event)?;
+		self.next_contract_state(&event.contract_event).map(|_| ())
+	}
+
+	fn validate_event_kind<A: std::fmt::Debug>(
+		&self,
+		event: &TransferEvent<A>,
 	) -> Result<(), InvalidEventError> {
 		match (&event.contract_event, &self.state) {
 			(BridgeContractEvent::Initiated(_), _) => {
@@ -110,6 +141,7 @@ impl TransferState {
 			}
 			(BridgeContractEvent::InitiatorCompleted(_), _) => Err(InvalidEventError::BadEvent(format!("Received a InitialtorCompleted event with state not SecretReceived, transfer_id: {} state:{}", self.transfer_id, self.state))),
 			(BridgeContractEvent::Refunded(_), _) => Ok(()),
+			(BridgeContractEvent::Reorged(id), _) => Err(InvalidEventError::Reorged(*id)),
 			(&BridgeContractEvent::Cancelled(_), _) => Ok(()),
 		}
 	}
@@ -118,6 +150,7 @@ impl TransferState {
 		chain_id: ChainId,
 		transfer_id: BridgeTransferId,
 		detail: BridgeTransferDetails<A>,
+		relayer_fee: Amount,
 	) -> (Self, TransferAction) {
 		println!("State transition_from_initiated amount {:?}", detail.amount);
 
@@ -130,8 +163,10 @@ impl TransferState {
 			hash_lock: detail.hash_lock,
 			time_lock: detail.time_lock,
 			amount: detail.amount,
-			contract_state: detail.state,
+			contract_state: BridgeTransferState::Initiated.into(),
 			retry_on_error: 0,
+			received_at: Instant::now(),
+			relayer_fee,
 		};
 
 		let action_type = TransferActionType::LockBridgeTransfer {
@@ -140,6 +175,7 @@ impl TransferState {
 			initiator: BridgeAddress(detail.initiator.0.into()),
 			recipient: BridgeAddress(detail.recipient.0.into()),
 			amount: detail.amount,
+			relayer_fee: state.relayer_fee,
 		};
 		let action = TransferAction { chain: chain_id, transfer_id, kind: action_type };
 		(state, action)
@@ -161,7 +197,13 @@ impl TransferState {
 		secret: HashLockPreImage,
 	) -> (Self, TransferActionType) {
 		self.state = TransferStateType::SecretReceived;
-		let action_type = TransferActionType::WaitAndCompleteInitiator(0, secret);
+		let action_type = TransferActionType::WaitAndCompleteInitiator(
+			0,
+			secret,
+			self.hash_lock,
+			COMPLETE_INITIATOR_MAX_RETRIES,
+			self.relayer_fee,
+		);
 		(self, action_type)
 	}
 
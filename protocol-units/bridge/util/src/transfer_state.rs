@@ -0,0 +1,186 @@
+//! States of a bridge transfer across both chains, and the contract events moving a transfer
+//! from one state to the next.
+
+use crate::chains::bridge_contracts::{BridgeContractEvent, BridgeContractEventType};
+use std::fmt;
+use thiserror::Error;
+
+/// State of a bridge transfer, as seen from the contract events of both chains.
+///
+/// The `u8` encoding is the one of `TransferState::contract_state`. It differs from the
+/// raw state values of the contracts, which aren't the same on both chains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BridgeTransferState {
+	/// The transfer was initiated on the initiator chain.
+	Initiated,
+	/// The funds were locked on the counterparty chain.
+	Locked,
+	/// The recipient claimed the locked funds with the secret.
+	Completed,
+	/// The initiator got its funds back.
+	Refunded,
+	/// The lock timed out and was cancelled on the counterparty chain.
+	Expired,
+}
+
+impl fmt::Display for BridgeTransferState {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Debug::fmt(self, f)
+	}
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum InvalidTransitionError {
+	#[error("Unknown bridge transfer state: {0}")]
+	UnknownState(u8),
+	#[error("Illegal transition from state {from} on event {event:?}")]
+	IllegalTransition { from: BridgeTransferState, event: BridgeContractEventType },
+}
+
+impl TryFrom<u8> for BridgeTransferState {
+	type Error = InvalidTransitionError;
+
+	fn try_from(value: u8) -> Result<Self, Self::Error> {
+		match value {
+			0 => Ok(Self::Initiated),
+			1 => Ok(Self::Locked),
+			2 => Ok(Self::Completed),
+			3 => Ok(Self::Refunded),
+			4 => Ok(Self::Expired),
+			_ => Err(InvalidTransitionError::UnknownState(value)),
+		}
+	}
+}
+
+impl From<BridgeTransferState> for u8 {
+	fn from(state: BridgeTransferState) -> Self {
+		match state {
+			BridgeTransferState::Initiated => 0,
+			BridgeTransferState::Locked => 1,
+			BridgeTransferState::Completed => 2,
+			BridgeTransferState::Refunded => 3,
+			BridgeTransferState::Expired => 4,
+		}
+	}
+}
+
+/// The state reached by a transfer in state `current` when `event` is received.
+///
+/// The completion on the initiator chain keeps a completed transfer completed, and the
+/// cancellation of the lock keeps a refunded transfer refunded. Any other event not moving
+/// the transfer forward is an illegal transition.
+pub fn transition<A>(
+	current: BridgeTransferState,
+	event: &BridgeContractEvent<A>,
+) -> Result<BridgeTransferState, InvalidTransitionError> {
+	use BridgeTransferState::*;
+
+	match (current, event) {
+		(Initiated, BridgeContractEvent::Locked(_)) => Ok(Locked),
+		(Initiated | Locked | Expired, BridgeContractEvent::Refunded(_)) => Ok(Refunded),
+		(Locked, BridgeContractEvent::CounterPartyCompleted(..)) => Ok(Completed),
+		(Locked, BridgeContractEvent::Cancelled(_)) => Ok(Expired),
+		(Completed, BridgeContractEvent::InitiatorCompleted(_)) => Ok(Completed),
+		(Refunded, BridgeContractEvent::Cancelled(_)) => Ok(Refunded),
+		(from, event) => {
+			Err(InvalidTransitionError::IllegalTransition { from, event: event.event_type() })
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::{
+		Amount, BridgeAddress, BridgeTransferDetails, BridgeTransferId, HashLock, HashLockPreImage,
+		LockDetails, TimeLock,
+	};
+
+	const ID: BridgeTransferId = BridgeTransferId([1; 32]);
+
+	fn initiated() -> BridgeContractEvent<Vec<u8>> {
+		BridgeContractEvent::Initiated(BridgeTransferDetails {
+			bridge_transfer_id: ID,
+			initiator: BridgeAddress(vec![1]),
+			recipient: BridgeAddress(vec![2]),
+			hash_lock: HashLock([3; 32]),
+			time_lock: TimeLock(60),
+			amount: Amount(1),
+			state: 0,
+			initiated_at: 0,
+		})
+	}
+
+	fn locked() -> BridgeContractEvent<Vec<u8>> {
+		BridgeContractEvent::Locked(LockDetails {
+			bridge_transfer_id: ID,
+			initiator: BridgeAddress(vec![1]),
+			recipient: BridgeAddress(vec![2]),
+			hash_lock: HashLock([3; 32]),
+			time_lock: TimeLock(60),
+			amount: Amount(1),
+		})
+	}
+
+	fn counterparty_completed() -> BridgeContractEvent<Vec<u8>> {
+		BridgeContractEvent::CounterPartyCompleted(ID, HashLockPreImage([4; 32]))
+	}
+
+	#[test]
+	fn test_state_u8_round_trip() {
+		for value in 0..=4u8 {
+			let state = BridgeTransferState::try_from(value).unwrap();
+			assert_eq!(u8::from(state), value);
+		}
+		assert_eq!(BridgeTransferState::try_from(5), Err(InvalidTransitionError::UnknownState(5)));
+	}
+
+	#[test]
+	fn test_legal_transitions() {
+		use BridgeTransferState::*;
+
+		let legal = [
+			(Initiated, locked(), Locked),
+			(Initiated, BridgeContractEvent::Refunded(ID), Refunded),
+			(Locked, counterparty_completed(), Completed),
+			(Locked, BridgeContractEvent::Cancelled(ID), Expired),
+			(Locked, BridgeContractEvent::Refunded(ID), Refunded),
+			(Completed, BridgeContractEvent::InitiatorCompleted(ID), Completed),
+			(Expired, BridgeContractEvent::Refunded(ID), Refunded),
+			(Refunded, BridgeContractEvent::Cancelled(ID), Refunded),
+		];
+		for (current, event, expected) in legal {
+			assert_eq!(transition(current, &event), Ok(expected), "{current} on {event:?}");
+		}
+	}
+
+	#[test]
+	fn test_illegal_transitions() {
+		use BridgeTransferState::*;
+
+		let illegal = [
+			(Initiated, initiated()),
+			(Initiated, counterparty_completed()),
+			(Initiated, BridgeContractEvent::InitiatorCompleted(ID)),
+			(Locked, locked()),
+			(Locked, BridgeContractEvent::InitiatorCompleted(ID)),
+			(Completed, locked()),
+			(Completed, BridgeContractEvent::Refunded(ID)),
+			(Completed, BridgeContractEvent::Cancelled(ID)),
+			(Refunded, locked()),
+			(Refunded, counterparty_completed()),
+			(Expired, counterparty_completed()),
+			(Initiated, BridgeContractEvent::Reorged(ID)),
+		];
+		for (current, event) in illegal {
+			assert_eq!(
+				transition(current, &event),
+				Err(InvalidTransitionError::IllegalTransition {
+					from: current,
+					event: event.event_type()
+				}),
+				"{current} on {event:?}"
+			);
+		}
+	}
+}
@@ -1,11 +1,13 @@
-use alloy::primitives::Uint;
+use crate::chains::bridge_contracts::BridgeContractError;
+use alloy::primitives::{keccak256, Address, Uint, U256};
 use alloy::serde::quantity::vec;
 use derive_more::{Deref, DerefMut};
 use hex::{self, FromHexError};
 use rand::Rng;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
 use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::{fmt::Debug, hash::Hash};
 use thiserror::Error;
 
@@ -23,7 +25,7 @@ pub enum AddressError {
 	AddressConvertionlError(String),
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum ChainId {
 	ONE,
 	TWO,
@@ -48,7 +50,7 @@ impl fmt::Display for ChainId {
 	}
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct BridgeTransferId(pub BridgeHash);
 
 impl BridgeTransferId {
@@ -58,9 +60,10 @@ impl BridgeTransferId {
 			bytes.as_slice().try_into().map_err(|_| FromHexError::InvalidStringLength)?;
 		Ok(BridgeTransferId(array))
 	}
-	pub fn gen_unique_hash<R: Rng>(rng: &mut R) -> Self {
+	/// Generates a random id, pass a seeded `rng` to get reproducible ids in tests.
+	pub fn random<R: Rng>(rng: &mut R) -> Self {
 		let mut random_bytes = [0u8; 32];
-		rng.fill(&mut random_bytes);
+		rng.fill_bytes(&mut random_bytes);
 		BridgeTransferId(random_bytes)
 	}
 
@@ -70,6 +73,28 @@ impl BridgeTransferId {
 	}
 }
 
+/// Derives the id of the transfer of `amount` from `initiator` to `recipient` with `nonce`,
+/// as `keccak256(abi.encodePacked(initiator, recipient, amount, nonce))` with the amount
+/// and the nonce encoded as `uint256`.
+///
+/// This must stay in sync with the computation of the transfer ids in `NativeBridge.sol`,
+/// or the transfers completed with the derived ids are rejected on chain.
+/// The atomic bridge contracts also hash the block timestamp and a nonce kept by the contract,
+/// which their events don't carry, so their ids can't be checked against this derivation.
+pub fn derive_bridge_transfer_id(
+	initiator: &[u8],
+	recipient: &[u8],
+	amount: Amount,
+	nonce: Nonce,
+) -> BridgeTransferId {
+	let mut packed = Vec::with_capacity(initiator.len() + recipient.len() + 64);
+	packed.extend_from_slice(initiator);
+	packed.extend_from_slice(recipient);
+	packed.extend_from_slice(&U256::from(amount.0).to_be_bytes::<32>());
+	packed.extend_from_slice(&U256::from(nonce.0).to_be_bytes::<32>());
+	BridgeTransferId(keccak256(packed).0)
+}
+
 impl TryFrom<Vec<u8>> for BridgeTransferId {
 	type Error = Vec<u8>;
 
@@ -84,7 +109,7 @@ impl fmt::Display for BridgeTransferId {
 	}
 }
 
-#[derive(Deref, Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[derive(Deref, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct BridgeAddress<A>(pub A);
 
 impl BridgeAddress<Vec<u8>> {
@@ -92,6 +117,26 @@ impl BridgeAddress<Vec<u8>> {
 		let array = [0u8; 32];
 		BridgeAddress(array.to_vec())
 	}
+
+	/// The 32 bytes of a Movement account address.
+	pub fn try_as_movement_address(&self) -> Result<[u8; 32], BridgeContractError> {
+		self.0.as_slice().try_into().map_err(|_| {
+			BridgeContractError::BadAddressEncoding(format!(
+				"Movement address must be 32 bytes, got {}",
+				self.0.len()
+			))
+		})
+	}
+
+	pub fn try_as_eth_address(&self) -> Result<Address, BridgeContractError> {
+		if self.0.len() != Address::len_bytes() {
+			return Err(BridgeContractError::BadAddressEncoding(format!(
+				"Ethereum address must be 20 bytes, got {}",
+				self.0.len()
+			)));
+		}
+		Ok(Address::from_slice(&self.0))
+	}
 }
 
 impl From<&str> for BridgeAddress<Vec<u8>> {
@@ -127,7 +172,7 @@ where
 	}
 }
 
-#[derive(Deref, Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[derive(Deref, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct HashLock(pub [u8; 32]);
 
 impl HashLock {
@@ -149,9 +194,19 @@ impl HashLock {
 		let array = [0u8; 32];
 		HashLock(array)
 	}
+
+	/// The hash lock unlocked by `preimage`, its keccak256 hash.
+	pub fn from_preimage(preimage: &HashLockPreImage) -> Self {
+		HashLock(keccak256(preimage.0).0)
+	}
+
+	/// Whether `preimage` unlocks the hash lock, as checked by the contracts on completion.
+	pub fn verify_preimage(&self, preimage: &HashLockPreImage) -> bool {
+		*self == Self::from_preimage(preimage)
+	}
 }
 
-#[derive(Deref, Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[derive(Deref, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct HashLockPreImage(pub [u8; 32]);
 
 impl AsRef<[u8]> for HashLockPreImage {
@@ -170,7 +225,28 @@ impl HashLockPreImage {
 	}
 }
 
-#[derive(Deref, Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+/// Identifies one completion of a transfer. The same `(BridgeTransferId, Nonce)`
+/// pair must not be submitted twice.
+#[derive(Deref, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Nonce(pub u128);
+
+impl From<&HashLockPreImage> for Nonce {
+	/// The nonce of a completion is derived from the hash of the secret it reveals,
+	/// so the persisted nonces don't hold part of the secret.
+	fn from(pre_image: &HashLockPreImage) -> Self {
+		let mut bytes = [0u8; 16];
+		bytes.copy_from_slice(&keccak256(pre_image.0)[..16]);
+		Nonce(u128::from_be_bytes(bytes))
+	}
+}
+
+impl fmt::Display for Nonce {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.0)
+	}
+}
+
+#[derive(Deref, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TimeLock(pub u64);
 
 impl From<Uint<256, 4>> for TimeLock {
@@ -181,7 +257,24 @@ impl From<Uint<256, 4>> for TimeLock {
 	}
 }
 
-#[derive(Deref, DerefMut, Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+impl TimeLock {
+	/// Time lock expiring `duration` from now.
+	pub fn from_duration(duration: Duration) -> Self {
+		TimeLock(current_timestamp_secs().saturating_add(duration.as_secs()))
+	}
+
+	/// Whether the time lock timestamp is in the past.
+	pub fn has_expired(&self) -> bool {
+		self.0 < current_timestamp_secs()
+	}
+
+	/// Seconds left before the time lock expires, `None` once it has expired.
+	pub fn remaining_secs(&self) -> Option<u64> {
+		self.0.checked_sub(current_timestamp_secs())
+	}
+}
+
+#[derive(Deref, DerefMut, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Amount(pub u64);
 
 impl From<Uint<256, 4>> for Amount {
@@ -192,13 +285,42 @@ impl From<Uint<256, 4>> for Amount {
 	}
 }
 
+impl Amount {
+	/// Converts an Ethereum amount, failing instead of truncating when it does not fit in 64 bits.
+	pub fn try_from_u256(value: U256) -> Result<Self, ConversionError> {
+		u64::try_from(value)
+			.map(Amount)
+			.map_err(|_| ConversionError::AmountOverflow(value))
+	}
+
+	pub fn checked_add(&self, other: &Amount) -> Option<Amount> {
+		self.0.checked_add(other.0).map(Amount)
+	}
+
+	pub fn checked_sub(&self, other: &Amount) -> Option<Amount> {
+		self.0.checked_sub(other.0).map(Amount)
+	}
+
+	pub fn is_zero(&self) -> bool {
+		self.0 == 0
+	}
+}
+
+impl fmt::Display for Amount {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.0)
+	}
+}
+
 #[derive(Error, Debug)]
 pub enum ConversionError {
 	#[error("Invalid conversion from AssetType to Uint")]
 	InvalidConversion,
+	#[error("Amount {0} does not fit in 64 bits")]
+	AmountOverflow(U256),
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 pub struct BridgeTransferDetails<A> {
 	pub bridge_transfer_id: BridgeTransferId,
 	pub initiator: BridgeAddress<A>,
@@ -207,9 +329,29 @@ pub struct BridgeTransferDetails<A> {
 	pub time_lock: TimeLock,
 	pub amount: Amount,
 	pub state: u8,
+	/// Unix timestamp in seconds of the block of the initiation event.
+	/// When the chain doesn't report it, or the details are read from the contract storage,
+	/// the time the relayer received the details.
+	#[serde(default)]
+	pub initiated_at: u64,
+}
+
+impl<A> BridgeTransferDetails<A> {
+	/// Seconds elapsed since the transfer was initiated.
+	pub fn age_secs(&self, current_timestamp: u64) -> u64 {
+		current_timestamp.saturating_sub(self.initiated_at)
+	}
+}
+
+/// Current unix timestamp in seconds.
+pub fn current_timestamp_secs() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|d| d.as_secs())
+		.unwrap_or_default()
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 pub struct BridgeTransferDetailsCounterparty<A> {
 	pub bridge_transfer_id: BridgeTransferId,
 	pub initiator: BridgeAddress<Vec<u8>>,
@@ -220,7 +362,7 @@ pub struct BridgeTransferDetailsCounterparty<A> {
 	pub state: u8,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LockDetails<A> {
 	pub bridge_transfer_id: BridgeTransferId,
 	pub initiator: BridgeAddress<Vec<u8>>,
@@ -229,3 +371,113 @@ pub struct LockDetails<A> {
 	pub time_lock: TimeLock,
 	pub amount: Amount,
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_derive_bridge_transfer_id() {
+		// computed with keccak256(abi.encodePacked(initiator, recipient, amount, nonce))
+		// for an address initiator and a bytes32 recipient, as in `initiateBridgeTransfer`
+		let bridge_transfer_id =
+			derive_bridge_transfer_id(&[0x11; 20], &[0x22; 32], Amount(100), Nonce(1));
+		assert_eq!(
+			bridge_transfer_id,
+			BridgeTransferId::parse(
+				"7aaff5b8fa4c49d4d7182d8d8c3fc9e34599f5e19ecb83421adcd763b6dd6b38"
+			)
+			.unwrap()
+		);
+		assert_ne!(
+			bridge_transfer_id,
+			derive_bridge_transfer_id(&[0x11; 20], &[0x22; 32], Amount(100), Nonce(2))
+		);
+	}
+
+	#[test]
+	fn test_nonce_from_pre_image() {
+		let pre_image = HashLockPreImage([0x33; 32]);
+		let nonce = Nonce::from(&pre_image);
+		assert_eq!(nonce, Nonce::from(&pre_image));
+		assert_ne!(nonce.0.to_be_bytes(), pre_image.0[..16]);
+		assert_ne!(nonce, Nonce::from(&HashLockPreImage([0x44; 32])));
+	}
+
+	#[test]
+	fn test_bridge_address_lengths() {
+		let movement = BridgeAddress(vec![0x22; 32]);
+		assert_eq!(movement.try_as_movement_address().unwrap(), [0x22; 32]);
+		assert!(matches!(
+			movement.try_as_eth_address(),
+			Err(BridgeContractError::BadAddressEncoding(_))
+		));
+
+		let eth = BridgeAddress(vec![0x11; 20]);
+		assert_eq!(eth.try_as_eth_address().unwrap(), Address::repeat_byte(0x11));
+		assert!(matches!(
+			eth.try_as_movement_address(),
+			Err(BridgeContractError::BadAddressEncoding(_))
+		));
+	}
+
+	#[test]
+	fn test_amount_arithmetic() {
+		assert_eq!(Amount(40).checked_add(&Amount(2)), Some(Amount(42)));
+		assert_eq!(Amount(42).checked_sub(&Amount(2)), Some(Amount(40)));
+		assert!(Amount(0).is_zero());
+		assert!(!Amount(1).is_zero());
+		assert_eq!(Amount(100).to_string(), "100");
+	}
+
+	#[test]
+	fn test_amount_overflow_and_underflow() {
+		assert_eq!(Amount(u64::MAX).checked_add(&Amount(1)), None);
+		assert_eq!(Amount(1).checked_sub(&Amount(2)), None);
+	}
+
+	#[test]
+	fn test_amount_try_from_u256() {
+		assert_eq!(Amount::try_from_u256(U256::from(100u64)).unwrap(), Amount(100));
+		assert!(matches!(
+			Amount::try_from_u256(U256::from(u64::MAX) + U256::from(1u64)),
+			Err(ConversionError::AmountOverflow(_))
+		));
+	}
+
+	#[test]
+	fn test_time_lock_in_the_past_has_expired() {
+		let time_lock = TimeLock(current_timestamp_secs() - 60);
+		assert!(time_lock.has_expired());
+		assert_eq!(time_lock.remaining_secs(), None);
+	}
+
+	#[test]
+	fn test_time_lock_in_the_future_has_not_expired() {
+		let time_lock = TimeLock::from_duration(Duration::from_secs(3600));
+		assert!(!time_lock.has_expired());
+		let remaining = time_lock.remaining_secs().unwrap();
+		// allow for the clock ticking between the two calls
+		assert!((3599..=3600).contains(&remaining));
+	}
+
+	#[test]
+	fn test_hash_lock_verifies_its_preimage() {
+		let preimage = HashLockPreImage::random();
+		let hash_lock = HashLock::from_preimage(&preimage);
+		assert!(hash_lock.verify_preimage(&preimage));
+		// keccak256 of 32 zero bytes
+		assert_eq!(
+			HashLock::from_preimage(&HashLockPreImage([0; 32])),
+			HashLock::parse("290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563")
+				.unwrap()
+		);
+	}
+
+	#[test]
+	fn test_hash_lock_rejects_other_preimage() {
+		let hash_lock = HashLock::from_preimage(&HashLockPreImage([1; 32]));
+		assert!(!hash_lock.verify_preimage(&HashLockPreImage([2; 32])));
+		assert!(!HashLock::test().verify_preimage(&HashLockPreImage([0; 32])));
+	}
+}
@@ -0,0 +1,58 @@
+//! Compares the throughput of the signed blob verifier on blobs it has not verified yet
+//! with the throughput on blobs found in its verification cache.
+//!
+//! Run with `cargo bench -p movement-celestia-da-light-node-verifier --bench verification`.
+
+use ecdsa::SigningKey;
+use k256::Secp256k1;
+use movement_celestia_da_light_node_verifier::{signed::Verifier, VerifierOperations};
+use movement_celestia_da_util::ir_blob::{
+	BlobTypeTag, InnerSignedBlobV2Data, IntermediateBlobRepresentation,
+};
+use std::time::{Duration, Instant};
+
+const BLOB_COUNT: u64 = 1000;
+const CHAIN_ID: u64 = 27;
+
+/// Verifies all the `blobs` and returns the time it took.
+async fn verify_all(
+	verifier: &Verifier<Secp256k1>,
+	blobs: &[IntermediateBlobRepresentation],
+) -> Result<Duration, anyhow::Error> {
+	let start = Instant::now();
+	for blob in blobs {
+		verifier.verify(blob.clone(), 1).await?;
+	}
+	Ok(start.elapsed())
+}
+
+#[tokio::main]
+async fn main() -> Result<(), anyhow::Error> {
+	let signing_key = SigningKey::<Secp256k1>::random(&mut rand::thread_rng());
+	let blobs = (0..BLOB_COUNT)
+		.map(|timestamp| {
+			let data = InnerSignedBlobV2Data::new(
+				vec![7; 1024],
+				timestamp,
+				CHAIN_ID,
+				BlobTypeTag::TransactionBatch,
+			);
+			Ok(data.try_to_sign(&signing_key)?.into())
+		})
+		.collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+	let verifier = Verifier::<Secp256k1>::new(CHAIN_ID);
+	let uncached = verify_all(&verifier, &blobs).await?;
+	let cached = verify_all(&verifier, &blobs).await?;
+
+	for (name, elapsed) in [("uncached", uncached), ("cached", cached)] {
+		println!(
+			"{:>8}: {} blobs in {:?}, {:.0} blobs/s",
+			name,
+			BLOB_COUNT,
+			elapsed,
+			BLOB_COUNT as f64 / elapsed.as_secs_f64()
+		);
+	}
+	Ok(())
+}
@@ -1,7 +1,9 @@
 use crate::{Error, Verified, VerifierOperations};
 use celestia_rpc::Client;
 use celestia_types::{nmt::Namespace, Blob};
-use movement_celestia_da_util::ir_blob::IntermediateBlobRepresentation;
+use movement_celestia_da_util::ir_blob::{
+	celestia::check_namespace, IntermediateBlobRepresentation,
+};
 use std::sync::Arc;
 
 #[derive(Clone)]
@@ -26,6 +28,9 @@ impl VerifierOperations<Blob, IntermediateBlobRepresentation> for Verifier {
 		blob: Blob,
 		_height: u64,
 	) -> Result<Verified<IntermediateBlobRepresentation>, Error> {
+		check_namespace(&blob.namespace, &self.namespace)
+			.map_err(|e| Error::Validation(e.to_string()))?;
+
 		// Only assert that we can indeed get an IntermediateBlobRepresentation from the Blob
 		let ir_blob = IntermediateBlobRepresentation::try_from(blob)
 			.map_err(|e| Error::Internal(e.to_string()))?;
@@ -35,6 +40,49 @@ impl VerifierOperations<Blob, IntermediateBlobRepresentation> for Verifier {
 }
 
 pub mod pessimistic;
+
+/// Verifies the NMT inclusion proofs of the blobs, alongside the optimistic [`Verifier`].
+pub use pessimistic::Verifier as PessimisticCelestiaVerifier;
+
+#[cfg(test)]
+mod namespace_tests {
+	use super::*;
+	use ecdsa::SigningKey;
+	use k256::Secp256k1;
+	use movement_celestia_da_util::ir_blob::{
+		celestia::{CelestiaIntermediateBlobRepresentation, CompressionConfig},
+		BlobTypeTag, InnerSignedBlobV2Data,
+	};
+
+	fn celestia_blob(namespace: Namespace, data: Vec<u8>) -> Result<Blob, anyhow::Error> {
+		let signing_key = SigningKey::<Secp256k1>::random(&mut rand::thread_rng());
+		let ir_blob = InnerSignedBlobV2Data::new(data, 123, 27, BlobTypeTag::TransactionBatch)
+			.try_to_sign(&signing_key)?;
+		CelestiaIntermediateBlobRepresentation(
+			ir_blob.into(),
+			namespace,
+			CompressionConfig::default(),
+		)
+		.try_into()
+	}
+
+	#[tokio::test]
+	async fn test_blob_of_another_namespace_does_not_verify() -> Result<(), anyhow::Error> {
+		// the namespace is checked before the client is used, so it is never connected
+		let client = Arc::new(Client::new("http://localhost:26658", None).await?);
+		let namespace = Namespace::new_v0(b"movement")?;
+		let verifier = Verifier::new(client, namespace);
+
+		let verified = verifier.verify(celestia_blob(namespace, vec![1, 2, 3])?, 1).await?;
+		assert_eq!(verified.inner().blob(), &[1, 2, 3]);
+
+		let other_namespace = Namespace::new_v0(b"other")?;
+		let result = verifier.verify(celestia_blob(other_namespace, vec![1, 2, 3])?, 1).await;
+		assert!(matches!(result, Err(Error::Validation(_))));
+
+		Ok(())
+	}
+}
 #[cfg(all(test, feature = "integration-tests"))]
 mod tests {
 	use super::*;
@@ -1,6 +1,10 @@
 use crate::{Error, Verified, VerifierOperations};
 use celestia_rpc::{BlobClient, Client, HeaderClient};
-use celestia_types::{consts::appconsts::AppVersion, nmt::Namespace, Blob};
+use celestia_types::{
+	consts::appconsts::AppVersion,
+	nmt::{Namespace, NamespaceProof},
+	Blob,
+};
 use movement_celestia_da_util::ir_blob::IntermediateBlobRepresentation;
 use std::sync::Arc;
 
@@ -16,18 +20,15 @@ impl Verifier {
 	pub fn new(client: Arc<Client>, namespace: Namespace) -> Self {
 		Self { client, namespace }
 	}
-}
 
-#[tonic::async_trait]
-impl VerifierOperations<Blob, IntermediateBlobRepresentation> for Verifier {
-	/// Verifies a Celestia Blob as a Valid IntermediateBlobRepresentation
-	async fn verify(
+	/// Verifies the NMT `proofs` of the shares of `blob` against the row root of the
+	/// header at `height`, and converts the blob.
+	async fn verify_inclusion(
 		&self,
 		blob: Blob,
 		height: u64,
+		proofs: &[NamespaceProof],
 	) -> Result<Verified<IntermediateBlobRepresentation>, Error> {
-		//@l-monninger: the light node itself does most of the work of verify blobs. The verification under the feature flag below is useful in zero-trust environments.
-
 		blob.validate(AppVersion::V2).map_err(|e| Error::Validation(e.to_string()))?;
 
 		// wait for the header to be at the correct height
@@ -45,17 +46,13 @@ impl VerifierOperations<Blob, IntermediateBlobRepresentation> for Verifier {
 			.dah;
 		let root_hash = dah.row_root(0).ok_or(Error::Validation("No root hash".to_string()))?;
 
-		// get the proof
-		let proofs = self
-			.client
-			.blob_get_proof(height, self.namespace.clone(), blob.commitment)
-			.await
-			.map_err(|e| Error::Internal(e.to_string()))?;
-
 		// get the leaves
 		let leaves = blob.to_shares().map_err(|e| Error::Internal(e.to_string()))?;
 
 		// check if included
+		if proofs.is_empty() {
+			return Err(Error::Validation("no inclusion proof".to_string()));
+		}
 		for proof in proofs.iter() {
 			proof
 				.verify_complete_namespace(&root_hash, &leaves, self.namespace.into())
@@ -70,3 +67,95 @@ impl VerifierOperations<Blob, IntermediateBlobRepresentation> for Verifier {
 		Ok(Verified::new(ir_blob))
 	}
 }
+
+#[tonic::async_trait]
+impl VerifierOperations<Blob, IntermediateBlobRepresentation> for Verifier {
+	/// Verifies a Celestia Blob as a Valid IntermediateBlobRepresentation
+	async fn verify(
+		&self,
+		blob: Blob,
+		height: u64,
+	) -> Result<Verified<IntermediateBlobRepresentation>, Error> {
+		//@l-monninger: the light node itself does most of the work of verify blobs. The verification under the feature flag below is useful in zero-trust environments.
+
+		// get the proof
+		let proofs = self
+			.client
+			.blob_get_proof(height, self.namespace.clone(), blob.commitment)
+			.await
+			.map_err(|e| Error::Internal(e.to_string()))?;
+
+		self.verify_inclusion(blob, height, &proofs).await
+	}
+}
+
+#[tonic::async_trait]
+impl VerifierOperations<(Blob, NamespaceProof), IntermediateBlobRepresentation> for Verifier {
+	/// Verifies a Celestia Blob with an inclusion proof obtained by the caller,
+	/// e.g. from another node, against the header of the trusted Celestia node.
+	async fn verify(
+		&self,
+		(blob, proof): (Blob, NamespaceProof),
+		height: u64,
+	) -> Result<Verified<IntermediateBlobRepresentation>, Error> {
+		self.verify_inclusion(blob, height, &[proof]).await
+	}
+}
+
+#[cfg(all(test, feature = "integration-tests"))]
+mod tests {
+	use super::*;
+	use celestia_rpc::BlobClient;
+	use celestia_types::TxConfig;
+	use ecdsa::SigningKey;
+	use k256::Secp256k1;
+	use movement_celestia_da_util::{
+		config::CelestiaDaLightNodeConfig,
+		ir_blob::{
+			celestia::{CelestiaIntermediateBlobRepresentation, CompressionConfig},
+			BlobTypeTag, InnerSignedBlobV2Data,
+		},
+	};
+
+	fn celestia_blob(namespace: Namespace, data: Vec<u8>) -> Result<Blob, anyhow::Error> {
+		let signing_key = SigningKey::<Secp256k1>::random(&mut rand::thread_rng());
+		let ir_blob = InnerSignedBlobV2Data::new(data, 123, 27, BlobTypeTag::TransactionBatch)
+			.try_to_sign(&signing_key)?;
+		CelestiaIntermediateBlobRepresentation(
+			ir_blob.into(),
+			namespace,
+			CompressionConfig::default(),
+		)
+		.try_into()
+	}
+
+	#[tokio::test]
+	pub async fn test_inclusion_proof_verifies() -> Result<(), anyhow::Error> {
+		let dot_movement = dot_movement::DotMovement::try_from_env()?;
+		let config = dot_movement.try_get_config_from_json::<CelestiaDaLightNodeConfig>()?;
+		let client = Arc::new(config.connect_celestia().await?);
+		let namespace = config.celestia_namespace();
+		let verifier = Verifier::new(client.clone(), namespace);
+
+		let blob = celestia_blob(namespace, vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9])?;
+		let height = client.blob_submit(&[blob.clone()], TxConfig::default()).await?;
+		let proof = client
+			.blob_get_proof(height, namespace, blob.commitment)
+			.await?
+			.into_iter()
+			.next()
+			.expect("no proof of the submitted blob");
+
+		let verified = verifier.verify((blob.clone(), proof.clone()), height).await?;
+		assert_eq!(verified.inner().blob(), &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+		// a blob that was never posted does not verify with the proof of another blob
+		let fabricated = celestia_blob(namespace, vec![9, 8, 7])?;
+		assert!(verifier.verify((fabricated, proof.clone()), height).await.is_err());
+
+		// nor at another height
+		assert!(verifier.verify((blob, proof), height + 1).await.is_err());
+
+		Ok(())
+	}
+}
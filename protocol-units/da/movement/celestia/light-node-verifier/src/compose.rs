@@ -0,0 +1,124 @@
+//! Combinators to build trust policies out of several verifiers.
+
+use crate::{Error, Verified, VerifierOperations};
+
+/// A verifier that accepts a blob only if both verifiers accept it.
+/// The second verifier is not run if the first one rejects the blob.
+#[derive(Clone)]
+pub struct AndVerifier<V1, V2> {
+	pub first: V1,
+	pub second: V2,
+}
+
+impl<V1, V2> AndVerifier<V1, V2> {
+	pub fn new(first: V1, second: V2) -> Self {
+		Self { first, second }
+	}
+}
+
+#[tonic::async_trait]
+impl<I, O, V1, V2> VerifierOperations<I, O> for AndVerifier<V1, V2>
+where
+	I: Clone + Send + Sync + 'static,
+	O: Send + Sync + 'static,
+	V1: VerifierOperations<I, O> + Send + Sync,
+	V2: VerifierOperations<I, O> + Send + Sync,
+{
+	/// Returns the blob verified by the first verifier.
+	async fn verify(&self, blob: I, height: u64) -> Result<Verified<O>, Error> {
+		let verified = self.first.verify(blob.clone(), height).await?;
+		self.second.verify(blob, height).await?;
+		Ok(verified)
+	}
+}
+
+/// A verifier that accepts a blob if either verifier accepts it.
+/// The second verifier is only run if the first one rejects the blob.
+#[derive(Clone)]
+pub struct OrVerifier<V1, V2> {
+	pub first: V1,
+	pub second: V2,
+}
+
+impl<V1, V2> OrVerifier<V1, V2> {
+	pub fn new(first: V1, second: V2) -> Self {
+		Self { first, second }
+	}
+}
+
+#[tonic::async_trait]
+impl<I, O, V1, V2> VerifierOperations<I, O> for OrVerifier<V1, V2>
+where
+	I: Clone + Send + Sync + 'static,
+	O: Send + Sync + 'static,
+	V1: VerifierOperations<I, O> + Send + Sync,
+	V2: VerifierOperations<I, O> + Send + Sync,
+{
+	/// Returns the error of the second verifier if both reject the blob.
+	async fn verify(&self, blob: I, height: u64) -> Result<Verified<O>, Error> {
+		match self.first.verify(blob.clone(), height).await {
+			Ok(verified) => Ok(verified),
+			Err(_) => self.second.verify(blob, height).await,
+		}
+	}
+}
+
+#[cfg(test)]
+pub mod tests {
+	use super::*;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	/// Accepts or rejects every blob and counts its calls.
+	struct MockVerifier {
+		accept: bool,
+		calls: AtomicUsize,
+	}
+
+	impl MockVerifier {
+		fn new(accept: bool) -> Self {
+			Self { accept, calls: AtomicUsize::new(0) }
+		}
+	}
+
+	#[tonic::async_trait]
+	impl VerifierOperations<u8, u8> for MockVerifier {
+		async fn verify(&self, blob: u8, _height: u64) -> Result<Verified<u8>, Error> {
+			self.calls.fetch_add(1, Ordering::SeqCst);
+			if self.accept {
+				Ok(Verified::new(blob))
+			} else {
+				Err(Error::Validation("rejected".to_string()))
+			}
+		}
+	}
+
+	#[tokio::test]
+	async fn test_and_verifier() -> Result<(), anyhow::Error> {
+		for (first, second) in [(true, true), (true, false), (false, true), (false, false)] {
+			let verifier = AndVerifier::new(MockVerifier::new(first), MockVerifier::new(second));
+			let result = verifier.verify(7, 1).await;
+			assert_eq!(result.is_ok(), first && second, "{} and {}", first, second);
+			if let Ok(verified) = result {
+				assert_eq!(verified.into_inner(), 7);
+			}
+			// the second verifier is skipped once the first one rejects
+			assert_eq!(verifier.second.calls.load(Ordering::SeqCst), usize::from(first));
+		}
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_or_verifier() -> Result<(), anyhow::Error> {
+		for (first, second) in [(true, true), (true, false), (false, true), (false, false)] {
+			let verifier = OrVerifier::new(MockVerifier::new(first), MockVerifier::new(second));
+			let result = verifier.verify(7, 1).await;
+			assert_eq!(result.is_ok(), first || second, "{} or {}", first, second);
+			if let Ok(verified) = result {
+				assert_eq!(verified.into_inner(), 7);
+			}
+			// the second verifier is skipped once the first one accepts
+			assert_eq!(verifier.second.calls.load(Ordering::SeqCst), usize::from(!first));
+		}
+		Ok(())
+	}
+}
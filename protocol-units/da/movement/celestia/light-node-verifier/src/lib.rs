@@ -1,4 +1,5 @@
 pub mod celestia;
+pub mod compose;
 pub mod permissioned_signers;
 pub mod signed;
 
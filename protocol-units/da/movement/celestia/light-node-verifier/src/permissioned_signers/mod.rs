@@ -48,6 +48,8 @@ where
 		celestia_client: Arc<Client>,
 		celestia_namespace: Namespace,
 		known_signers_sec1_bytes: T,
+		chain_id: u64,
+		threshold: usize,
 	) -> Self
 	where
 		T: IntoIterator,
@@ -55,9 +57,19 @@ where
 	{
 		Self {
 			celestia: CelestiaVerifier::new(celestia_client, celestia_namespace),
-			known_signers: InKnownSignersVerifier::new(known_signers_sec1_bytes),
+			known_signers: InKnownSignersVerifier::new(
+				known_signers_sec1_bytes,
+				chain_id,
+				threshold,
+			),
 		}
 	}
+
+	/// Sets whether the V1 blobs, which carry no chain id, are rejected.
+	pub fn with_enforced_chain_id(mut self, enforce_chain_id: bool) -> Self {
+		self.known_signers = self.known_signers.with_enforced_chain_id(enforce_chain_id);
+		self
+	}
 }
 
 #[tonic::async_trait]
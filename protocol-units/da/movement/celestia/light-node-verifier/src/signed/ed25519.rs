@@ -0,0 +1,120 @@
+use crate::{Error, Verified, VerifierOperations};
+use movement_celestia_da_util::ir_blob::IntermediateBlobRepresentation;
+use std::collections::HashSet;
+
+/// Verifies that a V1 blob was signed with Ed25519, Movement's native signature scheme, by a known signer.
+#[derive(Debug, Clone)]
+pub struct Ed25519Verifier {
+	/// The Ed25519 public keys of the known signers.
+	pub known_signers: HashSet<[u8; 32]>,
+}
+
+impl Ed25519Verifier {
+	pub fn new<T>(known_signers: T) -> Self
+	where
+		T: IntoIterator<Item = [u8; 32]>,
+	{
+		Self { known_signers: known_signers.into_iter().collect() }
+	}
+
+	fn is_known_signer(&self, signer: &[u8]) -> bool {
+		<[u8; 32]>::try_from(signer).is_ok_and(|signer| self.known_signers.contains(&signer))
+	}
+}
+
+#[tonic::async_trait]
+impl VerifierOperations<IntermediateBlobRepresentation, IntermediateBlobRepresentation>
+	for Ed25519Verifier
+{
+	async fn verify(
+		&self,
+		blob: IntermediateBlobRepresentation,
+		_height: u64,
+	) -> Result<Verified<IntermediateBlobRepresentation>, Error> {
+		let inner = match &blob {
+			IntermediateBlobRepresentation::SignedV1(inner) => inner,
+			_ => {
+				return Err(Error::Validation(format!(
+					"Ed25519 signatures are only supported for V1 blobs, got V{}",
+					blob.version()
+				)))
+			}
+		};
+		if !self.is_known_signer(&inner.signer) {
+			return Err(Error::Validation("signer not known".to_string()));
+		}
+		inner.try_verify_ed25519().map_err(|e| Error::Validation(e.to_string()))?;
+
+		Ok(Verified::new(blob))
+	}
+}
+
+#[cfg(test)]
+pub mod tests {
+	use super::*;
+	use ed25519_dalek::SigningKey;
+	use movement_celestia_da_util::ir_blob::{
+		BlobTypeTag, InnerSignedBlobV1Data, InnerSignedBlobV2Data,
+	};
+
+	fn signing_key() -> SigningKey {
+		SigningKey::from_bytes(&rand::random())
+	}
+
+	fn signed_blob(
+		signing_key: &SigningKey,
+	) -> Result<IntermediateBlobRepresentation, anyhow::Error> {
+		let data = InnerSignedBlobV1Data::new(vec![1, 2, 3], 123);
+		Ok(data.try_to_sign_ed25519(signing_key)?.into())
+	}
+
+	#[tokio::test]
+	async fn test_blob_of_known_signer_is_verified() -> Result<(), anyhow::Error> {
+		let signing_key = signing_key();
+		let verifier = Ed25519Verifier::new([signing_key.verifying_key().to_bytes()]);
+
+		let blob = signed_blob(&signing_key)?;
+		let verified = verifier.verify(blob, 1).await?;
+		assert_eq!(verified.inner().blob(), [1, 2, 3]);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_unknown_signer_is_rejected() -> Result<(), anyhow::Error> {
+		let verifier = Ed25519Verifier::new([signing_key().verifying_key().to_bytes()]);
+
+		let blob = signed_blob(&signing_key())?;
+		match verifier.verify(blob, 1).await {
+			Err(Error::Validation(message)) => assert_eq!(message, "signer not known"),
+			Err(e) => panic!("unexpected error: {}", e),
+			Ok(_) => panic!("blob of an unknown signer was verified"),
+		}
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_tampered_blob_is_rejected() -> Result<(), anyhow::Error> {
+		let signing_key = signing_key();
+		let verifier = Ed25519Verifier::new([signing_key.verifying_key().to_bytes()]);
+
+		let mut inner = match signed_blob(&signing_key)? {
+			IntermediateBlobRepresentation::SignedV1(inner) => inner,
+			_ => unreachable!(),
+		};
+		inner.data.blob = vec![4, 5, 6];
+		assert!(matches!(verifier.verify(inner.into(), 1).await, Err(Error::Validation(_))));
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_ecdsa_blob_is_rejected() -> Result<(), anyhow::Error> {
+		let verifier = Ed25519Verifier::new([signing_key().verifying_key().to_bytes()]);
+
+		let ecdsa_key = ecdsa::SigningKey::<k256::Secp256k1>::random(&mut rand::thread_rng());
+		let data =
+			InnerSignedBlobV2Data::new(vec![1, 2, 3], 123, 27, BlobTypeTag::TransactionBatch);
+		let blob = data.try_to_sign(&ecdsa_key)?.into();
+		assert!(matches!(verifier.verify(blob, 1).await, Err(Error::Validation(_))));
+		Ok(())
+	}
+}
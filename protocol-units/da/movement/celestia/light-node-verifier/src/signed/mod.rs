@@ -1,3 +1,5 @@
+pub mod ed25519;
+
 use crate::{Error, Verified, VerifierOperations};
 use ecdsa::{
 	elliptic_curve::{
@@ -11,11 +13,19 @@ use ecdsa::{
 	hazmat::{DigestPrimitive, SignPrimitive, VerifyPrimitive},
 	SignatureSize,
 };
+use lru::LruCache;
 use movement_celestia_da_util::ir_blob::IntermediateBlobRepresentation;
-use std::collections::HashSet;
+use std::{
+	collections::HashSet,
+	num::NonZeroUsize,
+	sync::{Arc, Mutex, RwLock},
+};
 use tracing::info;
 
-/// A verifier that checks the signature of the inner blob.
+/// The number of verified blobs remembered by default by a [`Verifier`].
+pub const DEFAULT_VERIFICATION_CACHE_CAPACITY: usize = 1024;
+
+/// A verifier that checks the signature of the inner blob and that it was signed for the chain.
 #[derive(Clone)]
 pub struct Verifier<C>
 where
@@ -26,6 +36,12 @@ where
 	FieldBytesSize<C>: ModulusSize,
 {
 	pub _curve_marker: std::marker::PhantomData<C>,
+	/// The chain id the blobs must be signed for.
+	pub chain_id: u64,
+	/// Whether the blobs without a chain id, the V1 blobs, are rejected.
+	pub enforce_chain_id: bool,
+	/// The digests of the blobs whose signatures were already verified, see [`Verifier::cache_key`].
+	pub cache: Arc<Mutex<LruCache<[u8; 32], ()>>>,
 }
 
 impl<C> Verifier<C>
@@ -36,8 +52,40 @@ where
 	AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C> + VerifyPrimitive<C>,
 	FieldBytesSize<C>: ModulusSize,
 {
-	pub fn new() -> Self {
-		Self { _curve_marker: std::marker::PhantomData }
+	pub fn new(chain_id: u64) -> Self {
+		let capacity = NonZeroUsize::new(DEFAULT_VERIFICATION_CACHE_CAPACITY)
+			.expect("verification cache capacity is not zero");
+		Self::with_cache_capacity(chain_id, capacity)
+	}
+
+	/// Creates a verifier remembering up to `capacity` verified blobs.
+	pub fn with_cache_capacity(chain_id: u64, capacity: NonZeroUsize) -> Self {
+		Self {
+			_curve_marker: std::marker::PhantomData,
+			chain_id,
+			enforce_chain_id: false,
+			cache: Arc::new(Mutex::new(LruCache::new(capacity))),
+		}
+	}
+
+	/// Sets whether the V1 blobs, which carry no chain id, are rejected.
+	/// They are accepted by default, so that the blobs written before V2 still sync.
+	pub fn with_enforced_chain_id(mut self, enforce_chain_id: bool) -> Self {
+		self.enforce_chain_id = enforce_chain_id;
+		self
+	}
+
+	/// Forgets all the verified blobs.
+	pub fn clear_cache(&self) {
+		self.cache.lock().expect("verification cache lock poisoned").clear();
+	}
+
+	/// The cache key of a blob: the digest of the whole blob.
+	/// The id is not recomputed before the lookup, so a blob whose data or signatures were
+	/// changed after a successful verification must not hit the cache.
+	fn cache_key(blob: &IntermediateBlobRepresentation) -> Result<[u8; 32], Error> {
+		blob.digest()
+			.map_err(|e| Error::Internal(format!("failed to hash blob: {}", e)))
 	}
 }
 
@@ -56,13 +104,20 @@ where
 		blob: IntermediateBlobRepresentation,
 		_height: u64,
 	) -> Result<Verified<IntermediateBlobRepresentation>, Error> {
-		blob.verify_signature::<C>().map_err(|e| Error::Validation(e.to_string()))?;
+		let key = Self::cache_key(&blob)?;
+		if self.cache.lock().expect("verification cache lock poisoned").get(&key).is_some() {
+			return Ok(Verified::new(blob));
+		}
+
+		blob.verify_signature::<C>(self.chain_id, self.enforce_chain_id)
+			.map_err(|e| Error::Validation(e.to_string()))?;
 
+		self.cache.lock().expect("verification cache lock poisoned").put(key, ());
 		Ok(Verified::new(blob))
 	}
 }
 
-/// Verifies that the signer of the inner blob is in the known signers set.
+/// Verifies that at least `threshold` of the signers of the inner blob are in the known signers set.
 /// This is built around an inner signer because we should always check the signature first. That is, this composition prevents unsafe usage.
 #[derive(Clone)]
 pub struct InKnownSignersVerifier<C>
@@ -75,7 +130,14 @@ where
 {
 	pub inner_verifier: Verifier<C>,
 	/// The set of known signers in sec1 bytes hex format.
-	pub known_signers_sec1_bytes_hex: HashSet<String>,
+	pub known_signers_sec1_bytes_hex: Arc<RwLock<HashSet<String>>>,
+	/// The signers in sec1 bytes hex format whose blobs are rejected, even when they are known.
+	pub revoked_signers_sec1_bytes_hex: Arc<RwLock<HashSet<String>>>,
+	/// The number of distinct known signers a blob needs, 1 for a single trusted signer.
+	pub threshold: usize,
+	/// Whether the known signers can be replaced with [`Self::update_known_signers`]
+	/// and revoked with [`Self::revoke_signer`].
+	pub allow_runtime_updates: bool,
 }
 
 impl<C> InKnownSignersVerifier<C>
@@ -86,18 +148,81 @@ where
 	AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C> + VerifyPrimitive<C>,
 	FieldBytesSize<C>: ModulusSize,
 {
-	pub fn new<T>(known_signers_sec1_bytes_hex: T) -> Self
+	pub fn new<T>(known_signers_sec1_bytes_hex: T, chain_id: u64, threshold: usize) -> Self
 	where
 		T: IntoIterator,
 		T::Item: Into<String>,
 	{
 		Self {
-			inner_verifier: Verifier::new(),
-			known_signers_sec1_bytes_hex: known_signers_sec1_bytes_hex
-				.into_iter()
-				.map(Into::into)
-				.collect(),
+			inner_verifier: Verifier::new(chain_id),
+			known_signers_sec1_bytes_hex: Arc::new(RwLock::new(
+				known_signers_sec1_bytes_hex.into_iter().map(Into::into).collect(),
+			)),
+			revoked_signers_sec1_bytes_hex: Arc::new(RwLock::new(HashSet::new())),
+			threshold,
+			allow_runtime_updates: false,
+		}
+	}
+
+	/// Sets whether the known signers can be replaced while the verifier is in use.
+	pub fn with_runtime_updates(mut self, allow_runtime_updates: bool) -> Self {
+		self.allow_runtime_updates = allow_runtime_updates;
+		self
+	}
+
+	/// Sets whether the V1 blobs, which carry no chain id, are rejected.
+	pub fn with_enforced_chain_id(mut self, enforce_chain_id: bool) -> Self {
+		self.inner_verifier = self.inner_verifier.with_enforced_chain_id(enforce_chain_id);
+		self
+	}
+
+	fn check_runtime_updates(&self) -> Result<(), Error> {
+		if !self.allow_runtime_updates {
+			return Err(Error::Internal(
+				"runtime updates of the known signers are disabled".into(),
+			));
 		}
+		Ok(())
+	}
+
+	/// Replaces the set of known signers of this verifier and of its clones,
+	/// the verified blobs are forgotten.
+	/// Fails if the runtime updates are not allowed.
+	pub fn update_known_signers(&self, new_set: HashSet<String>) -> Result<(), Error> {
+		self.check_runtime_updates()?;
+		*self.known_signers_sec1_bytes_hex.write().expect("known signers lock poisoned") = new_set;
+		self.inner_verifier.clear_cache();
+		Ok(())
+	}
+
+	/// Rejects the blobs signed by `signer_hex`, for this verifier and its clones.
+	/// Fails if the runtime updates are not allowed.
+	pub fn revoke_signer(&self, signer_hex: &str) -> Result<(), Error> {
+		self.check_runtime_updates()?;
+		self.revoked_signers_sec1_bytes_hex
+			.write()
+			.expect("revoked signers lock poisoned")
+			.insert(signer_hex.to_string());
+		Ok(())
+	}
+
+	/// Accepts again the blobs signed by `signer_hex` if it is known.
+	/// Fails if the runtime updates are not allowed.
+	pub fn unrevoke_signer(&self, signer_hex: &str) -> Result<(), Error> {
+		self.check_runtime_updates()?;
+		self.revoked_signers_sec1_bytes_hex
+			.write()
+			.expect("revoked signers lock poisoned")
+			.remove(signer_hex);
+		Ok(())
+	}
+
+	/// The number of known signers.
+	pub fn known_signer_count(&self) -> usize {
+		self.known_signers_sec1_bytes_hex
+			.read()
+			.expect("known signers lock poisoned")
+			.len()
 	}
 }
 
@@ -118,9 +243,34 @@ where
 	) -> Result<Verified<IntermediateBlobRepresentation>, Error> {
 		let ir_blob = self.inner_verifier.verify(blob, height).await?;
 		info!("Verified inner blob");
-		let signer = ir_blob.inner().signer_hex();
-		if !self.known_signers_sec1_bytes_hex.contains(&signer) {
-			return Err(Error::Validation("signer not in known signers".to_string()));
+		let known_signers: HashSet<String> = {
+			let known_signers_sec1_bytes_hex =
+				self.known_signers_sec1_bytes_hex.read().expect("known signers lock poisoned");
+			ir_blob
+				.inner()
+				.valid_signers::<C>()
+				.into_iter()
+				.map(hex::encode)
+				.filter(|signer| known_signers_sec1_bytes_hex.contains(signer))
+				.collect()
+		};
+		// a blob is never accepted without a known signer, even with a threshold of 0
+		if known_signers.is_empty() || known_signers.len() < self.threshold {
+			return Err(Error::Validation("threshold not met".to_string()));
+		}
+		let revoked = {
+			let revoked_signers_sec1_bytes_hex = self
+				.revoked_signers_sec1_bytes_hex
+				.read()
+				.expect("revoked signers lock poisoned");
+			ir_blob
+				.inner()
+				.valid_signers::<C>()
+				.into_iter()
+				.any(|signer| revoked_signers_sec1_bytes_hex.contains(&hex::encode(signer)))
+		};
+		if revoked {
+			return Err(Error::Validation("signer revoked".to_string()));
 		}
 
 		Ok(ir_blob)
@@ -129,5 +279,372 @@ where
 
 #[cfg(test)]
 pub mod tests {
-	// TODO: we need to recreate the signed verifier tests
+	use super::*;
+	use ecdsa::SigningKey;
+	use k256::Secp256k1;
+	use movement_celestia_da_util::ir_blob::{
+		BlobTypeTag, InnerMultiSignedBlobV3, InnerSignedBlobV1Data, InnerSignedBlobV2Data,
+	};
+
+	fn signed_blob(
+		signing_key: &SigningKey<Secp256k1>,
+		chain_id: u64,
+	) -> Result<IntermediateBlobRepresentation, anyhow::Error> {
+		let data =
+			InnerSignedBlobV2Data::new(vec![1, 2, 3], 123, chain_id, BlobTypeTag::TransactionBatch);
+		Ok(data.try_to_sign(signing_key)?.into())
+	}
+
+	fn known_signers_verifier(
+		signing_key: &SigningKey<Secp256k1>,
+		chain_id: u64,
+	) -> InKnownSignersVerifier<Secp256k1> {
+		let signer = hex::encode(signing_key.verifying_key().to_sec1_bytes());
+		InKnownSignersVerifier::new([signer], chain_id, 1)
+	}
+
+	fn multi_signed_blob(
+		signing_keys: &[SigningKey<Secp256k1>],
+	) -> Result<IntermediateBlobRepresentation, anyhow::Error> {
+		let data =
+			InnerSignedBlobV2Data::new(vec![1, 2, 3], 123, 27, BlobTypeTag::TransactionBatch);
+		let mut blob = InnerMultiSignedBlobV3::new::<Secp256k1>(data);
+		for signing_key in signing_keys {
+			blob.try_add_signature(signing_key)?;
+		}
+		Ok(blob.into())
+	}
+
+	/// A 2-of-3 verifier and the keys of its validators.
+	fn threshold_verifier() -> (InKnownSignersVerifier<Secp256k1>, Vec<SigningKey<Secp256k1>>) {
+		let validators: Vec<_> = (0..3)
+			.map(|_| SigningKey::<Secp256k1>::random(&mut rand::thread_rng()))
+			.collect();
+		let known_signers =
+			validators.iter().map(|key| hex::encode(key.verifying_key().to_sec1_bytes()));
+		(InKnownSignersVerifier::new(known_signers, 27, 2), validators)
+	}
+
+	fn assert_threshold_not_met(result: Result<Verified<IntermediateBlobRepresentation>, Error>) {
+		match result {
+			Err(Error::Validation(message)) => assert_eq!(message, "threshold not met"),
+			Err(e) => panic!("unexpected error: {}", e),
+			Ok(_) => panic!("blob below the threshold was verified"),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_blob_of_the_same_chain_is_verified() -> Result<(), anyhow::Error> {
+		let signing_key = SigningKey::<Secp256k1>::random(&mut rand::thread_rng());
+		let verifier = known_signers_verifier(&signing_key, 27);
+
+		let blob = signed_blob(&signing_key, 27)?;
+		verifier.verify(blob, 1).await?;
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_blob_of_another_chain_is_rejected() -> Result<(), anyhow::Error> {
+		let signing_key = SigningKey::<Secp256k1>::random(&mut rand::thread_rng());
+		let verifier = known_signers_verifier(&signing_key, 27);
+
+		// a validly signed blob of another chain must not be replayed on this one
+		let blob = signed_blob(&signing_key, 126)?;
+		match verifier.verify(blob, 1).await {
+			Err(Error::Validation(message)) => assert_eq!(message, "chain_id mismatch"),
+			Err(e) => panic!("unexpected error: {}", e),
+			Ok(_) => panic!("blob of another chain was verified"),
+		}
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_v1_blob_is_rejected_when_the_chain_id_is_enforced() -> Result<(), anyhow::Error> {
+		let signing_key = SigningKey::<Secp256k1>::random(&mut rand::thread_rng());
+		let blob: IntermediateBlobRepresentation =
+			InnerSignedBlobV1Data::new(vec![1, 2, 3], 123).try_to_sign(&signing_key)?.into();
+
+		let verifier = known_signers_verifier(&signing_key, 27).with_enforced_chain_id(true);
+		match verifier.verify(blob, 1).await {
+			Err(Error::Validation(message)) => assert_eq!(message, "V1 blob carries no chain id"),
+			Err(e) => panic!("unexpected error: {}", e),
+			Ok(_) => panic!("V1 blob was verified"),
+		}
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_v1_blob_is_verified_by_default() -> Result<(), anyhow::Error> {
+		let signing_key = SigningKey::<Secp256k1>::random(&mut rand::thread_rng());
+		let blob: IntermediateBlobRepresentation =
+			InnerSignedBlobV1Data::new(vec![1, 2, 3], 123).try_to_sign(&signing_key)?.into();
+
+		// the blobs written before the chain id was signed must still sync from genesis
+		let verifier = known_signers_verifier(&signing_key, 27);
+		verifier.verify(blob, 1).await?;
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_unknown_signer_is_rejected() -> Result<(), anyhow::Error> {
+		let signing_key = SigningKey::<Secp256k1>::random(&mut rand::thread_rng());
+		let other_key = SigningKey::<Secp256k1>::random(&mut rand::thread_rng());
+		let verifier = known_signers_verifier(&other_key, 27);
+
+		let blob = signed_blob(&signing_key, 27)?;
+		assert!(matches!(verifier.verify(blob, 1).await, Err(Error::Validation(_))));
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_verified_blob_is_cached() -> Result<(), anyhow::Error> {
+		let signing_key = SigningKey::<Secp256k1>::random(&mut rand::thread_rng());
+		let verifier =
+			Verifier::<Secp256k1>::with_cache_capacity(27, NonZeroUsize::new(1).unwrap());
+
+		let blob = signed_blob(&signing_key, 27)?;
+		verifier.verify(blob.clone(), 1).await?;
+		assert!(verifier
+			.cache
+			.lock()
+			.unwrap()
+			.contains(&Verifier::<Secp256k1>::cache_key(&blob)?));
+		verifier.verify(blob.clone(), 1).await?;
+
+		// the least recently verified blob is evicted
+		let other_data =
+			InnerSignedBlobV2Data::new(vec![4], 456, 27, BlobTypeTag::TransactionBatch);
+		verifier.verify(other_data.try_to_sign(&signing_key)?.into(), 1).await?;
+		assert!(!verifier
+			.cache
+			.lock()
+			.unwrap()
+			.contains(&Verifier::<Secp256k1>::cache_key(&blob)?));
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_cache_does_not_skip_tampered_data() -> Result<(), anyhow::Error> {
+		let signing_key = SigningKey::<Secp256k1>::random(&mut rand::thread_rng());
+		let verifier = Verifier::<Secp256k1>::new(27);
+		let blob = signed_blob(&signing_key, 27)?;
+		verifier.verify(blob.clone(), 1).await?;
+
+		// the cached id and signature are replayed with other data
+		let mut tampered = match blob {
+			IntermediateBlobRepresentation::SignedV2(inner) => inner,
+			_ => unreachable!(),
+		};
+		tampered.data.blob = vec![6, 6, 6];
+		assert!(matches!(verifier.verify(tampered.into(), 1).await, Err(Error::Validation(_))));
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_cache_does_not_skip_tampered_signatures() -> Result<(), anyhow::Error> {
+		let (verifier, validators) = threshold_verifier();
+		let blob = multi_signed_blob(&validators[..1])?;
+		assert_threshold_not_met(verifier.verify(blob.clone(), 1).await);
+
+		// the id of the blob is cached, a forged signature of another validator must still fail
+		let mut tampered = match blob {
+			IntermediateBlobRepresentation::MultiSignedV3(inner) => inner,
+			_ => unreachable!(),
+		};
+		let mut forged = tampered.signatures[0].clone();
+		forged.signer = validators[1].verifying_key().to_sec1_bytes().to_vec();
+		tampered.signatures.push(forged);
+		assert!(matches!(verifier.verify(tampered.into(), 1).await, Err(Error::Validation(_))));
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_known_signers_change_clears_the_cache() -> Result<(), anyhow::Error> {
+		let signing_key = SigningKey::<Secp256k1>::random(&mut rand::thread_rng());
+		let verifier = known_signers_verifier(&signing_key, 27).with_runtime_updates(true);
+
+		let blob = signed_blob(&signing_key, 27)?;
+		verifier.verify(blob.clone(), 1).await?;
+		assert_eq!(verifier.inner_verifier.cache.lock().unwrap().len(), 1);
+
+		verifier.update_known_signers(HashSet::new())?;
+		assert!(verifier.inner_verifier.cache.lock().unwrap().is_empty());
+		assert_eq!(verifier.known_signer_count(), 0);
+		assert!(verifier.verify(blob, 1).await.is_err());
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_runtime_updates_are_disabled_by_default() -> Result<(), anyhow::Error> {
+		let signing_key = SigningKey::<Secp256k1>::random(&mut rand::thread_rng());
+		let verifier = known_signers_verifier(&signing_key, 27);
+
+		assert!(matches!(verifier.update_known_signers(HashSet::new()), Err(Error::Internal(_))));
+		assert_eq!(verifier.known_signer_count(), 1);
+		Ok(())
+	}
+
+	#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+	async fn test_update_known_signers_while_verifying() -> Result<(), anyhow::Error> {
+		let signing_key = SigningKey::<Secp256k1>::random(&mut rand::thread_rng());
+		let rotated_key = SigningKey::<Secp256k1>::random(&mut rand::thread_rng());
+		let verifier = known_signers_verifier(&signing_key, 27).with_runtime_updates(true);
+
+		// the signer stays known while the other validator rotates in and out
+		let signer = hex::encode(signing_key.verifying_key().to_sec1_bytes());
+		let rotated = hex::encode(rotated_key.verifying_key().to_sec1_bytes());
+		let updater = {
+			let verifier = verifier.clone();
+			tokio::spawn(async move {
+				for i in 0..200 {
+					let mut new_set = HashSet::from([signer.clone()]);
+					if i % 2 == 0 {
+						new_set.insert(rotated.clone());
+					}
+					verifier.update_known_signers(new_set)?;
+					tokio::task::yield_now().await;
+				}
+				Ok::<_, Error>(())
+			})
+		};
+
+		let verifiers: Vec<_> = (0..4)
+			.map(|_| {
+				let verifier = verifier.clone();
+				let blob = signed_blob(&signing_key, 27);
+				tokio::spawn(async move {
+					let blob = blob?;
+					for _ in 0..50 {
+						verifier.verify(blob.clone(), 1).await?;
+						assert!((1..=2).contains(&verifier.known_signer_count()));
+					}
+					Ok::<_, anyhow::Error>(())
+				})
+			})
+			.collect();
+
+		updater.await??;
+		for verifier in verifiers {
+			verifier.await??;
+		}
+		assert_eq!(verifier.known_signer_count(), 1);
+		Ok(())
+	}
+
+	fn assert_signer_revoked(result: Result<Verified<IntermediateBlobRepresentation>, Error>) {
+		match result {
+			Err(Error::Validation(message)) => assert_eq!(message, "signer revoked"),
+			Err(e) => panic!("unexpected error: {}", e),
+			Ok(_) => panic!("blob of a revoked signer was verified"),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_revoked_signer_is_rejected() -> Result<(), anyhow::Error> {
+		let signing_key = SigningKey::<Secp256k1>::random(&mut rand::thread_rng());
+		let verifier = known_signers_verifier(&signing_key, 27).with_runtime_updates(true);
+		let signer = hex::encode(signing_key.verifying_key().to_sec1_bytes());
+
+		let blob = signed_blob(&signing_key, 27)?;
+		verifier.verify(blob.clone(), 1).await?;
+
+		// the blob is in the verification cache, the revocation must still apply
+		verifier.revoke_signer(&signer)?;
+		assert_signer_revoked(verifier.verify(blob.clone(), 1).await);
+
+		verifier.unrevoke_signer(&signer)?;
+		verifier.verify(blob, 1).await?;
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_revocation_is_disabled_by_default() -> Result<(), anyhow::Error> {
+		let signing_key = SigningKey::<Secp256k1>::random(&mut rand::thread_rng());
+		let verifier = known_signers_verifier(&signing_key, 27);
+		let signer = hex::encode(signing_key.verifying_key().to_sec1_bytes());
+
+		assert!(matches!(verifier.revoke_signer(&signer), Err(Error::Internal(_))));
+		assert!(matches!(verifier.unrevoke_signer(&signer), Err(Error::Internal(_))));
+		verifier.verify(signed_blob(&signing_key, 27)?, 1).await?;
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_revoked_signer_rejects_blob_meeting_the_threshold() -> Result<(), anyhow::Error> {
+		let (verifier, validators) = threshold_verifier();
+		let verifier = verifier.with_runtime_updates(true);
+		verifier.revoke_signer(&hex::encode(validators[2].verifying_key().to_sec1_bytes()))?;
+
+		// the revoked signer is still known, the threshold is met but its signature taints the blob
+		let blob = multi_signed_blob(&validators)?;
+		assert_signer_revoked(verifier.verify(blob, 1).await);
+
+		let blob = multi_signed_blob(&validators[..2])?;
+		verifier.verify(blob, 1).await?;
+
+		// unrevoking a signer does not make it known
+		let unknown = SigningKey::<Secp256k1>::random(&mut rand::thread_rng());
+		let unknown_signer = hex::encode(unknown.verifying_key().to_sec1_bytes());
+		verifier.revoke_signer(&unknown_signer)?;
+		verifier.unrevoke_signer(&unknown_signer)?;
+		assert_threshold_not_met(verifier.verify(multi_signed_blob(&[unknown])?, 1).await);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_exactly_threshold_signers() -> Result<(), anyhow::Error> {
+		let (verifier, validators) = threshold_verifier();
+
+		let blob = multi_signed_blob(&validators[..2])?;
+		verifier.verify(blob, 1).await?;
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_below_threshold_signers() -> Result<(), anyhow::Error> {
+		let (verifier, validators) = threshold_verifier();
+
+		let blob = multi_signed_blob(&validators[..1])?;
+		assert_threshold_not_met(verifier.verify(blob, 1).await);
+
+		// signing twice with the same key counts once
+		let blob = multi_signed_blob(&[validators[0].clone(), validators[0].clone()])?;
+		assert_threshold_not_met(verifier.verify(blob, 1).await);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_unknown_signers_are_not_counted() -> Result<(), anyhow::Error> {
+		let (verifier, validators) = threshold_verifier();
+		let unknown = SigningKey::<Secp256k1>::random(&mut rand::thread_rng());
+
+		let blob = multi_signed_blob(&[validators[0].clone(), unknown.clone()])?;
+		assert_threshold_not_met(verifier.verify(blob, 1).await);
+
+		let blob = multi_signed_blob(&[validators[0].clone(), unknown, validators[2].clone()])?;
+		verifier.verify(blob, 1).await?;
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_garbage_signature_does_not_veto_the_threshold() -> Result<(), anyhow::Error> {
+		let (verifier, validators) = threshold_verifier();
+
+		let mut blob = match multi_signed_blob(&validators)? {
+			IntermediateBlobRepresentation::MultiSignedV3(blob) => blob,
+			_ => unreachable!("multi_signed_blob returns a V3 blob"),
+		};
+		// the third known signer's signature is garbage, the two others meet the threshold
+		blob.signatures[2].signature = vec![0; 64];
+		verifier.verify(blob.clone().into(), 1).await?;
+
+		// nor does a garbage entry claiming a signer already counted
+		blob.signatures[2].signer = blob.signatures[0].signer.clone();
+		verifier.verify(blob.clone().into(), 1).await?;
+
+		// the garbage entries are not counted towards the threshold
+		blob.signatures.remove(1);
+		assert_threshold_not_met(verifier.verify(blob.into(), 1).await);
+		Ok(())
+	}
 }
@@ -1,7 +1,9 @@
+use crate::v1::metrics;
 use movement_celestia_da_util::config::Config;
 use movement_da_light_node_proto::light_node_service_server::{
 	LightNodeService, LightNodeServiceServer,
 };
+use poem::listener::TcpListener;
 use tonic::transport::Server;
 use tracing::info;
 
@@ -15,6 +17,9 @@ pub trait LightNodeV1Operations: LightNodeService + Send + Sync + Sized + Clone
 	/// Tries to get the service address
 	fn try_service_address(&self) -> Result<String, anyhow::Error>;
 
+	/// Tries to get the metrics and health check address
+	fn try_metrics_address(&self) -> Result<String, anyhow::Error>;
+
 	/// Runs the server
 	async fn run_server(&self) -> Result<(), anyhow::Error> {
 		let reflection = tonic_reflection::server::Builder::configure()
@@ -34,6 +39,17 @@ pub trait LightNodeV1Operations: LightNodeService + Send + Sync + Sized + Clone
 		Ok(())
 	}
 
+	/// Runs the metrics and health check server
+	async fn run_metrics_server(&self) -> Result<(), anyhow::Error> {
+		let address = self.try_metrics_address()?;
+		info!("Metrics server listening on: {}", address);
+		poem::Server::new(TcpListener::bind(address))
+			.run(metrics::routes(self.clone()))
+			.await?;
+
+		Ok(())
+	}
+
 	/// Runs the server and the background tasks.
 	async fn run(self) -> Result<(), anyhow::Error> {
 		let background_handle = self.run_background_tasks();
@@ -43,9 +59,10 @@ pub trait LightNodeV1Operations: LightNodeService + Send + Sync + Sized + Clone
 			Ok::<_, anyhow::Error>(())
 		};
 		let server = self.run_server();
+		let metrics_server = self.run_metrics_server();
 
 		info!("Running server and background tasks.");
-		tokio::try_join!(server, background_tasks)?;
+		tokio::try_join!(server, metrics_server, background_tasks)?;
 
 		Ok(())
 	}
@@ -50,7 +50,12 @@ where
 		AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
 		FieldBytesSize<C>: ModulusSize,
 	{
-		let config = self.godfig.try_wait_for_ready().await?;
+		let config: Config = self.godfig.try_wait_for_ready().await?;
+		// Fail on a misconfiguration before any client is built.
+		config.validate().map_err(|errors| {
+			let errors: Vec<_> = errors.iter().map(|err| err.to_string()).collect();
+			anyhow::anyhow!("Invalid light node config: {}", errors.join(", "))
+		})?;
 		LightNodeV1::try_from_config(config).await
 	}
 
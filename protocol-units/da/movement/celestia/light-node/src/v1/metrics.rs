@@ -0,0 +1,115 @@
+use movement_da_light_node_proto::light_node_service_server::LightNodeService;
+use movement_da_light_node_proto::{GetSyncStatusRequest, GetSyncStatusResponse};
+use poem::{
+	get, handler,
+	http::StatusCode,
+	middleware::Tracing,
+	web::{Data, Json},
+	Endpoint, EndpointExt, IntoResponse, Response, Route,
+};
+
+/// Builds the routes serving the light node sync status for Prometheus and health checks.
+pub fn routes<L>(light_node: L) -> impl Endpoint
+where
+	L: LightNodeService + Clone,
+{
+	Route::new()
+		.at("/health", get(health::<L>::default()))
+		.at("/metrics", get(metrics::<L>::default()))
+		.data(light_node)
+		.with(Tracing)
+}
+
+async fn sync_status<L>(light_node: &L) -> Result<GetSyncStatusResponse, poem::Error>
+where
+	L: LightNodeService,
+{
+	light_node
+		.get_sync_status(tonic::Request::new(GetSyncStatusRequest {}))
+		.await
+		.map(tonic::Response::into_inner)
+		.map_err(|e| poem::Error::from_string(e.message(), StatusCode::SERVICE_UNAVAILABLE))
+}
+
+/// Reports the sync status, answering with 503 while the light node lags behind Celestia
+/// or when the Celestia network head can't be read.
+#[handler]
+async fn health<L>(light_node: Data<&L>) -> Result<Response, poem::Error>
+where
+	L: LightNodeService + Clone,
+{
+	let status = sync_status(light_node.0).await?;
+	let code = if status.is_synced { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+	Ok(Json(serde_json::json!({
+		"latest_submitted_height": status.latest_submitted_height,
+		"latest_celestia_height": status.latest_celestia_height,
+		"lag_blocks": status.lag_blocks,
+		"is_synced": status.is_synced,
+	}))
+	.with_status(code)
+	.into_response())
+}
+
+/// Renders the sync status in the Prometheus text exposition format.
+#[handler]
+async fn metrics<L>(light_node: Data<&L>) -> Result<Response, poem::Error>
+where
+	L: LightNodeService + Clone,
+{
+	let status = sync_status(light_node.0).await?;
+	Ok(render_metrics(&status)
+		.with_content_type("text/plain; version=0.0.4")
+		.into_response())
+}
+
+fn render_metrics(status: &GetSyncStatusResponse) -> String {
+	let gauges = [
+		(
+			"movement_da_light_node_latest_submitted_height",
+			"Celestia height of the last blob submitted by the light node.",
+			status.latest_submitted_height,
+		),
+		(
+			"movement_da_light_node_latest_celestia_height",
+			"Latest Celestia network head height seen by the light node.",
+			status.latest_celestia_height,
+		),
+		(
+			"movement_da_light_node_lag_blocks",
+			"Number of Celestia blocks the last submission lags behind the network head.",
+			status.lag_blocks,
+		),
+		(
+			"movement_da_light_node_is_synced",
+			"Whether the light node is caught up with Celestia.",
+			status.is_synced as u64,
+		),
+	];
+	gauges
+		.iter()
+		.map(|(name, help, value)| {
+			format!("# HELP {name} {help}\n# TYPE {name} gauge\n{name} {value}\n")
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_render_metrics() {
+		let status = GetSyncStatusResponse {
+			latest_submitted_height: 10,
+			latest_celestia_height: 15,
+			lag_blocks: 5,
+			is_synced: false,
+		};
+		let rendered = render_metrics(&status);
+		assert!(rendered.contains("# TYPE movement_da_light_node_lag_blocks gauge\n"));
+		assert!(rendered.contains("movement_da_light_node_latest_submitted_height 10\n"));
+		assert!(rendered.contains("movement_da_light_node_latest_celestia_height 15\n"));
+		assert!(rendered.contains("movement_da_light_node_lag_blocks 5\n"));
+		assert!(rendered.contains("movement_da_light_node_is_synced 0\n"));
+	}
+}
@@ -6,6 +6,8 @@ pub mod light_node;
 
 pub mod manager;
 
+pub mod metrics;
+
 #[cfg(not(feature = "sequencer"))]
 pub use passthrough::*;
 
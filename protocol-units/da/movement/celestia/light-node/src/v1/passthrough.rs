@@ -1,6 +1,9 @@
 use movement_celestia_da_util::ir_blob::IntermediateBlobRepresentation;
 use std::fmt::{self, Debug, Formatter};
-use std::sync::Arc;
+use std::sync::{
+	atomic::{AtomicBool, AtomicU64, Ordering},
+	Arc,
+};
 use tokio_stream::{Stream, StreamExt};
 use tracing::{debug, error, info};
 
@@ -13,7 +16,10 @@ use movement_celestia_da_light_node_verifier::{
 };
 use movement_celestia_da_util::{
 	config::Config,
-	ir_blob::{celestia::CelestiaIntermediateBlobRepresentation, InnerSignedBlobV1Data},
+	ir_blob::{
+		celestia::{CelestiaIntermediateBlobRepresentation, CompressionConfig},
+		BlobTypeTag, InnerSignedBlobV2Data,
+	},
 };
 use movement_da_light_node_proto::light_node_service_server::LightNodeService;
 use movement_da_light_node_proto::*;
@@ -32,6 +38,10 @@ use ecdsa::{
 	SignatureSize, SigningKey,
 };
 
+/// Number of Celestia blocks the last submission can lag behind the network head
+/// while the light node is still considered synced.
+const SYNCED_MAX_LAG_BLOCKS: u64 = 2;
+
 #[derive(Clone)]
 pub struct LightNodeV1<C>
 where
@@ -48,6 +58,10 @@ where
 		Box<dyn VerifierOperations<CelestiaBlob, IntermediateBlobRepresentation> + Send + Sync>,
 	>,
 	pub signing_key: SigningKey<C>,
+	/// Celestia height of the last blob submitted by this node.
+	pub last_submitted_height: Arc<AtomicU64>,
+	/// Whether the last submission of blobs to Celestia failed.
+	pub submission_failed: Arc<AtomicBool>,
 }
 
 impl<C> Debug for LightNodeV1<C>
@@ -87,12 +101,19 @@ where
 			config: config.clone(),
 			celestia_namespace: config.celestia_namespace(),
 			default_client: client.clone(),
-			verifier: Arc::new(Box::new(Verifier::<C>::new(
-				client,
-				config.celestia_namespace(),
-				config.da_signers_sec1_keys(),
-			))),
+			verifier: Arc::new(Box::new(
+				Verifier::<C>::new(
+					client,
+					config.celestia_namespace(),
+					config.da_signers_sec1_keys(),
+					config.movement_da_light_node_chain_id(),
+					config.da_signers_threshold(),
+				)
+				.with_enforced_chain_id(config.movement_da_light_node_enforce_chain_id()),
+			)),
 			signing_key,
+			last_submitted_height: Arc::new(AtomicU64::new(0)),
+			submission_failed: Arc::new(AtomicBool::new(false)),
 		})
 	}
 
@@ -100,6 +121,10 @@ where
 		Ok(self.config.movement_da_light_node_service())
 	}
 
+	fn try_metrics_address(&self) -> Result<String, anyhow::Error> {
+		Ok(self.config.movement_da_light_node_metrics_service())
+	}
+
 	/// Runs background tasks for the LightNodeV1 instance.
 	async fn run_background_tasks(&self) -> Result<(), anyhow::Error> {
 		Ok(())
@@ -119,12 +144,22 @@ where
 		// mark the timestamp as now in milliseconds
 		let timestamp = chrono::Utc::now().timestamp_micros() as u64;
 
-		// sign the blob data and the timestamp
-		let data = InnerSignedBlobV1Data::new(data, timestamp).try_to_sign(&self.signing_key)?;
+		// sign the blob data, the timestamp and the chain id
+		let data = InnerSignedBlobV2Data::new(
+			data,
+			timestamp,
+			self.config.movement_da_light_node_chain_id(),
+			BlobTypeTag::TransactionBatch,
+		)
+		.try_to_sign(&self.signing_key)?;
 
 		// create the celestia blob
-		CelestiaIntermediateBlobRepresentation(data.into(), self.celestia_namespace.clone())
-			.try_into()
+		CelestiaIntermediateBlobRepresentation(
+			data.into(),
+			self.celestia_namespace.clone(),
+			CompressionConfig::default(),
+		)
+		.try_into()
 	}
 
 	/// Submits a CelestiaBlob to the Celestia node.
@@ -133,8 +168,10 @@ where
 		// config.with_gas(2);
 		let height = self.default_client.blob_submit(&[blob], config).await.map_err(|e| {
 			error!(error = %e, "failed to submit the blob");
+			self.submission_failed.store(true, Ordering::Relaxed);
 			anyhow::anyhow!("Failed submitting the blob: {}", e)
 		})?;
+		self.record_submission(height);
 
 		Ok(height)
 	}
@@ -147,12 +184,19 @@ where
 		let height =
 			self.default_client.blob_submit(blobs, TxConfig::default()).await.map_err(|e| {
 				error!(error = %e, "failed to submit the blobs");
+				self.submission_failed.store(true, Ordering::Relaxed);
 				anyhow::anyhow!("Failed submitting the blob: {}", e)
 			})?;
+		self.record_submission(height);
 
 		Ok(height)
 	}
 
+	fn record_submission(&self, height: u64) {
+		self.last_submitted_height.fetch_max(height, Ordering::Relaxed);
+		self.submission_failed.store(false, Ordering::Relaxed);
+	}
+
 	/// Submits a blob to the Celestia node.
 	pub async fn submit_blob(&self, data: Vec<u8>) -> Result<Blob, anyhow::Error> {
 		let celestia_blob = self.create_new_celestia_blob(data)?;
@@ -517,4 +561,74 @@ where
 
 		Ok(tonic::Response::new(BatchWriteResponse { blobs: blob_responses }))
 	}
+
+	/// Whether the light node is caught up with Celestia, see [`sync_status`].
+	async fn get_sync_status(
+		&self,
+		_request: tonic::Request<GetSyncStatusRequest>,
+	) -> std::result::Result<tonic::Response<GetSyncStatusResponse>, tonic::Status> {
+		let latest_celestia_height: u64 = self
+			.default_client
+			.header_network_head()
+			.await
+			.map_err(|e| tonic::Status::internal(e.to_string()))?
+			.height()
+			.into();
+		Ok(tonic::Response::new(sync_status(
+			self.last_submitted_height.load(Ordering::Relaxed),
+			latest_celestia_height,
+			self.submission_failed.load(Ordering::Relaxed),
+		)))
+	}
+}
+
+/// The sync status of a light node that last submitted a blob at `latest_submitted_height`.
+/// The node only lags behind Celestia while its submissions fail:
+/// a node that is idle, or hasn't submitted anything yet, is synced.
+fn sync_status(
+	latest_submitted_height: u64,
+	latest_celestia_height: u64,
+	submission_failed: bool,
+) -> GetSyncStatusResponse {
+	let lag_blocks = if submission_failed {
+		latest_celestia_height.saturating_sub(latest_submitted_height)
+	} else {
+		0
+	};
+	GetSyncStatusResponse {
+		latest_submitted_height,
+		latest_celestia_height,
+		lag_blocks,
+		is_synced: lag_blocks <= SYNCED_MAX_LAG_BLOCKS,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_fresh_node_is_synced() {
+		let status = sync_status(0, 1000, false);
+		assert_eq!(status.lag_blocks, 0);
+		assert!(status.is_synced);
+	}
+
+	#[test]
+	fn test_idle_node_is_synced() {
+		let status = sync_status(900, 1000, false);
+		assert_eq!(status.lag_blocks, 0);
+		assert!(status.is_synced);
+	}
+
+	#[test]
+	fn test_failing_node_lags() {
+		let status = sync_status(999, 1000, true);
+		assert_eq!(status.lag_blocks, 1);
+		assert!(status.is_synced);
+
+		let status = sync_status(900, 1000, true);
+		assert_eq!(status.lag_blocks, 100);
+		assert!(!status.is_synced);
+	}
 }
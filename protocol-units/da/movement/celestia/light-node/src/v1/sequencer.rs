@@ -110,6 +110,10 @@ where
 		self.pass_through.try_service_address()
 	}
 
+	fn try_metrics_address(&self) -> Result<String, anyhow::Error> {
+		self.pass_through.try_metrics_address()
+	}
+
 	async fn run_background_tasks(&self) -> Result<(), anyhow::Error> {
 		self.run_block_proposer().await?;
 
@@ -511,6 +515,14 @@ where
 
 		Ok(tonic::Response::new(grpc::BatchWriteResponse { blobs: intents }))
 	}
+
+	/// Whether the light node is caught up with Celestia.
+	async fn get_sync_status(
+		&self,
+		request: tonic::Request<grpc::GetSyncStatusRequest>,
+	) -> std::result::Result<tonic::Response<grpc::GetSyncStatusResponse>, tonic::Status> {
+		self.pass_through.get_sync_status(request).await
+	}
 }
 
 pub mod block {
@@ -0,0 +1,81 @@
+//! Compares the compression ratio and time of the blob compression algorithms
+//! on batches shaped like signed Aptos coin transfers.
+//!
+//! Run with `cargo bench -p movement-celestia-da-util --bench compression`.
+
+use movement_celestia_da_util::ir_blob::celestia::{CompressionAlgorithm, CompressionConfig};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::Serialize;
+use std::time::Instant;
+
+/// Mirrors the fields of a signed `0x1::aptos_account::transfer` transaction.
+#[derive(Serialize)]
+struct Transfer {
+	sender: [u8; 32],
+	sequence_number: u64,
+	module: String,
+	function: String,
+	recipient: [u8; 32],
+	amount: u64,
+	max_gas_amount: u64,
+	gas_unit_price: u64,
+	expiration_timestamp_secs: u64,
+	chain_id: u8,
+	public_key: [u8; 32],
+	signature: Vec<u8>,
+}
+
+/// Serializes a batch of `size` transfers between a pool of accounts.
+fn transaction_batch(rng: &mut StdRng, size: usize) -> Vec<u8> {
+	let accounts: Vec<[u8; 32]> = (0..20).map(|_| rng.gen()).collect();
+	let transfers: Vec<Transfer> = (0..size)
+		.map(|i| {
+			let sender = accounts[rng.gen_range(0..accounts.len())];
+			Transfer {
+				sender,
+				sequence_number: i as u64,
+				module: "0x1::aptos_account".to_string(),
+				function: "transfer".to_string(),
+				recipient: accounts[rng.gen_range(0..accounts.len())],
+				amount: rng.gen_range(1..1_000_000),
+				max_gas_amount: 200_000,
+				gas_unit_price: 100,
+				expiration_timestamp_secs: 1_700_000_000 + i as u64,
+				chain_id: 27,
+				public_key: sender,
+				signature: (0..64).map(|_| rng.gen()).collect(),
+			}
+		})
+		.collect();
+	bcs::to_bytes(&transfers).expect("failed to serialize the batch")
+}
+
+fn main() -> Result<(), anyhow::Error> {
+	let mut rng = StdRng::seed_from_u64(0);
+	let configs = [
+		CompressionConfig { algorithm: CompressionAlgorithm::None, level: 0 },
+		CompressionConfig { algorithm: CompressionAlgorithm::Lz4, level: 0 },
+		CompressionConfig { algorithm: CompressionAlgorithm::Zstd, level: 0 },
+		CompressionConfig { algorithm: CompressionAlgorithm::Zstd, level: 3 },
+		CompressionConfig { algorithm: CompressionAlgorithm::Zstd, level: 19 },
+	];
+
+	for batch_size in [1, 100, 1000] {
+		let batch = transaction_batch(&mut rng, batch_size);
+		println!("batch of {} transactions, {} bytes", batch_size, batch.len());
+		for config in configs {
+			let start = Instant::now();
+			let compressed = config.compress(&batch)?;
+			let elapsed = start.elapsed();
+			println!(
+				"  {:?} level {:>2}: {:>8} bytes, ratio {:.3}, {:?}",
+				config.algorithm,
+				config.level,
+				compressed.len(),
+				batch.len() as f64 / compressed.len() as f64,
+				elapsed
+			);
+		}
+	}
+	Ok(())
+}
@@ -82,6 +82,22 @@ env_default!(
 	30730
 );
 
+// The default M1 DA Light Node metrics and health check listen hostname
+env_default!(
+	default_movement_da_light_node_metrics_listen_hostname,
+	"MOVEMENT_DA_LIGHT_NODE_METRICS_LISTEN_HOSTNAME",
+	String,
+	"0.0.0.0".to_string()
+);
+
+// The default M1 DA Light Node metrics and health check listen port
+env_default!(
+	default_movement_da_light_node_metrics_listen_port,
+	"MOVEMENT_DA_LIGHT_NODE_METRICS_LISTEN_PORT",
+	u16,
+	30735
+);
+
 // The default M1 DA Light Node connection protocol
 env_default!(
 	default_movement_da_light_node_connection_protocol,
@@ -157,3 +173,39 @@ env_default!(default_da_light_node_is_initial, "MOVEMENT_DA_LIGHT_NODE_IS_INITIA
 
 // Whether to use http1 for Movement Light Node Connections
 env_default!(default_movement_da_light_node_http1, "MOVEMENT_DA_LIGHT_NODE_HTTP1", bool, true);
+
+// The chain id the blobs are signed for, the same as the Maptos chain id
+env_default!(default_movement_da_light_node_chain_id, "MAPTOS_CHAIN_ID", u64, 27);
+
+// Whether the blobs must carry the chain id, rejecting the V1 blobs which have none.
+// Off by default so that the V1 blobs written before the chain id was signed still sync.
+env_default!(
+	default_movement_da_light_node_enforce_chain_id,
+	"MOVEMENT_DA_LIGHT_NODE_ENFORCE_CHAIN_ID",
+	bool,
+	false
+);
+
+// The maximum size of the serialized transactions of a batch written to the light node
+env_default!(
+	default_movement_da_light_node_max_batch_bytes,
+	"MOVEMENT_DA_LIGHT_NODE_MAX_BATCH_BYTES",
+	usize,
+	1024 * 1024
+);
+
+// The maximum number of transactions of a batch written to the light node
+env_default!(
+	default_movement_da_light_node_max_batch_count,
+	"MOVEMENT_DA_LIGHT_NODE_MAX_BATCH_COUNT",
+	usize,
+	2048
+);
+
+// The number of recently written transactions remembered to skip their duplicates
+env_default!(
+	default_movement_da_light_node_dedup_capacity,
+	"MOVEMENT_DA_LIGHT_NODE_DEDUP_CAPACITY",
+	usize,
+	16384
+);
@@ -1,19 +1,29 @@
 use crate::config::common::{
 	default_celestia_rpc_connection_hostname, default_celestia_rpc_connection_port,
 	default_celestia_rpc_connection_protocol, default_celestia_websocket_connection_hostname,
-	default_celestia_websocket_connection_port, default_movement_da_light_node_connection_hostname,
-	default_movement_da_light_node_connection_port, default_movement_da_light_node_http1,
+	default_celestia_websocket_connection_port, default_movement_da_light_node_chain_id,
+	default_movement_da_light_node_connection_hostname,
+	default_movement_da_light_node_connection_port, default_movement_da_light_node_dedup_capacity,
+	default_movement_da_light_node_enforce_chain_id, default_movement_da_light_node_http1,
 	default_movement_da_light_node_listen_hostname, default_movement_da_light_node_listen_port,
+	default_movement_da_light_node_max_batch_bytes, default_movement_da_light_node_max_batch_count,
+	default_movement_da_light_node_metrics_listen_hostname,
+	default_movement_da_light_node_metrics_listen_port,
 };
 use ecdsa::SigningKey;
+use godfig::env_default;
 use k256::Secp256k1;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct DaSigners {
 	pub private_key_hex: String,
 	pub public_keys_hex: HashSet<String>,
+	/// The number of distinct known signers a blob needs to be accepted
+	#[serde(default = "default_da_signers_threshold")]
+	pub threshold: usize,
 }
 
 /// The default da signing private key
@@ -44,6 +54,9 @@ pub fn default_da_signers_sec1_keys() -> HashSet<String> {
 	}
 }
 
+// The number of known signers a blob needs to be accepted
+env_default!(default_da_signers_threshold, "DA_SIGNERS_THRESHOLD", usize, 1);
+
 pub fn default_da_signers() -> DaSigners {
 	let da_signer = default_da_signing_private_key();
 
@@ -59,6 +72,7 @@ pub fn default_da_signers() -> DaSigners {
 	DaSigners {
 		private_key_hex: hex::encode(da_signer.to_bytes().as_slice()),
 		public_keys_hex: trusted_signers,
+		threshold: default_da_signers_threshold(),
 	}
 }
 
@@ -88,6 +102,84 @@ pub mod signers_serialization_test {
 	}
 }
 
+/// The PEM encoded certificates of a mutually authenticated TLS connection to the
+/// movement-da-light-node service
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TlsConfig {
+	/// The CA certificate the light node certificate is verified with
+	pub ca_cert: PathBuf,
+	/// The certificate the client authenticates with
+	pub client_cert: PathBuf,
+	/// The private key of the client certificate
+	pub client_key: PathBuf,
+}
+
+impl TlsConfig {
+	/// Reads the certificate paths from `MOVEMENT_DA_LIGHT_NODE_TLS_CA_CERT`,
+	/// `MOVEMENT_DA_LIGHT_NODE_TLS_CLIENT_CERT` and `MOVEMENT_DA_LIGHT_NODE_TLS_CLIENT_KEY`.
+	/// TLS is disabled when none of them is set, setting only some of them is an error.
+	pub fn from_env() -> Result<Option<Self>, anyhow::Error> {
+		Self::from_vars(|name| std::env::var(name).ok())
+	}
+
+	fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Option<Self>, anyhow::Error> {
+		match (
+			var("MOVEMENT_DA_LIGHT_NODE_TLS_CA_CERT"),
+			var("MOVEMENT_DA_LIGHT_NODE_TLS_CLIENT_CERT"),
+			var("MOVEMENT_DA_LIGHT_NODE_TLS_CLIENT_KEY"),
+		) {
+			(Some(ca_cert), Some(client_cert), Some(client_key)) => Ok(Some(Self {
+				ca_cert: ca_cert.into(),
+				client_cert: client_cert.into(),
+				client_key: client_key.into(),
+			})),
+			(None, None, None) => Ok(None),
+			_ => Err(anyhow::anyhow!(
+				"The CA certificate, client certificate and client key must all be set to use TLS"
+			)),
+		}
+	}
+}
+
+/// Defaults to plaintext when the TLS variables are invalid, the error is reported by
+/// `Config::validate`.
+pub fn default_movement_da_light_node_tls_config() -> Option<TlsConfig> {
+	TlsConfig::from_env().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tls_config_test {
+	use super::*;
+	use std::collections::HashMap;
+
+	fn from_vars(vars: &[(&str, &str)]) -> Result<Option<TlsConfig>, anyhow::Error> {
+		let vars: HashMap<_, _> = vars.iter().copied().collect();
+		TlsConfig::from_vars(|name| vars.get(name).map(|value| value.to_string()))
+	}
+
+	#[test]
+	fn test_tls_config_from_vars() -> Result<(), anyhow::Error> {
+		assert_eq!(from_vars(&[])?, None);
+
+		let tls_config = from_vars(&[
+			("MOVEMENT_DA_LIGHT_NODE_TLS_CA_CERT", "/certs/ca.pem"),
+			("MOVEMENT_DA_LIGHT_NODE_TLS_CLIENT_CERT", "/certs/client.pem"),
+			("MOVEMENT_DA_LIGHT_NODE_TLS_CLIENT_KEY", "/certs/client.key"),
+		])?;
+		assert_eq!(
+			tls_config,
+			Some(TlsConfig {
+				ca_cert: "/certs/ca.pem".into(),
+				client_cert: "/certs/client.pem".into(),
+				client_key: "/certs/client.key".into(),
+			})
+		);
+
+		assert!(from_vars(&[("MOVEMENT_DA_LIGHT_NODE_TLS_CA_CERT", "/certs/ca.pem")]).is_err());
+		Ok(())
+	}
+}
+
 /// The inner configuration for the local Celestia Appd Runner
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Config {
@@ -121,6 +213,14 @@ pub struct Config {
 	#[serde(default = "default_movement_da_light_node_listen_port")]
 	pub movement_da_light_node_listen_port: u16,
 
+	/// The hostname to listen on for the light node metrics and health check endpoints
+	#[serde(default = "default_movement_da_light_node_metrics_listen_hostname")]
+	pub movement_da_light_node_metrics_listen_hostname: String,
+
+	/// The port to listen on for the light node metrics and health check endpoints
+	#[serde(default = "default_movement_da_light_node_metrics_listen_port")]
+	pub movement_da_light_node_metrics_listen_port: u16,
+
 	/// The protocol for movement-celestia-da-light-node connection
 	#[serde(default = "default_celestia_rpc_connection_protocol")]
 	pub movement_da_light_node_connection_protocol: String,
@@ -137,9 +237,35 @@ pub struct Config {
 	#[serde(default = "default_movement_da_light_node_http1")]
 	pub movement_da_light_node_http1: bool,
 
+	/// The certificates to connect to the movement-da-light-node service over TLS, none for plaintext.
+	/// TLS is only supported over HTTP/2.
+	#[serde(default = "default_movement_da_light_node_tls_config")]
+	pub movement_da_light_node_tls_config: Option<TlsConfig>,
+
 	/// The DA signers
 	#[serde(default = "default_da_signers")]
 	pub da_signers: DaSigners,
+
+	/// The chain id the blobs are signed for, blobs of other chains are rejected
+	#[serde(default = "default_movement_da_light_node_chain_id")]
+	pub movement_da_light_node_chain_id: u64,
+
+	/// Whether the blobs must carry the chain id, V1 blobs are rejected when set.
+	/// Historical V1 blobs are accepted by default.
+	#[serde(default = "default_movement_da_light_node_enforce_chain_id")]
+	pub movement_da_light_node_enforce_chain_id: bool,
+
+	/// The maximum size of the serialized transactions of a batch written to the light node
+	#[serde(default = "default_movement_da_light_node_max_batch_bytes")]
+	pub movement_da_light_node_max_batch_bytes: usize,
+
+	/// The maximum number of transactions of a batch written to the light node
+	#[serde(default = "default_movement_da_light_node_max_batch_count")]
+	pub movement_da_light_node_max_batch_count: usize,
+
+	/// The number of recently written transactions remembered to skip their duplicates
+	#[serde(default = "default_movement_da_light_node_dedup_capacity")]
+	pub movement_da_light_node_dedup_capacity: usize,
 }
 
 impl Default for Config {
@@ -155,12 +281,25 @@ impl Default for Config {
 			movement_da_light_node_listen_hostname: default_movement_da_light_node_listen_hostname(
 			),
 			movement_da_light_node_listen_port: default_movement_da_light_node_listen_port(),
+			movement_da_light_node_metrics_listen_hostname:
+				default_movement_da_light_node_metrics_listen_hostname(),
+			movement_da_light_node_metrics_listen_port:
+				default_movement_da_light_node_metrics_listen_port(),
 			movement_da_light_node_connection_hostname:
 				default_movement_da_light_node_connection_hostname(),
 			movement_da_light_node_connection_port: default_movement_da_light_node_connection_port(
 			),
 			movement_da_light_node_http1: default_movement_da_light_node_http1(),
+			movement_da_light_node_tls_config: default_movement_da_light_node_tls_config(),
 			da_signers: default_da_signers(),
+			movement_da_light_node_chain_id: default_movement_da_light_node_chain_id(),
+			movement_da_light_node_enforce_chain_id:
+				default_movement_da_light_node_enforce_chain_id(),
+			movement_da_light_node_max_batch_bytes: default_movement_da_light_node_max_batch_bytes(
+			),
+			movement_da_light_node_max_batch_count: default_movement_da_light_node_max_batch_count(
+			),
+			movement_da_light_node_dedup_capacity: default_movement_da_light_node_dedup_capacity(),
 		}
 	}
 }
@@ -4,6 +4,7 @@ use celestia_rpc::Client;
 use celestia_types::nmt::Namespace;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use thiserror::Error;
 
 pub mod common;
 pub mod local;
@@ -28,7 +29,106 @@ impl Default for Config {
 	}
 }
 
+/// Misconfiguration detected by `Config::validate`.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ConfigError {
+	#[error("Invalid Celestia namespace: {0}")]
+	InvalidNamespace(String),
+	#[error("Invalid url {url}: {reason}")]
+	InvalidUrl { url: String, reason: String },
+	#[error("memseq_build_time (block time in ms) must be greater than 0")]
+	ZeroBlockTime,
+	#[error("memseq_max_block_size must be greater than 0")]
+	ZeroMaxBlockSize,
+	#[error("movement_da_light_node_max_batch_count must be greater than 0")]
+	ZeroMaxBatchCount,
+	#[error("Invalid MOVEMENT_DA_LIGHT_NODE_TLS config: {0}")]
+	InvalidTlsConfig(String),
+}
+
 impl Config {
+	fn local(&self) -> &local::Config {
+		match self {
+			Config::Local(local) => local,
+			Config::Arabica(local) => local,
+			Config::Mocha(local) => local,
+		}
+	}
+
+	/// The config of the network, to override some of its fields.
+	pub fn local_mut(&mut self) -> &mut local::Config {
+		match self {
+			Config::Local(local) => local,
+			Config::Arabica(local) => local,
+			Config::Mocha(local) => local,
+		}
+	}
+
+	/// Checks the config before any client is built, so that a misconfiguration is reported
+	/// at startup instead of failing deep in the Celestia client. Returns all the errors found.
+	pub fn validate(&self) -> Result<(), Vec<ConfigError>> {
+		let local = self.local();
+		let mut errors = Vec::new();
+
+		// A v0 namespace is the version byte, 18 zero bytes and the 10 bytes user id.
+		let namespace = local.appd.celestia_namespace.as_bytes();
+		if namespace[0] != 0 {
+			errors.push(ConfigError::InvalidNamespace(format!(
+				"unsupported version {}, only v0 namespaces are supported",
+				namespace[0]
+			)));
+		} else if namespace[1..namespace.len() - 10].iter().any(|b| *b != 0) {
+			errors.push(ConfigError::InvalidNamespace(
+				"the namespace id must be exactly 10 bytes".to_string(),
+			));
+		} else if namespace[namespace.len() - 10..].iter().all(|b| *b == 0) {
+			errors.push(ConfigError::InvalidNamespace("the namespace id is all zeros".to_string()));
+		}
+
+		let urls = [
+			format!(
+				"{}://{}:{}",
+				local.appd.celestia_websocket_connection_protocol,
+				local.appd.celestia_websocket_connection_hostname,
+				local.appd.celestia_websocket_connection_port
+			),
+			format!(
+				"{}://{}:{}",
+				local.da_light_node.movement_da_light_node_connection_protocol,
+				local.da_light_node.movement_da_light_node_connection_hostname,
+				local.da_light_node.movement_da_light_node_connection_port
+			),
+		];
+		for url in urls {
+			if let Err(err) = url::Url::parse(&url) {
+				errors.push(ConfigError::InvalidUrl { url, reason: err.to_string() });
+			}
+		}
+
+		if local.memseq.memseq_build_time == 0 {
+			errors.push(ConfigError::ZeroBlockTime);
+		}
+		if local.memseq.memseq_max_block_size == 0 {
+			errors.push(ConfigError::ZeroMaxBlockSize);
+		}
+		// a batch could never take a transaction, the batches would be built empty forever
+		if local.da_light_node.movement_da_light_node_max_batch_count == 0 {
+			errors.push(ConfigError::ZeroMaxBatchCount);
+		}
+		// Without a TLS config the variables may have been rejected by its default.
+		if local.da_light_node.movement_da_light_node_tls_config.is_none() {
+			if let Err(err) = local::da_light_node::TlsConfig::from_env() {
+				errors.push(ConfigError::InvalidTlsConfig(err.to_string()));
+			}
+		}
+
+		if errors.is_empty() {
+			Ok(())
+		} else {
+			Err(errors)
+		}
+	}
+
 	/// Connects to a Celestia node using the config
 	pub async fn connect_celestia(&self) -> Result<Client, anyhow::Error> {
 		match self {
@@ -161,6 +261,39 @@ impl Config {
 		format!("{}:{}", hostname, port)
 	}
 
+	/// Gets M1 DA Light Node metrics listen hostname
+	pub fn movement_da_light_node_metrics_listen_hostname(&self) -> String {
+		match self {
+			Config::Local(local) => {
+				local.da_light_node.movement_da_light_node_metrics_listen_hostname.clone()
+			}
+			Config::Arabica(local) => {
+				local.da_light_node.movement_da_light_node_metrics_listen_hostname.clone()
+			}
+			Config::Mocha(local) => {
+				local.da_light_node.movement_da_light_node_metrics_listen_hostname.clone()
+			}
+		}
+	}
+
+	/// Gets M1 DA Light Node metrics listen port
+	pub fn movement_da_light_node_metrics_listen_port(&self) -> u16 {
+		match self {
+			Config::Local(local) => local.da_light_node.movement_da_light_node_metrics_listen_port,
+			Config::Arabica(local) => {
+				local.da_light_node.movement_da_light_node_metrics_listen_port
+			}
+			Config::Mocha(local) => local.da_light_node.movement_da_light_node_metrics_listen_port,
+		}
+	}
+
+	/// Gets M1 DA Light Node metrics and health check service
+	pub fn movement_da_light_node_metrics_service(&self) -> String {
+		let hostname = self.movement_da_light_node_metrics_listen_hostname();
+		let port = self.movement_da_light_node_metrics_listen_port();
+		format!("{}:{}", hostname, port)
+	}
+
 	/// Gets M1 DA Light Node connection hostname
 	pub fn movement_da_light_node_connection_hostname(&self) -> String {
 		match self {
@@ -194,6 +327,17 @@ impl Config {
 		}
 	}
 
+	/// Gets the certificates of the TLS connection to the movement-da-light-node service, if any
+	pub fn movement_da_light_node_tls_config(&self) -> Option<&local::da_light_node::TlsConfig> {
+		match self {
+			Config::Local(local) => local.da_light_node.movement_da_light_node_tls_config.as_ref(),
+			Config::Arabica(local) => {
+				local.da_light_node.movement_da_light_node_tls_config.as_ref()
+			}
+			Config::Mocha(local) => local.da_light_node.movement_da_light_node_tls_config.as_ref(),
+		}
+	}
+
 	/// Gets the memseq path
 	pub fn try_memseq_path(&self) -> Result<String, anyhow::Error> {
 		match self {
@@ -227,6 +371,60 @@ impl Config {
 		}
 	}
 
+	/// Gets the chain id the blobs are signed for
+	pub fn movement_da_light_node_chain_id(&self) -> u64 {
+		match self {
+			Config::Local(local) => local.da_light_node.movement_da_light_node_chain_id,
+			Config::Arabica(local) => local.da_light_node.movement_da_light_node_chain_id,
+			Config::Mocha(local) => local.da_light_node.movement_da_light_node_chain_id,
+		}
+	}
+
+	/// Gets whether the blobs must carry the chain id
+	pub fn movement_da_light_node_enforce_chain_id(&self) -> bool {
+		match self {
+			Config::Local(local) => local.da_light_node.movement_da_light_node_enforce_chain_id,
+			Config::Arabica(local) => local.da_light_node.movement_da_light_node_enforce_chain_id,
+			Config::Mocha(local) => local.da_light_node.movement_da_light_node_enforce_chain_id,
+		}
+	}
+
+	/// Gets the maximum size of the serialized transactions of a batch
+	pub fn movement_da_light_node_max_batch_bytes(&self) -> usize {
+		match self {
+			Config::Local(local) => local.da_light_node.movement_da_light_node_max_batch_bytes,
+			Config::Arabica(local) => local.da_light_node.movement_da_light_node_max_batch_bytes,
+			Config::Mocha(local) => local.da_light_node.movement_da_light_node_max_batch_bytes,
+		}
+	}
+
+	/// Gets the maximum number of transactions of a batch
+	pub fn movement_da_light_node_max_batch_count(&self) -> usize {
+		match self {
+			Config::Local(local) => local.da_light_node.movement_da_light_node_max_batch_count,
+			Config::Arabica(local) => local.da_light_node.movement_da_light_node_max_batch_count,
+			Config::Mocha(local) => local.da_light_node.movement_da_light_node_max_batch_count,
+		}
+	}
+
+	/// Gets the number of recently written transactions remembered to skip their duplicates
+	pub fn movement_da_light_node_dedup_capacity(&self) -> usize {
+		match self {
+			Config::Local(local) => local.da_light_node.movement_da_light_node_dedup_capacity,
+			Config::Arabica(local) => local.da_light_node.movement_da_light_node_dedup_capacity,
+			Config::Mocha(local) => local.da_light_node.movement_da_light_node_dedup_capacity,
+		}
+	}
+
+	/// Gets the number of known signers a blob needs to be accepted
+	pub fn da_signers_threshold(&self) -> usize {
+		match self {
+			Config::Local(local) => local.da_light_node.da_signers.threshold,
+			Config::Arabica(local) => local.da_light_node.da_signers.threshold,
+			Config::Mocha(local) => local.da_light_node.da_signers.threshold,
+		}
+	}
+
 	pub fn try_block_building_parameters(&self) -> Result<(u32, u64), anyhow::Error> {
 		match self {
 			Config::Local(local) => {
@@ -273,4 +471,37 @@ impl CelestiaDaLightNodeConfig {
 	pub fn celestia_namespace(&self) -> Namespace {
 		self.celestia_da_light_node_config.celestia_namespace()
 	}
+
+	/// Validates the config, see `Config::validate`.
+	pub fn validate(&self) -> Result<(), Vec<ConfigError>> {
+		self.celestia_da_light_node_config.validate()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_validate_default_config() {
+		assert_eq!(Config::Local(local::Config::default()).validate(), Ok(()));
+	}
+
+	#[test]
+	fn test_validate_reports_all_errors() {
+		let mut local = local::Config::default();
+		local.appd.celestia_namespace = Namespace::new_v0(&[0; 10]).unwrap();
+		local.da_light_node.movement_da_light_node_connection_hostname = "bad host".to_string();
+		local.memseq.memseq_build_time = 0;
+		local.memseq.memseq_max_block_size = 0;
+		local.da_light_node.movement_da_light_node_max_batch_count = 0;
+
+		let errors = Config::Local(local).validate().unwrap_err();
+		assert_eq!(errors.len(), 5);
+		assert!(matches!(errors[0], ConfigError::InvalidNamespace(_)));
+		assert!(matches!(errors[1], ConfigError::InvalidUrl { .. }));
+		assert_eq!(errors[2], ConfigError::ZeroBlockTime);
+		assert_eq!(errors[3], ConfigError::ZeroMaxBlockSize);
+		assert_eq!(errors[4], ConfigError::ZeroMaxBatchCount);
+	}
 }
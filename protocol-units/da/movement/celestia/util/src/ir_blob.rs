@@ -11,7 +11,9 @@ use ecdsa::{
 	signature::{digest::Digest, DigestVerifier},
 	SignatureSize, SigningKey, VerifyingKey,
 };
+use ed25519_dalek::Signer;
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InnerSignedBlobV1Data {
@@ -87,6 +89,35 @@ impl InnerSignedBlobV1Data {
 			id,
 		})
 	}
+
+	/// Signs the data with an Ed25519 key, the id and the prehash are computed with SHA-256.
+	pub fn try_to_sign_ed25519(
+		self,
+		signing_key: &ed25519_dalek::SigningKey,
+	) -> Result<InnerSignedBlobV1, anyhow::Error> {
+		let mut id_hasher = Sha256::new();
+		id_hasher.update(self.blob.as_slice());
+		id_hasher.update(&self.timestamp.to_be_bytes());
+		let id = Id(id_hasher.finalize().to_vec());
+
+		let signature = signing_key.try_sign(&self.ed25519_prehash(&id))?;
+
+		Ok(InnerSignedBlobV1 {
+			data: self,
+			signature: signature.to_bytes().to_vec(),
+			signer: signing_key.verifying_key().to_bytes().to_vec(),
+			id,
+		})
+	}
+
+	/// The SHA-256 hash of the data and its `id`, signed by the Ed25519 keys.
+	fn ed25519_prehash(&self, id: &Id) -> Vec<u8> {
+		let mut hasher = Sha256::new();
+		hasher.update(self.blob.as_slice());
+		hasher.update(&self.timestamp.to_be_bytes());
+		hasher.update(id.as_slice());
+		hasher.finalize().to_vec()
+	}
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -119,11 +150,258 @@ impl InnerSignedBlobV1 {
 			Err(_) => Err(anyhow::anyhow!("Failed to verify signature")),
 		}
 	}
+
+	/// Verifies a signature made by [`InnerSignedBlobV1Data::try_to_sign_ed25519`],
+	/// the signer is a 32 bytes Ed25519 public key.
+	pub fn try_verify_ed25519(&self) -> Result<(), anyhow::Error> {
+		let signer: [u8; 32] = self
+			.signer
+			.as_slice()
+			.try_into()
+			.map_err(|_| anyhow::anyhow!("Ed25519 signer must be 32 bytes"))?;
+		let verifying_key = ed25519_dalek::VerifyingKey::from_bytes(&signer)?;
+		let signature = ed25519_dalek::Signature::from_slice(self.signature.as_slice())?;
+
+		match verifying_key.verify_strict(&self.data.ed25519_prehash(&self.id), &signature) {
+			Ok(_) => Ok(()),
+			Err(_) => Err(anyhow::anyhow!("Failed to verify signature")),
+		}
+	}
+}
+
+/// What the data of a blob is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlobTypeTag {
+	TransactionBatch,
+	GovernanceProposal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InnerSignedBlobV2Data {
+	pub blob: Vec<u8>,
+	pub timestamp: u64,
+	/// The id of the chain the blob was created for.
+	pub origin_chain_id: u64,
+	pub blob_type: BlobTypeTag,
+}
+
+impl InnerSignedBlobV2Data {
+	pub fn new(
+		blob: Vec<u8>,
+		timestamp: u64,
+		origin_chain_id: u64,
+		blob_type: BlobTypeTag,
+	) -> Self {
+		Self { blob, timestamp, origin_chain_id, blob_type }
+	}
+
+	/// Feeds the signed fields to `hasher`.
+	fn update_digest(&self, hasher: &mut impl Digest) {
+		hasher.update(self.blob.as_slice());
+		hasher.update(&self.timestamp.to_be_bytes());
+		hasher.update(&self.origin_chain_id.to_be_bytes());
+		hasher.update(&[self.blob_type as u8]);
+	}
+
+	/// Computes the id of InnerSignedBlobV2Data
+	pub fn compute_id<C>(&self) -> Id
+	where
+		C: PrimeCurve + CurveArithmetic + DigestPrimitive + PointCompression,
+		Scalar<C>: Invert<Output = CtOption<Scalar<C>>> + SignPrimitive<C>,
+		SignatureSize<C>: ArrayLength<u8>,
+		AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C> + VerifyPrimitive<C>,
+		FieldBytesSize<C>: ModulusSize,
+	{
+		let mut id_hasher = C::Digest::new();
+		self.update_digest(&mut id_hasher);
+		Id(id_hasher.finalize().to_vec())
+	}
+
+	pub fn try_to_sign<C>(
+		self,
+		signing_key: &SigningKey<C>,
+	) -> Result<InnerSignedBlobV2, anyhow::Error>
+	where
+		C: PrimeCurve + CurveArithmetic + DigestPrimitive + PointCompression,
+		Scalar<C>: Invert<Output = CtOption<Scalar<C>>> + SignPrimitive<C>,
+		SignatureSize<C>: ArrayLength<u8>,
+		AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C> + VerifyPrimitive<C>,
+		FieldBytesSize<C>: ModulusSize,
+	{
+		let id = self.compute_id::<C>();
+		let BlobSignature { signature, signer } = self.try_sign_id(&id, signing_key)?;
+		Ok(InnerSignedBlobV2 { data: self, signature, signer, id })
+	}
+
+	/// Signs the data and its `id` with `signing_key`.
+	fn try_sign_id<C>(
+		&self,
+		id: &Id,
+		signing_key: &SigningKey<C>,
+	) -> Result<BlobSignature, anyhow::Error>
+	where
+		C: PrimeCurve + CurveArithmetic + DigestPrimitive + PointCompression,
+		Scalar<C>: Invert<Output = CtOption<Scalar<C>>> + SignPrimitive<C>,
+		SignatureSize<C>: ArrayLength<u8>,
+		AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C> + VerifyPrimitive<C>,
+		FieldBytesSize<C>: ModulusSize,
+	{
+		let mut hasher = C::Digest::new();
+		self.update_digest(&mut hasher);
+		hasher.update(id.as_slice());
+		let prehash = hasher.finalize();
+
+		let (signature, _recovery_id) = signing_key.sign_prehash_recoverable(prehash.as_slice())?;
+
+		Ok(BlobSignature {
+			signature: signature.to_vec(),
+			signer: signing_key.verifying_key().to_sec1_bytes().to_vec(),
+		})
+	}
+
+	/// Verifies that `signature` of the data and its `id` was made by its signer.
+	fn try_verify_id<C>(&self, id: &Id, signature: &BlobSignature) -> Result<(), anyhow::Error>
+	where
+		C: PrimeCurve + CurveArithmetic + DigestPrimitive + PointCompression,
+		Scalar<C>: Invert<Output = CtOption<Scalar<C>>> + SignPrimitive<C>,
+		SignatureSize<C>: ArrayLength<u8>,
+		AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C> + VerifyPrimitive<C>,
+		FieldBytesSize<C>: ModulusSize,
+	{
+		let mut hasher = C::Digest::new();
+		self.update_digest(&mut hasher);
+		hasher.update(id.as_slice());
+
+		let verifying_key = VerifyingKey::<C>::from_sec1_bytes(signature.signer.as_slice())?;
+		let signature = ecdsa::Signature::from_bytes(signature.signature.as_slice().into())?;
+
+		match verifying_key.verify_digest(hasher, &signature) {
+			Ok(_) => Ok(()),
+			Err(_) => Err(anyhow::anyhow!("Failed to verify signature")),
+		}
+	}
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InnerSignedBlobV2 {
+	pub data: InnerSignedBlobV2Data,
+	pub signature: Vec<u8>,
+	pub signer: Vec<u8>,
+	pub id: Id,
+}
+
+impl InnerSignedBlobV2 {
+	/// Verifies the signature and that the blob was signed for `chain_id`,
+	/// so that a blob of another chain can't be replayed.
+	pub fn try_verify<C>(&self, chain_id: u64) -> Result<(), anyhow::Error>
+	where
+		C: PrimeCurve + CurveArithmetic + DigestPrimitive + PointCompression,
+		Scalar<C>: Invert<Output = CtOption<Scalar<C>>> + SignPrimitive<C>,
+		SignatureSize<C>: ArrayLength<u8>,
+		AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C> + VerifyPrimitive<C>,
+		FieldBytesSize<C>: ModulusSize,
+	{
+		if self.data.origin_chain_id != chain_id {
+			anyhow::bail!("chain_id mismatch");
+		}
+
+		let signature =
+			BlobSignature { signature: self.signature.clone(), signer: self.signer.clone() };
+		self.data.try_verify_id::<C>(&self.id, &signature)
+	}
+}
+
+/// A signature of a blob and the sec1 bytes of its signer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlobSignature {
+	pub signature: Vec<u8>,
+	pub signer: Vec<u8>,
+}
+
+/// A blob signed by several signers, for the k-of-n trust models.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InnerMultiSignedBlobV3 {
+	pub data: InnerSignedBlobV2Data,
+	pub signatures: Vec<BlobSignature>,
+	pub id: Id,
+}
+
+impl InnerMultiSignedBlobV3 {
+	/// Creates a blob of `data` without signatures.
+	pub fn new<C>(data: InnerSignedBlobV2Data) -> Self
+	where
+		C: PrimeCurve + CurveArithmetic + DigestPrimitive + PointCompression,
+		Scalar<C>: Invert<Output = CtOption<Scalar<C>>> + SignPrimitive<C>,
+		SignatureSize<C>: ArrayLength<u8>,
+		AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C> + VerifyPrimitive<C>,
+		FieldBytesSize<C>: ModulusSize,
+	{
+		let id = data.compute_id::<C>();
+		Self { data, signatures: Vec::new(), id }
+	}
+
+	/// Adds the signature of `signing_key` to the blob.
+	pub fn try_add_signature<C>(&mut self, signing_key: &SigningKey<C>) -> Result<(), anyhow::Error>
+	where
+		C: PrimeCurve + CurveArithmetic + DigestPrimitive + PointCompression,
+		Scalar<C>: Invert<Output = CtOption<Scalar<C>>> + SignPrimitive<C>,
+		SignatureSize<C>: ArrayLength<u8>,
+		AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C> + VerifyPrimitive<C>,
+		FieldBytesSize<C>: ModulusSize,
+	{
+		let signature = self.data.try_sign_id(&self.id, signing_key)?;
+		self.signatures.push(signature);
+		Ok(())
+	}
+
+	/// Verifies that the blob was signed for `chain_id` and has at least one valid signature.
+	/// The invalid signatures are ignored, so that a bad entry can't veto the blob:
+	/// whether enough trusted signers signed it is left to the caller, see [`Self::valid_signers`].
+	pub fn try_verify<C>(&self, chain_id: u64) -> Result<(), anyhow::Error>
+	where
+		C: PrimeCurve + CurveArithmetic + DigestPrimitive + PointCompression,
+		Scalar<C>: Invert<Output = CtOption<Scalar<C>>> + SignPrimitive<C>,
+		SignatureSize<C>: ArrayLength<u8>,
+		AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C> + VerifyPrimitive<C>,
+		FieldBytesSize<C>: ModulusSize,
+	{
+		if self.data.origin_chain_id != chain_id {
+			anyhow::bail!("chain_id mismatch");
+		}
+		if self.valid_signers::<C>().is_empty() {
+			anyhow::bail!("blob has no valid signature");
+		}
+		Ok(())
+	}
+
+	/// The distinct signers whose signature of the blob is valid, in the order they signed.
+	pub fn valid_signers<C>(&self) -> Vec<&[u8]>
+	where
+		C: PrimeCurve + CurveArithmetic + DigestPrimitive + PointCompression,
+		Scalar<C>: Invert<Output = CtOption<Scalar<C>>> + SignPrimitive<C>,
+		SignatureSize<C>: ArrayLength<u8>,
+		AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C> + VerifyPrimitive<C>,
+		FieldBytesSize<C>: ModulusSize,
+	{
+		let mut signers: Vec<&[u8]> = Vec::new();
+		for signature in &self.signatures {
+			if signers.contains(&signature.signer.as_slice()) {
+				continue;
+			}
+			if self.data.try_verify_id::<C>(&self.id, signature).is_ok() {
+				signers.push(signature.signer.as_slice());
+			}
+		}
+		signers
+	}
+}
+
+/// The variants are serialized with their index first, new versions must only be appended.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum IntermediateBlobRepresentation {
 	SignedV1(InnerSignedBlobV1),
+	SignedV2(InnerSignedBlobV2),
+	MultiSignedV3(InnerMultiSignedBlobV3),
 }
 
 impl From<InnerSignedBlobV1> for IntermediateBlobRepresentation {
@@ -132,28 +410,136 @@ impl From<InnerSignedBlobV1> for IntermediateBlobRepresentation {
 	}
 }
 
+impl From<InnerSignedBlobV2> for IntermediateBlobRepresentation {
+	fn from(inner: InnerSignedBlobV2) -> Self {
+		IntermediateBlobRepresentation::SignedV2(inner)
+	}
+}
+
+impl From<InnerMultiSignedBlobV3> for IntermediateBlobRepresentation {
+	fn from(inner: InnerMultiSignedBlobV3) -> Self {
+		IntermediateBlobRepresentation::MultiSignedV3(inner)
+	}
+}
+
 impl IntermediateBlobRepresentation {
+	/// The latest version of the blob format.
+	pub const LATEST_VERSION: u8 = 3;
+
+	/// The version of the blob format, starting at 1.
+	pub fn version(&self) -> u8 {
+		match self {
+			IntermediateBlobRepresentation::SignedV1(_) => 1,
+			IntermediateBlobRepresentation::SignedV2(_) => 2,
+			IntermediateBlobRepresentation::MultiSignedV3(_) => 3,
+		}
+	}
+
 	pub fn blob(&self) -> &[u8] {
 		match self {
 			IntermediateBlobRepresentation::SignedV1(inner) => inner.data.blob.as_slice(),
+			IntermediateBlobRepresentation::SignedV2(inner) => inner.data.blob.as_slice(),
+			IntermediateBlobRepresentation::MultiSignedV3(inner) => inner.data.blob.as_slice(),
 		}
 	}
 
+	/// The signature of the blob, the first one for a multi-signed blob.
 	pub fn signature(&self) -> &[u8] {
 		match self {
 			IntermediateBlobRepresentation::SignedV1(inner) => inner.signature.as_slice(),
+			IntermediateBlobRepresentation::SignedV2(inner) => inner.signature.as_slice(),
+			IntermediateBlobRepresentation::MultiSignedV3(inner) => {
+				inner.signatures.first().map(|s| s.signature.as_slice()).unwrap_or_default()
+			}
 		}
 	}
 
 	pub fn timestamp(&self) -> u64 {
 		match self {
 			IntermediateBlobRepresentation::SignedV1(inner) => inner.data.timestamp,
+			IntermediateBlobRepresentation::SignedV2(inner) => inner.data.timestamp,
+			IntermediateBlobRepresentation::MultiSignedV3(inner) => inner.data.timestamp,
+		}
+	}
+
+	/// The chain the blob was created for, unknown for V1 blobs.
+	pub fn origin_chain_id(&self) -> Option<u64> {
+		match self {
+			IntermediateBlobRepresentation::SignedV1(_) => None,
+			IntermediateBlobRepresentation::SignedV2(inner) => Some(inner.data.origin_chain_id),
+			IntermediateBlobRepresentation::MultiSignedV3(inner) => {
+				Some(inner.data.origin_chain_id)
+			}
+		}
+	}
+
+	/// V1 blobs are all transaction batches.
+	pub fn blob_type(&self) -> BlobTypeTag {
+		match self {
+			IntermediateBlobRepresentation::SignedV1(_) => BlobTypeTag::TransactionBatch,
+			IntermediateBlobRepresentation::SignedV2(inner) => inner.data.blob_type,
+			IntermediateBlobRepresentation::MultiSignedV3(inner) => inner.data.blob_type,
 		}
 	}
 
+	/// The signer of the blob, the first one for a multi-signed blob.
 	pub fn signer(&self) -> &[u8] {
 		match self {
 			IntermediateBlobRepresentation::SignedV1(inner) => inner.signer.as_slice(),
+			IntermediateBlobRepresentation::SignedV2(inner) => inner.signer.as_slice(),
+			IntermediateBlobRepresentation::MultiSignedV3(inner) => {
+				inner.signatures.first().map(|s| s.signer.as_slice()).unwrap_or_default()
+			}
+		}
+	}
+
+	/// All the signatures of the blob, in the order of [`Self::signers`].
+	pub fn signatures(&self) -> Vec<&[u8]> {
+		match self {
+			IntermediateBlobRepresentation::SignedV1(inner) => vec![inner.signature.as_slice()],
+			IntermediateBlobRepresentation::SignedV2(inner) => vec![inner.signature.as_slice()],
+			IntermediateBlobRepresentation::MultiSignedV3(inner) => {
+				inner.signatures.iter().map(|s| s.signature.as_slice()).collect()
+			}
+		}
+	}
+
+	/// All the signers of the blob.
+	pub fn signers(&self) -> Vec<&[u8]> {
+		match self {
+			IntermediateBlobRepresentation::SignedV1(inner) => vec![inner.signer.as_slice()],
+			IntermediateBlobRepresentation::SignedV2(inner) => vec![inner.signer.as_slice()],
+			IntermediateBlobRepresentation::MultiSignedV3(inner) => {
+				inner.signatures.iter().map(|s| s.signer.as_slice()).collect()
+			}
+		}
+	}
+
+	/// The distinct signers of the blob whose signature is valid.
+	pub fn valid_signers<C>(&self) -> Vec<&[u8]>
+	where
+		C: PrimeCurve + CurveArithmetic + DigestPrimitive + PointCompression,
+		Scalar<C>: Invert<Output = CtOption<Scalar<C>>> + SignPrimitive<C>,
+		SignatureSize<C>: ArrayLength<u8>,
+		AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C> + VerifyPrimitive<C>,
+		FieldBytesSize<C>: ModulusSize,
+	{
+		match self {
+			IntermediateBlobRepresentation::SignedV1(inner) => match inner.try_verify::<C>() {
+				Ok(()) => vec![inner.signer.as_slice()],
+				Err(_) => vec![],
+			},
+			IntermediateBlobRepresentation::SignedV2(inner) => {
+				let signature = BlobSignature {
+					signature: inner.signature.clone(),
+					signer: inner.signer.clone(),
+				};
+				match inner.data.try_verify_id::<C>(&inner.id, &signature) {
+					Ok(()) => vec![inner.signer.as_slice()],
+					Err(_) => vec![],
+				}
+			}
+			IntermediateBlobRepresentation::MultiSignedV3(inner) => inner.valid_signers::<C>(),
 		}
 	}
 
@@ -164,10 +550,25 @@ impl IntermediateBlobRepresentation {
 	pub fn id(&self) -> &[u8] {
 		match self {
 			IntermediateBlobRepresentation::SignedV1(inner) => inner.id.as_slice(),
+			IntermediateBlobRepresentation::SignedV2(inner) => inner.id.as_slice(),
+			IntermediateBlobRepresentation::MultiSignedV3(inner) => inner.id.as_slice(),
 		}
 	}
 
-	pub fn verify_signature<C>(&self) -> Result<(), anyhow::Error>
+	/// The SHA-256 hash of the whole serialized blob, covering its data, id and signatures.
+	pub fn digest(&self) -> Result<[u8; 32], anyhow::Error> {
+		let serialized = bcs::to_bytes(self)?;
+		Ok(Sha256::digest(serialized).into())
+	}
+
+	/// Verifies the signatures of the blob and, for V2 and V3 blobs, that it was signed for `chain_id`.
+	/// V1 blobs don't carry a chain id: they are rejected if `enforce_chain_id` is set,
+	/// and otherwise only checked for their signature.
+	pub fn verify_signature<C>(
+		&self,
+		chain_id: u64,
+		enforce_chain_id: bool,
+	) -> Result<(), anyhow::Error>
 	where
 		C: PrimeCurve + CurveArithmetic + DigestPrimitive + PointCompression,
 		Scalar<C>: Invert<Output = CtOption<Scalar<C>>> + SignPrimitive<C>,
@@ -176,7 +577,12 @@ impl IntermediateBlobRepresentation {
 		FieldBytesSize<C>: ModulusSize,
 	{
 		match self {
+			IntermediateBlobRepresentation::SignedV1(_) if enforce_chain_id => {
+				anyhow::bail!("V1 blob carries no chain id")
+			}
 			IntermediateBlobRepresentation::SignedV1(inner) => inner.try_verify::<C>(),
+			IntermediateBlobRepresentation::SignedV2(inner) => inner.try_verify::<C>(chain_id),
+			IntermediateBlobRepresentation::MultiSignedV3(inner) => inner.try_verify::<C>(chain_id),
 		}
 	}
 }
@@ -199,6 +605,107 @@ pub mod test {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_ed25519_sign_and_verify() -> Result<(), anyhow::Error> {
+		let blob = InnerSignedBlobV1Data::new(vec![1, 2, 3], 123);
+		let signing_key = ed25519_dalek::SigningKey::from_bytes(&rand::random());
+		let signed_blob = blob.try_to_sign_ed25519(&signing_key)?;
+		assert_eq!(signed_blob.signer, signing_key.verifying_key().to_bytes());
+		assert_eq!(signed_blob.signature.len(), 64);
+		signed_blob.try_verify_ed25519()?;
+
+		let mut changed_blob = signed_blob.clone();
+		changed_blob.data.timestamp = 124;
+		assert!(changed_blob.try_verify_ed25519().is_err());
+
+		let mut changed_blob = signed_blob;
+		changed_blob.signer = ed25519_dalek::SigningKey::from_bytes(&rand::random())
+			.verifying_key()
+			.to_bytes()
+			.to_vec();
+		assert!(changed_blob.try_verify_ed25519().is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_v2_signature_covers_new_fields() -> Result<(), anyhow::Error> {
+		let blob =
+			InnerSignedBlobV2Data::new(vec![1, 2, 3], 123, 27, BlobTypeTag::TransactionBatch);
+		let signing_key = SigningKey::<k256::Secp256k1>::random(&mut rand::thread_rng());
+		let signed_blob = blob.try_to_sign(&signing_key)?;
+		signed_blob.try_verify::<k256::Secp256k1>(27)?;
+
+		let mut changed_blob = signed_blob.clone();
+		changed_blob.data.origin_chain_id = 28;
+		assert!(changed_blob.try_verify::<k256::Secp256k1>(28).is_err());
+
+		let mut changed_blob = signed_blob;
+		changed_blob.data.blob_type = BlobTypeTag::GovernanceProposal;
+		assert!(changed_blob.try_verify::<k256::Secp256k1>(27).is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_blob_of_another_chain_is_rejected() -> Result<(), anyhow::Error> {
+		let blob =
+			InnerSignedBlobV2Data::new(vec![1, 2, 3], 123, 27, BlobTypeTag::TransactionBatch);
+		let signing_key = SigningKey::<k256::Secp256k1>::random(&mut rand::thread_rng());
+		let signed_blob: IntermediateBlobRepresentation = blob.try_to_sign(&signing_key)?.into();
+
+		signed_blob.verify_signature::<k256::Secp256k1>(27, true)?;
+		let err = signed_blob.verify_signature::<k256::Secp256k1>(126, true).unwrap_err();
+		assert_eq!(err.to_string(), "chain_id mismatch");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_v1_blob_is_rejected_when_the_chain_id_is_enforced() -> Result<(), anyhow::Error> {
+		let signing_key = SigningKey::<k256::Secp256k1>::random(&mut rand::thread_rng());
+		let signed_blob: IntermediateBlobRepresentation =
+			InnerSignedBlobV1Data::new(vec![1, 2, 3], 123).try_to_sign(&signing_key)?.into();
+
+		let err = signed_blob.verify_signature::<k256::Secp256k1>(27, true).unwrap_err();
+		assert_eq!(err.to_string(), "V1 blob carries no chain id");
+		signed_blob.verify_signature::<k256::Secp256k1>(27, false)?;
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_v3_ignores_invalid_signatures() -> Result<(), anyhow::Error> {
+		let data =
+			InnerSignedBlobV2Data::new(vec![1, 2, 3], 123, 27, BlobTypeTag::TransactionBatch);
+		let mut blob = InnerMultiSignedBlobV3::new::<k256::Secp256k1>(data);
+		assert!(blob.try_verify::<k256::Secp256k1>(27).is_err());
+
+		for _ in 0..3 {
+			let signing_key = SigningKey::<k256::Secp256k1>::random(&mut rand::thread_rng());
+			blob.try_add_signature(&signing_key)?;
+		}
+		blob.try_verify::<k256::Secp256k1>(27)?;
+		assert_eq!(blob.valid_signers::<k256::Secp256k1>().len(), 3);
+
+		// a forged signature is not counted, but doesn't invalidate the other ones
+		let mut forged_blob = blob.clone();
+		forged_blob.signatures[1].signer = forged_blob.signatures[0].signer.clone();
+		forged_blob.try_verify::<k256::Secp256k1>(27)?;
+		assert_eq!(
+			forged_blob.valid_signers::<k256::Secp256k1>(),
+			vec![blob.signatures[0].signer.as_slice(), blob.signatures[2].signer.as_slice()]
+		);
+
+		// a blob without any valid signature is rejected
+		let mut forged_blob = blob.clone();
+		forged_blob.signatures.truncate(1);
+		forged_blob.signatures[0].signature = vec![0; 64];
+		assert!(forged_blob.try_verify::<k256::Secp256k1>(27).is_err());
+
+		Ok(())
+	}
 }
 
 pub mod celestia {
@@ -206,15 +713,100 @@ pub mod celestia {
 	use super::IntermediateBlobRepresentation;
 	use anyhow::Context;
 	use celestia_types::{consts::appconsts::AppVersion, nmt::Namespace, Blob as CelestiaBlob};
+	use serde::{Deserialize, Serialize};
+
+	/// The first bytes of a zstd frame.
+	/// Blobs written before the compression prefix was added are bare zstd frames.
+	const ZSTD_FRAME_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+	/// The compression of a blob, written as a magic byte in front of the compressed data.
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+	pub enum CompressionAlgorithm {
+		None,
+		Zstd,
+		Lz4,
+	}
+
+	impl CompressionAlgorithm {
+		fn magic_byte(&self) -> u8 {
+			match self {
+				CompressionAlgorithm::None => 0x00,
+				CompressionAlgorithm::Zstd => 0x01,
+				CompressionAlgorithm::Lz4 => 0x02,
+			}
+		}
+
+		fn from_magic_byte(byte: u8) -> Option<Self> {
+			match byte {
+				0x00 => Some(CompressionAlgorithm::None),
+				0x01 => Some(CompressionAlgorithm::Zstd),
+				0x02 => Some(CompressionAlgorithm::Lz4),
+				_ => None,
+			}
+		}
+	}
+
+	/// How the blobs are compressed before they are submitted.
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+	pub struct CompressionConfig {
+		pub algorithm: CompressionAlgorithm,
+		/// The compression level, only used by zstd.
+		pub level: i32,
+	}
+
+	impl Default for CompressionConfig {
+		fn default() -> Self {
+			CompressionConfig { algorithm: CompressionAlgorithm::Zstd, level: 0 }
+		}
+	}
+
+	impl CompressionConfig {
+		/// Compresses `data`, prefixed with the magic byte of the algorithm.
+		pub fn compress(&self, data: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+			let mut compressed = vec![self.algorithm.magic_byte()];
+			match self.algorithm {
+				CompressionAlgorithm::None => compressed.extend_from_slice(data),
+				CompressionAlgorithm::Zstd => {
+					compressed.extend(zstd::encode_all(data, self.level)?);
+				}
+				CompressionAlgorithm::Lz4 => {
+					compressed.extend(lz4_flex::compress_prepend_size(data));
+				}
+			}
+			Ok(compressed)
+		}
+	}
+
+	/// Decompresses `data` with the algorithm of its magic byte prefix.
+	pub fn decompress(data: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+		if data.starts_with(&ZSTD_FRAME_MAGIC) {
+			return Ok(zstd::decode_all(data)?);
+		}
+
+		let (&magic_byte, payload) = data.split_first().context("empty blob")?;
+		match CompressionAlgorithm::from_magic_byte(magic_byte) {
+			Some(CompressionAlgorithm::None) => Ok(payload.to_vec()),
+			Some(CompressionAlgorithm::Zstd) => Ok(zstd::decode_all(payload)?),
+			Some(CompressionAlgorithm::Lz4) => Ok(lz4_flex::decompress_size_prepended(payload)?),
+			None => anyhow::bail!("unknown compression magic byte: {:#04x}", magic_byte),
+		}
+	}
 
 	impl TryFrom<CelestiaBlob> for IntermediateBlobRepresentation {
 		type Error = anyhow::Error;
 
-		// todo: it would be nice to have this be self describing over the compression and serialization format
+		// todo: it would be nice to have this be self describing over the serialization format
 		fn try_from(blob: CelestiaBlob) -> Result<Self, Self::Error> {
-			// decompress blob.data with zstd
+			// decompress blob.data with the algorithm of its prefix
 			let decompressed =
-				zstd::decode_all(blob.data.as_slice()).context("failed to decompress blob")?;
+				decompress(blob.data.as_slice()).context("failed to decompress blob")?;
+
+			// the first byte is the bcs variant index, i.e. the version of the blob format
+			let variant = *decompressed.first().context("empty blob")?;
+			match variant.checked_add(1) {
+				Some(version) if version <= IntermediateBlobRepresentation::LATEST_VERSION => {}
+				_ => anyhow::bail!("unsupported blob variant: {}", variant),
+			}
 
 			// deserialize the decompressed data with bcs
 			let blob =
@@ -227,21 +819,56 @@ pub mod celestia {
 	pub struct CelestiaIntermediateBlobRepresentation(
 		pub IntermediateBlobRepresentation,
 		pub Namespace,
+		pub CompressionConfig,
 	);
 
+	/// Fails if a blob of `namespace` is not in the `expected_namespace` of the operator.
+	pub fn check_namespace(
+		namespace: &Namespace,
+		expected_namespace: &Namespace,
+	) -> Result<(), anyhow::Error> {
+		if namespace != expected_namespace {
+			anyhow::bail!(
+				"blob namespace {:?} is not the expected namespace {:?}",
+				namespace,
+				expected_namespace
+			);
+		}
+		Ok(())
+	}
+
+	impl CelestiaIntermediateBlobRepresentation {
+		/// Wraps `ir_blob` for `namespace`, failing if it is not the `expected_namespace`.
+		pub fn new(
+			ir_blob: IntermediateBlobRepresentation,
+			namespace: Namespace,
+			compression: CompressionConfig,
+			expected_namespace: Namespace,
+		) -> Result<Self, anyhow::Error> {
+			check_namespace(&namespace, &expected_namespace)?;
+			Ok(Self(ir_blob, namespace, compression))
+		}
+
+		/// The namespace the blob is submitted to.
+		pub fn namespace(&self) -> &Namespace {
+			&self.1
+		}
+	}
+
 	/// Tries to form a CelestiaBlob from a CelestiaIntermediateBlobRepresentation
 	impl TryFrom<CelestiaIntermediateBlobRepresentation> for CelestiaBlob {
 		type Error = anyhow::Error;
 
 		fn try_from(ir_blob: CelestiaIntermediateBlobRepresentation) -> Result<Self, Self::Error> {
-			// Extract the inner blob and namespace
-			let CelestiaIntermediateBlobRepresentation(ir_blob, namespace) = ir_blob;
+			// Extract the inner blob, namespace and compression
+			let CelestiaIntermediateBlobRepresentation(ir_blob, namespace, compression) = ir_blob;
 
 			// Serialize the inner blob with bcs
 			let serialized_blob = bcs::to_bytes(&ir_blob).context("failed to serialize blob")?;
 
-			// Compress the serialized data with zstd
-			let compressed_blob = zstd::encode_all(serialized_blob.as_slice(), 0)
+			// Compress the serialized data
+			let compressed_blob = compression
+				.compress(serialized_blob.as_slice())
 				.context("failed to compress blob")?;
 
 			// Construct the final CelestiaBlob by assigning the compressed data
@@ -250,4 +877,151 @@ pub mod celestia {
 				.map_err(|e| anyhow::anyhow!(e))?)
 		}
 	}
+
+	#[cfg(test)]
+	mod tests {
+		use super::*;
+		use crate::ir_blob::{
+			BlobTypeTag, InnerMultiSignedBlobV3, InnerSignedBlobV1Data, InnerSignedBlobV2Data,
+		};
+		use ecdsa::SigningKey;
+
+		fn roundtrip(
+			ir_blob: IntermediateBlobRepresentation,
+		) -> Result<IntermediateBlobRepresentation, anyhow::Error> {
+			roundtrip_with(ir_blob, CompressionConfig::default())
+		}
+
+		fn roundtrip_with(
+			ir_blob: IntermediateBlobRepresentation,
+			compression: CompressionConfig,
+		) -> Result<IntermediateBlobRepresentation, anyhow::Error> {
+			let namespace = Namespace::new_v0(b"movement")?;
+			let celestia_blob: CelestiaBlob =
+				CelestiaIntermediateBlobRepresentation(ir_blob, namespace, compression)
+					.try_into()?;
+			celestia_blob.try_into()
+		}
+
+		#[test]
+		fn test_v1_blob_roundtrip() -> Result<(), anyhow::Error> {
+			let signing_key = SigningKey::<k256::Secp256k1>::random(&mut rand::thread_rng());
+			let signed_blob =
+				InnerSignedBlobV1Data::new(vec![1, 2, 3], 123).try_to_sign(&signing_key)?;
+
+			let ir_blob = roundtrip(signed_blob.into())?;
+			assert_eq!(ir_blob.version(), 1);
+			assert_eq!(ir_blob.blob(), &[1, 2, 3]);
+			assert_eq!(ir_blob.timestamp(), 123);
+			assert_eq!(ir_blob.origin_chain_id(), None);
+			assert_eq!(ir_blob.blob_type(), BlobTypeTag::TransactionBatch);
+			ir_blob.verify_signature::<k256::Secp256k1>(27, false)?;
+			Ok(())
+		}
+
+		#[test]
+		fn test_v2_blob_roundtrip() -> Result<(), anyhow::Error> {
+			let signing_key = SigningKey::<k256::Secp256k1>::random(&mut rand::thread_rng());
+			let signed_blob =
+				InnerSignedBlobV2Data::new(vec![4, 5], 456, 27, BlobTypeTag::GovernanceProposal)
+					.try_to_sign(&signing_key)?;
+
+			let ir_blob = roundtrip(signed_blob.into())?;
+			assert_eq!(ir_blob.version(), 2);
+			assert_eq!(ir_blob.blob(), &[4, 5]);
+			assert_eq!(ir_blob.timestamp(), 456);
+			assert_eq!(ir_blob.origin_chain_id(), Some(27));
+			assert_eq!(ir_blob.blob_type(), BlobTypeTag::GovernanceProposal);
+			ir_blob.verify_signature::<k256::Secp256k1>(27, true)?;
+			Ok(())
+		}
+
+		#[test]
+		fn test_v3_blob_roundtrip() -> Result<(), anyhow::Error> {
+			let data = InnerSignedBlobV2Data::new(vec![6], 789, 27, BlobTypeTag::TransactionBatch);
+			let mut signed_blob = InnerMultiSignedBlobV3::new::<k256::Secp256k1>(data);
+			for _ in 0..2 {
+				let signing_key = SigningKey::<k256::Secp256k1>::random(&mut rand::thread_rng());
+				signed_blob.try_add_signature(&signing_key)?;
+			}
+
+			let ir_blob = roundtrip(signed_blob.into())?;
+			assert_eq!(ir_blob.version(), 3);
+			assert_eq!(ir_blob.blob(), &[6]);
+			assert_eq!(ir_blob.signers().len(), 2);
+			ir_blob.verify_signature::<k256::Secp256k1>(27, true)?;
+			Ok(())
+		}
+
+		#[test]
+		fn test_new_checks_the_namespace() -> Result<(), anyhow::Error> {
+			let signing_key = SigningKey::<k256::Secp256k1>::random(&mut rand::thread_rng());
+			let ir_blob: IntermediateBlobRepresentation =
+				InnerSignedBlobV1Data::new(vec![1, 2, 3], 123).try_to_sign(&signing_key)?.into();
+			let namespace = Namespace::new_v0(b"movement")?;
+			let other_namespace = Namespace::new_v0(b"other")?;
+
+			let blob = CelestiaIntermediateBlobRepresentation::new(
+				ir_blob.clone(),
+				namespace,
+				CompressionConfig::default(),
+				namespace,
+			)?;
+			assert_eq!(blob.namespace(), &namespace);
+
+			assert!(CelestiaIntermediateBlobRepresentation::new(
+				ir_blob,
+				other_namespace,
+				CompressionConfig::default(),
+				namespace,
+			)
+			.is_err());
+			Ok(())
+		}
+
+		#[test]
+		fn test_compression_roundtrip() -> Result<(), anyhow::Error> {
+			let signing_key = SigningKey::<k256::Secp256k1>::random(&mut rand::thread_rng());
+			let signed_blob =
+				InnerSignedBlobV1Data::new(vec![7; 1024], 123).try_to_sign(&signing_key)?;
+
+			for algorithm in
+				[CompressionAlgorithm::None, CompressionAlgorithm::Zstd, CompressionAlgorithm::Lz4]
+			{
+				let compression = CompressionConfig { algorithm, level: 3 };
+				let ir_blob = roundtrip_with(signed_blob.clone().into(), compression)?;
+				assert_eq!(ir_blob.blob(), vec![7; 1024].as_slice(), "{:?}", algorithm);
+				ir_blob.verify_signature::<k256::Secp256k1>(27, false)?;
+			}
+			Ok(())
+		}
+
+		#[test]
+		fn test_decompress_unprefixed_zstd() -> Result<(), anyhow::Error> {
+			// blobs submitted before the magic byte prefix are bare zstd frames
+			let data = zstd::encode_all([1u8, 2, 3].as_slice(), 0)?;
+			assert_eq!(decompress(&data)?, vec![1, 2, 3]);
+			Ok(())
+		}
+
+		#[test]
+		fn test_decompress_unknown_magic_byte() {
+			assert!(decompress(&[0x7f, 1, 2, 3]).is_err());
+			assert!(decompress(&[]).is_err());
+		}
+
+		#[test]
+		fn test_unknown_blob_version_is_rejected() -> Result<(), anyhow::Error> {
+			let namespace = Namespace::new_v0(b"movement")?;
+			for variant in [7u8, u8::MAX] {
+				let data = zstd::encode_all([variant, 0, 0].as_slice(), 0)?;
+				let celestia_blob = CelestiaBlob::new(namespace, data, AppVersion::V2)
+					.map_err(|e| anyhow::anyhow!(e))?;
+
+				let result: Result<IntermediateBlobRepresentation, _> = celestia_blob.try_into();
+				assert!(result.unwrap_err().to_string().contains("unsupported blob variant"));
+			}
+			Ok(())
+		}
+	}
 }
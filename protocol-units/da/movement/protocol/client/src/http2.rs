@@ -1,4 +1,7 @@
+use anyhow::Context;
 use movement_da_light_node_proto::light_node_service_client::LightNodeServiceClient;
+use std::path::Path;
+use tonic::transport::{Certificate, ClientTlsConfig, Endpoint, Identity};
 
 #[derive(Debug, Clone)]
 pub struct Http2 {
@@ -12,6 +15,32 @@ impl Http2 {
 		Ok(Http2 { client })
 	}
 
+	/// Connects to a light node service over mutually authenticated TLS.
+	///
+	/// The light node certificate is verified with the `ca_cert` certificate, and the client
+	/// authenticates with `client_cert` and its private key `client_key`, all PEM encoded.
+	pub async fn connect_with_tls(
+		connection_string: &str,
+		ca_cert: &Path,
+		client_cert: &Path,
+		client_key: &Path,
+	) -> Result<Self, anyhow::Error> {
+		let endpoint = Endpoint::from_shared(connection_string.to_string())?;
+		// tonic only applies the TLS config to https connections
+		if endpoint.uri().scheme_str() != Some("https") {
+			anyhow::bail!("TLS requires an https connection string, got {}", connection_string);
+		}
+
+		let read = |path: &Path| {
+			std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))
+		};
+		let tls_config = ClientTlsConfig::new()
+			.ca_certificate(Certificate::from_pem(read(ca_cert)?))
+			.identity(Identity::from_pem(read(client_cert)?, read(client_key)?));
+		let channel = endpoint.tls_config(tls_config)?.connect().await?;
+		Ok(Http2 { client: LightNodeServiceClient::new(channel) })
+	}
+
 	/// Returns a reference to the client.
 	pub fn client(&self) -> &LightNodeServiceClient<tonic::transport::Channel> {
 		&self.client
@@ -1,5 +1,8 @@
 pub mod http1;
 pub mod http2;
+pub mod retry;
+
+use std::path::Path;
 
 /// An enum wrapping MovementDaLightNodeClients over complex types.
 ///
@@ -23,6 +26,19 @@ impl MovementDaLightNodeClient {
 		Ok(Self::Http2(http2::Http2::connect(connection_string).await?))
 	}
 
+	/// Creates an http2 connection to the light node service over mutually authenticated TLS.
+	pub async fn try_http2_with_tls(
+		connection_string: &str,
+		ca_cert: &Path,
+		client_cert: &Path,
+		client_key: &Path,
+	) -> Result<Self, anyhow::Error> {
+		Ok(Self::Http2(
+			http2::Http2::connect_with_tls(connection_string, ca_cert, client_cert, client_key)
+				.await?,
+		))
+	}
+
 	/// Stream reads from a given height.
 	pub async fn stream_read_from_height(
 		&mut self,
@@ -0,0 +1,298 @@
+use crate::MovementDaLightNodeClient;
+use movement_da_light_node_proto::{
+	BatchWriteRequest, BatchWriteResponse, StreamReadFromHeightRequest,
+	StreamReadFromHeightResponse,
+};
+use rand::Rng;
+use std::future::Future;
+use std::time::Duration;
+use tonic::{Code, Status};
+use tracing::warn;
+
+/// How a [`RetryableClient`] retries the calls failing because the light node is unreachable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryConfig {
+	/// The delay before the first retry, doubled after each retry.
+	pub initial_backoff: Duration,
+	/// The maximum delay between two retries.
+	pub max_backoff: Duration,
+	/// The number of retries after the first call.
+	pub max_retries: u32,
+}
+
+impl Default for RetryConfig {
+	fn default() -> Self {
+		Self {
+			initial_backoff: Duration::from_millis(100),
+			max_backoff: Duration::from_secs(30),
+			max_retries: 10,
+		}
+	}
+}
+
+impl RetryConfig {
+	/// The delay before the retry number `retry`, starting at 0, without the jitter.
+	fn backoff(&self, retry: u32) -> Duration {
+		self.initial_backoff
+			.saturating_mul(2u32.saturating_pow(retry))
+			.min(self.max_backoff)
+	}
+}
+
+/// Whether the read failed because the light node could not be reached in time.
+fn is_transient_read(status: &Status) -> bool {
+	matches!(status.code(), Code::Unavailable | Code::DeadlineExceeded)
+}
+
+/// Whether the write failed because the light node could not be reached.
+/// A write whose deadline was exceeded may still have been applied, retrying it could write
+/// the batch twice.
+fn is_transient_write(status: &Status) -> bool {
+	status.code() == Code::Unavailable
+}
+
+/// Spreads `delay` by ±10% so that the clients don't retry in lockstep.
+fn with_jitter(delay: Duration) -> Duration {
+	delay.mul_f64(rand::thread_rng().gen_range(0.9, 1.1))
+}
+
+/// Calls `call` until it succeeds, fails with a status that `is_transient` rejects
+/// or runs out of retries.
+async fn retry_transient<T, F, Fut>(
+	config: &RetryConfig,
+	is_transient: fn(&Status) -> bool,
+	mut call: F,
+) -> Result<T, Status>
+where
+	F: FnMut() -> Fut,
+	Fut: Future<Output = Result<T, Status>>,
+{
+	let mut retry = 0;
+	loop {
+		match call().await {
+			Err(status) if is_transient(&status) && retry < config.max_retries => {
+				let delay = with_jitter(config.backoff(retry));
+				warn!(
+					"light node call failed with {:?}, retry {} in {:?}",
+					status.code(),
+					retry + 1,
+					delay
+				);
+				tokio::time::sleep(delay).await;
+				retry += 1;
+			}
+			result => return result,
+		}
+	}
+}
+
+/// A light node client retrying the calls that fail while the light node is unavailable,
+/// with an exponential backoff.
+#[derive(Debug, Clone)]
+pub struct RetryableClient {
+	client: MovementDaLightNodeClient,
+	config: RetryConfig,
+}
+
+impl RetryableClient {
+	pub fn new(client: MovementDaLightNodeClient, config: RetryConfig) -> Self {
+		Self { client, config }
+	}
+
+	/// Returns a reference to the inner client.
+	pub fn client(&self) -> &MovementDaLightNodeClient {
+		&self.client
+	}
+
+	/// Stream reads from a given height.
+	/// Only opening the stream is retried, the errors of the stream are returned as is.
+	pub async fn stream_read_from_height(
+		&mut self,
+		request: StreamReadFromHeightRequest,
+	) -> Result<tonic::Streaming<StreamReadFromHeightResponse>, Status> {
+		let client = &self.client;
+		retry_transient(&self.config, is_transient_read, || {
+			let mut client = client.clone();
+			let request = request.clone();
+			async move { client.stream_read_from_height(request).await }
+		})
+		.await
+	}
+
+	/// Writes a batch of transactions to the light node.
+	/// Only the writes that could not reach the light node are retried.
+	pub async fn batch_write(
+		&mut self,
+		request: BatchWriteRequest,
+	) -> Result<BatchWriteResponse, Status> {
+		let client = &self.client;
+		retry_transient(&self.config, is_transient_write, || {
+			let mut client = client.clone();
+			let request = request.clone();
+			async move { client.batch_write(request).await }
+		})
+		.await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use movement_da_light_node_proto::light_node_service_server::{
+		LightNodeService, LightNodeServiceServer,
+	};
+	use movement_da_light_node_proto::*;
+	use std::pin::Pin;
+	use std::sync::{
+		atomic::{AtomicU32, Ordering},
+		Arc,
+	};
+	use tokio_stream::{wrappers::TcpListenerStream, Stream};
+
+	type ResponseStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send + 'static>>;
+
+	/// A light node answering the batch writes once it failed `failures` of them.
+	struct MockLightNode {
+		failures: u32,
+		status: Code,
+		calls: AtomicU32,
+	}
+
+	#[tonic::async_trait]
+	impl LightNodeService for MockLightNode {
+		type StreamReadFromHeightStream = ResponseStream<StreamReadFromHeightResponse>;
+		type StreamReadLatestStream = ResponseStream<StreamReadLatestResponse>;
+		type StreamWriteBlobStream = ResponseStream<StreamWriteBlobResponse>;
+
+		async fn stream_read_from_height(
+			&self,
+			_request: tonic::Request<StreamReadFromHeightRequest>,
+		) -> Result<tonic::Response<Self::StreamReadFromHeightStream>, Status> {
+			Err(Status::unimplemented("mock"))
+		}
+
+		async fn stream_read_latest(
+			&self,
+			_request: tonic::Request<StreamReadLatestRequest>,
+		) -> Result<tonic::Response<Self::StreamReadLatestStream>, Status> {
+			Err(Status::unimplemented("mock"))
+		}
+
+		async fn stream_write_blob(
+			&self,
+			_request: tonic::Request<tonic::Streaming<StreamWriteBlobRequest>>,
+		) -> Result<tonic::Response<Self::StreamWriteBlobStream>, Status> {
+			Err(Status::unimplemented("mock"))
+		}
+
+		async fn read_at_height(
+			&self,
+			_request: tonic::Request<ReadAtHeightRequest>,
+		) -> Result<tonic::Response<ReadAtHeightResponse>, Status> {
+			Err(Status::unimplemented("mock"))
+		}
+
+		async fn batch_read(
+			&self,
+			_request: tonic::Request<BatchReadRequest>,
+		) -> Result<tonic::Response<BatchReadResponse>, Status> {
+			Err(Status::unimplemented("mock"))
+		}
+
+		async fn batch_write(
+			&self,
+			_request: tonic::Request<BatchWriteRequest>,
+		) -> Result<tonic::Response<BatchWriteResponse>, Status> {
+			if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+				return Err(Status::new(self.status, "mock failure"));
+			}
+			Ok(tonic::Response::new(BatchWriteResponse { blobs: vec![] }))
+		}
+
+		async fn get_sync_status(
+			&self,
+			_request: tonic::Request<GetSyncStatusRequest>,
+		) -> Result<tonic::Response<GetSyncStatusResponse>, Status> {
+			Err(Status::unimplemented("mock"))
+		}
+	}
+
+	/// Serves `mock` on a local port and returns a client with 3 retries.
+	async fn retryable_client(mock: Arc<MockLightNode>) -> Result<RetryableClient, anyhow::Error> {
+		let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+		let address = listener.local_addr()?;
+		tokio::spawn(
+			tonic::transport::Server::builder()
+				.add_service(LightNodeServiceServer::from_arc(mock))
+				.serve_with_incoming(TcpListenerStream::new(listener)),
+		);
+
+		let client = MovementDaLightNodeClient::try_http2(&format!("http://{}", address)).await?;
+		let config = RetryConfig {
+			initial_backoff: Duration::from_millis(1),
+			max_backoff: Duration::from_millis(10),
+			max_retries: 3,
+		};
+		Ok(RetryableClient::new(client, config))
+	}
+
+	fn mock_light_node(failures: u32, status: Code) -> Arc<MockLightNode> {
+		Arc::new(MockLightNode { failures, status, calls: AtomicU32::new(0) })
+	}
+
+	#[test]
+	fn test_backoff_is_capped() {
+		let config = RetryConfig::default();
+		assert_eq!(config.backoff(0), Duration::from_millis(100));
+		assert_eq!(config.backoff(3), Duration::from_millis(800));
+		assert_eq!(config.backoff(20), Duration::from_secs(30));
+		assert_eq!(config.backoff(u32::MAX), Duration::from_secs(30));
+
+		let delay = with_jitter(Duration::from_secs(10));
+		assert!((Duration::from_secs(9)..=Duration::from_secs(11)).contains(&delay));
+	}
+
+	#[tokio::test]
+	async fn test_batch_write_succeeds_after_transient_failures() -> Result<(), anyhow::Error> {
+		let mock = mock_light_node(3, Code::Unavailable);
+		let mut client = retryable_client(mock.clone()).await?;
+
+		client.batch_write(BatchWriteRequest { blobs: vec![] }).await?;
+		assert_eq!(mock.calls.load(Ordering::SeqCst), 4);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_batch_write_gives_up_after_max_retries() -> Result<(), anyhow::Error> {
+		let mock = mock_light_node(4, Code::Unavailable);
+		let mut client = retryable_client(mock.clone()).await?;
+
+		let status = client.batch_write(BatchWriteRequest { blobs: vec![] }).await.unwrap_err();
+		assert_eq!(status.code(), Code::Unavailable);
+		assert_eq!(mock.calls.load(Ordering::SeqCst), 4);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_batch_write_past_its_deadline_is_not_retried() -> Result<(), anyhow::Error> {
+		// the light node may have written the batch before the deadline was exceeded
+		let mock = mock_light_node(1, Code::DeadlineExceeded);
+		let mut client = retryable_client(mock.clone()).await?;
+
+		let status = client.batch_write(BatchWriteRequest { blobs: vec![] }).await.unwrap_err();
+		assert_eq!(status.code(), Code::DeadlineExceeded);
+		assert_eq!(mock.calls.load(Ordering::SeqCst), 1);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_other_errors_are_not_retried() -> Result<(), anyhow::Error> {
+		let mock = mock_light_node(1, Code::InvalidArgument);
+		let mut client = retryable_client(mock.clone()).await?;
+
+		let status = client.batch_write(BatchWriteRequest { blobs: vec![] }).await.unwrap_err();
+		assert_eq!(status.code(), Code::InvalidArgument);
+		assert_eq!(mock.calls.load(Ordering::SeqCst), 1);
+		Ok(())
+	}
+}
@@ -0,0 +1,25 @@
+#!/usr/bin/env bash
+# Regenerates the self-signed certificates of the TLS tests.
+set -euo pipefail
+cd "$(dirname "$0")"
+
+key() { openssl genpkey -algorithm EC -pkeyopt ec_paramgen_curve:P-256 -out "$1"; }
+
+key ca.key
+openssl req -x509 -new -key ca.key -days 36500 -subj "/CN=light-node-test-ca" \
+	-addext "basicConstraints=critical,CA:TRUE" -addext "keyUsage=critical,keyCertSign" \
+	-out ca.pem
+
+sign() {
+	local name=$1 usage=$2 san=$3
+	key "$name.key"
+	openssl req -new -key "$name.key" -subj "/CN=light-node-test-$name" -out "$name.csr"
+	openssl x509 -req -in "$name.csr" -CA ca.pem -CAkey ca.key -CAcreateserial -days 36500 \
+		-extfile <(printf "basicConstraints=critical,CA:FALSE\nextendedKeyUsage=%s\n%s" "$usage" "$san") \
+		-out "$name.pem"
+	rm "$name.csr"
+}
+
+sign server serverAuth "subjectAltName=DNS:localhost,IP:127.0.0.1"
+sign client clientAuth ""
+rm ca.key ca.srl
@@ -0,0 +1,144 @@
+//! Connects to a light node served over TLS with client authentication.
+//! The certificates in `tests/certs` are regenerated with `tests/certs/gen.sh`.
+
+use movement_da_light_node_client::MovementDaLightNodeClient;
+use movement_da_light_node_proto::light_node_service_client::LightNodeServiceClient;
+use movement_da_light_node_proto::light_node_service_server::{
+	LightNodeService, LightNodeServiceServer,
+};
+use movement_da_light_node_proto::*;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use tokio_stream::{wrappers::TcpListenerStream, Stream};
+use tonic::transport::{Certificate, ClientTlsConfig, Endpoint, Identity, Server, ServerTlsConfig};
+use tonic::Status;
+
+type ResponseStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send + 'static>>;
+
+/// A light node only answering the batch writes.
+struct MockLightNode;
+
+#[tonic::async_trait]
+impl LightNodeService for MockLightNode {
+	type StreamReadFromHeightStream = ResponseStream<StreamReadFromHeightResponse>;
+	type StreamReadLatestStream = ResponseStream<StreamReadLatestResponse>;
+	type StreamWriteBlobStream = ResponseStream<StreamWriteBlobResponse>;
+
+	async fn stream_read_from_height(
+		&self,
+		_request: tonic::Request<StreamReadFromHeightRequest>,
+	) -> Result<tonic::Response<Self::StreamReadFromHeightStream>, Status> {
+		Err(Status::unimplemented("mock"))
+	}
+
+	async fn stream_read_latest(
+		&self,
+		_request: tonic::Request<StreamReadLatestRequest>,
+	) -> Result<tonic::Response<Self::StreamReadLatestStream>, Status> {
+		Err(Status::unimplemented("mock"))
+	}
+
+	async fn stream_write_blob(
+		&self,
+		_request: tonic::Request<tonic::Streaming<StreamWriteBlobRequest>>,
+	) -> Result<tonic::Response<Self::StreamWriteBlobStream>, Status> {
+		Err(Status::unimplemented("mock"))
+	}
+
+	async fn read_at_height(
+		&self,
+		_request: tonic::Request<ReadAtHeightRequest>,
+	) -> Result<tonic::Response<ReadAtHeightResponse>, Status> {
+		Err(Status::unimplemented("mock"))
+	}
+
+	async fn batch_read(
+		&self,
+		_request: tonic::Request<BatchReadRequest>,
+	) -> Result<tonic::Response<BatchReadResponse>, Status> {
+		Err(Status::unimplemented("mock"))
+	}
+
+	async fn batch_write(
+		&self,
+		_request: tonic::Request<BatchWriteRequest>,
+	) -> Result<tonic::Response<BatchWriteResponse>, Status> {
+		Ok(tonic::Response::new(BatchWriteResponse { blobs: vec![] }))
+	}
+
+	async fn get_sync_status(
+		&self,
+		_request: tonic::Request<GetSyncStatusRequest>,
+	) -> Result<tonic::Response<GetSyncStatusResponse>, Status> {
+		Err(Status::unimplemented("mock"))
+	}
+}
+
+fn cert(name: &str) -> PathBuf {
+	Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/certs").join(name)
+}
+
+/// Serves the mock light node over TLS, requiring a client certificate signed by the test CA,
+/// and returns its connection string.
+async fn serve_tls() -> Result<String, anyhow::Error> {
+	let identity =
+		Identity::from_pem(std::fs::read(cert("server.pem"))?, std::fs::read(cert("server.key"))?);
+	let tls_config = ServerTlsConfig::new()
+		.identity(identity)
+		.client_ca_root(Certificate::from_pem(std::fs::read(cert("ca.pem"))?));
+
+	let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+	let address = listener.local_addr()?;
+	tokio::spawn(
+		Server::builder()
+			.tls_config(tls_config)?
+			.add_service(LightNodeServiceServer::new(MockLightNode))
+			.serve_with_incoming(TcpListenerStream::new(listener)),
+	);
+	Ok(format!("https://{}", address))
+}
+
+#[tokio::test]
+async fn test_batch_write_over_mutual_tls() -> Result<(), anyhow::Error> {
+	let connection_string = serve_tls().await?;
+	let mut client = MovementDaLightNodeClient::try_http2_with_tls(
+		&connection_string,
+		&cert("ca.pem"),
+		&cert("client.pem"),
+		&cert("client.key"),
+	)
+	.await?;
+
+	client.batch_write(BatchWriteRequest { blobs: vec![] }).await?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_client_without_certificate_is_rejected() -> Result<(), anyhow::Error> {
+	let connection_string = serve_tls().await?;
+	let tls_config = ClientTlsConfig::new()
+		.ca_certificate(Certificate::from_pem(std::fs::read(cert("ca.pem"))?));
+	let channel = Endpoint::from_shared(connection_string)?
+		.tls_config(tls_config)?
+		.connect()
+		.await;
+
+	// With TLS 1.3 the server may only reject the missing certificate after the handshake.
+	if let Ok(channel) = channel {
+		let mut client = LightNodeServiceClient::new(channel);
+		assert!(client.batch_write(BatchWriteRequest { blobs: vec![] }).await.is_err());
+	}
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_tls_requires_https() {
+	let result = MovementDaLightNodeClient::try_http2_with_tls(
+		"http://127.0.0.1:1",
+		&cert("ca.pem"),
+		&cert("client.pem"),
+		&cert("client.key"),
+	)
+	.await;
+	assert!(result.is_err());
+}
@@ -5,6 +5,7 @@ use services::Services;
 
 pub use aptos_crypto::hash::HashValue;
 pub use aptos_types::{
+	account_address::AccountAddress,
 	block_executor::partitioner::ExecutableBlock,
 	block_executor::partitioner::ExecutableTransactions,
 	block_metadata::BlockMetadata,
@@ -13,21 +14,27 @@ pub use aptos_types::{
 };
 use maptos_execution_util::config::Config;
 use movement_types::block::BlockCommitment;
+use prometheus::Registry;
 
 use async_trait::async_trait;
 use tokio::sync::mpsc::Sender;
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
 
 use std::future::Future;
+use std::sync::Arc;
 
 #[async_trait]
 pub trait DynOptFinExecutor {
 	type Context: MakeOptFinServices;
 
 	/// Initialize the background task responsible for transaction processing.
+	/// The task stops once `shutdown` is cancelled.
 	fn background(
 		&self,
 		transaction_sender: Sender<(u64, SignedTransaction)>,
 		config: &Config,
+		shutdown: CancellationToken,
 	) -> Result<
 		(Self::Context, impl Future<Output = Result<(), anyhow::Error>> + Send + 'static),
 		anyhow::Error,
@@ -73,8 +80,12 @@ pub trait DynOptFinExecutor {
 		timestamp: u64,
 	) -> Result<BlockMetadata, anyhow::Error>;
 
-	/// Decrements transactions in flight on the transaction channel.
-	fn decrement_transactions_in_flight(&self, count: u64);
+	/// Decrements transactions in flight on the transaction channel,
+	/// given the sender of each transaction leaving it.
+	fn decrement_transactions_in_flight(&self, senders: &[AccountAddress]);
+
+	/// Gets the sender of the limit on transactions in flight, to update it at runtime.
+	fn transactions_in_flight_limit(&self) -> Arc<watch::Sender<Option<u64>>>;
 
 	/// Gets the config
 	fn config(&self) -> &Config;
@@ -82,4 +93,7 @@ pub trait DynOptFinExecutor {
 
 pub trait MakeOptFinServices {
 	fn services(&self) -> Services;
+
+	/// The registry of the Prometheus metrics served by the node.
+	fn metrics_registry(&self) -> &Registry;
 }
@@ -1,18 +1,22 @@
 use crate::{
-	BlockMetadata, DynOptFinExecutor, ExecutableBlock, HashValue, MakeOptFinServices, Services,
-	SignedTransaction,
+	AccountAddress, BlockMetadata, DynOptFinExecutor, ExecutableBlock, HashValue,
+	MakeOptFinServices, Services, SignedTransaction,
 };
 use maptos_execution_util::config::Config;
 use maptos_fin_view::FinalityView;
 use maptos_opt_executor::{Context as OptContext, Executor as OptExecutor};
 use movement_types::block::BlockCommitment;
+use prometheus::Registry;
 
 use anyhow::format_err;
 use async_trait::async_trait;
 use tokio::sync::mpsc::Sender;
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
 use tracing::debug;
 
 use std::future::Future;
+use std::sync::Arc;
 
 pub struct Executor {
 	executor: OptExecutor,
@@ -44,6 +48,10 @@ impl MakeOptFinServices for Context {
 		let fin = self.fin_service.clone();
 		Services::new(opt, fin)
 	}
+
+	fn metrics_registry(&self) -> &Registry {
+		self.opt_context.metrics_registry()
+	}
 }
 
 #[async_trait]
@@ -54,6 +62,7 @@ impl DynOptFinExecutor for Executor {
 		&self,
 		transaction_sender: Sender<(u64, SignedTransaction)>,
 		config: &Config,
+		shutdown: CancellationToken,
 	) -> Result<
 		(Context, impl Future<Output = Result<(), anyhow::Error>> + Send + 'static),
 		anyhow::Error,
@@ -68,7 +77,7 @@ impl DynOptFinExecutor for Executor {
 		let background = async move {
 			// The indexer runtime should live as long as the Tx pipe.
 			let _indexer_runtime = indexer_runtime;
-			background.run().await?;
+			background.run(shutdown).await?;
 			Ok(())
 		};
 		Ok((Context { opt_context, fin_service }, background))
@@ -136,8 +145,12 @@ impl DynOptFinExecutor for Executor {
 		Ok(BlockMetadata::new(block_id, epoch, round, signer.author(), vec![], vec![], timestamp))
 	}
 
-	fn decrement_transactions_in_flight(&self, count: u64) {
-		self.executor.decrement_transactions_in_flight(count)
+	fn decrement_transactions_in_flight(&self, senders: &[AccountAddress]) {
+		self.executor.decrement_transactions_in_flight(senders)
+	}
+
+	fn transactions_in_flight_limit(&self) -> Arc<watch::Sender<Option<u64>>> {
+		self.executor.transactions_in_flight_limit()
 	}
 
 	fn config(&self) -> &Config {
@@ -232,7 +245,8 @@ mod tests {
 		config.chain.maptos_private_key = private_key.clone();
 		let (executor, _tempdir) = setup(config.clone())?;
 		let (tx_sender, mut tx_receiver) = mpsc::channel(16);
-		let (context, background) = executor.background(tx_sender, &config)?;
+		let (context, background) =
+			executor.background(tx_sender, &config, CancellationToken::new())?;
 		let services = context.services();
 		let api = services.get_opt_apis();
 
@@ -263,7 +277,8 @@ mod tests {
 		config.chain.maptos_read_only = true;
 		let (tx_sender, _tx_receiver) = mpsc::channel(16);
 		let (executor, _tempdir) = setup(config.clone())?;
-		let (context, background) = executor.background(tx_sender, &config)?;
+		let (context, background) =
+			executor.background(tx_sender, &config, CancellationToken::new())?;
 		let services = context.services();
 		let api = services.get_opt_apis();
 
@@ -292,7 +307,8 @@ mod tests {
 		config.chain.maptos_private_key = private_key.clone();
 		let (executor, _tempdir) = setup(config.clone())?;
 		let (tx_sender, mut tx_receiver) = mpsc::channel(16);
-		let (context, background) = executor.background(tx_sender, &config)?;
+		let (context, background) =
+			executor.background(tx_sender, &config, CancellationToken::new())?;
 		let services = context.services();
 		let api = services.get_opt_apis();
 
@@ -351,7 +367,8 @@ mod tests {
 		config.chain.maptos_private_key = private_key.clone();
 		let (executor, _tempdir) = setup(config.clone())?;
 		let (tx_sender, mut tx_receiver) = mpsc::channel(16);
-		let (context, background) = executor.background(tx_sender, &config)?;
+		let (context, background) =
+			executor.background(tx_sender, &config, CancellationToken::new())?;
 		let services = context.services();
 		let api = services.get_opt_apis();
 
@@ -432,7 +449,8 @@ mod tests {
 		let config = Config::default();
 		let (tx_sender, _tx_receiver) = mpsc::channel(16);
 		let executor = Executor::try_from_config(config.clone())?;
-		let (context, background) = executor.background(tx_sender, &config)?;
+		let (context, background) =
+			executor.background(tx_sender, &config, CancellationToken::new())?;
 		let config = executor.config();
 		let services = context.services();
 		let apis = services.get_opt_apis();
@@ -505,7 +523,8 @@ mod tests {
 		let config = Config::default();
 		let (tx_sender, _tx_receiver) = mpsc::channel(16);
 		let executor = Executor::try_from_config(config.clone())?;
-		let (context, background) = executor.background(tx_sender, &config)?;
+		let (context, background) =
+			executor.background(tx_sender, &config, CancellationToken::new())?;
 		let config = executor.config();
 		let services = context.services();
 
@@ -0,0 +1,55 @@
+use prometheus::{Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge, Opts, Registry};
+
+/// Creates the gauge of the transactions in flight, shared with the executor releasing them.
+pub fn transactions_in_flight_gauge() -> Result<IntGauge, prometheus::Error> {
+	IntGauge::new("maptos_transactions_in_flight", "Number of transactions in flight")
+}
+
+/// Prometheus metrics of the [`TransactionPipe`](super::TransactionPipe).
+pub struct TransactionPipeMetrics {
+	/// The number of transactions submitted to the pipe.
+	pub transactions_submitted_total: IntCounter,
+	/// The number of submitted transactions that were not accepted, by reason.
+	pub transactions_rejected_total: IntCounterVec,
+	/// The number of transactions in flight.
+	pub transactions_in_flight: IntGauge,
+	/// The time spent processing a submission.
+	pub submission_latency_seconds: Histogram,
+}
+
+impl TransactionPipeMetrics {
+	/// Creates the metrics and registers them with `registry`, along with the gauge of the
+	/// transactions in flight.
+	pub fn try_new(
+		registry: &Registry,
+		transactions_in_flight: IntGauge,
+	) -> Result<Self, prometheus::Error> {
+		let transactions_submitted_total = IntCounter::new(
+			"maptos_transactions_submitted_total",
+			"Number of transactions submitted to the transaction pipe",
+		)?;
+		let transactions_rejected_total = IntCounterVec::new(
+			Opts::new(
+				"maptos_transactions_rejected_total",
+				"Number of transactions rejected by the transaction pipe",
+			),
+			&["reason"],
+		)?;
+		let submission_latency_seconds = Histogram::with_opts(HistogramOpts::new(
+			"maptos_submission_latency_seconds",
+			"Time spent processing a transaction submission",
+		))?;
+
+		registry.register(Box::new(transactions_submitted_total.clone()))?;
+		registry.register(Box::new(transactions_rejected_total.clone()))?;
+		registry.register(Box::new(transactions_in_flight.clone()))?;
+		registry.register(Box::new(submission_latency_seconds.clone()))?;
+
+		Ok(Self {
+			transactions_submitted_total,
+			transactions_rejected_total,
+			transactions_in_flight,
+			submission_latency_seconds,
+		})
+	}
+}
@@ -1,11 +1,13 @@
 mod task;
 
+mod metrics;
 mod read_only;
 mod transaction_pipe;
 
 mod error;
 
 pub use error::Error;
+pub use metrics::{transactions_in_flight_gauge, TransactionPipeMetrics};
 use read_only::NullMempool;
 pub use task::BackgroundTask;
 pub use transaction_pipe::TransactionPipe;
@@ -7,11 +7,16 @@ use aptos_mempool::MempoolClientRequest;
 use aptos_storage_interface::DbReader;
 use aptos_types::transaction::SignedTransaction;
 
+use crate::gc_account_sequence_number::UsedSequenceNumberPool;
+use crate::gc_sender_in_flight::SenderInFlightCounter;
 use aptos_account_whitelist::config::Config as WhitelistConfig;
 use futures::channel::mpsc as futures_mpsc;
 use movement_collections::garbage::counted::GcCounter;
+use prometheus::{IntGauge, Registry};
+use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
+use tokio_util::sync::CancellationToken;
 
 /// The background task for the executor, processing the incoming transactions
 /// in a mempool. If the executor is configured in the read-only mode,
@@ -35,7 +40,14 @@ impl BackgroundTask {
 		mempool_config: &MempoolConfig,
 		whitelist_config: &WhitelistConfig,
 		transactions_in_flight: Arc<RwLock<GcCounter>>,
-		transactions_in_flight_limit: Option<u64>,
+		transactions_in_flight_limit: watch::Receiver<Option<u64>>,
+		senders_in_flight: Arc<RwLock<SenderInFlightCounter>>,
+		per_sender_limit: Option<u64>,
+		too_new_tolerance: u64,
+		used_sequence_number_pool: UsedSequenceNumberPool,
+		used_sequence_number_pool_path: PathBuf,
+		metrics_registry: &Registry,
+		transactions_in_flight_gauge: IntGauge,
 	) -> Result<Self, anyhow::Error> {
 		Ok(Self {
 			inner: BackgroundInner::Full(TransactionPipe::new(
@@ -47,6 +59,13 @@ impl BackgroundTask {
 				whitelist_config,
 				transactions_in_flight,
 				transactions_in_flight_limit,
+				senders_in_flight,
+				per_sender_limit,
+				too_new_tolerance,
+				used_sequence_number_pool,
+				used_sequence_number_pool_path,
+				metrics_registry,
+				transactions_in_flight_gauge,
 			)?),
 		})
 	}
@@ -57,13 +76,16 @@ impl BackgroundTask {
 		Self { inner: BackgroundInner::ReadOnly(NullMempool::new(mempool_client_receiver)) }
 	}
 
-	/// Runs the background task.
-	pub async fn run(self) -> Result<(), Error> {
+	/// Runs the background task until `shutdown` is cancelled.
+	pub async fn run(self, shutdown: CancellationToken) -> Result<(), Error> {
 		use BackgroundInner::*;
 
 		match self.inner {
-			Full(transaction_pipe) => transaction_pipe.run().await,
-			ReadOnly(null_mempool) => null_mempool.run().await,
+			Full(transaction_pipe) => transaction_pipe.run(shutdown).await,
+			ReadOnly(null_mempool) => tokio::select! {
+				result = null_mempool.run() => result,
+				_ = shutdown.cancelled() => Ok(()),
+			},
 		}
 	}
 
@@ -1,10 +1,11 @@
 //! Task processing incoming transactions for the opt API.
 
-use super::Error;
+use super::{Error, TransactionPipeMetrics};
 
 use maptos_execution_util::config::mempool::Config as MempoolConfig;
 
 use aptos_config::config::NodeConfig;
+use aptos_crypto::HashValue;
 use aptos_mempool::core_mempool::CoreMempool;
 use aptos_mempool::SubmissionStatus;
 use aptos_mempool::{core_mempool::TimelineState, MempoolClientRequest};
@@ -17,17 +18,25 @@ use aptos_vm_validator::vm_validator::{self, TransactionValidation, VMValidator}
 use std::collections::HashSet;
 
 use crate::gc_account_sequence_number::UsedSequenceNumberPool;
+use crate::gc_sender_in_flight::SenderInFlightCounter;
 use aptos_account_whitelist::config::Config as WhitelistConfig;
 use futures::channel::mpsc as futures_mpsc;
 use futures::StreamExt;
+use lru::LruCache;
 use movement_collections::garbage::counted::GcCounter;
+use prometheus::{IntGauge, Registry};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, info_span, warn, Instrument};
 
-const GC_INTERVAL: Duration = Duration::from_secs(30);
-const TOO_NEW_TOLERANCE: u64 = 32;
+// Above this ratio of transactions in flight to their limit, GC runs at the minimum interval.
+const HIGH_PRESSURE_RATIO: f64 = 0.8;
+// Below this ratio of transactions in flight to their limit, GC runs at the maximum interval.
+const LOW_PRESSURE_RATIO: f64 = 0.2;
 
 pub struct TransactionPipe {
 	// The receiver for the mempool client.
@@ -40,14 +49,36 @@ pub struct TransactionPipe {
 	core_mempool: CoreMempool,
 	// Shared reference on the counter of transactions in flight.
 	transactions_in_flight: Arc<RwLock<GcCounter>>,
-	// The configured limit on transactions in flight
-	in_flight_limit: Option<u64>,
+	// The limit on transactions in flight, which can be updated while the pipe runs
+	in_flight_limit: watch::Receiver<Option<u64>>,
+	// Shared reference on the counters of transactions in flight per sender.
+	senders_in_flight: Arc<RwLock<SenderInFlightCounter>>,
+	// The configured limit on transactions in flight for a single sender
+	per_sender_limit: Option<u64>,
+	// How far ahead of the committed sequence number a transaction is accepted
+	too_new_tolerance: u64,
+	// How long the buffered requests are still processed on shutdown
+	drain_timeout: Duration,
+	// The recently accepted transactions, by hash, with the time they were accepted
+	accepted_transactions: LruCache<HashValue, Instant>,
+	// How long an accepted transaction is remembered for
+	dedup_ttl: Duration,
+	// The maximum size of a serialized transaction
+	max_transaction_size_bytes: usize,
 	// Timestamp of the last garbage collection
 	last_gc: Instant,
+	// The garbage collection interval under high pressure
+	min_gc_interval: Duration,
+	// The garbage collection interval under low pressure
+	max_gc_interval: Duration,
 	// The pool of used sequence numbers
 	used_sequence_number_pool: UsedSequenceNumberPool,
+	// Where the pool of used sequence numbers is persisted
+	used_sequence_number_pool_path: PathBuf,
 	/// The accounts whitelisted for ingress
 	whitelisted_accounts: Option<HashSet<AccountAddress>>,
+	// Prometheus metrics of the submissions
+	metrics: Arc<TransactionPipeMetrics>,
 }
 
 enum SequenceNumberValidity {
@@ -64,9 +95,22 @@ impl TransactionPipe {
 		mempool_config: &MempoolConfig,
 		whitelist_config: &WhitelistConfig,
 		transactions_in_flight: Arc<RwLock<GcCounter>>,
-		transactions_in_flight_limit: Option<u64>,
+		transactions_in_flight_limit: watch::Receiver<Option<u64>>,
+		senders_in_flight: Arc<RwLock<SenderInFlightCounter>>,
+		per_sender_limit: Option<u64>,
+		too_new_tolerance: u64,
+		used_sequence_number_pool: UsedSequenceNumberPool,
+		used_sequence_number_pool_path: PathBuf,
+		metrics_registry: &Registry,
+		transactions_in_flight_gauge: IntGauge,
 	) -> Result<Self, anyhow::Error> {
 		let whitelisted_accounts = whitelist_config.whitelisted_accounts()?;
+		let metrics = Arc::new(TransactionPipeMetrics::try_new(
+			metrics_registry,
+			transactions_in_flight_gauge,
+		)?);
+		let dedup_cache_size = NonZeroUsize::new(mempool_config.dedup_cache_size)
+			.ok_or_else(|| anyhow::anyhow!("The dedup cache size must not be zero"))?;
 		info!("Whitelisted accounts: {:?}", whitelisted_accounts);
 
 		Ok(TransactionPipe {
@@ -76,12 +120,20 @@ impl TransactionPipe {
 			core_mempool: CoreMempool::new(node_config),
 			transactions_in_flight,
 			in_flight_limit: transactions_in_flight_limit,
+			senders_in_flight,
+			per_sender_limit,
+			too_new_tolerance,
+			drain_timeout: Duration::from_millis(mempool_config.drain_timeout_ms),
+			accepted_transactions: LruCache::new(dedup_cache_size),
+			dedup_ttl: Duration::from_millis(mempool_config.dedup_ttl_ms),
+			max_transaction_size_bytes: mempool_config.max_transaction_size_bytes,
 			last_gc: Instant::now(),
-			used_sequence_number_pool: UsedSequenceNumberPool::new(
-				mempool_config.sequence_number_ttl_ms,
-				mempool_config.gc_slot_duration_ms,
-			),
+			min_gc_interval: Duration::from_millis(mempool_config.min_gc_interval_ms),
+			max_gc_interval: Duration::from_millis(mempool_config.max_gc_interval_ms),
+			used_sequence_number_pool,
+			used_sequence_number_pool_path,
 			whitelisted_accounts,
+			metrics,
 		})
 	}
 
@@ -96,30 +148,97 @@ impl TransactionPipe {
 		}
 	}
 
-	pub async fn run(mut self) -> Result<(), Error> {
+	/// Runs the pipe until `shutdown` is cancelled, then processes the requests
+	/// already buffered in the channel for up to `drain_timeout`.
+	pub async fn run(mut self, shutdown: CancellationToken) -> Result<(), Error> {
 		loop {
-			self.tick().await?;
+			let next = tokio::select! {
+				next = self.mempool_client_receiver.next() => next,
+				_ = shutdown.cancelled() => break,
+			};
+			self.process(next).await?;
 		}
+		self.drain().await?;
+		self.persist_used_sequence_numbers();
+		Ok(())
 	}
 
 	/// Pipes a batch of transactions from the mempool to the transaction channel.
 	/// todo: it may be wise to move the batching logic up a level to the consuming structs.
 	pub(crate) async fn tick(&mut self) -> Result<(), Error> {
 		let next = self.mempool_client_receiver.next().await;
-		if let Some(request) = next {
+		self.process(next).await
+	}
+
+	/// Submits a batch of transactions, validating each one independently.
+	/// The accepted transactions are forwarded back to back, so that the ingress task
+	/// writes them to the DA together. The statuses are returned in the order of the batch.
+	async fn submit_transaction_batch(
+		&mut self,
+		transactions: Vec<SignedTransaction>,
+	) -> Result<Vec<SubmissionStatus>, Error> {
+		let mut statuses = Vec::with_capacity(transactions.len());
+		for transaction in transactions {
+			let span = info_span!(
+				target: "movement_timing",
+				"submit_transaction",
+				tx_hash = %transaction.committed_hash(),
+				sender = %transaction.sender(),
+				sequence_number = transaction.sequence_number(),
+			);
+			statuses.push(self.submit_transaction(transaction).instrument(span).await?);
+		}
+		Ok(statuses)
+	}
+
+	/// Closes the channel and processes the requests left in it, giving up after `drain_timeout`.
+	async fn drain(&mut self) -> Result<(), Error> {
+		self.mempool_client_receiver.close();
+		let drain_timeout = self.drain_timeout;
+		let drain = async {
+			while let Some(request) = self.mempool_client_receiver.next().await {
+				self.process(Some(request)).await?;
+			}
+			Ok::<_, Error>(())
+		};
+		match tokio::time::timeout(drain_timeout, drain).await {
+			Ok(result) => result,
+			Err(_) => {
+				warn!("Transaction pipe not drained after {:?}", drain_timeout);
+				Ok(())
+			}
+		}
+	}
+
+	/// Processes `next`, batching it with the submissions already waiting in the channel.
+	async fn process(&mut self, next: Option<MempoolClientRequest>) -> Result<(), Error> {
+		let mut next = match next {
+			Some(request) => Some(request),
+			None => return Err(Error::InputClosed),
+		};
+		while let Some(request) = next.take() {
 			match request {
 				MempoolClientRequest::SubmitTransaction(transaction, callback) => {
-					let span = info_span!(
-						target: "movement_timing",
-						"submit_transaction",
-						tx_hash = %transaction.committed_hash(),
-						sender = %transaction.sender(),
-						sequence_number = transaction.sequence_number(),
-					);
-					let status = self.submit_transaction(transaction).instrument(span).await?;
-					callback.send(Ok(status)).unwrap_or_else(|_| {
-						debug!("SubmitTransaction request canceled");
-					});
+					let mut transactions = vec![transaction];
+					let mut callbacks = vec![callback];
+					while let Ok(Some(request)) = self.mempool_client_receiver.try_next() {
+						match request {
+							MempoolClientRequest::SubmitTransaction(transaction, callback) => {
+								transactions.push(transaction);
+								callbacks.push(callback);
+							}
+							request => {
+								next = Some(request);
+								break;
+							}
+						}
+					}
+					let statuses = self.submit_transaction_batch(transactions).await?;
+					for (callback, status) in callbacks.into_iter().zip(statuses) {
+						callback.send(Ok(status)).unwrap_or_else(|_| {
+							debug!("SubmitTransaction request canceled");
+						});
+					}
 				}
 				MempoolClientRequest::GetTransactionByHash(hash, sender) => {
 					let mempool_result = self.core_mempool.get_by_hash(hash);
@@ -128,17 +247,16 @@ impl TransactionPipe {
 					});
 				}
 			}
-		} else {
-			return Err(Error::InputClosed);
 		}
 
-		if self.last_gc.elapsed() >= GC_INTERVAL {
+		if self.last_gc.elapsed() >= self.gc_interval() {
 			// todo: these will be slightly off, but gc does not need to be exact
 			let now = Instant::now();
 			let epoch_ms_now = chrono::Utc::now().timestamp_millis() as u64;
 
 			// garbage collect the used sequence number pool
 			self.used_sequence_number_pool.gc(epoch_ms_now);
+			self.persist_used_sequence_numbers();
 
 			// garbage collect the transactions in flight
 			{
@@ -146,6 +264,14 @@ impl TransactionPipe {
 				let mut transactions_in_flight = self.transactions_in_flight.write().unwrap();
 				transactions_in_flight.gc(epoch_ms_now);
 			}
+			self.update_in_flight_gauge();
+
+			// garbage collect the transactions in flight per sender
+			{
+				// unwrap because failure indicates poisoned lock
+				let mut senders_in_flight = self.senders_in_flight.write().unwrap();
+				senders_in_flight.gc(epoch_ms_now);
+			}
 
 			// garbage collect the core mempool
 			self.core_mempool.gc();
@@ -156,6 +282,42 @@ impl TransactionPipe {
 		Ok(())
 	}
 
+	/// Computes the garbage collection interval from the pressure on the transactions in flight,
+	/// interpolating linearly between the maximum and the minimum interval.
+	fn gc_interval(&self) -> Duration {
+		let Some(in_flight_limit) = *self.in_flight_limit.borrow() else {
+			return self.max_gc_interval;
+		};
+		let in_flight = {
+			// unwrap because failure indicates poisoned lock
+			let transactions_in_flight = self.transactions_in_flight.read().unwrap();
+			transactions_in_flight.get_count()
+		};
+		let ratio =
+			if in_flight_limit == 0 { 1.0 } else { in_flight as f64 / in_flight_limit as f64 };
+
+		if ratio >= HIGH_PRESSURE_RATIO {
+			self.min_gc_interval
+		} else if ratio <= LOW_PRESSURE_RATIO {
+			self.max_gc_interval
+		} else {
+			let pressure =
+				(ratio - LOW_PRESSURE_RATIO) / (HIGH_PRESSURE_RATIO - LOW_PRESSURE_RATIO);
+			let range = self.max_gc_interval.saturating_sub(self.min_gc_interval);
+			self.max_gc_interval.saturating_sub(range.mul_f64(pressure))
+		}
+	}
+
+	/// Persists the used sequence numbers, only logging a failure: losing them
+	/// merely reopens the replay window until they expire.
+	fn persist_used_sequence_numbers(&self) {
+		if let Err(e) =
+			self.used_sequence_number_pool.try_persist(&self.used_sequence_number_pool_path)
+		{
+			warn!("Failed to persist the used sequence numbers: {:?}", e);
+		}
+	}
+
 	fn has_invalid_sequence_number(
 		&self,
 		transaction: &SignedTransaction,
@@ -184,7 +346,7 @@ impl TransactionPipe {
 
 		let min_sequence_number = (min_used_sequence_number).max(committed_sequence_number);
 
-		let max_sequence_number = committed_sequence_number + TOO_NEW_TOLERANCE;
+		let max_sequence_number = committed_sequence_number + self.too_new_tolerance;
 
 		info!(
 			"min_sequence_number: {:?} max_sequence_number: {:?} transaction_sequence_number {:?}",
@@ -212,15 +374,72 @@ impl TransactionPipe {
 		Ok(SequenceNumberValidity::Valid(committed_sequence_number))
 	}
 
+	/// Submits a transaction, recording the outcome in the metrics.
 	async fn submit_transaction(
 		&mut self,
 		transaction: SignedTransaction,
 	) -> Result<SubmissionStatus, Error> {
+		let start = Instant::now();
+		self.metrics.transactions_submitted_total.inc();
+		let result = self.try_submit_transaction(transaction).await;
+		self.metrics.submission_latency_seconds.observe(start.elapsed().as_secs_f64());
+
+		let reason = match &result {
+			Ok((status, _)) if status.code == MempoolStatusCode::Accepted => None,
+			Ok((status, _)) => Some(format!("{:?}", status.code)),
+			Err(_) => Some("InternalError".to_string()),
+		};
+		if let Some(reason) = reason {
+			self.metrics.transactions_rejected_total.with_label_values(&[&reason]).inc();
+		}
+
+		self.update_in_flight_gauge();
+
+		result
+	}
+
+	/// Sets the gauge of the transactions in flight to their current count.
+	fn update_in_flight_gauge(&self) {
+		let in_flight = {
+			// unwrap because failure indicates poisoned lock
+			let transactions_in_flight = self.transactions_in_flight.read().unwrap();
+			transactions_in_flight.get_count()
+		};
+		self.metrics.transactions_in_flight.set(in_flight as i64);
+	}
+
+	async fn try_submit_transaction(
+		&mut self,
+		transaction: SignedTransaction,
+	) -> Result<SubmissionStatus, Error> {
+		// Reject oversized transactions before spending any time on them
+		let transaction_size = bcs::serialized_size(&transaction)
+			.map_err(|e| Error::InternalError(format!("Failed to size transaction: {:?}", e)))?;
+		if transaction_size > self.max_transaction_size_bytes {
+			info!("Transaction too large: {} bytes", transaction_size);
+			let status =
+				MempoolStatus::new(MempoolStatusCode::InvalidUpdate).with_message(format!(
+					"Transaction size {} bytes exceeds the limit of {} bytes",
+					transaction_size, self.max_transaction_size_bytes
+				));
+			return Ok((status, None));
+		}
+
 		// Check whether the account is whitelisted
 		if !self.is_whitelisted(&transaction.sender())? {
 			return Ok((MempoolStatus::new(MempoolStatusCode::TooManyTransactions), None));
 		}
 
+		// Acknowledge a retransmission of an accepted transaction without forwarding it again
+		let transaction_hash = transaction.committed_hash();
+		if let Some(accepted_at) = self.accepted_transactions.get(&transaction_hash) {
+			if accepted_at.elapsed() < self.dedup_ttl {
+				debug!("Transaction already accepted: {}", transaction_hash);
+				return Ok((MempoolStatus::new(MempoolStatusCode::Accepted), None));
+			}
+			self.accepted_transactions.pop(&transaction_hash);
+		}
+
 		// For now, we are going to consider a transaction in flight until it exits the mempool and is sent to the DA as is indicated by WriteBatch.
 		let in_flight = {
 			let transactions_in_flight = self.transactions_in_flight.read().unwrap();
@@ -231,7 +450,8 @@ impl TransactionPipe {
 			in_flight = %in_flight,
 			"transactions_in_flight"
 		);
-		if let Some(inflight_limit) = self.in_flight_limit {
+		let in_flight_limit = *self.in_flight_limit.borrow();
+		if let Some(inflight_limit) = in_flight_limit {
 			if in_flight >= inflight_limit {
 				info!(
 					target: "movement_timing",
@@ -241,6 +461,23 @@ impl TransactionPipe {
 				return Ok((status, None));
 			}
 		}
+		if let Some(per_sender_limit) = self.per_sender_limit {
+			let sender_in_flight = {
+				let senders_in_flight = self.senders_in_flight.read().unwrap();
+				senders_in_flight.get_count(&transaction.sender())
+			};
+			if sender_in_flight >= per_sender_limit {
+				info!(
+					target: "movement_timing",
+					sender = %transaction.sender(),
+					sender_in_flight = %sender_in_flight,
+					"shedding_sender_load"
+				);
+				let status = MempoolStatus::new(MempoolStatusCode::MempoolIsFull)
+					.with_message("Too many transactions in flight for the sender".to_string());
+				return Ok((status, Some(DiscardedVMStatus::SEQUENCE_NUMBER_TOO_NEW)));
+			}
+		}
 
 		// Pre-execute Tx to validate its content.
 		// Re-create the validator for each Tx because it uses a frozen version of the ledger.
@@ -291,7 +528,12 @@ impl TransactionPipe {
 					let mut transactions_in_flight = self.transactions_in_flight.write().unwrap();
 					transactions_in_flight.increment(now, 1);
 				}
+				{
+					let mut senders_in_flight = self.senders_in_flight.write().unwrap();
+					senders_in_flight.increment(&sender, now);
+				}
 				self.core_mempool.commit_transaction(&sender, sequence_number);
+				self.accepted_transactions.put(transaction_hash, Instant::now());
 
 				// update the used sequence number pool
 				info!(
@@ -322,7 +564,6 @@ mod tests {
 	use super::*;
 	use crate::{Context, Executor, Service};
 	use aptos_api::{accept_type::AcceptType, transactions::SubmitTransactionPost};
-	use aptos_crypto::HashValue;
 	use aptos_types::{
 		account_config,
 		block_executor::partitioner::{ExecutableBlock, ExecutableTransactions},
@@ -386,6 +627,90 @@ mod tests {
 		Ok(())
 	}
 
+	#[tokio::test]
+	async fn test_pipe_transaction_batch() -> Result<(), anyhow::Error> {
+		// set up
+		let maptos_config = Config::default();
+		let (_context, mut transaction_pipe, mut tx_receiver, _tempdir) = setup();
+		let user_transactions: Vec<_> =
+			(0..10).map(|i| create_signed_transaction(i, &maptos_config)).collect();
+
+		// submit the batch
+		let statuses = transaction_pipe.submit_transaction_batch(user_transactions.clone()).await?;
+		assert_eq!(statuses.len(), user_transactions.len());
+		for (status, _vm_status_code) in statuses {
+			assert_eq!(status.code, MempoolStatusCode::Accepted);
+		}
+
+		// receive the transactions in order
+		for user_transaction in user_transactions {
+			let received_transaction = tx_receiver.recv().await.unwrap();
+			assert_eq!(received_transaction.1, user_transaction);
+		}
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_pipe_batches_buffered_submissions() -> Result<(), anyhow::Error> {
+		// set up
+		let maptos_config = Config::default();
+		let (context, mut transaction_pipe, mut tx_receiver, _tempdir) = setup();
+		let user_transactions: Vec<_> =
+			(0..10).map(|i| create_signed_transaction(i, &maptos_config)).collect();
+
+		// buffer the submissions before the pipe ticks
+		let mut callbacks = Vec::new();
+		for user_transaction in &user_transactions {
+			let (req_sender, callback) = oneshot::channel();
+			context
+				.mempool_client_sender()
+				.send(MempoolClientRequest::SubmitTransaction(user_transaction.clone(), req_sender))
+				.await?;
+			callbacks.push(callback);
+		}
+
+		// a single tick submits the whole batch
+		transaction_pipe.tick().await?;
+		for callback in callbacks {
+			let (status, _vm_status_code) = callback.await??;
+			assert_eq!(status.code, MempoolStatusCode::Accepted);
+		}
+		for user_transaction in user_transactions {
+			let received_transaction = tx_receiver.recv().await.unwrap();
+			assert_eq!(received_transaction.1, user_transaction);
+		}
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_pipe_shutdown_drains_buffered_requests() -> Result<(), anyhow::Error> {
+		// set up
+		let maptos_config = Config::default();
+		let (context, transaction_pipe, mut tx_receiver, _tempdir) = setup();
+		let user_transaction = create_signed_transaction(1, &maptos_config);
+
+		// buffer a transaction before the pipe runs
+		let (req_sender, callback) = oneshot::channel();
+		context
+			.mempool_client_sender()
+			.send(MempoolClientRequest::SubmitTransaction(user_transaction.clone(), req_sender))
+			.await?;
+
+		// the pipe is already shut down, but still processes the buffered transaction
+		let shutdown = CancellationToken::new();
+		shutdown.cancel();
+		transaction_pipe.run(shutdown).await?;
+
+		let (status, _vm_status_code) = callback.await??;
+		assert_eq!(status.code, MempoolStatusCode::Accepted);
+		let received_transaction = tx_receiver.recv().await.unwrap();
+		assert_eq!(received_transaction.1, user_transaction);
+
+		Ok(())
+	}
+
 	#[tokio::test]
 	async fn test_pipe_mempool_cancellation() -> Result<(), anyhow::Error> {
 		// set up
@@ -452,6 +777,29 @@ mod tests {
 		Ok(())
 	}
 
+	#[tokio::test]
+	async fn test_duplicate_transaction_within_ttl_is_not_forwarded() -> Result<(), anyhow::Error> {
+		// set up
+		let maptos_config = Config::default();
+		let (_context, mut transaction_pipe, mut tx_receiver, _tempdir) = setup();
+		let user_transaction = create_signed_transaction(1, &maptos_config);
+
+		let (mempool_status, _) =
+			transaction_pipe.submit_transaction(user_transaction.clone()).await?;
+		assert_eq!(mempool_status.code, MempoolStatusCode::Accepted);
+		let received_transaction = tx_receiver.recv().await.unwrap();
+		assert_eq!(received_transaction.1, user_transaction);
+
+		// the retransmission is acknowledged, but not forwarded again
+		let (mempool_status, vm_status) =
+			transaction_pipe.submit_transaction(user_transaction).await?;
+		assert_eq!(mempool_status.code, MempoolStatusCode::Accepted);
+		assert_eq!(vm_status, None);
+		assert!(tx_receiver.try_recv().is_err());
+
+		Ok(())
+	}
+
 	#[tokio::test]
 	async fn test_pipe_mempool_from_api() -> Result<(), anyhow::Error> {
 		let (context, mut transaction_pipe, mut tx_receiver, _tempdir) = setup();
@@ -543,6 +891,250 @@ mod tests {
 		let (mempool_status, _) = transaction_pipe.submit_transaction(user_transaction).await?;
 		assert_eq!(mempool_status.code, MempoolStatusCode::InvalidSeqNumber);
 
+		// with a tolerance of 1, a sequence number 2 ahead is too new
+		let (tx_sender, _tx_receiver) = mpsc::channel(16);
+		let (executor, config, _tempdir) =
+			Executor::try_test_default_with_config(GENESIS_KEYPAIR.0.clone(), |config| {
+				config.chain.maptos_too_new_tolerance = 1;
+			})?;
+		let (_context, background) = executor.background(tx_sender)?;
+		let mut transaction_pipe = background.into_transaction_pipe();
+
+		let user_transaction = create_signed_transaction(2, &config.chain);
+		let (mempool_status, _) = transaction_pipe.submit_transaction(user_transaction).await?;
+		assert_eq!(mempool_status.code, MempoolStatusCode::InvalidSeqNumber);
+
+		let user_transaction = create_signed_transaction(1, &config.chain);
+		let (mempool_status, _) = transaction_pipe.submit_transaction(user_transaction).await?;
+		assert_eq!(mempool_status.code, MempoolStatusCode::Accepted);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_load_shedding_with_config_override() -> Result<(), anyhow::Error> {
+		// set up with no room for transactions in flight
+		let (tx_sender, _tx_receiver) = mpsc::channel(16);
+		let (executor, config, _tempdir) =
+			Executor::try_test_default_with_config(GENESIS_KEYPAIR.0.clone(), |config| {
+				config.load_shedding.max_transactions_in_flight = Some(0);
+			})?;
+		assert_eq!(config.load_shedding.max_transactions_in_flight, Some(0));
+		let (_context, background) = executor.background(tx_sender)?;
+		let mut transaction_pipe = background.into_transaction_pipe();
+
+		// the transaction should be shed
+		let user_transaction = create_signed_transaction(0, &config.chain);
+		let (mempool_status, _) = transaction_pipe.submit_transaction(user_transaction).await?;
+		assert_eq!(mempool_status.code, MempoolStatusCode::MempoolIsFull);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_load_shedding_limit_updated_while_running() -> Result<(), anyhow::Error> {
+		// set up with no room for transactions in flight
+		let (tx_sender, _tx_receiver) = mpsc::channel(16);
+		let (executor, config, _tempdir) =
+			Executor::try_test_default_with_config(GENESIS_KEYPAIR.0.clone(), |config| {
+				config.load_shedding.max_transactions_in_flight = Some(0);
+			})?;
+		let (_context, background) = executor.background(tx_sender)?;
+		let mut transaction_pipe = background.into_transaction_pipe();
+
+		let user_transaction = create_signed_transaction(0, &config.chain);
+		let (mempool_status, _) =
+			transaction_pipe.submit_transaction(user_transaction.clone()).await?;
+		assert_eq!(mempool_status.code, MempoolStatusCode::MempoolIsFull);
+
+		// raising the limit applies to the running pipe
+		executor.transactions_in_flight_limit().send_replace(Some(1));
+		let (mempool_status, _) = transaction_pipe.submit_transaction(user_transaction).await?;
+		assert_eq!(mempool_status.code, MempoolStatusCode::Accepted);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_per_sender_load_shedding() -> Result<(), anyhow::Error> {
+		// set up with room for a single transaction in flight per sender
+		let (tx_sender, _tx_receiver) = mpsc::channel(16);
+		let (executor, config, _tempdir) =
+			Executor::try_test_default_with_config(GENESIS_KEYPAIR.0.clone(), |config| {
+				config.load_shedding.max_transactions_in_flight_per_sender = Some(1);
+			})?;
+		let (_context, background) = executor.background(tx_sender)?;
+		let mut transaction_pipe = background.into_transaction_pipe();
+
+		// the first transaction of the sender is accepted
+		let user_transaction = create_signed_transaction(0, &config.chain);
+		let (mempool_status, _) = transaction_pipe.submit_transaction(user_transaction).await?;
+		assert_eq!(mempool_status.code, MempoolStatusCode::Accepted);
+
+		// the second one is shed while the first is in flight
+		let user_transaction = create_signed_transaction(1, &config.chain);
+		let (mempool_status, vm_status) =
+			transaction_pipe.submit_transaction(user_transaction).await?;
+		assert_eq!(mempool_status.code, MempoolStatusCode::MempoolIsFull);
+		assert_eq!(vm_status, Some(DiscardedVMStatus::SEQUENCE_NUMBER_TOO_NEW));
+
+		// once the first transaction has left, the sender can submit again
+		executor.decrement_transactions_in_flight(&[account_config::aptos_test_root_address()]);
+		let user_transaction = create_signed_transaction(1, &config.chain);
+		let (mempool_status, _) = transaction_pipe.submit_transaction(user_transaction).await?;
+		assert_eq!(mempool_status.code, MempoolStatusCode::Accepted);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_per_sender_in_flight_gc() -> Result<(), anyhow::Error> {
+		// set up with a short time to live for the transactions in flight
+		let (tx_sender, _tx_receiver) = mpsc::channel(16);
+		let (executor, config, _tempdir) =
+			Executor::try_test_default_with_config(GENESIS_KEYPAIR.0.clone(), |config| {
+				config.load_shedding.max_transactions_in_flight_per_sender = Some(1);
+				config.mempool.sequence_number_ttl_ms = 200;
+				config.mempool.gc_slot_duration_ms = 100;
+			})?;
+		let (context, background) = executor.background(tx_sender)?;
+		let mut transaction_pipe = background.into_transaction_pipe();
+
+		let user_transaction = create_signed_transaction(0, &config.chain);
+		let (mempool_status, _) = transaction_pipe.submit_transaction(user_transaction).await?;
+		assert_eq!(mempool_status.code, MempoolStatusCode::Accepted);
+		let sender = account_config::aptos_test_root_address();
+		assert_eq!(transaction_pipe.senders_in_flight.read().unwrap().get_count(&sender), 1);
+
+		// let the count expire and make the next tick run the gc sweep
+		tokio::time::sleep(Duration::from_millis(400)).await;
+		transaction_pipe.last_gc = Instant::now() - transaction_pipe.max_gc_interval;
+		let (req_sender, callback) = oneshot::channel();
+		context
+			.mempool_client_sender()
+			.send(MempoolClientRequest::GetTransactionByHash(HashValue::random(), req_sender))
+			.await?;
+		transaction_pipe.tick().await?;
+		assert!(callback.await?.is_none());
+
+		assert_eq!(transaction_pipe.senders_in_flight.read().unwrap().get_count(&sender), 0);
+		assert_eq!(transaction_pipe.metrics.transactions_in_flight.get(), 0);
+		let user_transaction = create_signed_transaction(1, &config.chain);
+		let (mempool_status, _) = transaction_pipe.submit_transaction(user_transaction).await?;
+		assert_eq!(mempool_status.code, MempoolStatusCode::Accepted);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_gc_interval_follows_in_flight_pressure() -> Result<(), anyhow::Error> {
+		let (tx_sender, _tx_receiver) = mpsc::channel(16);
+		let (executor, config, _tempdir) =
+			Executor::try_test_default_with_config(GENESIS_KEYPAIR.0.clone(), |config| {
+				config.load_shedding.max_transactions_in_flight = Some(2);
+				config.mempool.min_gc_interval_ms = 100;
+				config.mempool.max_gc_interval_ms = 60_000;
+			})?;
+		let (context, background) = executor.background(tx_sender)?;
+		let mut transaction_pipe = background.into_transaction_pipe();
+
+		// without transactions in flight, GC runs at the maximum interval
+		assert_eq!(transaction_pipe.gc_interval(), Duration::from_millis(60_000));
+
+		// simulate high load by filling the transactions in flight
+		for sequence_number in 0..2 {
+			let user_transaction = create_signed_transaction(sequence_number, &config.chain);
+			let (mempool_status, _) = transaction_pipe.submit_transaction(user_transaction).await?;
+			assert_eq!(mempool_status.code, MempoolStatusCode::Accepted);
+		}
+		assert_eq!(transaction_pipe.gc_interval(), Duration::from_millis(100));
+
+		// the next tick after the minimum interval runs the gc sweep
+		let last_gc = Instant::now();
+		transaction_pipe.last_gc = last_gc;
+		tokio::time::sleep(Duration::from_millis(150)).await;
+		let (req_sender, callback) = oneshot::channel();
+		context
+			.mempool_client_sender()
+			.send(MempoolClientRequest::GetTransactionByHash(HashValue::random(), req_sender))
+			.await?;
+		transaction_pipe.tick().await?;
+		assert!(callback.await?.is_none());
+		assert!(transaction_pipe.last_gc > last_gc);
+
+		// between the pressure thresholds, the interval is between the bounds
+		executor.decrement_transactions_in_flight(&[account_config::aptos_test_root_address()]);
+		assert_eq!(transaction_pipe.metrics.transactions_in_flight.get(), 1);
+		let gc_interval = transaction_pipe.gc_interval();
+		assert!(gc_interval > Duration::from_millis(100));
+		assert!(gc_interval < Duration::from_millis(60_000));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_transaction_size_limit() -> Result<(), anyhow::Error> {
+		let user_transaction = create_signed_transaction(0, &Config::default());
+		let transaction_size = bcs::serialized_size(&user_transaction)?;
+
+		// one byte over the limit is rejected
+		let (tx_sender, _tx_receiver) = mpsc::channel(16);
+		let (executor, _config, _tempdir) =
+			Executor::try_test_default_with_config(GENESIS_KEYPAIR.0.clone(), |config| {
+				config.mempool.max_transaction_size_bytes = transaction_size - 1;
+			})?;
+		let (_context, background) = executor.background(tx_sender)?;
+		let mut transaction_pipe = background.into_transaction_pipe();
+		let (mempool_status, _) =
+			transaction_pipe.submit_transaction(user_transaction.clone()).await?;
+		assert_eq!(mempool_status.code, MempoolStatusCode::InvalidUpdate);
+
+		// exactly at the limit is accepted
+		let (tx_sender, _tx_receiver) = mpsc::channel(16);
+		let (executor, _config, _tempdir) =
+			Executor::try_test_default_with_config(GENESIS_KEYPAIR.0.clone(), |config| {
+				config.mempool.max_transaction_size_bytes = transaction_size;
+			})?;
+		let (_context, background) = executor.background(tx_sender)?;
+		let mut transaction_pipe = background.into_transaction_pipe();
+		let (mempool_status, _) = transaction_pipe.submit_transaction(user_transaction).await?;
+		assert_eq!(mempool_status.code, MempoolStatusCode::Accepted);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_submission_metrics() -> Result<(), anyhow::Error> {
+		let (context, mut transaction_pipe, _tx_receiver, _tempdir) = setup();
+
+		// one accepted submission
+		let user_transaction = create_signed_transaction(0, &context.config().chain);
+		let (mempool_status, _) = transaction_pipe.submit_transaction(user_transaction).await?;
+		assert_eq!(mempool_status.code, MempoolStatusCode::Accepted);
+
+		// one rejected submission, too far ahead of the committed sequence number
+		let user_transaction = create_signed_transaction(1000, &context.config().chain);
+		let (mempool_status, _) = transaction_pipe.submit_transaction(user_transaction).await?;
+		assert_eq!(mempool_status.code, MempoolStatusCode::InvalidSeqNumber);
+
+		let metrics = &transaction_pipe.metrics;
+		assert_eq!(metrics.transactions_submitted_total.get(), 2);
+		assert_eq!(
+			metrics
+				.transactions_rejected_total
+				.with_label_values(&["InvalidSeqNumber"])
+				.get(),
+			1
+		);
+		assert_eq!(metrics.transactions_in_flight.get(), 1);
+		assert_eq!(metrics.submission_latency_seconds.get_sample_count(), 2);
+
+		// the metrics are exposed through the registry of the context
+		let families = context.metrics_registry().gather();
+		assert!(families
+			.iter()
+			.any(|family| family.get_name() == "maptos_transactions_submitted_total"));
+
 		Ok(())
 	}
 
@@ -2,6 +2,7 @@ use aptos_config::config::NodeConfig;
 use aptos_mempool::MempoolClientSender;
 use aptos_storage_interface::{DbReader, DbReaderWriter};
 use maptos_execution_util::config::Config;
+use prometheus::Registry;
 
 use std::sync::Arc;
 
@@ -11,6 +12,7 @@ pub struct Context {
 	pub(crate) mempool_client_sender: MempoolClientSender,
 	pub(crate) maptos_config: Config,
 	pub(crate) node_config: NodeConfig,
+	pub(crate) metrics_registry: Registry,
 }
 
 impl Context {
@@ -19,8 +21,9 @@ impl Context {
 		mempool_client_sender: MempoolClientSender,
 		maptos_config: Config,
 		node_config: NodeConfig,
+		metrics_registry: Registry,
 	) -> Self {
-		Context { db, mempool_client_sender, maptos_config, node_config }
+		Context { db, mempool_client_sender, maptos_config, node_config, metrics_registry }
 	}
 
 	/// Returns a reference on the data store reader.
@@ -40,4 +43,9 @@ impl Context {
 	pub fn node_config(&self) -> &NodeConfig {
 		&self.node_config
 	}
+
+	/// Returns the registry of the Prometheus metrics.
+	pub fn metrics_registry(&self) -> &Registry {
+		&self.metrics_registry
+	}
 }
@@ -1,5 +1,7 @@
 use super::Executor;
-use crate::background::BackgroundTask;
+use crate::background::{transactions_in_flight_gauge, BackgroundTask};
+use crate::gc_account_sequence_number::UsedSequenceNumberPool;
+use crate::gc_sender_in_flight::SenderInFlightCounter;
 use crate::{bootstrap, Context};
 
 use aptos_config::config::NodeConfig;
@@ -16,7 +18,9 @@ use maptos_execution_util::config::Config;
 use anyhow::Context as _;
 use futures::channel::mpsc as futures_mpsc;
 use movement_collections::garbage::{counted::GcCounter, Duration};
-use tokio::sync::mpsc;
+use prometheus::Registry;
+use tokio::sync::{mpsc, watch};
+use tracing::warn;
 
 #[cfg(test)]
 use tempfile::TempDir;
@@ -28,6 +32,9 @@ use std::sync::{Arc, RwLock};
 // Allow 2^16 transactions before appling backpressure given theoretical maximum TPS of 170k.
 const EXECUTOR_CHANNEL_SIZE: usize = 2_usize.pow(16);
 
+// File in the db directory holding the used sequence numbers of the transaction pipe.
+const USED_SEQUENCE_NUMBER_POOL_FILE: &str = "used_sequence_numbers.bcs";
+
 impl Executor {
 	pub fn bootstrap(maptos_config: &Config) -> Result<Self, anyhow::Error> {
 		// get dot movement
@@ -101,9 +108,11 @@ impl Executor {
 		node_config.storage.dir = dot_movement.get_path().join("maptos-storage");
 		node_config.storage.set_data_dir(node_config.storage.dir.clone());
 
+		let db_path =
+			maptos_config.chain.maptos_db_path.as_ref().context("No db path provided.")?;
 		let (db, signer) = bootstrap::maybe_bootstrap_empty_db(
 			&node_config,
-			maptos_config.chain.maptos_db_path.as_ref().context("No db path provided.")?,
+			db_path,
 			maptos_config.chain.maptos_chain_id.clone(),
 			&maptos_config.chain.maptos_private_key.public_key(),
 		)?;
@@ -114,6 +123,16 @@ impl Executor {
 				Duration::try_new(maptos_config.mempool.sequence_number_ttl_ms)?,
 				Duration::try_new(maptos_config.mempool.gc_slot_duration_ms)?,
 			))),
+			senders_in_flight: Arc::new(RwLock::new(SenderInFlightCounter::new(
+				maptos_config.mempool.sequence_number_ttl_ms,
+				maptos_config.mempool.gc_slot_duration_ms,
+			))),
+			transactions_in_flight_limit: Arc::new(
+				watch::channel(maptos_config.load_shedding.max_transactions_in_flight).0,
+			),
+			used_sequence_number_pool_path: db_path.join(USED_SEQUENCE_NUMBER_POOL_FILE),
+			metrics_registry: Registry::new(),
+			transactions_in_flight_gauge: transactions_in_flight_gauge()?,
 			config: maptos_config.clone(),
 			node_config: node_config.clone(),
 		})
@@ -127,6 +146,18 @@ impl Executor {
 	pub fn try_test_default(
 		private_key: Ed25519PrivateKey,
 	) -> Result<(Self, TempDir), anyhow::Error> {
+		let (executor, _config, tempdir) = Self::try_test_default_with_config(private_key, |_| {})?;
+		Ok((executor, tempdir))
+	}
+
+	/// Like [`Executor::try_test_default`], but lets the caller adjust the
+	/// default configuration before the executor is constructed.
+	/// The private key and the temporary db path are set before `overrides` is called.
+	#[cfg(test)]
+	pub fn try_test_default_with_config(
+		private_key: Ed25519PrivateKey,
+		overrides: impl FnOnce(&mut Config),
+	) -> Result<(Self, Config, TempDir), anyhow::Error> {
 		let tempdir = tempfile::tempdir()?;
 
 		let mut maptos_config = Config::default();
@@ -134,8 +165,33 @@ impl Executor {
 
 		// replace the db path with the temporary directory
 		maptos_config.chain.maptos_db_path.replace(tempdir.path().to_path_buf());
-		let executor = Self::try_from_config(maptos_config)?;
-		Ok((executor, tempdir))
+
+		// apply the caller's overrides
+		overrides(&mut maptos_config);
+
+		let executor = Self::try_from_config(maptos_config.clone())?;
+		Ok((executor, maptos_config, tempdir))
+	}
+
+	/// Restores the used sequence numbers persisted by a previous run, so that they
+	/// cannot be replayed after a restart. Starts with an empty pool if there are none.
+	fn restore_used_sequence_number_pool(&self) -> UsedSequenceNumberPool {
+		let path = &self.used_sequence_number_pool_path;
+		let sequence_number_ttl_ms = self.config.mempool.sequence_number_ttl_ms;
+		let gc_slot_duration_ms = self.config.mempool.gc_slot_duration_ms;
+		if path.exists() {
+			let now = chrono::Utc::now().timestamp_millis() as u64;
+			match UsedSequenceNumberPool::try_restore(
+				path,
+				sequence_number_ttl_ms,
+				gc_slot_duration_ms,
+				now,
+			) {
+				Ok(pool) => return pool,
+				Err(e) => warn!("Failed to restore the used sequence numbers: {:?}", e),
+			}
+		}
+		UsedSequenceNumberPool::new(sequence_number_ttl_ms, gc_slot_duration_ms)
 	}
 
 	/// Creates an instance of [`Context`] and the background [`TransactionPipe`]
@@ -157,6 +213,7 @@ impl Executor {
 		let background_task = if maptos_config.chain.maptos_read_only {
 			BackgroundTask::read_only(mempool_client_receiver)
 		} else {
+			let used_sequence_number_pool = self.restore_used_sequence_number_pool();
 			BackgroundTask::transaction_pipe(
 				mempool_client_receiver,
 				transaction_sender,
@@ -165,11 +222,24 @@ impl Executor {
 				&self.config.mempool,
 				&self.config.access_control,
 				self.transactions_in_flight.clone(),
-				maptos_config.load_shedding.max_transactions_in_flight,
+				self.transactions_in_flight_limit.subscribe(),
+				self.senders_in_flight.clone(),
+				maptos_config.load_shedding.max_transactions_in_flight_per_sender,
+				maptos_config.chain.maptos_too_new_tolerance,
+				used_sequence_number_pool,
+				self.used_sequence_number_pool_path.clone(),
+				&self.metrics_registry,
+				self.transactions_in_flight_gauge.clone(),
 			)?
 		};
 
-		let cx = Context::new(self.db().clone(), mempool_client_sender, maptos_config, node_config);
+		let cx = Context::new(
+			self.db().clone(),
+			mempool_client_sender,
+			maptos_config,
+			node_config,
+			self.metrics_registry.clone(),
+		);
 
 		Ok((cx, background_task))
 	}
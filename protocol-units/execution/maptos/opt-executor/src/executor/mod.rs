@@ -7,14 +7,19 @@ use aptos_config::config::NodeConfig;
 use aptos_crypto::HashValue;
 use aptos_executor::block_executor::BlockExecutor;
 use aptos_storage_interface::{DbReader, DbReaderWriter};
+use aptos_types::account_address::AccountAddress;
 use aptos_types::validator_signer::ValidatorSigner;
 use aptos_vm::AptosVM;
 
 use tracing::info;
 
+use crate::gc_sender_in_flight::SenderInFlightCounter;
 use maptos_execution_util::config::Config;
 use movement_collections::garbage::counted::GcCounter;
+use prometheus::{IntGauge, Registry};
+use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
+use tokio::sync::watch;
 
 /// The `Executor` is responsible for executing blocks and managing the state of the execution
 /// against the `AptosVM`.
@@ -25,6 +30,16 @@ pub struct Executor {
 	pub signer: ValidatorSigner,
 	// Shared reference on the counter of transactions in flight.
 	transactions_in_flight: Arc<RwLock<GcCounter>>,
+	// Shared reference on the counters of transactions in flight per sender.
+	senders_in_flight: Arc<RwLock<SenderInFlightCounter>>,
+	// Publishes the limit on transactions in flight to the transaction pipe.
+	transactions_in_flight_limit: Arc<watch::Sender<Option<u64>>>,
+	// Where the transaction pipe persists its used sequence numbers.
+	used_sequence_number_pool_path: PathBuf,
+	// The registry of the Prometheus metrics served by the node.
+	metrics_registry: Registry,
+	// The gauge of the transactions in flight, updated whenever their count changes.
+	transactions_in_flight_gauge: IntGauge,
 	// The config for the executor.
 	pub(crate) config: Config,
 	/// The node config derived from the maptos config.
@@ -40,7 +55,9 @@ impl Executor {
 		Arc::clone(&self.db().reader)
 	}
 
-	pub fn decrement_transactions_in_flight(&self, count: u64) {
+	/// Releases the transactions in flight of a block, given the sender of each transaction.
+	pub fn decrement_transactions_in_flight(&self, senders: &[AccountAddress]) {
+		let count = senders.len() as u64;
 		// unwrap because lock is poisoned
 		let mut transactions_in_flight = self.transactions_in_flight.write().unwrap();
 		let current = transactions_in_flight.get_count();
@@ -51,6 +68,19 @@ impl Executor {
 			"decrementing_transactions_in_flight",
 		);
 		transactions_in_flight.decrement(count);
+		self.transactions_in_flight_gauge.set(transactions_in_flight.get_count() as i64);
+
+		// unwrap because lock is poisoned
+		let mut senders_in_flight = self.senders_in_flight.write().unwrap();
+		for sender in senders {
+			senders_in_flight.decrement(sender);
+		}
+	}
+
+	/// Returns the sender of the limit on transactions in flight,
+	/// the transaction pipe applies the limits sent on it while running.
+	pub fn transactions_in_flight_limit(&self) -> Arc<watch::Sender<Option<u64>>> {
+		self.transactions_in_flight_limit.clone()
 	}
 
 	pub fn config(&self) -> &Config {
@@ -1,7 +1,11 @@
 use aptos_types::account_address::AccountAddress;
+use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
 use tracing::debug;
 
+#[derive(Serialize, Deserialize)]
 pub struct UsedSequenceNumberPool {
 	/// The number of milliseconds a sequence number is valid for.
 	sequence_number_ttl_ms: u64,
@@ -22,6 +26,38 @@ impl UsedSequenceNumberPool {
 		}
 	}
 
+	/// Writes the pool to `path`, so that it can be restored after a restart.
+	pub(crate) fn try_persist(&self, path: &Path) -> Result<(), anyhow::Error> {
+		let bytes = bcs::to_bytes(self)?;
+		// write to a temporary file first so that a crash never leaves a partial pool behind
+		let tmp_path = path.with_extension("tmp");
+		fs::write(&tmp_path, bytes)?;
+		fs::rename(&tmp_path, path)?;
+		Ok(())
+	}
+
+	/// Reads a pool written by [`UsedSequenceNumberPool::try_persist`].
+	/// The pool takes the given TTL and slot duration, which may have changed since it was
+	/// written, and the sequence numbers expired at `current_time_ms` are dropped.
+	pub(crate) fn try_restore(
+		path: &Path,
+		sequence_number_ttl_ms: u64,
+		gc_slot_duration_ms: u64,
+		current_time_ms: u64,
+	) -> Result<Self, anyhow::Error> {
+		let bytes = fs::read(path)?;
+		let restored: Self = bcs::from_bytes(&bytes)?;
+
+		let mut pool = Self::new(sequence_number_ttl_ms, gc_slot_duration_ms);
+		for (slot, lifetimes) in restored.sequence_number_lifetimes {
+			// rebin the lifetimes into the slots of the current duration
+			let slot = slot * restored.gc_slot_duration_ms / gc_slot_duration_ms;
+			pool.sequence_number_lifetimes.entry(slot).or_default().extend(lifetimes);
+		}
+		pool.gc(current_time_ms);
+		Ok(pool)
+	}
+
 	/// Gets a sequence number for an account
 	pub(crate) fn get_sequence_number(&self, account: &AccountAddress) -> Option<u64> {
 		// check each slot for the account
@@ -140,6 +176,53 @@ pub mod test {
 		assert_eq!(pool.get_sequence_number(&account2), None);
 	}
 
+	#[test]
+	fn test_persist_and_restore() -> Result<(), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+		let path = dir.path().join("used_sequence_numbers.bcs");
+		let mut pool = UsedSequenceNumberPool::new(1000, 100);
+		let account1 = AccountAddress::random();
+		let account2 = AccountAddress::random();
+
+		pool.set_sequence_number(&account1, 1, 0);
+		pool.set_sequence_number(&account2, 2, 500);
+		pool.try_persist(&path)?;
+
+		let mut restored = UsedSequenceNumberPool::try_restore(&path, 1000, 100, 1000)?;
+		assert_eq!(restored.get_sequence_number(&account1), Some(1));
+		assert_eq!(restored.get_sequence_number(&account2), Some(2));
+
+		// the restored pool keeps the lifetimes of the sequence numbers
+		restored.gc(1100);
+		assert_eq!(restored.get_sequence_number(&account1), None);
+		assert_eq!(restored.get_sequence_number(&account2), Some(2));
+		Ok(())
+	}
+
+	#[test]
+	fn test_restore_applies_the_ttl() -> Result<(), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+		let path = dir.path().join("used_sequence_numbers.bcs");
+		let mut pool = UsedSequenceNumberPool::new(1000, 100);
+		let account1 = AccountAddress::random();
+		let account2 = AccountAddress::random();
+
+		pool.set_sequence_number(&account1, 1, 0);
+		pool.set_sequence_number(&account2, 2, 1500);
+		pool.try_persist(&path)?;
+
+		// the sequence numbers expired while the node was down are dropped
+		let restored = UsedSequenceNumberPool::try_restore(&path, 1000, 100, 2000)?;
+		assert_eq!(restored.get_sequence_number(&account1), None);
+		assert_eq!(restored.get_sequence_number(&account2), Some(2));
+
+		// with a shorter TTL, in slots of another duration
+		let restored = UsedSequenceNumberPool::try_restore(&path, 200, 50, 2000)?;
+		assert_eq!(restored.get_sequence_number(&account1), None);
+		assert_eq!(restored.get_sequence_number(&account2), None);
+		Ok(())
+	}
+
 	#[test]
 	fn test_gc_removes_some_not_all() {
 		let mut pool = UsedSequenceNumberPool::new(1000, 100);
@@ -0,0 +1,136 @@
+use aptos_types::account_address::AccountAddress;
+use std::collections::{BTreeMap, HashMap};
+use tracing::debug;
+
+/// Counts the transactions in flight for each sender, so that a single account
+/// cannot occupy all of the in-flight slots.
+pub struct SenderInFlightCounter {
+	/// The number of milliseconds a count is valid for.
+	ttl_ms: u64,
+	/// The duration of a garbage collection slot in milliseconds.
+	gc_slot_duration_ms: u64,
+	/// The counts per sender, indexed by slot.
+	sender_counts: BTreeMap<u64, HashMap<AccountAddress, u64>>,
+}
+
+impl SenderInFlightCounter {
+	/// Creates a new SenderInFlightCounter with a specified garbage collection slot duration.
+	pub(crate) fn new(ttl_ms: u64, gc_slot_duration_ms: u64) -> Self {
+		SenderInFlightCounter { ttl_ms, gc_slot_duration_ms, sender_counts: BTreeMap::new() }
+	}
+
+	/// Gets the number of transactions in flight for an account.
+	pub(crate) fn get_count(&self, account: &AccountAddress) -> u64 {
+		self.sender_counts.values().filter_map(|counts| counts.get(account)).sum()
+	}
+
+	/// Counts one more transaction in flight for an account.
+	pub(crate) fn increment(&mut self, account: &AccountAddress, current_time_ms: u64) {
+		let slot = current_time_ms / self.gc_slot_duration_ms;
+		*self.sender_counts.entry(slot).or_default().entry(*account).or_insert(0) += 1;
+	}
+
+	/// Releases one transaction in flight of an account, from its oldest slot.
+	/// Does nothing if the account has no transaction in flight.
+	pub(crate) fn decrement(&mut self, account: &AccountAddress) {
+		let slot = self
+			.sender_counts
+			.iter()
+			.find_map(|(slot, counts)| counts.contains_key(account).then_some(*slot));
+		if let Some(slot) = slot {
+			let counts = self.sender_counts.get_mut(&slot).expect("slot was just found");
+			match counts.get_mut(account) {
+				Some(count) if *count > 1 => *count -= 1,
+				_ => {
+					counts.remove(account);
+				}
+			}
+			if counts.is_empty() {
+				self.sender_counts.remove(&slot);
+			}
+		}
+	}
+
+	/// Garbage collects counts that have expired.
+	/// This should be called periodically.
+	pub(crate) fn gc(&mut self, current_time_ms: u64) {
+		let gc_slot = current_time_ms / self.gc_slot_duration_ms;
+
+		// remove all slots that are too old
+		let slot_cutoff = gc_slot.saturating_sub(self.ttl_ms / self.gc_slot_duration_ms);
+		let to_keep = self.sender_counts.split_off(&slot_cutoff);
+		for (slot, counts) in &self.sender_counts {
+			debug!(
+				"Garbage collecting {} senders in flight from slot {} timestamp {}",
+				counts.len(),
+				slot,
+				slot * self.gc_slot_duration_ms
+			);
+		}
+		self.sender_counts = to_keep;
+	}
+}
+
+#[cfg(test)]
+pub mod test {
+
+	use super::*;
+
+	#[test]
+	fn test_increments() {
+		let mut counter = SenderInFlightCounter::new(1000, 100);
+		let account1 = AccountAddress::random();
+		let account2 = AccountAddress::random();
+
+		counter.increment(&account1, 0);
+		counter.increment(&account1, 100);
+		counter.increment(&account2, 0);
+		assert_eq!(counter.get_count(&account1), 2);
+		assert_eq!(counter.get_count(&account2), 1);
+	}
+
+	#[test]
+	fn test_decrements_oldest_first() {
+		let mut counter = SenderInFlightCounter::new(1000, 100);
+		let account1 = AccountAddress::random();
+		let account2 = AccountAddress::random();
+
+		counter.increment(&account1, 0);
+		counter.increment(&account2, 0);
+		counter.increment(&account2, 100);
+		counter.decrement(&account2);
+		assert_eq!(counter.get_count(&account1), 1);
+		assert_eq!(counter.get_count(&account2), 1);
+		assert_eq!(counter.sender_counts[&0].get(&account2), None);
+
+		// only the decremented account is released
+		counter.decrement(&account2);
+		assert_eq!(counter.get_count(&account1), 1);
+		assert_eq!(counter.get_count(&account2), 0);
+
+		// decrementing an account without transactions in flight does nothing
+		counter.decrement(&account2);
+		assert_eq!(counter.get_count(&account1), 1);
+		counter.decrement(&account1);
+		assert!(counter.sender_counts.is_empty());
+	}
+
+	#[test]
+	fn test_gc() {
+		let mut counter = SenderInFlightCounter::new(1000, 100);
+		let account1 = AccountAddress::random();
+		let account2 = AccountAddress::random();
+
+		counter.increment(&account1, 0);
+		counter.increment(&account2, 1000);
+		counter.gc(1000);
+		assert_eq!(counter.get_count(&account1), 1);
+		assert_eq!(counter.get_count(&account2), 1);
+		counter.gc(1100);
+		assert_eq!(counter.get_count(&account1), 0);
+		assert_eq!(counter.get_count(&account2), 1);
+		counter.gc(2100);
+		assert_eq!(counter.get_count(&account2), 0);
+		assert!(counter.sender_counts.is_empty());
+	}
+}
@@ -4,6 +4,7 @@ pub mod context;
 #[warn(unused_imports)]
 pub mod executor;
 pub mod gc_account_sequence_number;
+pub mod gc_sender_in_flight;
 pub mod indexer;
 pub mod service;
 
@@ -8,8 +8,12 @@ use aptos_api::{
 use aptos_storage_interface::DbReaderWriter;
 
 use futures::prelude::*;
-use poem::{http::Method, listener::TcpListener, middleware::Cors, EndpointExt, Route, Server};
-use tracing::info;
+use poem::{
+	error::InternalServerError, handler, http::Method, listener::TcpListener, middleware::Cors,
+	web::Data, EndpointExt, Route, Server,
+};
+use prometheus::{Encoder as _, Registry, TextEncoder};
+use tracing::{info, warn};
 
 use std::future::Future;
 use std::sync::Arc;
@@ -20,6 +24,10 @@ pub struct Service {
 	context: Arc<aptos_api::Context>,
 	// URL for the API endpoint
 	listen_url: String,
+	// Registry of the metrics served on the metrics endpoint
+	metrics_registry: Registry,
+	// URL for the metrics endpoint, if the metrics are served
+	metrics_listen_url: Option<String>,
 }
 
 impl Service {
@@ -29,6 +37,7 @@ impl Service {
 			mempool_client_sender,
 			maptos_config,
 			node_config,
+			metrics_registry,
 		} = cx;
 		let context = Arc::new(aptos_api::Context::new(
 			maptos_config.chain.maptos_chain_id.clone(),
@@ -42,7 +51,19 @@ impl Service {
 			maptos_config.chain.maptos_rest_listen_hostname,
 			maptos_config.chain.maptos_rest_listen_port
 		);
-		Service { context, listen_url }
+		let metrics_listen_url = maptos_config.chain.maptos_metrics_enabled.then(|| {
+			format!(
+				"{}:{}",
+				maptos_config.chain.maptos_metrics_listen_hostname,
+				maptos_config.chain.maptos_metrics_listen_port
+			)
+		});
+		Service {
+			context,
+			listen_url,
+			metrics_registry: metrics_registry.clone(),
+			metrics_listen_url,
+		}
 	}
 
 	pub fn api_context(&self) -> Arc<aptos_api::Context> {
@@ -80,12 +101,42 @@ impl Service {
 			)
 			.with(cors);
 
-		Server::new(listener)
+		let api_server = Server::new(listener)
 			.run(app)
-			.map_err(|e| anyhow::anyhow!("Server error: {:?}", e))
+			.map_err(|e| anyhow::anyhow!("Server error: {:?}", e));
+
+		let metrics_server = self.metrics_listen_url.clone().map(|metrics_listen_url| {
+			info!("Starting maptos-opt-executor metrics at: {:?}", metrics_listen_url);
+			let metrics_app =
+				Route::new().at("/metrics", poem::get(metrics).data(self.metrics_registry.clone()));
+			Server::new(TcpListener::bind(metrics_listen_url)).run(metrics_app)
+		});
+
+		async move {
+			// the API keeps being served when the metrics can't be
+			let metrics_server = async {
+				if let Some(metrics_server) = metrics_server {
+					if let Err(e) = metrics_server.await {
+						warn!("Metrics server error: {:?}", e);
+					}
+				}
+			};
+			let (api_result, ()) = future::join(api_server, metrics_server).await;
+			api_result
+		}
 	}
 }
 
+/// Renders the registered metrics in the Prometheus text format.
+#[handler]
+fn metrics(Data(registry): Data<&Registry>) -> poem::Result<Vec<u8>> {
+	let mut buffer = Vec::new();
+	TextEncoder::new()
+		.encode(&registry.gather(), &mut buffer)
+		.map_err(InternalServerError)?;
+	Ok(buffer)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
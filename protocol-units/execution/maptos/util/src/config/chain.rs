@@ -1,9 +1,11 @@
 use super::common::{
 	default_enable_pruning, default_genesis_block_hash_hex, default_genesis_timestamp_microseconds,
 	default_maptos_chain_id, default_maptos_epoch_snapshot_prune_window,
-	default_maptos_ledger_prune_window, default_maptos_private_key, default_maptos_read_only,
-	default_maptos_rest_listen_hostname, default_maptos_rest_listen_port,
-	default_maptos_state_merkle_prune_window,
+	default_maptos_ledger_prune_window, default_maptos_metrics_enabled,
+	default_maptos_metrics_listen_hostname, default_maptos_metrics_listen_port,
+	default_maptos_private_key, default_maptos_read_only, default_maptos_rest_listen_hostname,
+	default_maptos_rest_listen_port, default_maptos_state_merkle_prune_window,
+	default_maptos_too_new_tolerance,
 };
 use aptos_crypto::ed25519::Ed25519PrivateKey;
 use aptos_types::chain_id::ChainId;
@@ -24,6 +26,18 @@ pub struct Config {
 	#[serde(default = "default_maptos_rest_listen_port")]
 	pub maptos_rest_listen_port: u16,
 
+	/// Whether the Prometheus metrics are served
+	#[serde(default = "default_maptos_metrics_enabled")]
+	pub maptos_metrics_enabled: bool,
+
+	/// The hostname the Prometheus metrics are served on
+	#[serde(default = "default_maptos_metrics_listen_hostname")]
+	pub maptos_metrics_listen_hostname: String,
+
+	/// The port the Prometheus metrics are served on
+	#[serde(default = "default_maptos_metrics_listen_port")]
+	pub maptos_metrics_listen_port: u16,
+
 	/// The private key for the Aptos node
 	#[serde(default = "default_maptos_private_key")]
 	pub maptos_private_key: Ed25519PrivateKey,
@@ -31,6 +45,11 @@ pub struct Config {
 	#[serde(default = "default_maptos_read_only")]
 	pub maptos_read_only: bool,
 
+	/// How far ahead of the committed sequence number of its account
+	/// a transaction is accepted
+	#[serde(default = "default_maptos_too_new_tolerance")]
+	pub maptos_too_new_tolerance: u64,
+
 	/// Whether or not to prune
 	#[serde(default = "default_enable_pruning")]
 	pub enabled_pruning: bool,
@@ -65,8 +84,12 @@ impl Default for Config {
 			maptos_chain_id: default_maptos_chain_id(),
 			maptos_rest_listen_hostname: default_maptos_rest_listen_hostname(),
 			maptos_rest_listen_port: default_maptos_rest_listen_port(),
+			maptos_metrics_enabled: default_maptos_metrics_enabled(),
+			maptos_metrics_listen_hostname: default_maptos_metrics_listen_hostname(),
+			maptos_metrics_listen_port: default_maptos_metrics_listen_port(),
 			maptos_private_key: default_maptos_private_key(),
 			maptos_read_only: default_maptos_read_only(),
+			maptos_too_new_tolerance: default_maptos_too_new_tolerance(),
 			enabled_pruning: default_enable_pruning(),
 			maptos_ledger_prune_window: default_maptos_ledger_prune_window(),
 			maptos_epoch_snapshot_prune_window: default_maptos_epoch_snapshot_prune_window(),
@@ -29,6 +29,20 @@ env_default!(
 // The default Maptos API connection port
 env_default!(default_maptos_rest_connection_port, "MAPTOS_API_CONNECTION_PORT", u16, 30731);
 
+// Whether the Prometheus metrics are served by default
+env_default!(default_maptos_metrics_enabled, "MAPTOS_METRICS_ENABLED", bool, false);
+
+// The default metrics listen hostname
+env_default!(
+	default_maptos_metrics_listen_hostname,
+	"MAPTOS_METRICS_LISTEN_HOSTNAME",
+	String,
+	"0.0.0.0".to_string()
+);
+
+// The default metrics listen port
+env_default!(default_maptos_metrics_listen_port, "MAPTOS_METRICS_LISTEN_PORT", u16, 9464);
+
 // The default faucet API listen hostname
 env_default!(
 	default_maptos_faucet_rest_listen_hostname,
@@ -76,6 +90,9 @@ env_default!(default_maptos_chain_id, "MAPTOS_CHAIN_ID", ChainId, ChainId::from_
 // The default read-only mode
 env_default!(default_maptos_read_only, "MAPTOS_READ_ONLY", bool, false);
 
+// The default number of sequence numbers a transaction may be ahead of its account
+env_default!(default_maptos_too_new_tolerance, "MAPTOS_TOO_NEW_TOLERANCE", u64, 32);
+
 // The default private key
 pub fn default_maptos_private_key() -> Ed25519PrivateKey {
 	match std::env::var("MAPTOS_PRIVATE_KEY") {
@@ -138,6 +155,27 @@ env_default!(
 	10
 );
 
+env_default!(
+	default_maptos_indexer_worker_multiplier,
+	"MAPTOS_INDEXER_WORKER_MULTIPLIER",
+	usize,
+	2
+);
+
+env_default!(
+	default_maptos_indexer_min_worker_threads,
+	"MAPTOS_INDEXER_MIN_WORKER_THREADS",
+	usize,
+	16
+);
+
+env_default!(
+	default_maptos_indexer_migration_timeout,
+	"MAPTOS_INDEXER_MIGRATION_TIMEOUT_SEC",
+	u64,
+	60
+);
+
 env_default!(default_enable_pruning, "MAPTOS_ENABLE_PRUNING", bool, false);
 
 env_default!(default_maptos_ledger_prune_window, "MAPTOS_LEDGER_PRUNING_WINDOW", u64, 50_000_000);
@@ -186,8 +224,31 @@ env_default!(
 
 env_default!(default_max_transactions_in_flight, "MAPTOS_MAX_TRANSACTIONS_IN_FLIGHT", u64);
 
+env_default!(
+	default_max_transactions_in_flight_per_sender,
+	"MAPTOS_MAX_TRANSACTIONS_IN_FLIGHT_PER_SENDER",
+	u64
+);
+
 env_default!(default_sequence_number_ttl_ms, "MAPTOS_SEQUENCE_NUMBER_TTL_MS", u64, 1000 * 60 * 3);
 
 env_default!(default_gc_slot_duration_ms, "MAPTOS_GC_SLOT_DURATION_MS", u64, 1000 * 2);
 
+env_default!(default_min_gc_interval_ms, "MAPTOS_MIN_GC_INTERVAL_MS", u64, 1000 * 5);
+
+env_default!(default_max_gc_interval_ms, "MAPTOS_MAX_GC_INTERVAL_MS", u64, 1000 * 30);
+
+env_default!(default_drain_timeout_ms, "MAPTOS_DRAIN_TIMEOUT_MS", u64, 1000 * 5);
+
+env_default!(default_dedup_cache_size, "MAPTOS_DEDUP_CACHE_SIZE", usize, 2_usize.pow(16));
+
+env_default!(default_dedup_ttl_ms, "MAPTOS_DEDUP_TTL_MS", u64, 1000 * 60 * 3);
+
+env_default!(
+	default_max_transaction_size_bytes,
+	"MAPTOS_MAX_TRANSACTION_SIZE_BYTES",
+	usize,
+	64 * 1024
+);
+
 env_default!(default_ingress_account_whitelist, "MAPTOS_INGRESS_ACCOUNT_WHITELIST", String);
@@ -2,6 +2,8 @@ use super::common::{
 	default_maptos_indexer_grpc_inactivity_timeout, default_maptos_indexer_grpc_listen_hostname,
 	default_maptos_indexer_grpc_listen_port, default_maptos_indexer_grpc_ping_interval,
 	default_maptos_indexer_healthcheck_hostname, default_maptos_indexer_healthcheck_port,
+	default_maptos_indexer_migration_timeout, default_maptos_indexer_min_worker_threads,
+	default_maptos_indexer_worker_multiplier,
 };
 use serde::{Deserialize, Serialize};
 
@@ -30,6 +32,18 @@ pub struct Config {
 	/// The port of the indexer health check entry point
 	#[serde(default = "default_maptos_indexer_healthcheck_port")]
 	pub maptos_indexer_grpc_healthcheck_port: u16,
+
+	/// Number of indexer runtime worker threads per CPU
+	#[serde(default = "default_maptos_indexer_worker_multiplier")]
+	pub worker_multiplier: usize,
+
+	/// Minimum number of indexer runtime worker threads
+	#[serde(default = "default_maptos_indexer_min_worker_threads")]
+	pub min_worker_threads: usize,
+
+	/// How long the processors wait for the database migrations of the default processor
+	#[serde(default = "default_maptos_indexer_migration_timeout")]
+	pub migration_timeout_sec: u64,
 }
 
 impl Default for Config {
@@ -43,6 +57,9 @@ impl Default for Config {
 			),
 			maptos_indexer_grpc_healthcheck_hostname: default_maptos_indexer_healthcheck_hostname(),
 			maptos_indexer_grpc_healthcheck_port: default_maptos_indexer_healthcheck_port(),
+			worker_multiplier: default_maptos_indexer_worker_multiplier(),
+			min_worker_threads: default_maptos_indexer_min_worker_threads(),
+			migration_timeout_sec: default_maptos_indexer_migration_timeout(),
 		}
 	}
 }
@@ -1,6 +1,8 @@
 //! Configuration for load-shedding limits.
 
-use super::common::default_max_transactions_in_flight;
+use super::common::{
+	default_max_transactions_in_flight, default_max_transactions_in_flight_per_sender,
+};
 
 use serde::{Deserialize, Serialize};
 
@@ -10,10 +12,17 @@ pub struct Config {
 	/// before new transactions are rejected.
 	#[serde(default = "default_max_transactions_in_flight")]
 	pub max_transactions_in_flight: Option<u64>,
+	/// The maximum number of transactions a single sender may have in flight
+	/// before its new transactions are rejected.
+	#[serde(default = "default_max_transactions_in_flight_per_sender")]
+	pub max_transactions_in_flight_per_sender: Option<u64>,
 }
 
 impl Default for Config {
 	fn default() -> Self {
-		Self { max_transactions_in_flight: default_max_transactions_in_flight() }
+		Self {
+			max_transactions_in_flight: default_max_transactions_in_flight(),
+			max_transactions_in_flight_per_sender: default_max_transactions_in_flight_per_sender(),
+		}
 	}
 }
@@ -1,5 +1,7 @@
 use super::common::{
-	default_gc_slot_duration_ms, default_ingress_account_whitelist, default_sequence_number_ttl_ms,
+	default_dedup_cache_size, default_dedup_ttl_ms, default_drain_timeout_ms,
+	default_gc_slot_duration_ms, default_ingress_account_whitelist, default_max_gc_interval_ms,
+	default_max_transaction_size_bytes, default_min_gc_interval_ms, default_sequence_number_ttl_ms,
 };
 use aptos_account_whitelist::file::{Whitelist, WhitelistOperations};
 use aptos_types::account_address::AccountAddress;
@@ -15,6 +17,32 @@ pub struct Config {
 	/// The duration of a garbage collection slot in milliseconds.
 	#[serde(default = "default_gc_slot_duration_ms")]
 	pub gc_slot_duration_ms: u64,
+
+	/// The garbage collection interval in milliseconds when the transactions
+	/// in flight are close to their limit.
+	#[serde(default = "default_min_gc_interval_ms")]
+	pub min_gc_interval_ms: u64,
+
+	/// The garbage collection interval in milliseconds when there are few
+	/// transactions in flight, or no limit on them.
+	#[serde(default = "default_max_gc_interval_ms")]
+	pub max_gc_interval_ms: u64,
+
+	/// How long the buffered requests are still processed on shutdown, in milliseconds.
+	#[serde(default = "default_drain_timeout_ms")]
+	pub drain_timeout_ms: u64,
+
+	/// The number of accepted transaction hashes remembered to acknowledge retransmissions.
+	#[serde(default = "default_dedup_cache_size")]
+	pub dedup_cache_size: usize,
+
+	/// The number of milliseconds an accepted transaction hash is remembered for.
+	#[serde(default = "default_dedup_ttl_ms")]
+	pub dedup_ttl_ms: u64,
+
+	/// The maximum size of a serialized transaction accepted by the mempool.
+	#[serde(default = "default_max_transaction_size_bytes")]
+	pub max_transaction_size_bytes: usize,
 }
 
 impl Default for Config {
@@ -22,6 +50,12 @@ impl Default for Config {
 		Self {
 			sequence_number_ttl_ms: default_sequence_number_ttl_ms(),
 			gc_slot_duration_ms: default_gc_slot_duration_ms(),
+			min_gc_interval_ms: default_min_gc_interval_ms(),
+			max_gc_interval_ms: default_max_gc_interval_ms(),
+			drain_timeout_ms: default_drain_timeout_ms(),
+			dedup_cache_size: default_dedup_cache_size(),
+			dedup_ttl_ms: default_dedup_ttl_ms(),
+			max_transaction_size_bytes: default_max_transaction_size_bytes(),
 		}
 	}
 }
@@ -1,8 +1,9 @@
-use alloy::providers::ProviderBuilder;
+use alloy::providers::{Provider, ProviderBuilder};
 use alloy::signers::local::PrivateKeySigner;
 use alloy_network::EthereumWallet;
 use alloy_primitives::Address;
 use alloy_primitives::U256;
+use alloy_transport::Transport;
 use anyhow::Context;
 use godfig::{backend::config_file::ConfigFile, Godfig};
 use mcr_settlement_client::eth_client::Client;
@@ -14,6 +15,29 @@ use std::str::FromStr;
 use tokio_stream::StreamExt;
 use tracing::info;
 
+/// Whether the genesis ceremony of the MCR domain has already been accepted by its staking contract.
+async fn is_genesis_accepted<T, P>(
+	governor_mcr: &MCR::MCRInstance<T, P>,
+) -> Result<bool, anyhow::Error>
+where
+	T: Transport + Clone,
+	P: Provider<T>,
+{
+	let MCR::stakingContractReturn { _0: staking_address } = governor_mcr
+		.stakingContract()
+		.call()
+		.await
+		.context("Failed to get the MCR staking contract")?;
+	let staking = MovementStaking::new(staking_address, governor_mcr.provider());
+	let MovementStaking::domainGenesisAcceptedReturn { _0: accepted } = staking
+		.domainGenesisAccepted(*governor_mcr.address())
+		.call()
+		.await
+		.context("Failed to check if the genesis ceremony was accepted")?;
+	Ok(accepted)
+}
+
+/// Runs the genesis ceremony, unless it has already been accepted.
 async fn run_genesis_ceremony(
 	config: &Config,
 	governor: PrivateKeySigner,
@@ -71,6 +95,12 @@ async fn run_genesis_ceremony(
 	let governor_mcr = MCR::new(mcr_address, &governor_rpc_provider);
 	let governor_staking = MovementStaking::new(staking_address, &governor_rpc_provider);
 
+	// the ceremony reverts once accepted, skip it when re-run
+	if is_genesis_accepted(&governor_mcr).await? {
+		info!("The genesis ceremony was already accepted");
+		return Ok(());
+	}
+
 	// Allow Alice and Bod to stake by adding to white list.
 	governor_staking
 		.whitelistAddress(alice_address)
@@ -226,15 +256,18 @@ pub async fn main() -> Result<(), anyhow::Error> {
 	let rpc_url = config.eth_rpc_connection_url();
 
 	let testing_config = config.testing.as_ref().context("Testing config not defined.")?;
-	run_genesis_ceremony(
-		&config,
-		PrivateKeySigner::from_str(&testing_config.mcr_testing_admin_account_private_key)?,
-		&rpc_url,
-		Address::from_str(&testing_config.move_token_contract_address)?,
-		Address::from_str(&testing_config.movement_staking_contract_address)?,
-		Address::from_str(&config.settle.mcr_contract_address)?,
-	)
-	.await?;
+	for _ in 0..2 {
+		// the second ceremony finds the genesis accepted and succeeds without effect
+		run_genesis_ceremony(
+			&config,
+			PrivateKeySigner::from_str(&testing_config.mcr_testing_admin_account_private_key)?,
+			&rpc_url,
+			Address::from_str(&testing_config.move_token_contract_address)?,
+			Address::from_str(&testing_config.movement_staking_contract_address)?,
+			Address::from_str(&config.settle.mcr_contract_address)?,
+		)
+		.await?;
+	}
 
 	// Build client 1 and send the first commitment.
 	//let settlement_config =
@@ -1,8 +1,9 @@
+use crate::logging::CommitmentLogger;
 use crate::send_eth_transaction::InsufficentFunds;
 use crate::send_eth_transaction::SendTransactionErrorRule;
 use crate::send_eth_transaction::UnderPriced;
 use crate::send_eth_transaction::VerifyRule;
-use crate::{CommitmentStream, McrSettlementClientOperations};
+use crate::{CommitmentStream, McrSettlementClientOperations, McrStakeOperations};
 use alloy::providers::fillers::ChainIdFiller;
 use alloy::providers::fillers::FillProvider;
 use alloy::providers::fillers::GasFiller;
@@ -17,18 +18,20 @@ use alloy_network::EthereumWallet;
 use alloy_primitives::Address;
 use alloy_primitives::U256;
 use alloy_sol_types::sol;
-use alloy_transport::BoxTransport;
+use alloy_transport::{BoxTransport, TransportError};
 use alloy_transport_ws::WsConnect;
 use anyhow::Context;
+use futures::TryStreamExt;
 use mcr_settlement_config::Config;
 use movement_types::block::{BlockCommitment, Commitment, Id};
 use serde_json::Value as JsonValue;
 use std::array::TryFromSliceError;
 use std::fs;
+use std::future::Future;
 use std::path::Path;
 use thiserror::Error;
-use tokio_stream::StreamExt;
-use tracing::info;
+use tokio_stream::{Stream, StreamExt};
+use tracing::{info, warn, Instrument};
 
 #[derive(Error, Debug)]
 pub enum McrEthConnectorError {
@@ -72,6 +75,11 @@ sol!(
 	"abis/MOVEToken.json"
 );
 
+/// The number of attempts to get the epoch of an accepted height before giving up on logging it.
+const EPOCH_LOOKUP_ATTEMPTS: u32 = 3;
+/// The delay between two attempts to get the epoch of an accepted height.
+const EPOCH_LOOKUP_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
+
 pub struct McrSettlementClient<P> {
 	run_commitment_admin_mode: bool,
 	rpc_provider: P,
@@ -81,6 +89,8 @@ pub struct McrSettlementClient<P> {
 	send_transaction_error_rules: Vec<Box<dyn VerifyRule>>,
 	gas_limit: u64,
 	send_transaction_retries: u32,
+	use_batching: bool,
+	commitment_logger: CommitmentLogger,
 }
 
 impl
@@ -137,6 +147,7 @@ impl
 			contract_address,
 			config.transactions.gas_limit,
 			config.transactions.transaction_send_retries,
+			config.transactions.use_batching,
 		)
 		.await
 		.context(
@@ -155,6 +166,7 @@ impl<P> McrSettlementClient<P> {
 		contract_address: Address,
 		gas_limit: u64,
 		send_transaction_retries: u32,
+		use_batching: bool,
 	) -> Result<Self, anyhow::Error>
 	where
 		P: Provider + Clone,
@@ -181,10 +193,188 @@ impl<P> McrSettlementClient<P> {
 			send_transaction_error_rules,
 			gas_limit,
 			send_transaction_retries,
+			use_batching,
+			commitment_logger: CommitmentLogger::new(signer_address),
 		})
 	}
 }
 
+impl<P> McrSettlementClient<P>
+where
+	P: Provider + Clone,
+{
+	/// Submits the commitments in a single `submitBatchBlockCommitment` transaction.
+	pub async fn submit_commitment_batch(
+		&self,
+		block_commitments: Vec<BlockCommitment>,
+	) -> Result<(), anyhow::Error> {
+		let contract = MCR::new(self.contract_address, &self.rpc_provider);
+
+		let span = self.commitment_logger.batch_span(&block_commitments);
+		let eth_block_commitment: Vec<_> = block_commitments
+			.iter()
+			.map(|block_commitment| {
+				Ok(MCR::BlockCommitment {
+					// Currently, to simplify the API, we'll say 0 is uncommitted all other numbers are legitimate heights
+					height: U256::from(block_commitment.height()),
+					commitment: alloy_primitives::FixedBytes(
+						block_commitment.commitment().as_bytes().clone(),
+					),
+					blockId: alloy_primitives::FixedBytes(
+						block_commitment.block_id().as_bytes().clone(),
+					),
+				})
+			})
+			.collect::<Result<Vec<_>, TryFromSliceError>>()?;
+
+		let call_builder = contract.submitBatchBlockCommitment(eth_block_commitment);
+
+		let result = crate::send_eth_transaction::send_transaction(
+			call_builder,
+			&self.send_transaction_error_rules,
+			self.send_transaction_retries,
+			self.gas_limit as u128,
+		)
+		.instrument(span)
+		.await;
+		match &result {
+			Ok(()) => block_commitments
+				.iter()
+				.for_each(|commitment| self.commitment_logger.commitment_submitted(commitment)),
+			Err(err) => block_commitments
+				.iter()
+				.for_each(|commitment| self.commitment_logger.commitment_rejected(commitment, err)),
+		}
+		result
+	}
+
+	/// Streams the commitments this validator posted from `from_height` to `to_height` included,
+	/// with their heights. The heights without a posted commitment are skipped.
+	/// The commitments are requested by pages of `page_size` concurrent calls.
+	pub fn get_commitment_history(
+		&self,
+		from_height: u64,
+		to_height: u64,
+		page_size: usize,
+	) -> impl Stream<Item = Result<(u64, BlockCommitment), anyhow::Error>> + '_ {
+		commitment_history(from_height, to_height, page_size, move |height| {
+			self.get_posted_commitment_at_height(height)
+		})
+	}
+
+	/// The address of the MCR contract, which is the domain of the validators' stakes.
+	pub fn contract_address(&self) -> Address {
+		self.contract_address
+	}
+
+	/// Gets the stake of `validator` in the current epoch of the staking `domain`,
+	/// in the staking token.
+	pub async fn get_validator_stake(
+		&self,
+		validator: Address,
+		domain: Address,
+	) -> Result<U256, anyhow::Error> {
+		let contract = MCR::new(self.contract_address, &self.ws_provider);
+		let MCR::stakingContractReturn { _0: staking_address } =
+			contract.stakingContract().call().await?;
+		let staking = MovementStaking::new(staking_address, &self.ws_provider);
+		let MovementStaking::tokenReturn { _0: token } = staking.token().call().await?;
+		let MovementStaking::getCurrentEpochStakeReturn { _0: stake } =
+			staking.getCurrentEpochStake(domain, token, validator).call().await?;
+		Ok(stake)
+	}
+
+	/// Collects the commitments streamed by [`Self::get_commitment_history`].
+	pub async fn collect_commitment_history(
+		&self,
+		from_height: u64,
+		to_height: u64,
+		page_size: usize,
+	) -> Result<Vec<(u64, BlockCommitment)>, anyhow::Error> {
+		self.get_commitment_history(from_height, to_height, page_size)
+			.try_collect()
+			.await
+	}
+}
+
+/// Posts the commitments as a batch with `submit_batch`, or one by one with `submit_one`
+/// when batching is disabled.
+/// The commitments are also posted one by one if the batch call reverts,
+/// as the contract may not support batching.
+async fn post_with_fallback<B, BF, O, OF>(
+	block_commitments: Vec<BlockCommitment>,
+	use_batching: bool,
+	submit_batch: B,
+	submit_one: O,
+) -> Result<(), anyhow::Error>
+where
+	B: FnOnce(Vec<BlockCommitment>) -> BF,
+	BF: Future<Output = Result<(), anyhow::Error>>,
+	O: Fn(BlockCommitment) -> OF,
+	OF: Future<Output = Result<(), anyhow::Error>>,
+{
+	if use_batching {
+		match submit_batch(block_commitments.clone()).await {
+			Err(err) if is_revert(&err) => {
+				warn!("The commitment batch reverted, submitting the commitments one by one: {err}")
+			}
+			result => return result,
+		}
+	}
+	for block_commitment in block_commitments {
+		submit_one(block_commitment).await?;
+	}
+	Ok(())
+}
+
+/// Streams the commitments returned by `fetch` from `from_height` to `to_height` included,
+/// calling `fetch` concurrently for the heights of each page of `page_size` heights.
+fn commitment_history<F, FF>(
+	from_height: u64,
+	to_height: u64,
+	page_size: usize,
+	fetch: F,
+) -> impl Stream<Item = Result<(u64, BlockCommitment), anyhow::Error>>
+where
+	F: Fn(u64) -> FF,
+	FF: Future<Output = Result<Option<BlockCommitment>, anyhow::Error>>,
+{
+	let page_size = page_size.max(1) as u64;
+	async_stream::try_stream! {
+		let mut page_start = from_height;
+		while page_start <= to_height {
+			let page_end = to_height.min(page_start.saturating_add(page_size - 1));
+			let page = futures::future::try_join_all((page_start..=page_end).map(|height| {
+				let commitment = fetch(height);
+				async move { Ok::<_, anyhow::Error>((height, commitment.await?)) }
+			}))
+			.await?;
+			for (height, commitment) in page {
+				if let Some(commitment) = commitment {
+					yield (height, commitment);
+				}
+			}
+			if page_end == to_height {
+				break;
+			}
+			page_start = page_end + 1;
+		}
+	}
+}
+
+/// Whether the submission failed because the contract call reverted.
+fn is_revert(err: &anyhow::Error) -> bool {
+	match err.downcast_ref::<McrEthConnectorError>() {
+		// the gas estimation fails with a revert
+		Some(McrEthConnectorError::SendTransactionError(
+			alloy_contract::Error::TransportError(TransportError::ErrorResp(payload)),
+		)) => payload.message.contains("revert"),
+		// the transaction was mined but failed
+		Some(McrEthConnectorError::RpcTransactionExecution(_)) => true,
+		_ => false,
+	}
+}
+
 #[async_trait::async_trait]
 impl<P> McrSettlementClientOperations for McrSettlementClient<P>
 where
@@ -205,7 +395,8 @@ where
 			blockId: alloy_primitives::FixedBytes(block_commitment.block_id().as_bytes().clone()),
 		};
 
-		if self.run_commitment_admin_mode {
+		let span = self.commitment_logger.span(&block_commitment);
+		let result = if self.run_commitment_admin_mode {
 			let call_builder = contract.forceLatestCommitment(eth_block_commitment);
 			crate::send_eth_transaction::send_transaction(
 				call_builder,
@@ -213,6 +404,7 @@ where
 				self.send_transaction_retries,
 				self.gas_limit as u128,
 			)
+			.instrument(span)
 			.await
 		} else {
 			let call_builder = contract.submitBlockCommitment(eth_block_commitment);
@@ -222,39 +414,21 @@ where
 				self.send_transaction_retries,
 				self.gas_limit as u128,
 			)
+			.instrument(span)
 			.await
-		}
+		};
+		self.commitment_logger.submission_result(&block_commitment, result)
 	}
 
 	async fn post_block_commitment_batch(
 		&self,
 		block_commitments: Vec<BlockCommitment>,
 	) -> Result<(), anyhow::Error> {
-		let contract = MCR::new(self.contract_address, &self.rpc_provider);
-
-		let eth_block_commitment: Vec<_> = block_commitments
-			.into_iter()
-			.map(|block_commitment| {
-				Ok(MCR::BlockCommitment {
-					// Currently, to simplify the API, we'll say 0 is uncommitted all other numbers are legitimate heights
-					height: U256::from(block_commitment.height()),
-					commitment: alloy_primitives::FixedBytes(
-						block_commitment.commitment().as_bytes().clone(),
-					),
-					blockId: alloy_primitives::FixedBytes(
-						block_commitment.block_id().as_bytes().clone(),
-					),
-				})
-			})
-			.collect::<Result<Vec<_>, TryFromSliceError>>()?;
-
-		let call_builder = contract.submitBatchBlockCommitment(eth_block_commitment);
-
-		crate::send_eth_transaction::send_transaction(
-			call_builder,
-			&self.send_transaction_error_rules,
-			self.send_transaction_retries,
-			self.gas_limit as u128,
+		post_with_fallback(
+			block_commitments,
+			self.use_batching,
+			|block_commitments| self.submit_commitment_batch(block_commitments),
+			|block_commitment| self.post_block_commitment(block_commitment),
 		)
 		.await
 	}
@@ -274,14 +448,17 @@ where
 			blockId: alloy_primitives::FixedBytes(block_commitment.block_id().as_bytes().clone()),
 		};
 
+		let span = self.commitment_logger.span(&block_commitment);
 		let call_builder = contract.forceLatestCommitment(eth_block_commitment);
-		crate::send_eth_transaction::send_transaction(
+		let result = crate::send_eth_transaction::send_transaction(
 			call_builder,
 			&self.send_transaction_error_rules,
 			self.send_transaction_retries,
 			self.gas_limit as u128,
 		)
-		.await
+		.instrument(span)
+		.await;
+		self.commitment_logger.submission_result(&block_commitment, result)
 	}
 
 	async fn stream_block_commitments(&self) -> Result<CommitmentStream, anyhow::Error> {
@@ -290,22 +467,66 @@ where
 		let contract = MCR::new(self.contract_address, &self.ws_provider);
 		let event_filter = contract.BlockAccepted_filter().watch().await?;
 
-		let stream = event_filter.into_stream().map(|event| {
-			event
-				.and_then(|(commitment, _)| {
-					let height = commitment.height.try_into().map_err(
-						|err: alloy::primitives::ruint::FromUintError<u64>| {
-							alloy_sol_types::Error::Other(err.to_string().into())
-						},
-					)?;
-					Ok(BlockCommitment::new(
-						height,
-						Id::new(commitment.blockHash.0),
-						Commitment::new(commitment.stateCommitment.0),
-					))
-				})
-				.map_err(|err| McrEthConnectorError::EventNotificationError(err).into())
-		});
+		let commitment_logger = self.commitment_logger.clone();
+		let ws_provider = self.ws_provider.clone();
+		let contract_address = self.contract_address;
+		let stream = event_filter
+			.into_stream()
+			.map(|event| {
+				event
+					.and_then(|(commitment, _)| {
+						let height = commitment.height.try_into().map_err(
+							|err: alloy::primitives::ruint::FromUintError<u64>| {
+								alloy_sol_types::Error::Other(err.to_string().into())
+							},
+						)?;
+						Ok(BlockCommitment::new(
+							height,
+							Id::new(commitment.blockHash.0),
+							Commitment::new(commitment.stateCommitment.0),
+						))
+					})
+					.map_err(|err| McrEthConnectorError::EventNotificationError(err).into())
+			})
+			.then(move |block_commitment| {
+				let commitment_logger = commitment_logger.clone();
+				let ws_provider = ws_provider.clone();
+				async move {
+					if let Ok(block_commitment) = &block_commitment {
+						let span = commitment_logger.span(block_commitment);
+						async {
+							commitment_logger.commitment_accepted(block_commitment);
+							let contract = MCR::new(contract_address, &ws_provider);
+							let height = block_commitment.height();
+							// a failed lookup is only logged, it must not end the stream
+							for attempt in 1..=EPOCH_LOOKUP_ATTEMPTS {
+								match contract
+									.blockHeightEpochAssignments(U256::from(height))
+									.call()
+									.await
+								{
+									Ok(MCR::blockHeightEpochAssignmentsReturn { _0: epoch }) => {
+										commitment_logger
+											.epoch_finalized(epoch.saturating_to::<u64>(), height);
+										break;
+									}
+									Err(err) => {
+										warn!(
+											"Failed to get the epoch of accepted height {height} (attempt {attempt}/{EPOCH_LOOKUP_ATTEMPTS}): {err}"
+										);
+										if attempt < EPOCH_LOOKUP_ATTEMPTS {
+											tokio::time::sleep(EPOCH_LOOKUP_RETRY_DELAY).await;
+										}
+									}
+								}
+							}
+						}
+						.instrument(span)
+						.await;
+					}
+					block_commitment
+				}
+			});
 		Ok(Box::pin(stream) as CommitmentStream)
 	}
 
@@ -367,6 +588,28 @@ where
 	}
 }
 
+#[async_trait::async_trait]
+impl<P> McrStakeOperations for McrSettlementClient<P>
+where
+	P: Provider + Clone,
+{
+	async fn get_stake(&self) -> Result<U256, anyhow::Error> {
+		self.get_validator_stake(self.signer_address, self.contract_address).await
+	}
+
+	async fn get_epoch_duration(&self) -> Result<u64, anyhow::Error> {
+		let contract = MCR::new(self.contract_address, &self.ws_provider);
+		let MCR::stakingContractReturn { _0: staking_address } =
+			contract.stakingContract().call().await?;
+		let staking = MovementStaking::new(staking_address, &self.ws_provider);
+		let MovementStaking::epochDurationByDomainReturn { _0: duration } =
+			staking.epochDurationByDomain(self.contract_address).call().await?;
+		Ok(duration
+			.try_into()
+			.context("Failed to convert the epoch duration from U256 to u64")?)
+	}
+}
+
 pub struct AnvilAddressEntry {
 	pub address: String,
 	pub private_key: String,
@@ -405,3 +648,133 @@ pub fn read_anvil_json_file_addresses<P: AsRef<Path>>(
 		.collect::<Vec<_>>();
 	Ok(res)
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	fn commitments(count: u64) -> Vec<BlockCommitment> {
+		(1..=count)
+			.map(|height| BlockCommitment::new(height, Default::default(), Commitment::test()))
+			.collect()
+	}
+
+	fn revert() -> anyhow::Error {
+		McrEthConnectorError::RpcTransactionExecution(
+			"Send commitment Transaction fail".to_string(),
+		)
+		.into()
+	}
+
+	#[tokio::test]
+	async fn test_commitment_history() -> Result<(), anyhow::Error> {
+		let calls = AtomicUsize::new(0);
+		let history: Vec<_> = commitment_history(1, 100, 16, |height| {
+			calls.fetch_add(1, Ordering::SeqCst);
+			async move { Ok(Some(BlockCommitment::new(height, Default::default(), Commitment::test()))) }
+		})
+		.try_collect()
+		.await?;
+		assert_eq!(calls.load(Ordering::SeqCst), 100);
+		assert_eq!(history.len(), 100);
+		assert!(history.iter().zip(1..).all(|((height, commitment), expected)| {
+			*height == expected && commitment.height() == expected
+		}));
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_commitment_history_skips_missing_heights() -> Result<(), anyhow::Error> {
+		let history: Vec<_> = commitment_history(1, 10, 3, |height| async move {
+			Ok((height % 2 == 0)
+				.then(|| BlockCommitment::new(height, Default::default(), Commitment::test())))
+		})
+		.try_collect()
+		.await?;
+		let heights: Vec<_> = history.iter().map(|(height, _)| *height).collect();
+		assert_eq!(heights, vec![2, 4, 6, 8, 10]);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_batch_is_submitted_in_one_call() -> Result<(), anyhow::Error> {
+		let batch_calls = AtomicUsize::new(0);
+		let single_calls = AtomicUsize::new(0);
+		post_with_fallback(
+			commitments(5),
+			true,
+			|batch| {
+				assert_eq!(batch.len(), 5);
+				batch_calls.fetch_add(1, Ordering::SeqCst);
+				async { Ok(()) }
+			},
+			|_| {
+				single_calls.fetch_add(1, Ordering::SeqCst);
+				async { Ok(()) }
+			},
+		)
+		.await?;
+		assert_eq!(batch_calls.load(Ordering::SeqCst), 1);
+		assert_eq!(single_calls.load(Ordering::SeqCst), 0);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_reverted_batch_falls_back_to_single_submissions() -> Result<(), anyhow::Error> {
+		let single_calls = AtomicUsize::new(0);
+		post_with_fallback(
+			commitments(5),
+			true,
+			|_| async { Err(revert()) },
+			|_| {
+				single_calls.fetch_add(1, Ordering::SeqCst);
+				async { Ok(()) }
+			},
+		)
+		.await?;
+		assert_eq!(single_calls.load(Ordering::SeqCst), 5);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_other_batch_errors_are_returned() {
+		let single_calls = AtomicUsize::new(0);
+		let result = post_with_fallback(
+			commitments(5),
+			true,
+			|_| async {
+				Err(McrEthConnectorError::InsufficientFunds("no funds".to_string()).into())
+			},
+			|_| {
+				single_calls.fetch_add(1, Ordering::SeqCst);
+				async { Ok(()) }
+			},
+		)
+		.await;
+		assert!(result.is_err());
+		assert_eq!(single_calls.load(Ordering::SeqCst), 0);
+	}
+
+	#[tokio::test]
+	async fn test_batching_disabled() -> Result<(), anyhow::Error> {
+		let batch_calls = AtomicUsize::new(0);
+		let single_calls = AtomicUsize::new(0);
+		post_with_fallback(
+			commitments(5),
+			false,
+			|_| {
+				batch_calls.fetch_add(1, Ordering::SeqCst);
+				async { Ok(()) }
+			},
+			|_| {
+				single_calls.fetch_add(1, Ordering::SeqCst);
+				async { Ok(()) }
+			},
+		)
+		.await?;
+		assert_eq!(batch_calls.load(Ordering::SeqCst), 0);
+		assert_eq!(single_calls.load(Ordering::SeqCst), 5);
+		Ok(())
+	}
+}
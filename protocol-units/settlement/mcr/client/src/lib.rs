@@ -1,3 +1,4 @@
+use alloy_primitives::U256;
 use movement_types::block::BlockCommitment;
 use tokio_stream::Stream;
 pub mod mock;
@@ -7,6 +8,7 @@ pub mod mock;
 // pub use mock::*;
 
 pub mod eth_client;
+pub mod logging;
 
 #[cfg(feature = "eth")]
 pub use eth_client::McrSettlementClient;
@@ -55,3 +57,19 @@ pub trait McrSettlementClientOperations {
 	/// Gets the max tolerable block height.
 	async fn get_max_tolerable_block_height(&self) -> Result<u64, anyhow::Error>;
 }
+
+/// Reads the stake of the validator the client settles for.
+#[async_trait::async_trait]
+pub trait McrStakeOperations {
+	/// Gets the stake of this validator in the current epoch of the MCR domain,
+	/// in the staking token.
+	async fn get_stake(&self) -> Result<U256, anyhow::Error>;
+
+	/// Gets the duration of the epochs of the MCR domain, in seconds.
+	async fn get_epoch_duration(&self) -> Result<u64, anyhow::Error>;
+
+	/// Whether the stake of this validator is below `min_stake`.
+	async fn is_underfunded(&self, min_stake: U256) -> Result<bool, anyhow::Error> {
+		Ok(self.get_stake().await? < min_stake)
+	}
+}
@@ -0,0 +1,95 @@
+//! Structured logging of the commitment settlement flow.
+//!
+//! All events are emitted with the `mcr_settlement` target so they can be
+//! filtered independently of the rest of the client logs.
+
+use alloy_primitives::Address;
+use movement_types::block::BlockCommitment;
+use std::fmt::Display;
+use tracing::{info, info_span, warn, Span};
+
+/// Emits structured events for commitments submitted by a validator.
+#[derive(Debug, Clone)]
+pub struct CommitmentLogger {
+	validator: Address,
+}
+
+impl CommitmentLogger {
+	pub fn new(validator: Address) -> Self {
+		Self { validator }
+	}
+
+	/// The span commitment related futures should be instrumented with.
+	pub fn span(&self, block_commitment: &BlockCommitment) -> Span {
+		info_span!(
+			target: "mcr_settlement",
+			"commitment",
+			height = block_commitment.height(),
+			hash = %block_commitment.commitment(),
+			validator = %self.validator,
+		)
+	}
+
+	/// The span a batch submission should be instrumented with.
+	pub fn batch_span(&self, block_commitments: &[BlockCommitment]) -> Span {
+		info_span!(
+			target: "mcr_settlement",
+			"commitment_batch",
+			size = block_commitments.len(),
+			first_height = block_commitments.first().map(BlockCommitment::height),
+			last_height = block_commitments.last().map(BlockCommitment::height),
+			validator = %self.validator,
+		)
+	}
+
+	pub fn commitment_submitted(&self, block_commitment: &BlockCommitment) {
+		info!(
+			target: "mcr_settlement",
+			height = block_commitment.height(),
+			hash = %block_commitment.commitment(),
+			validator = %self.validator,
+			"commitment_submitted"
+		);
+	}
+
+	pub fn commitment_accepted(&self, block_commitment: &BlockCommitment) {
+		info!(
+			target: "mcr_settlement",
+			height = block_commitment.height(),
+			hash = %block_commitment.commitment(),
+			"commitment_accepted"
+		);
+	}
+
+	pub fn commitment_rejected(&self, block_commitment: &BlockCommitment, reason: impl Display) {
+		warn!(
+			target: "mcr_settlement",
+			height = block_commitment.height(),
+			hash = %block_commitment.commitment(),
+			reason = %reason,
+			"commitment_rejected"
+		);
+	}
+
+	pub fn epoch_finalized(&self, epoch: u64, accepted_height: u64) {
+		info!(
+			target: "mcr_settlement",
+			epoch,
+			accepted_height,
+			"epoch_finalized"
+		);
+	}
+
+	/// Logs the outcome of a commitment submission and passes the result through.
+	pub fn submission_result<T>(
+		&self,
+		block_commitment: &BlockCommitment,
+		result: Result<T, anyhow::Error>,
+	) -> Result<T, anyhow::Error> {
+		match &result {
+			Ok(_) => self.commitment_submitted(block_commitment),
+			Err(err) => self.commitment_rejected(block_commitment, err),
+		}
+		result
+	}
+}
@@ -1,4 +1,5 @@
-use crate::{CommitmentStream, McrSettlementClientOperations};
+use crate::{CommitmentStream, McrSettlementClientOperations, McrStakeOperations};
+use alloy_primitives::U256;
 use mcr_settlement_config::Config;
 use movement_types::block::BlockCommitment;
 use std::collections::BTreeMap;
@@ -15,6 +16,10 @@ pub struct McrSettlementClient {
 	pub current_height: Arc<RwLock<u64>>,
 	pub block_lead_tolerance: u64,
 	paused_at_height: Arc<RwLock<Option<u64>>>,
+	stake: Arc<RwLock<U256>>,
+	/// The number of times the stake was read.
+	pub stake_reads: Arc<RwLock<u64>>,
+	pub epoch_duration: u64,
 }
 
 impl McrSettlementClient {
@@ -27,6 +32,9 @@ impl McrSettlementClient {
 			current_height: Arc::new(RwLock::new(0)),
 			block_lead_tolerance: 16,
 			paused_at_height: Arc::new(RwLock::new(None)),
+			stake: Arc::new(RwLock::new(U256::ZERO)),
+			stake_reads: Arc::new(RwLock::new(0)),
+			epoch_duration: 60,
 		}
 	}
 
@@ -44,6 +52,11 @@ impl McrSettlementClient {
 		commitments.insert(commitment.height(), commitment);
 	}
 
+	/// Sets the stake of the validator returned by `McrStakeOperations::get_stake`.
+	pub async fn set_stake(&self, stake: U256) {
+		*self.stake.write().await = stake;
+	}
+
 	/// Stop streaming commitments after the given height.
 	///
 	/// Any posted commitments will be accumulated.
@@ -149,6 +162,18 @@ impl McrSettlementClientOperations for McrSettlementClient {
 	}
 }
 
+#[async_trait::async_trait]
+impl McrStakeOperations for McrSettlementClient {
+	async fn get_stake(&self) -> Result<U256, anyhow::Error> {
+		*self.stake_reads.write().await += 1;
+		Ok(*self.stake.read().await)
+	}
+
+	async fn get_epoch_duration(&self) -> Result<u64, anyhow::Error> {
+		Ok(self.epoch_duration)
+	}
+}
+
 #[cfg(test)]
 pub mod test {
 
@@ -244,4 +269,15 @@ pub mod test {
 		assert_eq!(stream.next().await.expect("stream has ended")?, commitment2);
 		Ok(())
 	}
+
+	#[tokio::test]
+	async fn test_is_underfunded() -> Result<(), anyhow::Error> {
+		let client = McrSettlementClient::new();
+		client.set_stake(U256::from(999)).await;
+		assert!(client.is_underfunded(U256::from(1000)).await?);
+		client.set_stake(U256::from(1000)).await;
+		assert!(!client.is_underfunded(U256::from(1000)).await?);
+		assert_eq!(*client.stake_reads.read().await, 2);
+		Ok(())
+	}
 }
@@ -71,7 +71,8 @@ pub async fn send_transaction<
 ) -> Result<(), anyhow::Error> {
 	info!("Sending transaction with gas limit: {}", gas_limit);
 	//validate gas price.
-	let mut estimate_gas = base_call_builder.estimate_gas().await.expect("Failed to estimate gas");
+	let mut estimate_gas =
+		base_call_builder.estimate_gas().await.map_err(McrEthConnectorError::from)?;
 	// Add 20% because initial gas estimate are too low.
 	estimate_gas += (estimate_gas * 20) / 100;
 
@@ -1,3 +1,4 @@
+use alloy::primitives::U256;
 use alloy::signers::local::PrivateKeySigner;
 use godfig::env_default;
 use serde::{Deserialize, Serialize};
@@ -17,6 +18,10 @@ pub struct Config {
 	pub settlement_super_block_size: u64,
 	#[serde(default = "default_settlement_admin_mode")]
 	pub settlement_admin_mode: bool,
+	/// The minimum stake of a validator as a decimal string in the token's base unit,
+	/// the stake of the node is not monitored when 0
+	#[serde(default = "default_min_validator_stake")]
+	pub min_validator_stake: String,
 }
 
 pub fn default_signer_private_key() -> String {
@@ -36,10 +41,26 @@ env_default!(default_settlement_admin_mode, "MCR_SETTLEMENT_ADMIN_MODE", bool, f
 
 env_default!(default_settlement_super_block_size, "MCR_SETTLEMENT_SUPER_BLOCK_SIZE", u64, 1);
 
+env_default!(
+	default_min_validator_stake,
+	"MCR_SETTLEMENT_MIN_VALIDATOR_STAKE",
+	String,
+	"0".to_string()
+);
+
 pub fn default_should_settle() -> bool {
 	env::var("ETH_SIGNER_PRIVATE_KEY").is_ok()
 }
 
+impl Config {
+	/// Parses `min_validator_stake`, which may exceed a `u64` with an 18 decimals token.
+	pub fn parse_min_validator_stake(&self) -> Result<U256, anyhow::Error> {
+		self.min_validator_stake.parse().map_err(|e| {
+			anyhow::anyhow!("invalid min_validator_stake {:?}: {e}", self.min_validator_stake)
+		})
+	}
+}
+
 impl Default for Config {
 	fn default() -> Self {
 		Config {
@@ -48,6 +69,7 @@ impl Default for Config {
 			mcr_contract_address: default_mcr_contract_address(),
 			settlement_admin_mode: default_settlement_admin_mode(),
 			settlement_super_block_size: default_settlement_super_block_size(),
+			min_validator_stake: default_min_validator_stake(),
 		}
 	}
 }
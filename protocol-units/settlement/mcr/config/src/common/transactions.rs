@@ -10,6 +10,10 @@ pub struct Config {
 	pub batch_timeout: u64,
 	#[serde(default = "default_transaction_send_retries")]
 	pub transaction_send_retries: u32,
+	/// Whether a batch of commitments is submitted in a single transaction,
+	/// rather than one transaction per commitment
+	#[serde(default = "default_use_batching")]
+	pub use_batching: bool,
 }
 
 env_short_default!(default_gas_limit, u64, 10_000_000_000_000_000 as u64);
@@ -18,12 +22,15 @@ env_short_default!(default_batch_timeout, u64, 2000 as u64);
 
 env_short_default!(default_transaction_send_retries, u32, 10 as u32);
 
+env_short_default!(default_use_batching, bool, true);
+
 impl Default for Config {
 	fn default() -> Self {
 		Config {
 			gas_limit: default_gas_limit(),
 			batch_timeout: default_batch_timeout(),
 			transaction_send_retries: default_transaction_send_retries(),
+			use_batching: default_use_batching(),
 		}
 	}
 }
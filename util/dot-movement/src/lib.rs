@@ -1,6 +1,7 @@
 use std::io::Write;
 pub mod path;
 pub mod sync;
+pub mod versioned_config_file;
 
 #[derive(Debug, Clone)]
 pub struct DotMovement(std::path::PathBuf);
@@ -0,0 +1,136 @@
+use crate::DotMovement;
+use serde_json::Value;
+
+/// The top-level key holding the version of a config file.
+pub const VERSION_KEY: &str = "_version";
+
+/// Rewrites the JSON of a config from one version to the next.
+/// The migration at index `i` migrates version `i + 1` to version `i + 2`.
+pub type Migration = Box<dyn Fn(Value) -> Value + Send + Sync>;
+
+/// Applies to `config` the migrations from its version to the latest one, `migrations.len() + 1`.
+/// A config without a version is a version 1 config.
+/// The returned JSON has its version set to the latest one.
+pub fn migrate(mut config: Value, migrations: &[Migration]) -> Result<Value, anyhow::Error> {
+	let latest = migrations.len() as u64 + 1;
+	let version = match config.get(VERSION_KEY) {
+		None => 1,
+		Some(version) => version
+			.as_u64()
+			.filter(|version| *version >= 1)
+			.ok_or(anyhow::anyhow!("Invalid config version: {}", version))?,
+	};
+	if version > latest {
+		anyhow::bail!("Config version {} is newer than the supported version {}", version, latest);
+	}
+
+	for migration in &migrations[version as usize - 1..] {
+		config = migration(config);
+	}
+	config
+		.as_object_mut()
+		.ok_or(anyhow::anyhow!("Config is not a JSON object"))?
+		.insert(VERSION_KEY.to_string(), latest.into());
+	Ok(config)
+}
+
+impl DotMovement {
+	/// Tries to get a configuration from a JSON file, migrating it to the latest version first.
+	/// The file itself is left untouched.
+	pub fn try_get_versioned_config_from_json<T: serde::de::DeserializeOwned>(
+		&self,
+		migrations: &[Migration],
+	) -> Result<T, anyhow::Error> {
+		let file = std::fs::File::open(self.get_config_json_path())
+			.map_err(|e| anyhow::anyhow!("Failed to open file: {}", e))?;
+		let reader = std::io::BufReader::new(file);
+		let config: Value = serde_json::from_reader(reader)
+			.map_err(|e| anyhow::anyhow!("Failed to parse config: {}", e))?;
+		let mut config = migrate(config, migrations)?;
+		if let Some(config) = config.as_object_mut() {
+			config.remove(VERSION_KEY);
+		}
+		let config = serde_json::from_value(config)
+			.map_err(|e| anyhow::anyhow!("Failed to parse config: {}", e))?;
+		Ok(config)
+	}
+}
+
+#[cfg(test)]
+pub mod test {
+	use super::*;
+	use serde::Deserialize;
+
+	/// Version 2 of a Suzuka like config, `da_url` was named `celestia_url` in version 1.
+	#[derive(Debug, PartialEq, Deserialize)]
+	#[serde(deny_unknown_fields)]
+	struct SuzukaConfig {
+		da_url: String,
+		chain_id: u8,
+	}
+
+	fn rename_field(from: &'static str, to: &'static str) -> Migration {
+		Box::new(move |mut config| {
+			if let Some(config) = config.as_object_mut() {
+				if let Some(value) = config.remove(from) {
+					config.insert(to.to_string(), value);
+				}
+			}
+			config
+		})
+	}
+
+	fn suzuka_migrations() -> Vec<Migration> {
+		vec![rename_field("celestia_url", "da_url")]
+	}
+
+	fn dot_movement_with_config(
+		config: &Value,
+	) -> Result<(tempfile::TempDir, DotMovement), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+		let dot_movement = DotMovement::new(dir.path().to_str().unwrap());
+		dot_movement.try_write_config_to_json(config)?;
+		Ok((dir, dot_movement))
+	}
+
+	#[test]
+	fn test_v1_config_is_migrated() -> Result<(), anyhow::Error> {
+		// Version 1 configs were written without a version.
+		let v1 = serde_json::json!({ "celestia_url": "http://localhost:26658", "chain_id": 27 });
+		let (_dir, dot_movement) = dot_movement_with_config(&v1)?;
+
+		let config: SuzukaConfig =
+			dot_movement.try_get_versioned_config_from_json(&suzuka_migrations())?;
+		assert_eq!(
+			config,
+			SuzukaConfig { da_url: "http://localhost:26658".to_string(), chain_id: 27 }
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn test_latest_config_is_not_migrated() -> Result<(), anyhow::Error> {
+		let v2 = serde_json::json!({ "_version": 2, "da_url": "http://da:26658", "chain_id": 27 });
+		let (_dir, dot_movement) = dot_movement_with_config(&v2)?;
+
+		let config: SuzukaConfig =
+			dot_movement.try_get_versioned_config_from_json(&suzuka_migrations())?;
+		assert_eq!(config.da_url, "http://da:26658");
+		Ok(())
+	}
+
+	#[test]
+	fn test_newer_config_is_rejected() {
+		let v3 = serde_json::json!({ "_version": 3, "da_url": "http://da:26658", "chain_id": 27 });
+		assert!(migrate(v3, &suzuka_migrations()).is_err());
+		assert!(migrate(serde_json::json!({ "_version": 0 }), &suzuka_migrations()).is_err());
+	}
+
+	#[test]
+	fn test_migrate_sets_latest_version() -> Result<(), anyhow::Error> {
+		let config =
+			migrate(serde_json::json!({ "celestia_url": "http://da" }), &suzuka_migrations())?;
+		assert_eq!(config, serde_json::json!({ "_version": 2, "da_url": "http://da" }));
+		Ok(())
+	}
+}